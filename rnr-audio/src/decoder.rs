@@ -0,0 +1,170 @@
+use std::io::Cursor;
+use rnr_core::content::ContentError;
+
+/// Audio errors
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum AudioError {
+    #[error("Unsupported audio format")]
+    UnsupportedFormat,
+    #[error("Failed to decode audio: {0}")]
+    Decode(String),
+    #[error(transparent)]
+    Content(#[from] ContentError),
+}
+
+/// Decoded audio, stored as interleaved f32 samples in the range -1 to 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioBuffer {
+    sample_rate: u32,
+    channels: u16,
+    samples: Vec<f32>,
+}
+
+impl AudioBuffer {
+    /// Create a buffer from interleaved samples. Trailing samples that don't
+    /// make up a whole frame are dropped.
+    pub fn new(sample_rate: u32, channels: u16, mut samples: Vec<f32>) -> Self {
+        let channels = channels.max(1);
+        samples.truncate(samples.len() - samples.len() % channels as usize);
+        Self {
+            sample_rate: sample_rate.max(1),
+            channels,
+            samples,
+        }
+    }
+
+    /// Get the number of frames per second
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Get the number of channels
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Get the interleaved samples
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// Get the number of frames (one sample per channel)
+    pub fn num_frames(&self) -> usize {
+        self.samples.len() / self.channels as usize
+    }
+
+    /// Get the length of the audio in seconds
+    pub fn duration(&self) -> f64 {
+        self.num_frames() as f64 / self.sample_rate as f64
+    }
+
+    /// Get one frame as a left/right pair. Mono audio is played on both
+    /// sides, and channels past the second are ignored.
+    pub fn stereo_frame(&self, frame: usize) -> [f32; 2] {
+        let start = frame * self.channels as usize;
+        if self.channels == 1 {
+            [self.samples[start]; 2]
+        } else {
+            [self.samples[start], self.samples[start + 1]]
+        }
+    }
+}
+
+/// Decode a WAV or Ogg Vorbis file, telling them apart by their header
+pub fn decode(bytes: &[u8]) -> Result<AudioBuffer, AudioError> {
+    if bytes.starts_with(b"RIFF") {
+        decode_wav(bytes)
+    } else if bytes.starts_with(b"OggS") {
+        decode_ogg(bytes)
+    } else {
+        Err(AudioError::UnsupportedFormat)
+    }
+}
+
+/// Decode a WAV file holding integer or float PCM
+pub fn decode_wav(bytes: &[u8]) -> Result<AudioBuffer, AudioError> {
+    let reader = hound::WavReader::new(Cursor::new(bytes)).map_err(|e| AudioError::Decode(e.to_string()))?;
+    let spec = reader.spec();
+
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .into_samples::<f32>()
+            .collect::<Result<Vec<_>, _>>(),
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1_i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .into_samples::<i32>()
+                .map(|sample| sample.map(|sample| sample as f32 * scale))
+                .collect::<Result<Vec<_>, _>>()
+        }
+    }
+    .map_err(|e| AudioError::Decode(e.to_string()))?;
+
+    Ok(AudioBuffer::new(spec.sample_rate, spec.channels, samples))
+}
+
+/// Decode an Ogg Vorbis file
+pub fn decode_ogg(bytes: &[u8]) -> Result<AudioBuffer, AudioError> {
+    let mut reader = lewton::inside_ogg::OggStreamReader::new(Cursor::new(bytes))
+        .map_err(|e| AudioError::Decode(e.to_string()))?;
+
+    let mut samples = Vec::new();
+    while let Some(packet) = reader.read_dec_packet_itl().map_err(|e| AudioError::Decode(e.to_string()))? {
+        samples.extend(packet.into_iter().map(|sample| sample as f32 / 32768.0));
+    }
+
+    Ok(AudioBuffer::new(
+        reader.ident_hdr.audio_sample_rate,
+        reader.ident_hdr.audio_channels as u16,
+        samples,
+    ))
+}
+
+/// Encode 16-bit PCM as a WAV file, for building test audio
+#[cfg(test)]
+pub(crate) fn encode_wav(sample_rate: u32, channels: u16, samples: &[i16]) -> Vec<u8> {
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut bytes = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut bytes, spec).unwrap();
+    for &sample in samples {
+        writer.write_sample(sample).unwrap();
+    }
+    writer.finalize().unwrap();
+    bytes.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_wav() {
+        let bytes = encode_wav(8000, 2, &[16384, -16384, 0, 32767]);
+        let buffer = decode(&bytes).unwrap();
+
+        assert_eq!(buffer.sample_rate(), 8000);
+        assert_eq!(buffer.channels(), 2);
+        assert_eq!(buffer.num_frames(), 2);
+        assert_eq!(buffer.stereo_frame(0), [0.5, -0.5]);
+        assert!((buffer.duration() - 2.0 / 8000.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_mono_frames_play_on_both_sides() {
+        let buffer = AudioBuffer::new(100, 1, vec![0.25, 0.75]);
+        assert_eq!(buffer.stereo_frame(1), [0.75, 0.75]);
+    }
+
+    #[test]
+    fn test_decode_rejects_unknown_data() {
+        assert_eq!(decode(b"ID3 not a wav"), Err(AudioError::UnsupportedFormat));
+        assert!(matches!(decode(b"OggS broken stream"), Err(AudioError::Decode(_))));
+        assert!(matches!(decode(b"RIFF broken"), Err(AudioError::Decode(_))));
+    }
+}
@@ -0,0 +1,7 @@
+pub mod decoder;
+pub mod mixer;
+pub mod sound_service;
+
+pub use decoder::*;
+pub use mixer::*;
+pub use sound_service::*;
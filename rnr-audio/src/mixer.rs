@@ -0,0 +1,291 @@
+use std::rc::Rc;
+use std::collections::BTreeMap;
+use crate::decoder::AudioBuffer;
+
+/// Sample rate the mixer renders at by default
+pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+/// Number of channels the mixer renders (left and right)
+pub const OUTPUT_CHANNELS: usize = 2;
+
+/// Handle to a voice in a mixer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VoiceId(u64);
+
+/// One buffer being played by the mixer
+#[derive(Debug, Clone)]
+pub struct Voice {
+    buffer: Rc<AudioBuffer>,
+    /// Read position in source frames
+    position: f64,
+    pub playing: bool,
+    pub looped: bool,
+    pub volume: f32,
+    /// Playback speed, 1 being the buffer's own rate
+    pub speed: f32,
+    /// Per-channel gain, applied on top of the volume
+    pub gains: [f32; OUTPUT_CHANNELS],
+}
+
+impl Voice {
+    fn new(buffer: Rc<AudioBuffer>) -> Self {
+        Self {
+            buffer,
+            position: 0.0,
+            playing: false,
+            looped: false,
+            volume: 1.0,
+            speed: 1.0,
+            gains: [1.0; OUTPUT_CHANNELS],
+        }
+    }
+
+    /// Get the audio the voice plays
+    pub fn buffer(&self) -> &Rc<AudioBuffer> {
+        &self.buffer
+    }
+
+    /// Get the playback position in seconds
+    pub fn time_position(&self) -> f64 {
+        self.position / self.buffer.sample_rate() as f64
+    }
+
+    /// Seek to a position in seconds, clamped to the buffer
+    pub fn set_time_position(&mut self, time_position: f64) {
+        let frames = self.buffer.num_frames() as f64;
+        self.position = (time_position * self.buffer.sample_rate() as f64).clamp(0.0, frames);
+    }
+
+    /// Read the frame at a fractional position, interpolating linearly
+    /// between its neighbours
+    fn sample(&self, position: f64) -> [f32; OUTPUT_CHANNELS] {
+        let num_frames = self.buffer.num_frames();
+        let index = position as usize;
+        let fraction = (position - index as f64) as f32;
+
+        let current = self.buffer.stereo_frame(index);
+        let next_index = index + 1;
+        let next = if next_index < num_frames {
+            self.buffer.stereo_frame(next_index)
+        } else if self.looped {
+            self.buffer.stereo_frame(0)
+        } else {
+            [0.0; OUTPUT_CHANNELS]
+        };
+
+        [
+            current[0] + (next[0] - current[0]) * fraction,
+            current[1] + (next[1] - current[1]) * fraction,
+        ]
+    }
+}
+
+/// Output of one call to `Mixer::render`
+#[derive(Debug, Clone, Default)]
+pub struct MixOutput {
+    /// Interleaved stereo samples, clamped to -1 to 1
+    pub samples: Vec<f32>,
+    /// Voices that reached the end of a non-looped buffer and stopped
+    pub ended: Vec<VoiceId>,
+}
+
+/// Software mixer that sums voices into an interleaved stereo PCM buffer.
+///
+/// The mixer doesn't talk to an audio device, so it runs the same headless
+/// as it does on a client. Whoever owns it decides where the PCM goes.
+#[derive(Debug)]
+pub struct Mixer {
+    sample_rate: u32,
+    voices: BTreeMap<VoiceId, Voice>,
+    next_id: u64,
+}
+
+impl Mixer {
+    /// Create a mixer that renders at `sample_rate` frames per second
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate: sample_rate.max(1),
+            voices: BTreeMap::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Get the number of frames rendered per second
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Add a stopped voice that plays `buffer`
+    pub fn add_voice(&mut self, buffer: Rc<AudioBuffer>) -> VoiceId {
+        let id = VoiceId(self.next_id);
+        self.next_id += 1;
+        self.voices.insert(id, Voice::new(buffer));
+        id
+    }
+
+    /// Remove a voice
+    pub fn remove_voice(&mut self, id: VoiceId) -> Option<Voice> {
+        self.voices.remove(&id)
+    }
+
+    /// Get a voice
+    pub fn voice(&self, id: VoiceId) -> Option<&Voice> {
+        self.voices.get(&id)
+    }
+
+    /// Get a voice mutably
+    pub fn voice_mut(&mut self, id: VoiceId) -> Option<&mut Voice> {
+        self.voices.get_mut(&id)
+    }
+
+    /// Get the number of voices, playing or not
+    pub fn num_voices(&self) -> usize {
+        self.voices.len()
+    }
+
+    /// Render `frames` frames of every playing voice mixed together
+    pub fn render(&mut self, frames: usize) -> MixOutput {
+        let mut output = MixOutput {
+            samples: vec![0.0; frames * OUTPUT_CHANNELS],
+            ended: Vec::new(),
+        };
+
+        for (&id, voice) in self.voices.iter_mut() {
+            let num_frames = voice.buffer.num_frames() as f64;
+            if !voice.playing || num_frames == 0.0 {
+                continue;
+            }
+
+            let step = voice.buffer.sample_rate() as f64 / self.sample_rate as f64 * voice.speed.max(0.0) as f64;
+            for frame in output.samples.chunks_exact_mut(OUTPUT_CHANNELS) {
+                if voice.position >= num_frames {
+                    if voice.looped {
+                        voice.position %= num_frames;
+                    } else {
+                        voice.position = num_frames;
+                        voice.playing = false;
+                        output.ended.push(id);
+                        break;
+                    }
+                }
+
+                let sample = voice.sample(voice.position);
+                for channel in 0..OUTPUT_CHANNELS {
+                    frame[channel] += sample[channel] * voice.volume * voice.gains[channel];
+                }
+                voice.position += step;
+            }
+
+            // A voice that ran out on the last frame has ended too
+            if voice.playing && !voice.looped && voice.position >= num_frames {
+                voice.position = num_frames;
+                voice.playing = false;
+                output.ended.push(id);
+            }
+        }
+
+        for sample in &mut output.samples {
+            *sample = sample.clamp(-1.0, 1.0);
+        }
+        output
+    }
+}
+
+impl Default for Mixer {
+    fn default() -> Self {
+        Self::new(DEFAULT_SAMPLE_RATE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constant(sample_rate: u32, value: f32, frames: usize) -> Rc<AudioBuffer> {
+        Rc::new(AudioBuffer::new(sample_rate, 1, vec![value; frames]))
+    }
+
+    #[test]
+    fn test_mixes_voices() {
+        let mut mixer = Mixer::new(100);
+        let a = mixer.add_voice(constant(100, 0.25, 100));
+        let b = mixer.add_voice(constant(100, 0.5, 100));
+        mixer.voice_mut(a).unwrap().playing = true;
+        mixer.voice_mut(b).unwrap().playing = true;
+        mixer.voice_mut(b).unwrap().gains = [0.0, 1.0];
+
+        let output = mixer.render(10);
+        assert_eq!(output.samples.len(), 20);
+        assert_eq!(&output.samples[..2], &[0.25, 0.75]);
+        assert!((mixer.voice(a).unwrap().time_position() - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stopped_voices_are_silent() {
+        let mut mixer = Mixer::new(100);
+        mixer.add_voice(constant(100, 0.5, 100));
+
+        assert!(mixer.render(4).samples.iter().all(|&sample| sample == 0.0));
+    }
+
+    #[test]
+    fn test_voice_ends() {
+        let mut mixer = Mixer::new(100);
+        let id = mixer.add_voice(constant(100, 1.0, 5));
+        mixer.voice_mut(id).unwrap().playing = true;
+
+        let output = mixer.render(8);
+        assert_eq!(output.ended, vec![id]);
+        assert_eq!(output.samples[8], 1.0);
+        assert_eq!(output.samples[10], 0.0);
+        assert!(!mixer.voice(id).unwrap().playing);
+    }
+
+    #[test]
+    fn test_looped_voice_wraps() {
+        let mut mixer = Mixer::new(100);
+        let buffer = Rc::new(AudioBuffer::new(100, 1, vec![0.0, 0.5]));
+        let id = mixer.add_voice(buffer);
+        let voice = mixer.voice_mut(id).unwrap();
+        voice.playing = true;
+        voice.looped = true;
+
+        let output = mixer.render(4);
+        assert!(output.ended.is_empty());
+        let left: Vec<f32> = output.samples.iter().step_by(2).copied().collect();
+        assert_eq!(left, vec![0.0, 0.5, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn test_resamples_and_speeds_up() {
+        // A 50 Hz buffer played at double speed into a 100 Hz mixer reads one
+        // source frame per output frame
+        let mut mixer = Mixer::new(100);
+        let buffer = Rc::new(AudioBuffer::new(50, 1, vec![0.0, 0.5, 1.0, 0.5]));
+        let id = mixer.add_voice(buffer);
+        let voice = mixer.voice_mut(id).unwrap();
+        voice.playing = true;
+        voice.speed = 2.0;
+
+        let left: Vec<f32> = mixer.render(3).samples.iter().step_by(2).copied().collect();
+        assert_eq!(left, vec![0.0, 0.5, 1.0]);
+
+        // At normal speed the mixer interpolates between source frames
+        let voice = mixer.voice_mut(id).unwrap();
+        voice.speed = 1.0;
+        voice.set_time_position(0.0);
+        let left: Vec<f32> = mixer.render(3).samples.iter().step_by(2).copied().collect();
+        assert_eq!(left, vec![0.0, 0.25, 0.5]);
+    }
+
+    #[test]
+    fn test_output_is_clamped() {
+        let mut mixer = Mixer::new(100);
+        let id = mixer.add_voice(constant(100, 0.8, 10));
+        let voice = mixer.voice_mut(id).unwrap();
+        voice.playing = true;
+        voice.volume = 2.0;
+
+        assert_eq!(mixer.render(1).samples, vec![1.0, 1.0]);
+    }
+}
@@ -0,0 +1,468 @@
+use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use glam::Vec3;
+use rnr_core::content::ContentProvider;
+use rnr_core::instance::Instance;
+use rnr_core::signal::Signal;
+use rnr_core::types::CFrame;
+use rnr_core::variant::Variant;
+use rnr_datamodel::Sound;
+use crate::decoder::{decode, AudioBuffer, AudioError};
+use crate::mixer::{Mixer, VoiceId, DEFAULT_SAMPLE_RATE};
+
+/// Engine state kept for a Sound instance
+struct TrackedSound {
+    instance: Weak<RefCell<Instance>>,
+    /// SoundId the voice was created for
+    sound_id: String,
+    voice: Option<VoiceId>,
+    /// TimePosition as we last wrote it. Any other value was set by a script
+    /// and is treated as a seek.
+    time_position: f64,
+    ended: Signal<String>,
+}
+
+/// Work out how loud a 3D sound is and how it is panned.
+///
+/// Sounds are at full volume within `min_distance` and fall off inversely
+/// with distance beyond it, scaled by `rolloff_scale`, until they cut out at
+/// `max_distance`. Panning follows the emitter's direction along the
+/// listener's right vector.
+pub fn spatialize(
+    emitter: Vec3,
+    listener: &CFrame,
+    min_distance: f64,
+    max_distance: f64,
+    rolloff_scale: f64,
+) -> (f32, [f32; 2]) {
+    let offset = emitter - listener.position;
+    let distance = offset.length() as f64;
+
+    let attenuation = if distance <= min_distance {
+        1.0
+    } else if distance >= max_distance {
+        0.0
+    } else {
+        min_distance / (min_distance + rolloff_scale * (distance - min_distance))
+    };
+
+    let pan = if distance > 1e-4 {
+        offset.normalize().dot(listener.right_vector())
+    } else {
+        0.0
+    };
+    let gains = [(1.0 - pan).min(1.0), (1.0 + pan).min(1.0)];
+
+    (attenuation as f32, gains)
+}
+
+/// SoundService - plays Sound instances through a software mixer.
+///
+/// Every update finds the Sounds under the workspace and the service itself,
+/// brings the mixer's voices in line with their properties and renders the
+/// frame's worth of PCM. Sounds parented to parts are heard in 3D relative
+/// to the listener; all others are heard at their own volume everywhere.
+pub struct SoundService {
+    instance: Rc<RefCell<Instance>>,
+    content: ContentProvider,
+    mixer: Mixer,
+    /// Decoded audio by SoundId, including ids that failed to load
+    buffers: HashMap<String, Result<Rc<AudioBuffer>, AudioError>>,
+    sounds: Vec<TrackedSound>,
+    /// PCM rendered by the last update
+    output: Vec<f32>,
+    /// Simulated time and frames rendered, so rounding never drifts
+    time: f64,
+    frames_rendered: u64,
+}
+
+impl SoundService {
+    /// Create a SoundService that loads audio through `content`
+    pub fn new(content: ContentProvider) -> Rc<RefCell<Self>> {
+        Self::with_sample_rate(content, DEFAULT_SAMPLE_RATE)
+    }
+
+    /// Create a SoundService that mixes at the given sample rate
+    pub fn with_sample_rate(content: ContentProvider, sample_rate: u32) -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("SoundService");
+        instance.borrow_mut().set_class_name("SoundService");
+        instance.borrow_mut().set_property("RolloffScale", 1.0);
+
+        Rc::new(RefCell::new(Self {
+            instance,
+            content,
+            mixer: Mixer::new(sample_rate),
+            buffers: HashMap::new(),
+            sounds: Vec::new(),
+            output: Vec::new(),
+            time: 0.0,
+            frames_rendered: 0,
+        }))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the mixer
+    pub fn mixer(&self) -> &Mixer {
+        &self.mixer
+    }
+
+    /// Get how quickly 3D sounds fade with distance. 2 fades twice as fast.
+    pub fn rolloff_scale(&self) -> f64 {
+        self.instance.borrow().get_property("RolloffScale").and_then(Variant::as_float).unwrap_or(1.0)
+    }
+
+    /// Set how quickly 3D sounds fade with distance
+    pub fn set_rolloff_scale(&mut self, rolloff_scale: f64) {
+        self.instance.borrow_mut().set_property("RolloffScale", rolloff_scale.max(0.0));
+    }
+
+    /// Get the signal fired with the SoundId when a sound plays to its end.
+    /// Looped sounds never end.
+    pub fn ended(&mut self, sound: &Sound) -> Signal<String> {
+        self.tracked(sound.instance()).ended.clone()
+    }
+
+    /// Get the interleaved stereo PCM rendered by the last update
+    pub fn output(&self) -> &[f32] {
+        &self.output
+    }
+
+    /// Take the PCM rendered by the last update, leaving it empty
+    pub fn take_output(&mut self) -> Vec<f32> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Load and decode the audio for a SoundId, caching the result
+    pub fn load(&mut self, sound_id: &str) -> Result<Rc<AudioBuffer>, AudioError> {
+        if let Some(buffer) = self.buffers.get(sound_id) {
+            return buffer.clone();
+        }
+
+        let buffer = self.content
+            .load(sound_id)
+            .map_err(AudioError::from)
+            .and_then(|bytes| decode(&bytes))
+            .map(Rc::new);
+        if let Err(error) = &buffer {
+            eprintln!("Failed to load sound '{}': {}", sound_id, error);
+        }
+
+        self.buffers.insert(sound_id.to_string(), buffer.clone());
+        buffer
+    }
+
+    /// Advance every sound by `delta_time` seconds, hearing them from
+    /// `listener`, and render the frame's PCM into `output`
+    pub fn update(this: &Rc<RefCell<Self>>, delta_time: f64, workspace: &Rc<RefCell<Instance>>, listener: &CFrame) {
+        let ended = this.borrow_mut().mix(delta_time, workspace, listener);
+
+        // Fire after releasing the borrow so handlers can use the service
+        for (signal, sound_id) in ended {
+            signal.fire(&sound_id);
+        }
+    }
+
+    fn mix(&mut self, delta_time: f64, workspace: &Rc<RefCell<Instance>>, listener: &CFrame) -> Vec<(Signal<String>, String)> {
+        let sounds: Vec<Sound> = workspace.borrow()
+            .get_descendants()
+            .into_iter()
+            .chain(self.instance.borrow().get_descendants())
+            .filter_map(|instance| Sound::from_instance(&instance))
+            .collect();
+
+        self.prune(&sounds);
+        let rolloff_scale = self.rolloff_scale();
+        for sound in &sounds {
+            self.sync(sound, listener, rolloff_scale);
+        }
+
+        self.time += delta_time.max(0.0);
+        let target_frames = (self.time * self.mixer.sample_rate() as f64).round() as u64;
+        let frames = target_frames.saturating_sub(self.frames_rendered) as usize;
+        self.frames_rendered = target_frames.max(self.frames_rendered);
+
+        let mix = self.mixer.render(frames);
+        self.output = mix.samples;
+
+        let mut ended = Vec::new();
+        for tracked in &mut self.sounds {
+            let (Some(instance), Some(voice_id)) = (tracked.instance.upgrade(), tracked.voice) else {
+                continue;
+            };
+            let Some(voice) = self.mixer.voice_mut(voice_id) else {
+                continue;
+            };
+            let Some(sound) = Sound::from_instance(&instance) else {
+                continue;
+            };
+
+            if mix.ended.contains(&voice_id) {
+                // Finished sounds rewind, ready to be played again
+                voice.set_time_position(0.0);
+                sound.set_playing(false);
+                ended.push((tracked.ended.clone(), tracked.sound_id.clone()));
+            }
+
+            tracked.time_position = voice.time_position();
+            sound.set_time_position(tracked.time_position);
+        }
+        ended
+    }
+
+    /// Forget sounds that were destroyed, and silence ones that left the tree
+    fn prune(&mut self, sounds: &[Sound]) {
+        let mixer = &mut self.mixer;
+        self.sounds.retain_mut(|tracked| {
+            let Some(instance) = tracked.instance.upgrade() else {
+                if let Some(voice) = tracked.voice.take() {
+                    mixer.remove_voice(voice);
+                }
+                return false;
+            };
+
+            if !sounds.iter().any(|sound| Rc::ptr_eq(sound.instance(), &instance)) {
+                if let Some(voice) = tracked.voice.take() {
+                    mixer.remove_voice(voice);
+                }
+            }
+            true
+        });
+    }
+
+    /// Bring a sound's voice in line with its properties
+    fn sync(&mut self, sound: &Sound, listener: &CFrame, rolloff_scale: f64) {
+        let sound_id = sound.sound_id();
+        let index = self.tracked_index(sound.instance());
+
+        if self.sounds[index].sound_id != sound_id {
+            if let Some(voice) = self.sounds[index].voice.take() {
+                self.mixer.remove_voice(voice);
+            }
+            self.sounds[index].sound_id = sound_id.clone();
+        }
+
+        if self.sounds[index].voice.is_none() {
+            // Only load audio once something wants to hear it
+            if !sound.is_playing() || sound_id.is_empty() {
+                return;
+            }
+            match self.load(&sound_id) {
+                Ok(buffer) => {
+                    sound.set_time_length(buffer.duration());
+                    let voice = self.mixer.add_voice(buffer);
+                    self.sounds[index].voice = Some(voice);
+                    self.sounds[index].time_position = f64::NAN;
+                }
+                Err(_) => {
+                    sound.set_playing(false);
+                    return;
+                }
+            }
+        }
+
+        let tracked = &mut self.sounds[index];
+        let Some(voice) = tracked.voice.and_then(|voice| self.mixer.voice_mut(voice)) else {
+            return;
+        };
+
+        let time_position = sound.time_position();
+        if time_position != tracked.time_position {
+            voice.set_time_position(time_position);
+            tracked.time_position = time_position;
+        }
+
+        let (attenuation, gains) = match sound.emitter_position() {
+            Some(emitter) => spatialize(
+                emitter,
+                listener,
+                sound.roll_off_min_distance(),
+                sound.roll_off_max_distance(),
+                rolloff_scale,
+            ),
+            None => (1.0, [1.0, 1.0]),
+        };
+
+        voice.playing = sound.is_playing();
+        voice.looped = sound.looped();
+        voice.volume = sound.volume() as f32 * attenuation;
+        voice.speed = sound.playback_speed() as f32;
+        voice.gains = gains;
+    }
+
+    fn tracked(&mut self, instance: &Rc<RefCell<Instance>>) -> &mut TrackedSound {
+        let index = self.tracked_index(instance);
+        &mut self.sounds[index]
+    }
+
+    fn tracked_index(&mut self, instance: &Rc<RefCell<Instance>>) -> usize {
+        if let Some(index) = self.sounds
+            .iter()
+            .position(|tracked| tracked.instance.upgrade().is_some_and(|tracked| Rc::ptr_eq(&tracked, instance)))
+        {
+            return index;
+        }
+
+        self.sounds.push(TrackedSound {
+            instance: Rc::downgrade(instance),
+            sound_id: String::new(),
+            voice: None,
+            time_position: f64::NAN,
+            ended: Signal::new(),
+        });
+        self.sounds.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::fs;
+    use rnr_datamodel::BasePart;
+    use crate::decoder::encode_wav;
+
+    /// Sample rate of the test mixer, so one frame at 10 FPS is 10 samples
+    const SAMPLE_RATE: u32 = 100;
+
+    struct Fixture {
+        _dir: tempfile::TempDir,
+        service: Rc<RefCell<SoundService>>,
+        workspace: Rc<RefCell<Instance>>,
+    }
+
+    /// A service whose content folder holds `tone.wav`, 0.5 s of mono audio
+    /// at half amplitude
+    fn fixture() -> Fixture {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("sounds")).unwrap();
+        fs::write(dir.path().join("sounds/tone.wav"), encode_wav(SAMPLE_RATE, 1, &[16384; 50])).unwrap();
+
+        let service = SoundService::with_sample_rate(ContentProvider::new(dir.path()), SAMPLE_RATE);
+        Fixture { _dir: dir, service, workspace: Instance::new() }
+    }
+
+    fn tone(parent: &Rc<RefCell<Instance>>) -> Sound {
+        let sound = Sound::new();
+        sound.set_sound_id("rbxasset://sounds/tone.wav");
+        sound.set_volume(1.0);
+        Instance::set_parent(sound.instance(), Some(parent.clone()));
+        sound
+    }
+
+    fn update(fixture: &Fixture) -> Vec<f32> {
+        SoundService::update(&fixture.service, 0.1, &fixture.workspace, &CFrame::IDENTITY);
+        fixture.service.borrow_mut().take_output()
+    }
+
+    #[test]
+    fn test_plays_to_the_end() {
+        let fixture = fixture();
+        let sound = tone(&fixture.workspace);
+        let ended = Rc::new(Cell::new(0));
+        let counter = ended.clone();
+        fixture.service.borrow_mut().ended(&sound).connect(move |_| counter.set(counter.get() + 1));
+
+        // Nothing is rendered until the sound plays
+        assert!(update(&fixture).iter().all(|&sample| sample == 0.0));
+
+        sound.play();
+        let output = update(&fixture);
+        assert_eq!(output.len(), 20);
+        assert_eq!(&output[..2], &[0.5, 0.5]);
+        assert_eq!(sound.time_length(), 0.5);
+        assert!((sound.time_position() - 0.1).abs() < 1e-9);
+
+        for _ in 0..4 {
+            update(&fixture);
+        }
+        assert_eq!(ended.get(), 1);
+        assert!(!sound.is_playing());
+        assert_eq!(sound.time_position(), 0.0);
+    }
+
+    #[test]
+    fn test_pause_and_seek() {
+        let fixture = fixture();
+        let sound = tone(&fixture.workspace);
+        sound.play();
+        update(&fixture);
+
+        sound.pause();
+        assert!(update(&fixture).iter().all(|&sample| sample == 0.0));
+        assert!((sound.time_position() - 0.1).abs() < 1e-9);
+
+        sound.set_time_position(0.45);
+        sound.resume();
+        let output = update(&fixture);
+        assert_eq!(output[8], 0.5);
+        assert_eq!(output[10], 0.0);
+        assert!(!sound.is_playing());
+    }
+
+    #[test]
+    fn test_looped_sound_keeps_playing() {
+        let fixture = fixture();
+        let sound = tone(&fixture.workspace);
+        sound.set_looped(true);
+        sound.play();
+
+        for _ in 0..10 {
+            update(&fixture);
+        }
+        assert!(sound.is_playing());
+        assert!(update(&fixture).iter().all(|&sample| sample == 0.5));
+    }
+
+    #[test]
+    fn test_sounds_in_parts_are_positional() {
+        let fixture = fixture();
+        let part = BasePart::new("Part");
+        Instance::set_parent(part.instance(), Some(fixture.workspace.clone()));
+        let sound = tone(part.instance());
+        sound.play();
+
+        // To the listener's right and within RollOffMinDistance
+        part.set_position(Vec3::new(5.0, 0.0, 0.0));
+        let output = update(&fixture);
+        assert_eq!(&output[..2], &[0.0, 0.5]);
+
+        // Twice RollOffMinDistance away, straight ahead
+        part.set_position(Vec3::new(0.0, 0.0, -20.0));
+        let output = update(&fixture);
+        assert_eq!(&output[..2], &[0.25, 0.25]);
+
+        part.set_position(Vec3::new(0.0, 0.0, -20000.0));
+        assert!(update(&fixture).iter().all(|&sample| sample == 0.0));
+    }
+
+    #[test]
+    fn test_missing_audio_stops_the_sound() {
+        let fixture = fixture();
+        let sound = tone(&fixture.workspace);
+        sound.set_sound_id("rbxasset://sounds/missing.wav");
+        sound.play();
+
+        update(&fixture);
+        assert!(!sound.is_playing());
+        assert_eq!(fixture.service.borrow().mixer().num_voices(), 0);
+    }
+
+    #[test]
+    fn test_removed_sounds_are_silenced() {
+        let fixture = fixture();
+        let sound = tone(&fixture.workspace);
+        sound.set_looped(true);
+        sound.play();
+        update(&fixture);
+
+        Instance::set_parent(sound.instance(), None);
+        assert!(update(&fixture).iter().all(|&sample| sample == 0.0));
+        assert_eq!(fixture.service.borrow().mixer().num_voices(), 0);
+    }
+}
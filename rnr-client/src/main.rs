@@ -1,6 +1,8 @@
 use clap::Parser;
 use std::time::Instant;
 use tokio;
+use std::path::PathBuf;
+use rnr_datamodel::{InputReplay, RunContext};
 use rnr_world::{World, WorldConfig};
 // NetworkClient not yet implemented
 // use rnr_network::client::NetworkClient;
@@ -34,6 +36,14 @@ struct Args {
     #[arg(long, default_value = "true")]
     input: bool,
 
+    /// Play back recorded input from a replay file instead of a window
+    #[arg(long)]
+    input_replay: Option<PathBuf>,
+
+    /// Enable audio mixing
+    #[arg(long, default_value = "true")]
+    audio: bool,
+
     /// Target FPS
     #[arg(long, default_value = "60")]
     fps: u32,
@@ -59,19 +69,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Create world configuration
     let config = WorldConfig {
+        run_context: RunContext::Client,
         enable_rendering: args.render,
         enable_networking: args.network,
         enable_physics: args.physics,
         enable_input: args.input,
+        enable_audio: args.audio,
         target_fps: args.fps,
         viewport_width: args.width,
         viewport_height: args.height,
+        data_store_dir: None,
     };
 
     // Create and initialize world
     let mut world = World::new(config);
     world.initialize().await?;
 
+    if let Some(path) = &args.input_replay {
+        println!("Playing back input from {}", path.display());
+        world.set_input_replay(InputReplay::load(path)?);
+    }
+
     // Set up networking if enabled
     if args.network {
         println!("Setting up network connection...");
@@ -0,0 +1,142 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Scheme of content ids that point into the content folder
+pub const ASSET_SCHEME: &str = "rbxasset://";
+
+/// Scheme of content ids that point to a file on disk
+pub const FILE_SCHEME: &str = "file://";
+
+/// Content resolution errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContentError {
+    /// The content id is empty or malformed
+    InvalidContentId(String),
+    /// The content id uses a scheme we can't load, such as rbxassetid://
+    UnsupportedScheme(String),
+    /// No search path holds the asset
+    NotFound(String),
+    /// The asset exists but could not be read
+    Io(String),
+}
+
+impl std::fmt::Display for ContentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentError::InvalidContentId(id) => write!(f, "Invalid content id '{}'", id),
+            ContentError::UnsupportedScheme(id) => write!(f, "Unsupported content id '{}'", id),
+            ContentError::NotFound(id) => write!(f, "Content '{}' not found", id),
+            ContentError::Io(msg) => write!(f, "Failed to read content: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ContentError {}
+
+/// Resolves content ids (SoundId, TextureId, MeshId, ...) to files.
+///
+/// `rbxasset://path` is looked up in each search path in order.
+/// `file://path` and bare paths are used as they are.
+#[derive(Debug, Clone)]
+pub struct ContentProvider {
+    search_paths: Vec<PathBuf>,
+}
+
+impl ContentProvider {
+    /// Create a provider that looks up rbxasset:// ids under `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            search_paths: vec![root.into()],
+        }
+    }
+
+    /// Add a directory to search after the existing ones
+    pub fn add_search_path(&mut self, path: impl Into<PathBuf>) {
+        self.search_paths.push(path.into());
+    }
+
+    /// Get the directories rbxasset:// ids are looked up in
+    pub fn search_paths(&self) -> &[PathBuf] {
+        &self.search_paths
+    }
+
+    /// Find the file a content id refers to
+    pub fn resolve(&self, content_id: &str) -> Result<PathBuf, ContentError> {
+        let content_id = content_id.trim();
+        if content_id.is_empty() {
+            return Err(ContentError::InvalidContentId(content_id.to_string()));
+        }
+
+        if let Some(asset) = content_id.strip_prefix(ASSET_SCHEME) {
+            // Asset paths must stay inside the content folder
+            let asset = Path::new(asset);
+            if !asset.components().all(|component| matches!(component, Component::Normal(_))) {
+                return Err(ContentError::InvalidContentId(content_id.to_string()));
+            }
+
+            return self.search_paths
+                .iter()
+                .map(|root| root.join(asset))
+                .find(|path| path.is_file())
+                .ok_or_else(|| ContentError::NotFound(content_id.to_string()));
+        }
+
+        if content_id.contains("://") && !content_id.starts_with(FILE_SCHEME) {
+            return Err(ContentError::UnsupportedScheme(content_id.to_string()));
+        }
+
+        let path = PathBuf::from(content_id.strip_prefix(FILE_SCHEME).unwrap_or(content_id));
+        if path.is_file() {
+            Ok(path)
+        } else {
+            Err(ContentError::NotFound(content_id.to_string()))
+        }
+    }
+
+    /// Read the bytes of a content id
+    pub fn load(&self, content_id: &str) -> Result<Vec<u8>, ContentError> {
+        let path = self.resolve(content_id)?;
+        fs::read(&path).map_err(|e| ContentError::Io(format!("{}: {}", path.display(), e)))
+    }
+}
+
+impl Default for ContentProvider {
+    /// Search the engine's own content first, then the shared content folder
+    fn default() -> Self {
+        let mut provider = Self::new("Content/RNR");
+        provider.add_search_path("Content");
+        provider
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_asset() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        fs::create_dir_all(root.join("sounds")).unwrap();
+        fs::write(root.join("sounds/click.wav"), b"RIFF").unwrap();
+
+        let mut provider = ContentProvider::new(root.join("missing"));
+        provider.add_search_path(&root);
+
+        assert_eq!(provider.resolve("rbxasset://sounds/click.wav").unwrap(), root.join("sounds/click.wav"));
+        assert_eq!(provider.load("rbxasset://sounds/click.wav").unwrap(), b"RIFF");
+        assert!(matches!(provider.resolve("rbxasset://sounds/none.wav"), Err(ContentError::NotFound(_))));
+
+        let file_id = format!("file://{}", root.join("sounds/click.wav").display());
+        assert!(provider.resolve(&file_id).is_ok());
+    }
+
+    #[test]
+    fn test_resolve_rejects_bad_ids() {
+        let provider = ContentProvider::default();
+
+        assert!(matches!(provider.resolve(""), Err(ContentError::InvalidContentId(_))));
+        assert!(matches!(provider.resolve("rbxasset://../secret"), Err(ContentError::InvalidContentId(_))));
+        assert!(matches!(provider.resolve("rbxassetid://12345"), Err(ContentError::UnsupportedScheme(_))));
+    }
+}
@@ -1,6 +1,8 @@
 use std::rc::{Rc, Weak};
 use std::cell::RefCell;
-use crate::reflection::{ReflectionProperty, ReflectionFunction, PropertyType, AccessType, OperationType};
+use std::collections::BTreeMap;
+use crate::reflection::{ReflectionProperty, ReflectionFunction, PropertyType, AccessType, OperationType, superclass_of};
+use crate::variant::Variant;
 
 /// Trait for objects that can be notified of instance hierarchy changes
 pub trait InstanceListener {
@@ -23,8 +25,12 @@ pub struct Instance {
     archivable: bool,
     /// Class name for type identification
     class_name: String,
+    /// Class-specific property values, keyed by property name
+    properties: BTreeMap<String, Variant>,
     /// Listeners for hierarchy changes
     listeners: Vec<Box<dyn InstanceListener>>,
+    /// Set once the instance is destroyed; the parent can no longer change
+    parent_locked: bool,
 }
 
 impl Instance {
@@ -36,7 +42,9 @@ impl Instance {
             name: "Instance".to_string(),
             archivable: true,
             class_name: "Instance".to_string(),
+            properties: BTreeMap::new(),
             listeners: Vec::new(),
+            parent_locked: false,
         }))
     }
 
@@ -52,7 +60,14 @@ impl Instance {
 
     /// Check if this instance is of a specific type or inherits from it
     pub fn is_a(&self, class_name: &str) -> bool {
-        self.class_name == class_name
+        let mut current = Some(self.class_name.as_str());
+        while let Some(class) = current {
+            if class == class_name {
+                return true;
+            }
+            current = superclass_of(class);
+        }
+        false
     }
 
     /// Get the instance name
@@ -77,6 +92,21 @@ impl Instance {
         // TODO: Notify replicator of archivable change
     }
 
+    /// Get a class-specific property value
+    pub fn get_property(&self, name: &str) -> Option<&Variant> {
+        self.properties.get(name)
+    }
+
+    /// Set a class-specific property value
+    pub fn set_property(&mut self, name: &str, value: impl Into<Variant>) {
+        self.properties.insert(name.to_string(), value.into());
+    }
+
+    /// Get all class-specific property values, sorted by name
+    pub fn properties(&self) -> &BTreeMap<String, Variant> {
+        &self.properties
+    }
+
     /// Get parent instance
     pub fn parent(&self) -> Option<Rc<RefCell<Instance>>> {
         self.parent.upgrade()
@@ -92,6 +122,16 @@ impl Instance {
         self.children.len()
     }
 
+    /// Get all descendants, depth first in child order
+    pub fn get_descendants(&self) -> Vec<Rc<RefCell<Instance>>> {
+        let mut descendants = Vec::new();
+        for child in &self.children {
+            descendants.push(child.clone());
+            descendants.extend(child.borrow().get_descendants());
+        }
+        descendants
+    }
+
     /// Find first child with given name
     pub fn find_first_child(&self, name: &str) -> Option<Rc<RefCell<Instance>>> {
         self.children.iter().find(|child| {
@@ -138,10 +178,10 @@ impl Instance {
 
     /// Check if it's safe to add child
     pub fn can_add_child(parent: &Rc<RefCell<Instance>>, child: &Rc<RefCell<Instance>>) -> bool {
-        // Prevent self-references and existing parent relationships
+        // Prevent self-references and adding the same child twice
         if Rc::ptr_eq(child, parent) ||
            child.borrow().contains(parent) ||
-           child.borrow().parent().is_some() {
+           parent.borrow().contains(child) {
             return false;
         }
         true
@@ -149,47 +189,70 @@ impl Instance {
 
     /// Set parent instance
     pub fn set_parent(instance: &Rc<RefCell<Instance>>, new_parent: Option<Rc<RefCell<Instance>>>) {
+        if instance.borrow().parent_locked {
+            return;
+        }
+
         let can_set = Self::can_set_parent(instance, new_parent.as_ref());
         if can_set {
-            let mut instance_mut = instance.borrow_mut();
-
             // Remove from old parent
-            if let Some(old_parent) = instance_mut.parent.upgrade() {
+            let old_parent = instance.borrow().parent();
+            if let Some(old_parent) = old_parent {
                 old_parent.borrow_mut().remove_child_internal(instance);
             }
 
             // Set new parent
-            instance_mut.parent = match &new_parent {
+            instance.borrow_mut().parent = match &new_parent {
                 Some(p) => Rc::downgrade(p),
                 None => Weak::new(),
             };
 
             // Add to new parent
             if let Some(parent) = &new_parent {
-                parent.borrow_mut().add_child_internal(&parent, instance.clone());
+                parent.borrow_mut().add_child_internal(instance.clone());
             }
 
             // Notify listeners
-            for listener in &mut instance_mut.listeners {
+            for listener in &mut instance.borrow_mut().listeners {
                 listener.on_parent_changed(new_parent.clone());
             }
         }
     }
 
-    /// Internal method to add child (used by set_parent)
-    fn add_child_internal(&mut self, self_rc: &Rc<RefCell<Instance>>, child: Rc<RefCell<Instance>>) {
-        if Self::can_add_child(self_rc, &child) {
-            self.children.push(child.clone());
+    /// Destroy an instance and all of its descendants.
+    ///
+    /// The instance is removed from its parent and its Parent is locked, so it
+    /// can't be reused afterwards.
+    pub fn destroy(instance: &Rc<RefCell<Instance>>) {
+        let children = instance.borrow().children.clone();
+        for child in &children {
+            Self::destroy(child);
+        }
 
-            // Notify listeners
-            for listener in &mut self.listeners {
-                listener.on_child_added(child.clone());
-                listener.on_descendant_added(child.clone());
-            }
+        Self::set_parent(instance, None);
 
-            // Notify descendants recursively
-            self.notify_descendants_added(&child);
+        let mut instance_mut = instance.borrow_mut();
+        instance_mut.parent_locked = true;
+        instance_mut.listeners.clear();
+    }
+
+    /// Check if the instance has been destroyed
+    pub fn is_destroyed(&self) -> bool {
+        self.parent_locked
+    }
+
+    /// Internal method to add child (used by set_parent, which already validated it)
+    fn add_child_internal(&mut self, child: Rc<RefCell<Instance>>) {
+        self.children.push(child.clone());
+
+        // Notify listeners
+        for listener in &mut self.listeners {
+            listener.on_child_added(child.clone());
+            listener.on_descendant_added(child.clone());
         }
+
+        // Notify descendants recursively
+        self.notify_descendants_added(&child);
     }
 
     /// Internal method to remove child (used by set_parent)
@@ -361,7 +424,9 @@ impl Instance {
             name: self.name.clone(),
             archivable: self.archivable,
             class_name: self.class_name.clone(),
+            properties: self.properties.clone(),
             listeners: Vec::new(), // Listeners are not cloned
+            parent_locked: false,
         }));
 
         cloned
@@ -376,7 +441,9 @@ impl Clone for Instance {
             name: self.name.clone(),
             archivable: self.archivable,
             class_name: self.class_name.clone(),
+            properties: self.properties.clone(),
             listeners: Vec::new(),
+            parent_locked: false,
         }
     }
 }
@@ -431,4 +498,66 @@ mod tests {
         // instance2 should not have instance1 as parent due to cycle prevention
         assert!(instance2.borrow().parent().is_none() || !Rc::ptr_eq(&instance2.borrow().parent().unwrap(), &instance1));
     }
+
+    #[test]
+    fn test_is_a_inheritance() {
+        let instance = Instance::new();
+        instance.borrow_mut().set_class_name("SpawnLocation");
+
+        assert!(instance.borrow().is_a("SpawnLocation"));
+        assert!(instance.borrow().is_a("Part"));
+        assert!(instance.borrow().is_a("BasePart"));
+        assert!(instance.borrow().is_a("Instance"));
+        assert!(!instance.borrow().is_a("Model"));
+    }
+
+    #[test]
+    fn test_properties() {
+        let instance = Instance::new();
+        instance.borrow_mut().set_property("Anchored", true);
+
+        assert_eq!(instance.borrow().get_property("Anchored"), Some(&Variant::Bool(true)));
+        assert!(instance.borrow().get_property("Missing").is_none());
+
+        // Properties are copied by Clone
+        let cloned = instance.borrow().clone();
+        assert_eq!(cloned.borrow().get_property("Anchored"), Some(&Variant::Bool(true)));
+    }
+
+    #[test]
+    fn test_reparent() {
+        let first = Instance::new();
+        let second = Instance::new();
+        let child = Instance::new();
+
+        Instance::set_parent(&child, Some(first.clone()));
+        Instance::set_parent(&child, Some(second.clone()));
+
+        assert_eq!(first.borrow().num_children(), 0);
+        assert!(second.borrow().contains(&child));
+        assert!(Rc::ptr_eq(&child.borrow().parent().unwrap(), &second));
+    }
+
+    #[test]
+    fn test_destroy() {
+        let parent = Instance::new();
+        let child = Instance::new();
+        let grandchild = Instance::new();
+
+        Instance::set_parent(&child, Some(parent.clone()));
+        Instance::set_parent(&grandchild, Some(child.clone()));
+
+        assert_eq!(parent.borrow().get_descendants().len(), 2);
+
+        Instance::destroy(&child);
+
+        assert_eq!(parent.borrow().num_children(), 0);
+        assert_eq!(child.borrow().num_children(), 0);
+        assert!(child.borrow().is_destroyed());
+        assert!(grandchild.borrow().is_destroyed());
+
+        // Destroyed instances can't be parented again
+        Instance::set_parent(&child, Some(parent.clone()));
+        assert!(child.borrow().parent().is_none());
+    }
 }
@@ -1,5 +1,9 @@
+pub mod content;
 pub mod instance;
 pub mod reflection;
+pub mod signal;
+pub mod types;
+pub mod variant;
 
 #[cfg(test)]
 mod tests {
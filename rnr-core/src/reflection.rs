@@ -11,6 +11,47 @@ pub enum PropertyType {
     Vector2,
     Vector3,
     CFrame,
+    BrickColor,
+    Color3,
+    UDim,
+    UDim2,
+    Enum,
+}
+
+/// Get the superclass of a class.
+///
+/// Every class ultimately inherits from Instance, so only classes with a more
+/// specific parent need to be listed here.
+pub fn superclass_of(class_name: &str) -> Option<&'static str> {
+    match class_name {
+        "Instance" => None,
+        "BasePart" | "Model" => Some("PVInstance"),
+        "Part" => Some("BasePart"),
+        "SpawnLocation" | "Seat" | "FlagStand" => Some("Part"),
+        "VehicleSeat" => Some("BasePart"),
+        "Workspace" => Some("Model"),
+        "StarterGui" | "PlayerGui" => Some("BasePlayerGui"),
+        "GuiObject" | "LayerCollector" => Some("GuiBase2d"),
+        "ScreenGui" => Some("LayerCollector"),
+        "BillboardGui" => Some("LayerCollector"),
+        "Frame" | "TextLabel" | "TextBox" | "ImageLabel" | "GuiButton" => Some("GuiObject"),
+        "TextButton" | "ImageButton" => Some("GuiButton"),
+        "UILayout" | "UIPadding" | "UIConstraint" => Some("UIComponent"),
+        "UIGridStyleLayout" => Some("UILayout"),
+        "UIListLayout" | "UIGridLayout" => Some("UIGridStyleLayout"),
+        "UIAspectRatioConstraint" | "UISizeConstraint" => Some("UIConstraint"),
+        "Weld" | "ManualWeld" | "Snap" | "Glue" | "Motor" => Some("JointInstance"),
+        "Motor6D" => Some("Motor"),
+        "Tool" | "HopperBin" => Some("BackpackItem"),
+        "Flag" => Some("Tool"),
+        "Hint" => Some("Message"),
+        "IntValue" | "StringValue" => Some("ValueBase"),
+        "Shirt" | "Pants" => Some("Clothing"),
+        "Clothing" | "ShirtGraphic" | "BodyColors" | "CharacterMesh" => Some("CharacterAppearance"),
+        "Hat" | "Accessory" => Some("Accoutrement"),
+        "BodyVelocity" | "BodyPosition" | "BodyGyro" | "BodyForce" | "BodyAngularVelocity" | "BodyThrust" => Some("BodyMover"),
+        _ => Some("Instance"),
+    }
 }
 
 /// Access permissions for properties
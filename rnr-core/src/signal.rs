@@ -0,0 +1,189 @@
+use std::rc::Rc;
+use std::cell::{Cell, RefCell};
+
+type Callback<T> = Rc<RefCell<dyn FnMut(&T)>>;
+
+/// A connected callback
+struct Slot<T> {
+    callback: Callback<T>,
+    connected: Rc<Cell<bool>>,
+}
+
+/// An event that callbacks can connect to (the RBXScriptSignal equivalent).
+///
+/// Signals are cheap handles: cloning a signal gives another handle to the
+/// same set of connections.
+pub struct Signal<T> {
+    slots: Rc<RefCell<Vec<Slot<T>>>>,
+}
+
+impl<T> Signal<T> {
+    /// Create a new signal with no connections
+    pub fn new() -> Self {
+        Self {
+            slots: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    /// Connect a callback, returning a handle that can disconnect it
+    pub fn connect<F>(&self, callback: F) -> Connection
+    where
+        F: FnMut(&T) + 'static,
+    {
+        let connected = Rc::new(Cell::new(true));
+        let mut slots = self.slots.borrow_mut();
+        slots.retain(|slot| slot.connected.get());
+        slots.push(Slot {
+            callback: Rc::new(RefCell::new(callback)),
+            connected: connected.clone(),
+        });
+
+        Connection { connected }
+    }
+
+    /// Fire the signal, calling every connected callback in connection order.
+    ///
+    /// Callbacks may connect or disconnect while the signal is firing. A
+    /// callback that fires the same signal again is not re-entered.
+    pub fn fire(&self, args: &T) {
+        let slots: Vec<(Callback<T>, Rc<Cell<bool>>)> = self.slots.borrow()
+            .iter()
+            .filter(|slot| slot.connected.get())
+            .map(|slot| (slot.callback.clone(), slot.connected.clone()))
+            .collect();
+
+        for (callback, connected) in slots {
+            if !connected.get() {
+                continue;
+            }
+            if let Ok(mut callback) = callback.try_borrow_mut() {
+                callback(args);
+            }
+        }
+    }
+
+    /// Get the number of live connections
+    pub fn num_connections(&self) -> usize {
+        self.slots.borrow().iter().filter(|slot| slot.connected.get()).count()
+    }
+
+    /// Disconnect every callback
+    pub fn disconnect_all(&self) {
+        for slot in self.slots.borrow_mut().drain(..) {
+            slot.connected.set(false);
+        }
+    }
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots.clone(),
+        }
+    }
+}
+
+impl<T> Default for Signal<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> std::fmt::Debug for Signal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Signal")
+            .field("connections", &self.num_connections())
+            .finish()
+    }
+}
+
+/// Handle to a connected callback (the RBXScriptConnection equivalent).
+///
+/// Dropping a connection does not disconnect it.
+#[derive(Debug, Clone)]
+pub struct Connection {
+    connected: Rc<Cell<bool>>,
+}
+
+impl Connection {
+    /// Disconnect the callback
+    pub fn disconnect(&self) {
+        self.connected.set(false);
+    }
+
+    /// Check if the callback is still connected
+    pub fn is_connected(&self) -> bool {
+        self.connected.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_fire() {
+        let signal = Signal::<i32>::new();
+        let total = Rc::new(Cell::new(0));
+
+        let total_clone = total.clone();
+        signal.connect(move |value| total_clone.set(total_clone.get() + *value));
+
+        signal.fire(&5);
+        signal.fire(&7);
+
+        assert_eq!(total.get(), 12);
+        assert_eq!(signal.num_connections(), 1);
+    }
+
+    #[test]
+    fn test_signal_disconnect() {
+        let signal = Signal::<()>::new();
+        let count = Rc::new(Cell::new(0));
+
+        let count_clone = count.clone();
+        let connection = signal.connect(move |_| count_clone.set(count_clone.get() + 1));
+
+        signal.fire(&());
+        connection.disconnect();
+        signal.fire(&());
+
+        assert_eq!(count.get(), 1);
+        assert!(!connection.is_connected());
+        assert_eq!(signal.num_connections(), 0);
+    }
+
+    #[test]
+    fn test_signal_connection_order() {
+        let signal = Signal::<()>::new();
+        let order = Rc::new(RefCell::new(Vec::new()));
+
+        for i in 0..3 {
+            let order = order.clone();
+            signal.connect(move |_| order.borrow_mut().push(i));
+        }
+
+        signal.fire(&());
+        assert_eq!(*order.borrow(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_disconnect_while_firing() {
+        let signal = Signal::<()>::new();
+        let count = Rc::new(Cell::new(0));
+
+        let later: Rc<RefCell<Option<Connection>>> = Rc::new(RefCell::new(None));
+        let later_clone = later.clone();
+        signal.connect(move |_| {
+            if let Some(connection) = later_clone.borrow().as_ref() {
+                connection.disconnect();
+            }
+        });
+
+        let count_clone = count.clone();
+        *later.borrow_mut() = Some(signal.connect(move |_| count_clone.set(count_clone.get() + 1)));
+
+        signal.fire(&());
+        assert_eq!(count.get(), 0);
+    }
+}
@@ -0,0 +1,444 @@
+use glam::{Mat3, Quat, Vec3};
+use std::ops::Mul;
+
+/// A named color from the classic BrickColor palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BrickColor {
+    number: u16,
+}
+
+/// (number, name, rgb) for every palette entry we know about
+const BRICK_COLORS: &[(u16, &str, [u8; 3])] = &[
+    (1, "White", [242, 243, 243]),
+    (2, "Grey", [161, 165, 162]),
+    (5, "Brick yellow", [215, 197, 154]),
+    (9, "Light reddish violet", [232, 186, 200]),
+    (18, "Nougat", [204, 142, 105]),
+    (21, "Bright red", [196, 40, 28]),
+    (23, "Bright blue", [13, 105, 172]),
+    (24, "Bright yellow", [245, 205, 48]),
+    (26, "Black", [27, 42, 53]),
+    (28, "Dark green", [40, 127, 71]),
+    (29, "Medium green", [161, 196, 140]),
+    (37, "Bright green", [75, 151, 75]),
+    (38, "Dark orange", [160, 95, 53]),
+    (45, "Light blue", [180, 210, 228]),
+    (101, "Medium red", [218, 134, 122]),
+    (102, "Medium blue", [110, 153, 202]),
+    (104, "Bright violet", [107, 50, 124]),
+    (105, "Br. yellowish orange", [226, 155, 64]),
+    (106, "Bright orange", [218, 133, 65]),
+    (107, "Bright bluish green", [0, 143, 156]),
+    (119, "Br. yellowish green", [164, 189, 71]),
+    (125, "Light orange", [234, 184, 146]),
+    (135, "Sand blue", [116, 134, 157]),
+    (141, "Earth green", [39, 70, 45]),
+    (151, "Sand green", [120, 144, 130]),
+    (153, "Sand red", [149, 121, 119]),
+    (192, "Reddish brown", [105, 64, 40]),
+    (194, "Medium stone grey", [163, 162, 165]),
+    (199, "Dark stone grey", [99, 95, 98]),
+    (208, "Light stone grey", [229, 228, 223]),
+    (217, "Brown", [124, 92, 70]),
+    (226, "Cool yellow", [253, 234, 141]),
+    (1001, "Institutional white", [248, 248, 248]),
+    (1002, "Mid gray", [205, 205, 205]),
+    (1003, "Really black", [17, 17, 17]),
+    (1004, "Really red", [255, 0, 0]),
+    (1009, "New Yeller", [255, 255, 0]),
+    (1010, "Really blue", [0, 0, 255]),
+    (1020, "Lime green", [0, 255, 0]),
+    (1032, "Hot pink", [255, 0, 191]),
+];
+
+impl BrickColor {
+    /// Create a BrickColor from its palette number, falling back to the default
+    /// for numbers that aren't in the palette
+    pub fn new(number: u16) -> Self {
+        if BRICK_COLORS.iter().any(|(n, _, _)| *n == number) {
+            Self { number }
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Look up a BrickColor by name
+    pub fn from_name(name: &str) -> Option<Self> {
+        BRICK_COLORS
+            .iter()
+            .find(|(_, n, _)| *n == name)
+            .map(|(number, _, _)| Self { number: *number })
+    }
+
+    /// Get the palette number
+    pub fn number(&self) -> u16 {
+        self.number
+    }
+
+    /// Get the palette name
+    pub fn name(&self) -> &'static str {
+        self.entry().1
+    }
+
+    /// Get the color as 8-bit RGB
+    pub fn rgb(&self) -> [u8; 3] {
+        self.entry().2
+    }
+
+    /// Get the color as a Color3
+    pub fn color(&self) -> Color3 {
+        let [r, g, b] = self.rgb();
+        Color3::from_rgb(r, g, b)
+    }
+
+    fn entry(&self) -> &'static (u16, &'static str, [u8; 3]) {
+        BRICK_COLORS
+            .iter()
+            .find(|(n, _, _)| *n == self.number)
+            .expect("BrickColor numbers are always in the palette")
+    }
+
+    /// BrickColor.White() and friends, the shortcuts Roblox exposes
+    pub fn white() -> Self {
+        Self::new(1)
+    }
+
+    pub fn gray() -> Self {
+        Self::new(194)
+    }
+
+    pub fn dark_gray() -> Self {
+        Self::new(199)
+    }
+
+    pub fn black() -> Self {
+        Self::new(26)
+    }
+
+    pub fn red() -> Self {
+        Self::new(21)
+    }
+
+    pub fn yellow() -> Self {
+        Self::new(24)
+    }
+
+    pub fn green() -> Self {
+        Self::new(28)
+    }
+
+    pub fn blue() -> Self {
+        Self::new(23)
+    }
+}
+
+impl Default for BrickColor {
+    /// Medium stone grey, the default color of new parts
+    fn default() -> Self {
+        Self { number: 194 }
+    }
+}
+
+/// Coordinate frame: a position plus a rotation matrix.
+///
+/// Like Roblox, the look vector of a CFrame is its -Z axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CFrame {
+    pub position: Vec3,
+    pub rotation: Mat3,
+}
+
+impl CFrame {
+    pub const IDENTITY: Self = Self {
+        position: Vec3::ZERO,
+        rotation: Mat3::IDENTITY,
+    };
+
+    /// Create an unrotated CFrame at a position
+    pub fn new(position: Vec3) -> Self {
+        Self {
+            position,
+            rotation: Mat3::IDENTITY,
+        }
+    }
+
+    /// Create a CFrame from a position and rotation
+    pub fn from_rotation(position: Vec3, rotation: Quat) -> Self {
+        Self {
+            position,
+            rotation: Mat3::from_quat(rotation),
+        }
+    }
+
+    /// Create a CFrame at `eye` looking towards `target`
+    pub fn look_at(eye: Vec3, target: Vec3) -> Self {
+        let look = (target - eye).normalize_or_zero();
+        if look == Vec3::ZERO {
+            return Self::new(eye);
+        }
+
+        let up = if look.y.abs() > 0.999 { Vec3::Z } else { Vec3::Y };
+        let right = look.cross(up).normalize();
+        let up = right.cross(look).normalize();
+
+        Self {
+            position: eye,
+            rotation: Mat3::from_cols(right, up, -look),
+        }
+    }
+
+    /// Get the rotation as a quaternion
+    pub fn to_quat(&self) -> Quat {
+        Quat::from_mat3(&self.rotation)
+    }
+
+    /// Get the forward (-Z) direction
+    pub fn look_vector(&self) -> Vec3 {
+        -self.rotation.z_axis
+    }
+
+    /// Get the right (+X) direction
+    pub fn right_vector(&self) -> Vec3 {
+        self.rotation.x_axis
+    }
+
+    /// Get the up (+Y) direction
+    pub fn up_vector(&self) -> Vec3 {
+        self.rotation.y_axis
+    }
+
+    /// Get the inverse transform
+    pub fn inverse(&self) -> Self {
+        let rotation = self.rotation.transpose();
+        Self {
+            position: rotation * -self.position,
+            rotation,
+        }
+    }
+
+    /// Transform a point from object space into world space
+    pub fn point_to_world_space(&self, point: Vec3) -> Vec3 {
+        self.position + self.rotation * point
+    }
+
+    /// Transform a point from world space into object space
+    pub fn point_to_object_space(&self, point: Vec3) -> Vec3 {
+        self.rotation.transpose() * (point - self.position)
+    }
+
+    /// Interpolate towards `goal`, moving the position linearly and the
+    /// rotation along the shortest arc
+    pub fn lerp(&self, goal: &CFrame, alpha: f32) -> Self {
+        Self::from_rotation(
+            self.position.lerp(goal.position, alpha),
+            self.to_quat().slerp(goal.to_quat(), alpha),
+        )
+    }
+}
+
+impl Default for CFrame {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul for CFrame {
+    type Output = CFrame;
+
+    fn mul(self, rhs: CFrame) -> CFrame {
+        CFrame {
+            position: self.point_to_world_space(rhs.position),
+            rotation: self.rotation * rhs.rotation,
+        }
+    }
+}
+
+/// An RGB color with components from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Color3 {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+impl Color3 {
+    /// Create a color from components from 0 to 1
+    pub const fn new(r: f32, g: f32, b: f32) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Create a color from 8-bit components (Color3.fromRGB)
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0)
+    }
+
+    /// Interpolate towards `goal` component-wise
+    pub fn lerp(&self, goal: &Color3, alpha: f32) -> Self {
+        Self::new(
+            self.r + (goal.r - self.r) * alpha,
+            self.g + (goal.g - self.g) * alpha,
+            self.b + (goal.b - self.b) * alpha,
+        )
+    }
+}
+
+/// One axis of a GUI size or position: a fraction of the parent plus pixels
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct UDim {
+    pub scale: f32,
+    pub offset: i32,
+}
+
+impl UDim {
+    pub const fn new(scale: f32, offset: i32) -> Self {
+        Self { scale, offset }
+    }
+
+    /// Resolve against a parent length in pixels
+    pub fn resolve(&self, parent_length: f32) -> f32 {
+        self.scale * parent_length + self.offset as f32
+    }
+
+    /// Interpolate towards `goal`, rounding the offset to whole pixels
+    pub fn lerp(&self, goal: &UDim, alpha: f32) -> Self {
+        Self::new(
+            self.scale + (goal.scale - self.scale) * alpha,
+            (self.offset as f32 + (goal.offset - self.offset) as f32 * alpha).round() as i32,
+        )
+    }
+}
+
+/// A two-dimensional GUI size or position
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct UDim2 {
+    pub x: UDim,
+    pub y: UDim,
+}
+
+impl UDim2 {
+    pub const fn new(x_scale: f32, x_offset: i32, y_scale: f32, y_offset: i32) -> Self {
+        Self {
+            x: UDim::new(x_scale, x_offset),
+            y: UDim::new(y_scale, y_offset),
+        }
+    }
+
+    /// Create a UDim2 from scales only (UDim2.fromScale)
+    pub const fn from_scale(x_scale: f32, y_scale: f32) -> Self {
+        Self::new(x_scale, 0, y_scale, 0)
+    }
+
+    /// Create a UDim2 from offsets only (UDim2.fromOffset)
+    pub const fn from_offset(x_offset: i32, y_offset: i32) -> Self {
+        Self::new(0.0, x_offset, 0.0, y_offset)
+    }
+
+    /// Interpolate towards `goal` (UDim2:Lerp)
+    pub fn lerp(&self, goal: &UDim2, alpha: f32) -> Self {
+        Self {
+            x: self.x.lerp(&goal.x, alpha),
+            y: self.y.lerp(&goal.y, alpha),
+        }
+    }
+}
+
+/// An axis-aligned box in world space
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Region3 {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Region3 {
+    /// Create a region between two opposite corners, given in any order
+    pub fn new(a: Vec3, b: Vec3) -> Self {
+        Self { min: a.min(b), max: a.max(b) }
+    }
+
+    /// Get the size of the region
+    pub fn size(&self) -> Vec3 {
+        self.max - self.min
+    }
+
+    /// Get the center of the region
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Grow the region outwards onto a grid of `resolution` studs
+    /// (Region3:ExpandToGrid)
+    pub fn expand_to_grid(&self, resolution: f32) -> Self {
+        Self {
+            min: (self.min / resolution).floor() * resolution,
+            max: (self.max / resolution).ceil() * resolution,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_brick_color() {
+        let red = BrickColor::from_name("Bright red").unwrap();
+        assert_eq!(red.number(), 21);
+        assert_eq!(red, BrickColor::red());
+        assert_eq!(red.rgb(), [196, 40, 28]);
+
+        assert_eq!(BrickColor::default().name(), "Medium stone grey");
+        assert_eq!(BrickColor::new(9999), BrickColor::default());
+        assert!(BrickColor::from_name("Not a color").is_none());
+    }
+
+    #[test]
+    fn test_cframe_transform() {
+        let cframe = CFrame::from_rotation(Vec3::new(0.0, 5.0, 0.0), Quat::from_rotation_y(std::f32::consts::FRAC_PI_2));
+        let world = cframe.point_to_world_space(Vec3::new(0.0, 0.0, -1.0));
+
+        assert!((world - Vec3::new(-1.0, 5.0, 0.0)).length() < 1e-5);
+        assert!((cframe.point_to_object_space(world) - Vec3::new(0.0, 0.0, -1.0)).length() < 1e-5);
+
+        let round_trip = cframe * cframe.inverse();
+        assert!(round_trip.position.length() < 1e-5);
+    }
+
+    #[test]
+    fn test_cframe_lerp() {
+        let start = CFrame::new(Vec3::ZERO);
+        let goal = CFrame::from_rotation(Vec3::new(10.0, 0.0, 0.0), Quat::from_rotation_y(std::f32::consts::FRAC_PI_2));
+        let half = start.lerp(&goal, 0.5);
+
+        assert!((half.position - Vec3::new(5.0, 0.0, 0.0)).length() < 1e-5);
+        assert!(half.to_quat().angle_between(Quat::from_rotation_y(std::f32::consts::FRAC_PI_4)) < 1e-4);
+    }
+
+    #[test]
+    fn test_color_and_udim() {
+        assert_eq!(BrickColor::white().color(), Color3::from_rgb(242, 243, 243));
+        assert_eq!(Color3::new(0.0, 0.0, 0.0).lerp(&Color3::new(1.0, 0.5, 0.0), 0.5), Color3::new(0.5, 0.25, 0.0));
+
+        let size = UDim2::new(0.5, 10, 1.0, -20);
+        assert_eq!(size.x.resolve(200.0), 110.0);
+        assert_eq!(UDim2::from_offset(0, 0).lerp(&UDim2::from_offset(10, 5), 0.5), UDim2::from_offset(5, 3));
+    }
+
+    #[test]
+    fn test_cframe_look_at() {
+        let cframe = CFrame::look_at(Vec3::ZERO, Vec3::new(10.0, 0.0, 0.0));
+
+        assert!((cframe.look_vector() - Vec3::X).length() < 1e-5);
+        assert!((cframe.up_vector() - Vec3::Y).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_region3_expand_to_grid() {
+        let region = Region3::new(Vec3::new(5.0, -1.0, 8.0), Vec3::new(1.0, 3.0, 0.0));
+        assert_eq!(region.min, Vec3::new(1.0, -1.0, 0.0));
+        assert_eq!(region.center(), Vec3::new(3.0, 1.0, 4.0));
+
+        let grid = region.expand_to_grid(4.0);
+        assert_eq!(grid.min, Vec3::new(0.0, -4.0, 0.0));
+        assert_eq!(grid.max, Vec3::new(8.0, 4.0, 8.0));
+    }
+}
@@ -0,0 +1,293 @@
+use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use glam::{Vec2, Vec3};
+use crate::instance::Instance;
+use crate::reflection::PropertyType;
+use crate::types::{BrickColor, CFrame, Color3, UDim, UDim2};
+
+/// A dynamically typed property value
+#[derive(Clone)]
+pub enum Variant {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Vector2(Vec2),
+    Vector3(Vec3),
+    CFrame(CFrame),
+    BrickColor(BrickColor),
+    Color3(Color3),
+    UDim(UDim),
+    UDim2(UDim2),
+    /// The value of an enum item, like 256 for Enum.Material.Plastic
+    Enum(i64),
+    /// Reference to another instance. Held weakly so properties can't keep
+    /// instances alive or form reference cycles.
+    Instance(Option<Weak<RefCell<Instance>>>),
+}
+
+impl Variant {
+    /// Create an instance reference
+    pub fn instance_ref(instance: Option<&Rc<RefCell<Instance>>>) -> Self {
+        Variant::Instance(instance.map(Rc::downgrade))
+    }
+
+    /// Get the reflection type of this value
+    pub fn property_type(&self) -> PropertyType {
+        match self {
+            Variant::Bool(_) => PropertyType::Bool,
+            Variant::Int(_) => PropertyType::Int,
+            Variant::Float(_) => PropertyType::Float,
+            Variant::String(_) => PropertyType::String,
+            Variant::Vector2(_) => PropertyType::Vector2,
+            Variant::Vector3(_) => PropertyType::Vector3,
+            Variant::CFrame(_) => PropertyType::CFrame,
+            Variant::BrickColor(_) => PropertyType::BrickColor,
+            Variant::Color3(_) => PropertyType::Color3,
+            Variant::UDim(_) => PropertyType::UDim,
+            Variant::UDim2(_) => PropertyType::UDim2,
+            Variant::Enum(_) => PropertyType::Enum,
+            Variant::Instance(_) => PropertyType::Instance,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Variant::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Get an integer, accepting both Int values and enum items
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Variant::Int(i) | Variant::Enum(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Get a number, accepting both Float and Int values
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Variant::Float(f) => Some(*f),
+            Variant::Int(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Variant::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_vector2(&self) -> Option<Vec2> {
+        match self {
+            Variant::Vector2(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_vector3(&self) -> Option<Vec3> {
+        match self {
+            Variant::Vector3(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_cframe(&self) -> Option<CFrame> {
+        match self {
+            Variant::CFrame(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    pub fn as_brick_color(&self) -> Option<BrickColor> {
+        match self {
+            Variant::BrickColor(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    pub fn as_color3(&self) -> Option<Color3> {
+        match self {
+            Variant::Color3(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    pub fn as_udim(&self) -> Option<UDim> {
+        match self {
+            Variant::UDim(u) => Some(*u),
+            _ => None,
+        }
+    }
+
+    pub fn as_udim2(&self) -> Option<UDim2> {
+        match self {
+            Variant::UDim2(u) => Some(*u),
+            _ => None,
+        }
+    }
+
+    /// Get the referenced instance, if it is set and still alive
+    pub fn as_instance(&self) -> Option<Rc<RefCell<Instance>>> {
+        match self {
+            Variant::Instance(Some(weak)) => weak.upgrade(),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for Variant {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Variant::Bool(a), Variant::Bool(b)) => a == b,
+            (Variant::Int(a), Variant::Int(b)) => a == b,
+            (Variant::Float(a), Variant::Float(b)) => a == b,
+            (Variant::String(a), Variant::String(b)) => a == b,
+            (Variant::Vector2(a), Variant::Vector2(b)) => a == b,
+            (Variant::Vector3(a), Variant::Vector3(b)) => a == b,
+            (Variant::CFrame(a), Variant::CFrame(b)) => a == b,
+            (Variant::BrickColor(a), Variant::BrickColor(b)) => a == b,
+            (Variant::Color3(a), Variant::Color3(b)) => a == b,
+            (Variant::UDim(a), Variant::UDim(b)) => a == b,
+            (Variant::UDim2(a), Variant::UDim2(b)) => a == b,
+            (Variant::Enum(a), Variant::Enum(b)) => a == b,
+            (Variant::Instance(a), Variant::Instance(b)) => match (a, b) {
+                (Some(a), Some(b)) => Weak::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Debug for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Variant::Bool(b) => write!(f, "Bool({})", b),
+            Variant::Int(i) => write!(f, "Int({})", i),
+            Variant::Float(v) => write!(f, "Float({})", v),
+            Variant::String(s) => write!(f, "String({:?})", s),
+            Variant::Vector2(v) => write!(f, "Vector2({}, {})", v.x, v.y),
+            Variant::Vector3(v) => write!(f, "Vector3({}, {}, {})", v.x, v.y, v.z),
+            Variant::CFrame(c) => write!(f, "CFrame({:?})", c.position),
+            Variant::BrickColor(c) => write!(f, "BrickColor({})", c.name()),
+            Variant::Color3(c) => write!(f, "Color3({}, {}, {})", c.r, c.g, c.b),
+            Variant::UDim(u) => write!(f, "UDim({}, {})", u.scale, u.offset),
+            Variant::UDim2(u) => write!(f, "UDim2({}, {}, {}, {})", u.x.scale, u.x.offset, u.y.scale, u.y.offset),
+            Variant::Enum(value) => write!(f, "Enum({})", value),
+            Variant::Instance(instance) => match instance.as_ref().and_then(|weak| weak.upgrade()) {
+                Some(instance) => write!(f, "Instance({})", instance.borrow().name()),
+                None => write!(f, "Instance(nil)"),
+            },
+        }
+    }
+}
+
+impl From<bool> for Variant {
+    fn from(value: bool) -> Self {
+        Variant::Bool(value)
+    }
+}
+
+impl From<i64> for Variant {
+    fn from(value: i64) -> Self {
+        Variant::Int(value)
+    }
+}
+
+impl From<f64> for Variant {
+    fn from(value: f64) -> Self {
+        Variant::Float(value)
+    }
+}
+
+impl From<&str> for Variant {
+    fn from(value: &str) -> Self {
+        Variant::String(value.to_string())
+    }
+}
+
+impl From<String> for Variant {
+    fn from(value: String) -> Self {
+        Variant::String(value)
+    }
+}
+
+impl From<Vec2> for Variant {
+    fn from(value: Vec2) -> Self {
+        Variant::Vector2(value)
+    }
+}
+
+impl From<Vec3> for Variant {
+    fn from(value: Vec3) -> Self {
+        Variant::Vector3(value)
+    }
+}
+
+impl From<CFrame> for Variant {
+    fn from(value: CFrame) -> Self {
+        Variant::CFrame(value)
+    }
+}
+
+impl From<BrickColor> for Variant {
+    fn from(value: BrickColor) -> Self {
+        Variant::BrickColor(value)
+    }
+}
+
+impl From<Color3> for Variant {
+    fn from(value: Color3) -> Self {
+        Variant::Color3(value)
+    }
+}
+
+impl From<UDim> for Variant {
+    fn from(value: UDim) -> Self {
+        Variant::UDim(value)
+    }
+}
+
+impl From<UDim2> for Variant {
+    fn from(value: UDim2) -> Self {
+        Variant::UDim2(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_conversions() {
+        assert_eq!(Variant::from(true).as_bool(), Some(true));
+        assert_eq!(Variant::from(3i64).as_float(), Some(3.0));
+        assert_eq!(Variant::from("hello").as_str(), Some("hello"));
+        assert_eq!(Variant::from(Vec3::ONE).as_vector3(), Some(Vec3::ONE));
+        assert_eq!(Variant::from(Vec2::ONE).as_vector2(), Some(Vec2::ONE));
+        assert_eq!(Variant::from(BrickColor::red()).property_type(), PropertyType::BrickColor);
+        assert!(Variant::from(1.5).as_bool().is_none());
+        assert_eq!(Variant::Enum(256).as_int(), Some(256));
+        assert_eq!(Variant::Enum(256).property_type(), PropertyType::Enum);
+        assert_ne!(Variant::Enum(256), Variant::Int(256));
+    }
+
+    #[test]
+    fn test_instance_ref() {
+        let instance = Instance::new();
+        let value = Variant::instance_ref(Some(&instance));
+
+        assert!(Rc::ptr_eq(&value.as_instance().unwrap(), &instance));
+        assert_eq!(value, Variant::instance_ref(Some(&instance)));
+        assert_ne!(value, Variant::instance_ref(None));
+
+        drop(instance);
+        assert!(value.as_instance().is_none());
+    }
+}
@@ -0,0 +1,508 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use glam::{Mat3, Vec3};
+use rnr_core::instance::Instance;
+use rnr_core::types::{BrickColor, CFrame};
+use rnr_core::variant::Variant;
+use crate::joint::JointInstance;
+use crate::part::BasePart;
+
+/// Where on the Head an accessory's AttachmentPoint is held, the top middle
+pub const HEAD_ATTACHMENT: CFrame = CFrame { position: Vec3::new(0.0, 0.5, 0.0), rotation: Mat3::IDENTITY };
+
+/// Name of the weld holding an accessory to the Head
+pub const ACCESSORY_WELD_NAME: &str = "HeadWeld";
+
+/// A limb of an R6 character (Enum.BodyPart)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limb {
+    Head = 0,
+    Torso = 1,
+    LeftArm = 2,
+    RightArm = 3,
+    LeftLeg = 4,
+    RightLeg = 5,
+}
+
+impl Limb {
+    /// Every limb, in enum order
+    pub const ALL: [Limb; 6] = [Limb::Head, Limb::Torso, Limb::LeftArm, Limb::RightArm, Limb::LeftLeg, Limb::RightLeg];
+
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Get the limb with an enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        Self::ALL.get(usize::try_from(value).ok()?).copied()
+    }
+
+    /// Get the name of the limb's part in a character
+    pub fn part_name(self) -> &'static str {
+        match self {
+            Limb::Head => "Head",
+            Limb::Torso => "Torso",
+            Limb::LeftArm => "Left Arm",
+            Limb::RightArm => "Right Arm",
+            Limb::LeftLeg => "Left Leg",
+            Limb::RightLeg => "Right Leg",
+        }
+    }
+
+    /// Get the BodyColors property coloring the limb
+    pub fn color_property(self) -> &'static str {
+        match self {
+            Limb::Head => "HeadColor",
+            Limb::Torso => "TorsoColor",
+            Limb::LeftArm => "LeftArmColor",
+            Limb::RightArm => "RightArmColor",
+            Limb::LeftLeg => "LeftLegColor",
+            Limb::RightLeg => "RightLegColor",
+        }
+    }
+
+    /// Get the color a limb has without BodyColors
+    pub fn default_color(self) -> BrickColor {
+        match self {
+            Limb::Torso => BrickColor::blue(),
+            Limb::LeftLeg | Limb::RightLeg => BrickColor::from_name("Br. yellowish green").unwrap_or_default(),
+            _ => BrickColor::yellow(),
+        }
+    }
+
+    /// Find the limb's part in a character
+    pub fn find_part(self, character: &Rc<RefCell<Instance>>) -> Option<BasePart> {
+        character.borrow().find_first_child(self.part_name()).as_ref().and_then(BasePart::from_instance)
+    }
+}
+
+/// Typed view over BodyColors, which recolors a character's limbs
+#[derive(Debug, Clone)]
+pub struct BodyColors {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl BodyColors {
+    /// Create new body colors matching the default character
+    pub fn new() -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name("Body Colors");
+            instance.set_class_name("BodyColors");
+            for limb in Limb::ALL {
+                instance.set_property(limb.color_property(), limb.default_color());
+            }
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as body colors, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("BodyColors") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the color of a limb
+    pub fn color(&self, limb: Limb) -> BrickColor {
+        self.instance.borrow().get_property(limb.color_property()).and_then(Variant::as_brick_color).unwrap_or_else(|| limb.default_color())
+    }
+
+    /// Set the color of a limb
+    pub fn set_color(&self, limb: Limb, color: BrickColor) {
+        self.instance.borrow_mut().set_property(limb.color_property(), color);
+    }
+
+    /// Recolor the limbs of a character
+    pub fn apply(&self, character: &Rc<RefCell<Instance>>) {
+        for limb in Limb::ALL {
+            if let Some(part) = limb.find_part(character) {
+                part.set_brick_color(self.color(limb));
+            }
+        }
+    }
+}
+
+impl Default for BodyColors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The kinds of clothing, which are drawn over the limbs they cover
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClothingKind {
+    /// Covers the torso and arms
+    Shirt,
+    /// Covers the torso and legs, under a shirt
+    Pants,
+    /// A decal on the front of the torso, over a shirt
+    ShirtGraphic,
+}
+
+impl ClothingKind {
+    /// Get the class of the clothing
+    pub fn class_name(self) -> &'static str {
+        match self {
+            ClothingKind::Shirt => "Shirt",
+            ClothingKind::Pants => "Pants",
+            ClothingKind::ShirtGraphic => "ShirtGraphic",
+        }
+    }
+
+    /// Get the kind of clothing of a class
+    pub fn from_class_name(class_name: &str) -> Option<Self> {
+        match class_name {
+            "Shirt" => Some(ClothingKind::Shirt),
+            "Pants" => Some(ClothingKind::Pants),
+            "ShirtGraphic" => Some(ClothingKind::ShirtGraphic),
+            _ => None,
+        }
+    }
+
+    /// Get the property holding the clothing's texture
+    pub fn template_property(self) -> &'static str {
+        match self {
+            ClothingKind::Shirt => "ShirtTemplate",
+            ClothingKind::Pants => "PantsTemplate",
+            ClothingKind::ShirtGraphic => "Graphic",
+        }
+    }
+
+    /// Get where the clothing is drawn, lowest first
+    pub fn layer(self) -> usize {
+        match self {
+            ClothingKind::Pants => 0,
+            ClothingKind::Shirt => 1,
+            ClothingKind::ShirtGraphic => 2,
+        }
+    }
+
+    /// Check if the clothing covers a limb
+    pub fn covers(self, limb: Limb) -> bool {
+        match self {
+            ClothingKind::Shirt => matches!(limb, Limb::Torso | Limb::LeftArm | Limb::RightArm),
+            ClothingKind::Pants => matches!(limb, Limb::Torso | Limb::LeftLeg | Limb::RightLeg),
+            ClothingKind::ShirtGraphic => limb == Limb::Torso,
+        }
+    }
+}
+
+/// Typed view over a Shirt, Pants or ShirtGraphic in a character
+#[derive(Debug, Clone)]
+pub struct Clothing {
+    instance: Rc<RefCell<Instance>>,
+    kind: ClothingKind,
+}
+
+impl Clothing {
+    /// Create new clothing with a texture
+    pub fn new(kind: ClothingKind, template: &str) -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name(kind.class_name());
+            instance.set_class_name(kind.class_name());
+            instance.set_property(kind.template_property(), template);
+        }
+
+        Self { instance, kind }
+    }
+
+    /// View an existing instance as clothing, if it is some
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        let kind = ClothingKind::from_class_name(instance.borrow().class_name())?;
+        Some(Self { instance: instance.clone(), kind })
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the kind of clothing
+    pub fn kind(&self) -> ClothingKind {
+        self.kind
+    }
+
+    /// Get the texture drawn over the limbs covered
+    pub fn template(&self) -> String {
+        self.instance.borrow().get_property(self.kind.template_property()).and_then(Variant::as_str).unwrap_or_default().to_string()
+    }
+
+    /// Set the texture drawn over the limbs covered
+    pub fn set_template(&self, template: &str) {
+        self.instance.borrow_mut().set_property(self.kind.template_property(), template);
+    }
+}
+
+/// Typed view over a Hat or Accessory, worn by welding its Handle to the
+/// character's Head
+#[derive(Debug, Clone)]
+pub struct Accessory {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl Accessory {
+    /// Create a new hat or accessory with a Handle of the given size
+    pub fn new(class_name: &str, handle_size: Vec3) -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name(class_name);
+            instance.set_class_name(class_name);
+            instance.set_property("AttachmentPoint", CFrame::IDENTITY);
+        }
+        let handle = BasePart::new("Part");
+        handle.instance().borrow_mut().set_name("Handle");
+        handle.set_size(handle_size);
+        Instance::set_parent(handle.instance(), Some(instance.clone()));
+
+        Self { instance }
+    }
+
+    /// View an existing instance as an accessory, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("Accoutrement") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the part that is worn
+    pub fn handle(&self) -> Option<BasePart> {
+        self.instance.borrow().find_first_child("Handle").as_ref().and_then(BasePart::from_instance)
+    }
+
+    /// Get the point of the Handle held at the top of the Head
+    pub fn attachment_point(&self) -> CFrame {
+        self.instance.borrow().get_property("AttachmentPoint").and_then(Variant::as_cframe).unwrap_or_default()
+    }
+
+    /// Set the point of the Handle held at the top of the Head
+    pub fn set_attachment_point(&self, attachment_point: CFrame) {
+        self.instance.borrow_mut().set_property("AttachmentPoint", attachment_point);
+    }
+
+    /// Weld the Handle to the Head of a character, moving it into place.
+    /// Returns None if either is missing or the accessory is already worn.
+    pub fn attach(&self, character: &Rc<RefCell<Instance>>) -> Option<JointInstance> {
+        let handle = self.handle()?;
+        let head = Limb::Head.find_part(character)?;
+        if handle.instance().borrow().find_first_child(ACCESSORY_WELD_NAME).is_some() {
+            return None;
+        }
+
+        let attachment_point = self.attachment_point();
+        handle.set_cframe(head.cframe() * HEAD_ATTACHMENT * attachment_point.inverse());
+        let weld = JointInstance::new("Weld", &head, &handle);
+        {
+            let mut instance = weld.instance().borrow_mut();
+            instance.set_name(ACCESSORY_WELD_NAME);
+            instance.set_property("C0", HEAD_ATTACHMENT);
+            instance.set_property("C1", attachment_point);
+        }
+        Instance::set_parent(weld.instance(), Some(handle.instance().clone()));
+        Some(weld)
+    }
+}
+
+/// Typed view over a CharacterMesh, which replaces the mesh of a limb
+#[derive(Debug, Clone)]
+pub struct CharacterMesh {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl CharacterMesh {
+    /// Create a new character mesh for a limb
+    pub fn new(body_part: Limb, mesh_id: &str) -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name("CharacterMesh");
+            instance.set_class_name("CharacterMesh");
+            instance.set_property("BodyPart", Variant::Enum(body_part.value()));
+            instance.set_property("MeshId", mesh_id);
+            instance.set_property("BaseTextureId", "");
+            instance.set_property("OverlayTextureId", "");
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a character mesh, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("CharacterMesh") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the limb whose mesh is replaced
+    pub fn body_part(&self) -> Limb {
+        self.instance.borrow().get_property("BodyPart").and_then(Variant::as_int).and_then(Limb::from_value).unwrap_or(Limb::Head)
+    }
+
+    /// Get the mesh drawn instead of the limb's block
+    pub fn mesh_id(&self) -> String {
+        self.string_property("MeshId")
+    }
+
+    /// Get the texture drawn under clothing
+    pub fn base_texture_id(&self) -> String {
+        self.string_property("BaseTextureId")
+    }
+
+    /// Set the texture drawn under clothing
+    pub fn set_base_texture_id(&self, texture: &str) {
+        self.instance.borrow_mut().set_property("BaseTextureId", texture);
+    }
+
+    /// Get the texture drawn over clothing
+    pub fn overlay_texture_id(&self) -> String {
+        self.string_property("OverlayTextureId")
+    }
+
+    /// Set the texture drawn over clothing
+    pub fn set_overlay_texture_id(&self, texture: &str) {
+        self.instance.borrow_mut().set_property("OverlayTextureId", texture);
+    }
+
+    fn string_property(&self, name: &str) -> String {
+        self.instance.borrow().get_property(name).and_then(Variant::as_str).unwrap_or_default().to_string()
+    }
+}
+
+/// How a limb of a character is drawn
+#[derive(Debug, Clone)]
+pub struct LimbAppearance {
+    pub limb: Limb,
+    pub part: BasePart,
+    /// Mesh drawn instead of the limb's block
+    pub mesh_id: Option<String>,
+    /// Textures drawn over the limb, lowest first
+    pub textures: Vec<String>,
+}
+
+/// Apply the appearance items in a character: BodyColors recolor the limbs
+/// and Hats and Accessories are welded to the Head. Clothing and
+/// CharacterMeshes are drawn from `limb_appearances`.
+pub fn apply_appearance(character: &Rc<RefCell<Instance>>) {
+    let children = character.borrow().children().to_vec();
+    for child in &children {
+        if let Some(body_colors) = BodyColors::from_instance(child) {
+            body_colors.apply(character);
+        } else if let Some(accessory) = Accessory::from_instance(child) {
+            accessory.attach(character);
+        }
+    }
+}
+
+/// Work out how each limb of a character is drawn from its clothing and
+/// CharacterMeshes
+pub fn limb_appearances(character: &Rc<RefCell<Instance>>) -> Vec<LimbAppearance> {
+    let children = character.borrow().children().to_vec();
+    let mut clothing: Vec<Clothing> = children.iter().filter_map(Clothing::from_instance).collect();
+    clothing.sort_by_key(|clothing| clothing.kind().layer());
+    let meshes: Vec<CharacterMesh> = children.iter().filter_map(CharacterMesh::from_instance).collect();
+
+    Limb::ALL.into_iter().filter_map(|limb| {
+        let part = limb.find_part(character)?;
+        let mesh = meshes.iter().rev().find(|mesh| mesh.body_part() == limb);
+        let mut textures = Vec::new();
+        textures.extend(mesh.map(CharacterMesh::base_texture_id));
+        textures.extend(clothing.iter().filter(|clothing| clothing.kind().covers(limb)).map(Clothing::template));
+        textures.extend(mesh.map(CharacterMesh::overlay_texture_id));
+        textures.retain(|texture| !texture.is_empty());
+
+        Some(LimbAppearance {
+            limb,
+            part,
+            mesh_id: mesh.map(CharacterMesh::mesh_id).filter(|mesh_id| !mesh_id.is_empty()),
+            textures,
+        })
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part::create_model;
+
+    fn character() -> Rc<RefCell<Instance>> {
+        let character = create_model("Player1");
+        for limb in Limb::ALL {
+            let part = BasePart::new("Part");
+            part.instance().borrow_mut().set_name(limb.part_name());
+            Instance::set_parent(part.instance(), Some(character.clone()));
+        }
+        Limb::Head.find_part(&character).unwrap().set_position(Vec3::new(0.0, 4.5, 0.0));
+        character
+    }
+
+    #[test]
+    fn test_body_colors_and_hats() {
+        let character = character();
+        let body_colors = BodyColors::new();
+        assert_eq!(body_colors.color(Limb::Torso), BrickColor::blue());
+        body_colors.set_color(Limb::LeftLeg, BrickColor::red());
+        Instance::set_parent(body_colors.instance(), Some(character.clone()));
+        let hat = Accessory::new("Hat", Vec3::new(2.0, 1.0, 2.0));
+        hat.set_attachment_point(CFrame::new(Vec3::new(0.0, -0.5, 0.0)));
+        Instance::set_parent(hat.instance(), Some(character.clone()));
+
+        apply_appearance(&character);
+        assert_eq!(Limb::LeftLeg.find_part(&character).unwrap().brick_color(), BrickColor::red());
+        assert_eq!(Limb::Head.find_part(&character).unwrap().brick_color(), BrickColor::yellow());
+        let handle = hat.handle().unwrap();
+        assert_eq!(handle.position(), Vec3::new(0.0, 5.5, 0.0));
+        let weld = JointInstance::from_instance(&handle.instance().borrow().find_first_child(ACCESSORY_WELD_NAME).unwrap()).unwrap();
+        assert_eq!(weld.part0().unwrap().instance().borrow().name(), "Head");
+        assert_eq!((weld.part0().unwrap().cframe() * weld.c0() * weld.c1().inverse()).position, handle.position());
+
+        // Spawning again doesn't weld twice
+        assert!(hat.attach(&character).is_none());
+    }
+
+    #[test]
+    fn test_clothing_layers_over_limbs() {
+        let character = character();
+        for clothing in [Clothing::new(ClothingKind::ShirtGraphic, "tee"), Clothing::new(ClothingKind::Shirt, "shirt"), Clothing::new(ClothingKind::Pants, "pants")] {
+            Instance::set_parent(clothing.instance(), Some(character.clone()));
+        }
+        let mesh = CharacterMesh::new(Limb::LeftArm, "arm.mesh");
+        mesh.set_overlay_texture_id("arm-overlay");
+        Instance::set_parent(mesh.instance(), Some(character.clone()));
+
+        let appearances = limb_appearances(&character);
+        let textures_of = |limb: Limb| appearances.iter().find(|appearance| appearance.limb == limb).unwrap().textures.clone();
+        assert_eq!(textures_of(Limb::Torso), vec!["pants", "shirt", "tee"]);
+        assert_eq!(textures_of(Limb::LeftArm), vec!["shirt", "arm-overlay"]);
+        assert_eq!(textures_of(Limb::RightLeg), vec!["pants"]);
+        assert!(textures_of(Limb::Head).is_empty());
+        assert_eq!(appearances[Limb::LeftArm.value() as usize].mesh_id.as_deref(), Some("arm.mesh"));
+        assert!(appearances[Limb::Head.value() as usize].mesh_id.is_none());
+    }
+}
@@ -0,0 +1,270 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use glam::Vec3;
+use rnr_core::instance::Instance;
+use rnr_core::types::CFrame;
+use rnr_core::variant::Variant;
+use crate::part::BasePart;
+
+/// The classic BodyMover classes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyMoverKind {
+    /// Pushes towards a target Velocity
+    Velocity,
+    /// Pulls towards a target Position
+    Position,
+    /// Turns towards a target CFrame's orientation
+    Gyro,
+    /// Applies a constant world space Force
+    Force,
+    /// Spins towards a target AngularVelocity
+    AngularVelocity,
+    /// Applies a constant Force in the part's space, at Location
+    Thrust,
+}
+
+impl BodyMoverKind {
+    /// Get the class name of this kind of body mover
+    pub fn class_name(self) -> &'static str {
+        match self {
+            BodyMoverKind::Velocity => "BodyVelocity",
+            BodyMoverKind::Position => "BodyPosition",
+            BodyMoverKind::Gyro => "BodyGyro",
+            BodyMoverKind::Force => "BodyForce",
+            BodyMoverKind::AngularVelocity => "BodyAngularVelocity",
+            BodyMoverKind::Thrust => "BodyThrust",
+        }
+    }
+
+    /// Look up a body mover kind by class name
+    pub fn from_class_name(class_name: &str) -> Option<Self> {
+        match class_name {
+            "BodyVelocity" => Some(BodyMoverKind::Velocity),
+            "BodyPosition" => Some(BodyMoverKind::Position),
+            "BodyGyro" => Some(BodyMoverKind::Gyro),
+            "BodyForce" => Some(BodyMoverKind::Force),
+            "BodyAngularVelocity" => Some(BodyMoverKind::AngularVelocity),
+            "BodyThrust" => Some(BodyMoverKind::Thrust),
+            _ => None,
+        }
+    }
+}
+
+/// Typed view over a BodyMover (BodyVelocity, BodyPosition, BodyGyro,
+/// BodyForce, BodyAngularVelocity, BodyThrust).
+///
+/// A body mover acts on the part it is parented to. Movers that chase a
+/// target use P (and D where the class has one) as gains, limited per axis
+/// by MaxForce or MaxTorque.
+#[derive(Debug, Clone)]
+pub struct BodyMover {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl BodyMover {
+    /// Create a new body mover with the class's default properties
+    pub fn new(kind: BodyMoverKind) -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name(kind.class_name());
+            instance.set_class_name(kind.class_name());
+            match kind {
+                BodyMoverKind::Velocity => {
+                    instance.set_property("Velocity", Vec3::new(0.0, 2.0, 0.0));
+                    instance.set_property("MaxForce", Vec3::splat(4000.0));
+                    instance.set_property("P", 1250.0);
+                }
+                BodyMoverKind::Position => {
+                    instance.set_property("Position", Vec3::new(0.0, 50.0, 0.0));
+                    instance.set_property("MaxForce", Vec3::splat(4000.0));
+                    instance.set_property("P", 10000.0);
+                    instance.set_property("D", 1250.0);
+                }
+                BodyMoverKind::Gyro => {
+                    instance.set_property("CFrame", CFrame::IDENTITY);
+                    instance.set_property("MaxTorque", Vec3::new(400000.0, 0.0, 400000.0));
+                    instance.set_property("P", 3000.0);
+                    instance.set_property("D", 500.0);
+                }
+                BodyMoverKind::Force => {
+                    instance.set_property("Force", Vec3::ZERO);
+                }
+                BodyMoverKind::AngularVelocity => {
+                    instance.set_property("AngularVelocity", Vec3::new(0.0, 2.0, 0.0));
+                    instance.set_property("MaxTorque", Vec3::splat(4000.0));
+                    instance.set_property("P", 1250.0);
+                }
+                BodyMoverKind::Thrust => {
+                    instance.set_property("Force", Vec3::ZERO);
+                    instance.set_property("Location", Vec3::ZERO);
+                }
+            }
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a body mover, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("BodyMover") && BodyMoverKind::from_class_name(instance.borrow().class_name()).is_some() {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get which body mover class this is
+    pub fn kind(&self) -> BodyMoverKind {
+        BodyMoverKind::from_class_name(self.instance.borrow().class_name()).unwrap_or(BodyMoverKind::Force)
+    }
+
+    /// Get the part the mover acts on: its parent, if that is a part
+    pub fn part(&self) -> Option<BasePart> {
+        self.instance.borrow().parent().as_ref().and_then(BasePart::from_instance)
+    }
+
+    fn vector3(&self, name: &str) -> Vec3 {
+        self.instance.borrow().get_property(name).and_then(Variant::as_vector3).unwrap_or_default()
+    }
+
+    fn float(&self, name: &str) -> f64 {
+        self.instance.borrow().get_property(name).and_then(Variant::as_float).unwrap_or_default()
+    }
+
+    /// Get the target velocity of a BodyVelocity
+    pub fn velocity(&self) -> Vec3 {
+        self.vector3("Velocity")
+    }
+
+    /// Set the target velocity of a BodyVelocity
+    pub fn set_velocity(&self, velocity: Vec3) {
+        self.instance.borrow_mut().set_property("Velocity", velocity);
+    }
+
+    /// Get the target position of a BodyPosition
+    pub fn position(&self) -> Vec3 {
+        self.vector3("Position")
+    }
+
+    /// Set the target position of a BodyPosition
+    pub fn set_position(&self, position: Vec3) {
+        self.instance.borrow_mut().set_property("Position", position);
+    }
+
+    /// Get the target orientation of a BodyGyro
+    pub fn cframe(&self) -> CFrame {
+        self.instance.borrow().get_property("CFrame").and_then(Variant::as_cframe).unwrap_or_default()
+    }
+
+    /// Set the target orientation of a BodyGyro
+    pub fn set_cframe(&self, cframe: CFrame) {
+        self.instance.borrow_mut().set_property("CFrame", cframe);
+    }
+
+    /// Get the target angular velocity of a BodyAngularVelocity, in radians
+    /// per second
+    pub fn angular_velocity(&self) -> Vec3 {
+        self.vector3("AngularVelocity")
+    }
+
+    /// Set the target angular velocity of a BodyAngularVelocity
+    pub fn set_angular_velocity(&self, angular_velocity: Vec3) {
+        self.instance.borrow_mut().set_property("AngularVelocity", angular_velocity);
+    }
+
+    /// Get the force of a BodyForce (world space) or BodyThrust (part space)
+    pub fn force(&self) -> Vec3 {
+        self.vector3("Force")
+    }
+
+    /// Set the force of a BodyForce or BodyThrust
+    pub fn set_force(&self, force: Vec3) {
+        self.instance.borrow_mut().set_property("Force", force);
+    }
+
+    /// Get where on the part a BodyThrust pushes, in part space
+    pub fn location(&self) -> Vec3 {
+        self.vector3("Location")
+    }
+
+    /// Set where on the part a BodyThrust pushes
+    pub fn set_location(&self, location: Vec3) {
+        self.instance.borrow_mut().set_property("Location", location);
+    }
+
+    /// Get the largest force the mover may apply along each axis
+    pub fn max_force(&self) -> Vec3 {
+        self.vector3("MaxForce")
+    }
+
+    /// Set the largest force the mover may apply along each axis
+    pub fn set_max_force(&self, max_force: Vec3) {
+        self.instance.borrow_mut().set_property("MaxForce", max_force.abs());
+    }
+
+    /// Get the largest torque the mover may apply about each axis
+    pub fn max_torque(&self) -> Vec3 {
+        self.vector3("MaxTorque")
+    }
+
+    /// Set the largest torque the mover may apply about each axis
+    pub fn set_max_torque(&self, max_torque: Vec3) {
+        self.instance.borrow_mut().set_property("MaxTorque", max_torque.abs());
+    }
+
+    /// Get the proportional gain
+    pub fn p(&self) -> f64 {
+        self.float("P")
+    }
+
+    /// Set the proportional gain
+    pub fn set_p(&self, p: f64) {
+        self.instance.borrow_mut().set_property("P", p.max(0.0));
+    }
+
+    /// Get the damping gain
+    pub fn d(&self) -> f64 {
+        self.float("D")
+    }
+
+    /// Set the damping gain
+    pub fn set_d(&self, d: f64) {
+        self.instance.borrow_mut().set_property("D", d.max(0.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_body_mover_defaults_and_part() {
+        let body_position = BodyMover::new(BodyMoverKind::Position);
+        assert!(body_position.instance().borrow().is_a("BodyMover"));
+        assert_eq!(body_position.kind(), BodyMoverKind::Position);
+        assert_eq!(body_position.position(), Vec3::new(0.0, 50.0, 0.0));
+        assert_eq!(body_position.max_force(), Vec3::splat(4000.0));
+        assert_eq!(body_position.p(), 10000.0);
+        assert_eq!(body_position.d(), 1250.0);
+
+        let gyro = BodyMover::new(BodyMoverKind::Gyro);
+        assert_eq!(gyro.max_torque(), Vec3::new(400000.0, 0.0, 400000.0));
+        gyro.set_max_torque(Vec3::new(-1.0, 2.0, 3.0));
+        assert_eq!(gyro.max_torque(), Vec3::new(1.0, 2.0, 3.0));
+
+        // Movers act on the part they are parented to
+        assert!(body_position.part().is_none());
+        let part = BasePart::new("Part");
+        Instance::set_parent(body_position.instance(), Some(part.instance().clone()));
+        assert!(Rc::ptr_eq(body_position.part().unwrap().instance(), part.instance()));
+
+        assert!(BodyMover::from_instance(part.instance()).is_none());
+        assert!(BodyMover::from_instance(gyro.instance()).is_some());
+    }
+}
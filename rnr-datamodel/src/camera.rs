@@ -0,0 +1,200 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use glam::{Vec2, Vec3};
+use rnr_core::instance::Instance;
+use rnr_core::types::CFrame;
+use rnr_core::variant::Variant;
+
+/// Default vertical field of view of a new camera, in degrees
+pub const DEFAULT_FIELD_OF_VIEW: f64 = 70.0;
+
+/// Smallest field of view a camera accepts, in degrees
+pub const MIN_FIELD_OF_VIEW: f64 = 1.0;
+
+/// Largest field of view a camera accepts, in degrees
+pub const MAX_FIELD_OF_VIEW: f64 = 120.0;
+
+/// How a camera is controlled (Enum.CameraType)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraType {
+    Fixed = 0,
+    Attach = 1,
+    Watch = 2,
+    Track = 3,
+    Follow = 4,
+    Custom = 5,
+    Scriptable = 6,
+}
+
+impl CameraType {
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Look up a camera type by enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(CameraType::Fixed),
+            1 => Some(CameraType::Attach),
+            2 => Some(CameraType::Watch),
+            3 => Some(CameraType::Track),
+            4 => Some(CameraType::Follow),
+            5 => Some(CameraType::Custom),
+            6 => Some(CameraType::Scriptable),
+            _ => None,
+        }
+    }
+}
+
+/// Typed view over a Camera instance.
+///
+/// The camera's CFrame looks down its -Z axis, the same convention the
+/// renderer's camera uses, so the two can be synced without conversion.
+#[derive(Debug, Clone)]
+pub struct Camera {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl Camera {
+    /// Create a new camera looking at the origin
+    pub fn new() -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name("Camera");
+            instance.set_class_name("Camera");
+            instance.set_property("CFrame", CFrame::look_at(Vec3::new(0.0, 20.0, 20.0), Vec3::ZERO));
+            instance.set_property("Focus", CFrame::IDENTITY);
+            instance.set_property("FieldOfView", DEFAULT_FIELD_OF_VIEW);
+            instance.set_property("CameraType", Variant::Enum(CameraType::Fixed.value()));
+            instance.set_property("CameraSubject", Variant::instance_ref(None));
+            instance.set_property("ViewportSize", Vec2::new(1.0, 1.0));
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a camera, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("Camera") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get where the camera is and which way it looks
+    pub fn cframe(&self) -> CFrame {
+        self.instance.borrow().get_property("CFrame").and_then(Variant::as_cframe).unwrap_or_default()
+    }
+
+    /// Move and rotate the camera
+    pub fn set_cframe(&self, cframe: CFrame) {
+        self.instance.borrow_mut().set_property("CFrame", cframe);
+    }
+
+    /// Get the point the camera is focused on
+    pub fn focus(&self) -> CFrame {
+        self.instance.borrow().get_property("Focus").and_then(Variant::as_cframe).unwrap_or_default()
+    }
+
+    /// Set the point the camera is focused on
+    pub fn set_focus(&self, focus: CFrame) {
+        self.instance.borrow_mut().set_property("Focus", focus);
+    }
+
+    /// Get the vertical field of view in degrees
+    pub fn field_of_view(&self) -> f64 {
+        self.instance.borrow().get_property("FieldOfView").and_then(Variant::as_float).unwrap_or(DEFAULT_FIELD_OF_VIEW)
+    }
+
+    /// Set the vertical field of view in degrees, clamped to 1-120
+    pub fn set_field_of_view(&self, field_of_view: f64) {
+        let field_of_view = field_of_view.clamp(MIN_FIELD_OF_VIEW, MAX_FIELD_OF_VIEW);
+        self.instance.borrow_mut().set_property("FieldOfView", field_of_view);
+    }
+
+    /// Get how the camera is controlled
+    pub fn camera_type(&self) -> CameraType {
+        self.instance.borrow()
+            .get_property("CameraType")
+            .and_then(Variant::as_int)
+            .and_then(CameraType::from_value)
+            .unwrap_or(CameraType::Fixed)
+    }
+
+    /// Set how the camera is controlled
+    pub fn set_camera_type(&self, camera_type: CameraType) {
+        self.instance.borrow_mut().set_property("CameraType", Variant::Enum(camera_type.value()));
+    }
+
+    /// Get the instance the camera follows, usually a Humanoid or part
+    pub fn camera_subject(&self) -> Option<Rc<RefCell<Instance>>> {
+        self.instance.borrow().get_property("CameraSubject").and_then(Variant::as_instance)
+    }
+
+    /// Set the instance the camera follows
+    pub fn set_camera_subject(&self, subject: Option<&Rc<RefCell<Instance>>>) {
+        self.instance.borrow_mut().set_property("CameraSubject", Variant::instance_ref(subject));
+    }
+
+    /// Get the size of the viewport in pixels
+    pub fn viewport_size(&self) -> Vec2 {
+        self.instance.borrow().get_property("ViewportSize").and_then(Variant::as_vector2).unwrap_or(Vec2::ONE)
+    }
+
+    /// Set the size of the viewport in pixels. This is driven by the engine,
+    /// scripts can only read it.
+    pub fn set_viewport_size(&self, viewport_size: Vec2) {
+        self.instance.borrow_mut().set_property("ViewportSize", viewport_size.max(Vec2::ONE));
+    }
+
+    /// Get the viewport's width divided by its height
+    pub fn aspect_ratio(&self) -> f32 {
+        let viewport_size = self.viewport_size();
+        viewport_size.x / viewport_size.y
+    }
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_camera_defaults() {
+        let camera = Camera::new();
+
+        assert_eq!(camera.field_of_view(), DEFAULT_FIELD_OF_VIEW);
+        assert_eq!(camera.camera_type(), CameraType::Fixed);
+        assert!(camera.camera_subject().is_none());
+        assert!((camera.cframe().look_vector() - Vec3::new(0.0, -1.0, -1.0).normalize()).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_camera_properties() {
+        let camera = Camera::new();
+        let subject = Instance::new();
+
+        camera.set_field_of_view(500.0);
+        camera.set_camera_type(CameraType::Scriptable);
+        camera.set_camera_subject(Some(&subject));
+        camera.set_viewport_size(Vec2::new(800.0, 600.0));
+
+        assert_eq!(camera.field_of_view(), MAX_FIELD_OF_VIEW);
+        assert_eq!(camera.camera_type(), CameraType::Scriptable);
+        assert!(Rc::ptr_eq(&camera.camera_subject().unwrap(), &subject));
+        assert!((camera.aspect_ratio() - 4.0 / 3.0).abs() < 1e-6);
+    }
+}
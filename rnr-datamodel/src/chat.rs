@@ -0,0 +1,573 @@
+use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
+use glam::Vec3;
+use rnr_core::instance::Instance;
+use rnr_core::types::BrickColor;
+use crate::part::BasePart;
+use crate::players::{Player, Players};
+
+/// Longest message a player can send, in characters
+pub const MAX_MESSAGE_LENGTH: usize = 200;
+
+/// How long a chat bubble stays above a character's head, in seconds
+pub const BUBBLE_LIFETIME: f64 = 15.0;
+
+/// Most bubbles stacked above one head. Older ones are dropped first.
+pub const MAX_BUBBLES_PER_SPEAKER: usize = 3;
+
+/// How far above the head part's center bubbles are drawn, in studs
+pub const BUBBLE_OFFSET: Vec3 = Vec3::new(0.0, 2.5, 0.0);
+
+/// User id used as the speaker of system messages
+pub const SYSTEM_USER_ID: i64 = 0;
+
+/// Who a message is delivered to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatTarget {
+    /// Every player
+    All,
+    /// Players on the team with this color
+    Team(BrickColor),
+    /// One player, by user id. The speaker gets a copy.
+    Whisper(i64),
+    /// Only the speaker, used for command replies and errors
+    System,
+}
+
+/// A message after filtering and routing
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatMessage {
+    pub speaker_user_id: i64,
+    pub speaker_name: String,
+    pub text: String,
+    pub target: ChatTarget,
+}
+
+/// A message on its way to one player's client
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatDelivery {
+    pub recipient_user_id: i64,
+    pub message: ChatMessage,
+}
+
+/// Arguments of Player.Chatted: the unfiltered message and, for whispers,
+/// who it was sent to
+#[derive(Debug, Clone)]
+pub struct PlayerChatted {
+    pub message: String,
+    pub recipient: Option<Player>,
+}
+
+/// A speech bubble shown above a character's head
+#[derive(Debug, Clone)]
+pub struct ChatBubble {
+    pub speaker_user_id: i64,
+    pub text: String,
+    adornee: Weak<RefCell<Instance>>,
+    expires_at: f64,
+}
+
+impl ChatBubble {
+    /// Get the part the bubble floats above, if it still exists
+    pub fn adornee(&self) -> Option<Rc<RefCell<Instance>>> {
+        self.adornee.upgrade()
+    }
+
+    /// Get where the bubble should be drawn in world space
+    pub fn world_position(&self) -> Option<Vec3> {
+        let adornee = self.adornee()?;
+        BasePart::from_instance(&adornee).map(|part| part.position() + BUBBLE_OFFSET)
+    }
+}
+
+/// Chat errors
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ChatError {
+    #[error("Message is empty")]
+    EmptyMessage,
+    #[error("Message is longer than {MAX_MESSAGE_LENGTH} characters")]
+    MessageTooLong,
+    #[error("Unknown command '/{0}'")]
+    UnknownCommand(String),
+    #[error("You are not allowed to use '/{0}'")]
+    NotAuthorized(String),
+    #[error("No player named '{0}'")]
+    UnknownPlayer(String),
+    #[error("You are not on a team")]
+    NoTeam,
+    #[error("{0}")]
+    CommandFailed(String),
+}
+
+/// Handler for a slash command. Gets the speaker and the words after the
+/// command name, and returns an optional reply for the speaker.
+pub type CommandHandler = Box<dyn FnMut(&Player, &[&str]) -> Result<Option<String>, String>>;
+
+/// Hook that rewrites a message before it is delivered, e.g. to mask
+/// profanity. Gets the speaker and the raw text.
+pub type TextFilter = Box<dyn FnMut(&Player, &str) -> String>;
+
+struct Command {
+    admin_only: bool,
+    handler: Rc<RefCell<CommandHandler>>,
+}
+
+/// Chat service - routes player messages, runs slash commands and tracks
+/// bubble chat.
+///
+/// Routed messages are queued as deliveries, one per recipient, for the
+/// network layer to send to each client with `take_deliveries`.
+pub struct Chat {
+    instance: Rc<RefCell<Instance>>,
+    commands: BTreeMap<String, Command>,
+    admins: HashSet<i64>,
+    filter: Option<Rc<RefCell<TextFilter>>>,
+    deliveries: Vec<ChatDelivery>,
+    bubbles: Vec<ChatBubble>,
+    /// Time of the last update in seconds
+    time: f64,
+}
+
+impl Chat {
+    /// Create a new Chat service with bubble chat on
+    pub fn new() -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("Chat");
+        instance.borrow_mut().set_class_name("Chat");
+        instance.borrow_mut().set_property("BubbleChatEnabled", true);
+
+        Rc::new(RefCell::new(Self {
+            instance,
+            commands: BTreeMap::new(),
+            admins: HashSet::new(),
+            filter: None,
+            deliveries: Vec::new(),
+            bubbles: Vec::new(),
+            time: 0.0,
+        }))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Check if messages to everyone or a team show bubbles above the
+    /// speaker's head
+    pub fn bubble_chat_enabled(&self) -> bool {
+        self.instance.borrow().get_property("BubbleChatEnabled").and_then(|value| value.as_bool()).unwrap_or(true)
+    }
+
+    /// Turn bubble chat on or off
+    pub fn set_bubble_chat_enabled(&mut self, enabled: bool) {
+        self.instance.borrow_mut().set_property("BubbleChatEnabled", enabled);
+    }
+
+    /// Register a slash command. Names are matched without the slash and
+    /// ignoring case. Registering a name again replaces the old command.
+    pub fn register_command(&mut self, name: &str, admin_only: bool, handler: CommandHandler) {
+        self.commands.insert(name.to_ascii_lowercase(), Command {
+            admin_only,
+            handler: Rc::new(RefCell::new(handler)),
+        });
+    }
+
+    /// Remove a slash command
+    pub fn unregister_command(&mut self, name: &str) {
+        self.commands.remove(&name.to_ascii_lowercase());
+    }
+
+    /// Allow or forbid a player to use admin commands
+    pub fn set_admin(&mut self, user_id: i64, admin: bool) {
+        if admin {
+            self.admins.insert(user_id);
+        } else {
+            self.admins.remove(&user_id);
+        }
+    }
+
+    /// Check if a player can use admin commands
+    pub fn is_admin(&self, user_id: i64) -> bool {
+        self.admins.contains(&user_id)
+    }
+
+    /// Install the hook every message passes through before delivery
+    pub fn set_filter(&mut self, filter: TextFilter) {
+        self.filter = Some(Rc::new(RefCell::new(filter)));
+    }
+
+    /// Remove the filter hook, delivering messages as they are sent
+    pub fn clear_filter(&mut self) {
+        self.filter = None;
+    }
+
+    /// Take the deliveries queued since the last call
+    pub fn take_deliveries(&mut self) -> Vec<ChatDelivery> {
+        std::mem::take(&mut self.deliveries)
+    }
+
+    /// Get the bubbles currently shown
+    pub fn bubbles(&self) -> &[ChatBubble] {
+        &self.bubbles
+    }
+
+    /// Expire old bubbles and ones whose head is gone
+    pub fn update(&mut self, time: f64) {
+        self.time = time;
+        self.bubbles.retain(|bubble| bubble.expires_at > time && bubble.adornee.upgrade().is_some());
+    }
+
+    /// Handle a message typed by a player.
+    ///
+    /// Messages starting with '/' are commands. `/w <name> <message>` and
+    /// `/whisper` send a whisper, `/t <message>` and `/team` send to the
+    /// speaker's team, and anything else runs a registered command. Every
+    /// message fires the speaker's Chatted signal with the raw text, then
+    /// ordinary messages are filtered and queued for delivery.
+    pub fn send(
+        chat: &Rc<RefCell<Self>>,
+        players: &Rc<RefCell<Players>>,
+        speaker: &Player,
+        text: &str,
+        target: ChatTarget,
+    ) -> Result<(), ChatError> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(ChatError::EmptyMessage);
+        }
+        if text.chars().count() > MAX_MESSAGE_LENGTH {
+            return Err(ChatError::MessageTooLong);
+        }
+
+        let Some(command_line) = text.strip_prefix('/') else {
+            return Self::post(chat, players, speaker, text, text, target);
+        };
+
+        let mut words = command_line.split_whitespace();
+        let name = words.next().unwrap_or_default().to_ascii_lowercase();
+        let args: Vec<&str> = words.collect();
+
+        match name.as_str() {
+            "w" | "whisper" => {
+                let Some((recipient_name, words)) = args.split_first() else {
+                    return Err(ChatError::CommandFailed("Usage: /w <player> <message>".to_string()));
+                };
+                let recipient = players.borrow()
+                    .get_player_by_name(recipient_name)
+                    .ok_or_else(|| ChatError::UnknownPlayer(recipient_name.to_string()))?;
+                let message = words.join(" ");
+                if message.is_empty() {
+                    return Err(ChatError::EmptyMessage);
+                }
+                Self::post(chat, players, speaker, text, &message, ChatTarget::Whisper(recipient.user_id()))
+            }
+            "t" | "team" => {
+                let message = args.join(" ");
+                if message.is_empty() {
+                    return Err(ChatError::EmptyMessage);
+                }
+                Self::post(chat, players, speaker, text, &message, ChatTarget::Team(speaker.team_color()))
+            }
+            _ => Self::run_command(chat, players, speaker, text, &name, &args),
+        }
+    }
+
+    /// Send a system message to one player
+    pub fn send_system_message(&mut self, recipient: &Player, text: &str) {
+        self.deliveries.push(ChatDelivery {
+            recipient_user_id: recipient.user_id(),
+            message: ChatMessage {
+                speaker_user_id: SYSTEM_USER_ID,
+                speaker_name: String::new(),
+                text: text.to_string(),
+                target: ChatTarget::System,
+            },
+        });
+    }
+
+    fn run_command(
+        chat: &Rc<RefCell<Self>>,
+        players: &Rc<RefCell<Players>>,
+        speaker: &Player,
+        raw_text: &str,
+        name: &str,
+        args: &[&str],
+    ) -> Result<(), ChatError> {
+        let handler = {
+            let chat = chat.borrow();
+            let command = chat.commands.get(name).ok_or_else(|| ChatError::UnknownCommand(name.to_string()))?;
+            if command.admin_only && !chat.is_admin(speaker.user_id()) {
+                return Err(ChatError::NotAuthorized(name.to_string()));
+            }
+            command.handler.clone()
+        };
+
+        fire_chatted(players, speaker, raw_text, None);
+
+        // Run the handler without holding the service, so it can chat too
+        let reply = (handler.borrow_mut())(speaker, args).map_err(ChatError::CommandFailed)?;
+        if let Some(reply) = reply {
+            chat.borrow_mut().send_system_message(speaker, &reply);
+        }
+        Ok(())
+    }
+
+    fn post(
+        chat: &Rc<RefCell<Self>>,
+        players: &Rc<RefCell<Players>>,
+        speaker: &Player,
+        raw_text: &str,
+        message: &str,
+        target: ChatTarget,
+    ) -> Result<(), ChatError> {
+        // Players can only talk to their own team
+        let target = match target {
+            ChatTarget::Team(_) => ChatTarget::Team(speaker.team_color()),
+            target => target,
+        };
+
+        let recipients: Vec<Player> = {
+            let players = players.borrow();
+            match target {
+                ChatTarget::All => players.get_players(),
+                ChatTarget::Team(color) => {
+                    if speaker.neutral() {
+                        return Err(ChatError::NoTeam);
+                    }
+                    players.get_players()
+                        .into_iter()
+                        .filter(|player| !player.neutral() && player.team_color() == color)
+                        .collect()
+                }
+                ChatTarget::Whisper(user_id) => {
+                    let recipient = players.get_player_by_user_id(user_id)
+                        .ok_or_else(|| ChatError::UnknownPlayer(user_id.to_string()))?;
+                    if recipient.user_id() == speaker.user_id() {
+                        vec![recipient]
+                    } else {
+                        vec![recipient, speaker.clone()]
+                    }
+                }
+                ChatTarget::System => vec![speaker.clone()],
+            }
+        };
+
+        let whisper_recipient = match target {
+            ChatTarget::Whisper(_) => recipients.first().cloned(),
+            _ => None,
+        };
+        fire_chatted(players, speaker, raw_text, whisper_recipient);
+
+        let filter = chat.borrow().filter.clone();
+        let text = match filter {
+            Some(filter) => (filter.borrow_mut())(speaker, message),
+            None => message.to_string(),
+        };
+
+        let mut chat = chat.borrow_mut();
+        let chat_message = ChatMessage {
+            speaker_user_id: speaker.user_id(),
+            speaker_name: speaker.name(),
+            text,
+            target,
+        };
+        for recipient in &recipients {
+            chat.deliveries.push(ChatDelivery {
+                recipient_user_id: recipient.user_id(),
+                message: chat_message.clone(),
+            });
+        }
+
+        if matches!(target, ChatTarget::All | ChatTarget::Team(_)) && chat.bubble_chat_enabled() {
+            chat.add_bubble(speaker, &chat_message.text);
+        }
+        Ok(())
+    }
+
+    fn add_bubble(&mut self, speaker: &Player, text: &str) {
+        let Some(head) = speaker.character().and_then(|character| character.borrow().find_first_child("Head")) else {
+            return;
+        };
+
+        self.bubbles.push(ChatBubble {
+            speaker_user_id: speaker.user_id(),
+            text: text.to_string(),
+            adornee: Rc::downgrade(&head),
+            expires_at: self.time + BUBBLE_LIFETIME,
+        });
+
+        let speaker_bubbles = self.bubbles.iter().filter(|bubble| bubble.speaker_user_id == speaker.user_id()).count();
+        if speaker_bubbles > MAX_BUBBLES_PER_SPEAKER {
+            let oldest = self.bubbles.iter().position(|bubble| bubble.speaker_user_id == speaker.user_id());
+            self.bubbles.remove(oldest.unwrap_or_default());
+        }
+    }
+}
+
+fn fire_chatted(players: &Rc<RefCell<Players>>, speaker: &Player, message: &str, recipient: Option<Player>) {
+    let signal = players.borrow().chatted(speaker).cloned();
+    if let Some(signal) = signal {
+        signal.fire(&PlayerChatted {
+            message: message.to_string(),
+            recipient,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debris::Debris;
+    use crate::teams::Team;
+
+    struct Fixture {
+        chat: Rc<RefCell<Chat>>,
+        players: Rc<RefCell<Players>>,
+        alice: Player,
+        bob: Player,
+        carol: Player,
+    }
+
+    fn fixture() -> Fixture {
+        let players = Players::new();
+        let alice = Players::add_player(&players, "Alice", 1);
+        let bob = Players::add_player(&players, "Bob", 2);
+        let carol = Players::add_player(&players, "Carol", 3);
+        Fixture { chat: Chat::new(), players, alice, bob, carol }
+    }
+
+    fn recipients(chat: &Rc<RefCell<Chat>>) -> Vec<i64> {
+        chat.borrow_mut().take_deliveries().into_iter().map(|delivery| delivery.recipient_user_id).collect()
+    }
+
+    #[test]
+    fn test_message_to_everyone() {
+        let f = fixture();
+        let heard = Rc::new(RefCell::new(Vec::new()));
+        let heard_clone = heard.clone();
+        f.players.borrow().chatted(&f.alice).unwrap().connect(move |chatted| heard_clone.borrow_mut().push(chatted.message.clone()));
+
+        Chat::send(&f.chat, &f.players, &f.alice, "  hello  ", ChatTarget::All).unwrap();
+
+        let deliveries = f.chat.borrow_mut().take_deliveries();
+        assert_eq!(deliveries.len(), 3);
+        assert_eq!(deliveries[0].message.text, "hello");
+        assert_eq!(deliveries[0].message.speaker_name, "Alice");
+        assert_eq!(*heard.borrow(), vec!["hello"]);
+    }
+
+    #[test]
+    fn test_rejects_bad_messages() {
+        let f = fixture();
+        assert_eq!(Chat::send(&f.chat, &f.players, &f.alice, "   ", ChatTarget::All), Err(ChatError::EmptyMessage));
+        let long = "a".repeat(MAX_MESSAGE_LENGTH + 1);
+        assert_eq!(Chat::send(&f.chat, &f.players, &f.alice, &long, ChatTarget::All), Err(ChatError::MessageTooLong));
+        assert!(recipients(&f.chat).is_empty());
+    }
+
+    #[test]
+    fn test_team_messages() {
+        let f = fixture();
+        let red = Team::new("Red", BrickColor::red());
+        f.alice.set_team(Some(&red));
+        f.carol.set_team(Some(&red));
+
+        Chat::send(&f.chat, &f.players, &f.alice, "/t push left", ChatTarget::All).unwrap();
+        assert_eq!(recipients(&f.chat), vec![1, 3]);
+
+        assert_eq!(Chat::send(&f.chat, &f.players, &f.bob, "/team hi", ChatTarget::All), Err(ChatError::NoTeam));
+    }
+
+    #[test]
+    fn test_whispers() {
+        let f = fixture();
+        let whispered_to = Rc::new(RefCell::new(None));
+        let whispered_clone = whispered_to.clone();
+        f.players.borrow().chatted(&f.alice).unwrap().connect(move |chatted| {
+            *whispered_clone.borrow_mut() = chatted.recipient.as_ref().map(|player| player.user_id());
+        });
+
+        Chat::send(&f.chat, &f.players, &f.alice, "/w bob meet at the tower", ChatTarget::All).unwrap();
+        let deliveries = f.chat.borrow_mut().take_deliveries();
+        assert_eq!(deliveries.iter().map(|d| d.recipient_user_id).collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(deliveries[0].message.text, "meet at the tower");
+        assert_eq!(deliveries[0].message.target, ChatTarget::Whisper(2));
+        assert_eq!(*whispered_to.borrow(), Some(2));
+
+        assert_eq!(
+            Chat::send(&f.chat, &f.players, &f.alice, "/w nobody hi", ChatTarget::All),
+            Err(ChatError::UnknownPlayer("nobody".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_commands() {
+        let f = fixture();
+        let kicked = Rc::new(RefCell::new(Vec::new()));
+        let kicked_clone = kicked.clone();
+        f.chat.borrow_mut().register_command("kick", true, Box::new(move |_, args| {
+            let name = args.first().ok_or("Usage: /kick <player>")?;
+            kicked_clone.borrow_mut().push(name.to_string());
+            Ok(Some(format!("Kicked {}", name)))
+        }));
+        f.chat.borrow_mut().set_admin(f.alice.user_id(), true);
+
+        assert_eq!(
+            Chat::send(&f.chat, &f.players, &f.bob, "/kick Carol", ChatTarget::All),
+            Err(ChatError::NotAuthorized("kick".to_string()))
+        );
+        assert_eq!(
+            Chat::send(&f.chat, &f.players, &f.alice, "/ban Carol", ChatTarget::All),
+            Err(ChatError::UnknownCommand("ban".to_string()))
+        );
+        assert_eq!(
+            Chat::send(&f.chat, &f.players, &f.alice, "/kick", ChatTarget::All),
+            Err(ChatError::CommandFailed("Usage: /kick <player>".to_string()))
+        );
+
+        Chat::send(&f.chat, &f.players, &f.alice, "/KICK Carol", ChatTarget::All).unwrap();
+        assert_eq!(*kicked.borrow(), vec!["Carol"]);
+
+        // Only the speaker sees the reply, and the command isn't broadcast
+        let deliveries = f.chat.borrow_mut().take_deliveries();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].recipient_user_id, 1);
+        assert_eq!(deliveries[0].message.target, ChatTarget::System);
+        assert_eq!(deliveries[0].message.text, "Kicked Carol");
+    }
+
+    #[test]
+    fn test_filter_hook() {
+        let f = fixture();
+        f.chat.borrow_mut().set_filter(Box::new(|_, text| text.replace("noob", "####")));
+
+        Chat::send(&f.chat, &f.players, &f.alice, "you noob", ChatTarget::All).unwrap();
+        let deliveries = f.chat.borrow_mut().take_deliveries();
+        assert!(deliveries.iter().all(|delivery| delivery.message.text == "you ####"));
+    }
+
+    #[test]
+    fn test_bubbles_follow_heads_and_expire() {
+        let f = fixture();
+        let workspace = Instance::new();
+        let debris = Debris::new();
+        let character = f.players.borrow_mut().load_character(&f.alice, &workspace, &mut debris.borrow_mut());
+        let head = BasePart::from_instance(&character.borrow().find_first_child("Head").unwrap()).unwrap();
+
+        for i in 0..4 {
+            Chat::send(&f.chat, &f.players, &f.alice, &format!("message {}", i), ChatTarget::All).unwrap();
+        }
+        // Whispers are private and never bubble
+        Chat::send(&f.chat, &f.players, &f.alice, "/w bob psst", ChatTarget::All).unwrap();
+
+        let chat = f.chat.borrow();
+        let texts: Vec<&str> = chat.bubbles().iter().map(|bubble| bubble.text.as_str()).collect();
+        assert_eq!(texts, vec!["message 1", "message 2", "message 3"]);
+        assert_eq!(chat.bubbles()[0].world_position(), Some(head.position() + BUBBLE_OFFSET));
+        drop(chat);
+
+        f.chat.borrow_mut().update(BUBBLE_LIFETIME + 1.0);
+        assert!(f.chat.borrow().bubbles().is_empty());
+    }
+}
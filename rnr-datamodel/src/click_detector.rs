@@ -0,0 +1,360 @@
+use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use rnr_core::instance::Instance;
+use rnr_core::signal::Signal;
+use rnr_core::variant::Variant;
+use crate::part::BasePart;
+use crate::players::{Player, Players};
+
+/// Default reach of a new click detector, in studs
+pub const DEFAULT_MAX_ACTIVATION_DISTANCE: f64 = 32.0;
+
+/// Typed view over a ClickDetector instance.
+///
+/// A click detector makes its parent part, or every part of its parent
+/// model, clickable by players whose character is within
+/// MaxActivationDistance of the part.
+#[derive(Debug, Clone)]
+pub struct ClickDetector {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl ClickDetector {
+    /// Create a new click detector
+    pub fn new() -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name("ClickDetector");
+            instance.set_class_name("ClickDetector");
+            instance.set_property("MaxActivationDistance", DEFAULT_MAX_ACTIVATION_DISTANCE);
+            instance.set_property("CursorIcon", "");
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a click detector, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("ClickDetector") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Find the click detector of a part: its own, or failing that the one
+    /// in the nearest model around it
+    pub fn for_part(part: &Rc<RefCell<Instance>>) -> Option<Self> {
+        let mut current = Some(part.clone());
+        let mut first = true;
+        while let Some(instance) = current {
+            if first || instance.borrow().is_a("Model") {
+                let detector = instance.borrow().find_first_child_of_type("ClickDetector");
+                if let Some(detector) = detector {
+                    return Self::from_instance(&detector);
+                }
+            }
+            first = false;
+            current = instance.borrow().parent();
+        }
+        None
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get how close a character must be to click, in studs
+    pub fn max_activation_distance(&self) -> f64 {
+        self.instance.borrow().get_property("MaxActivationDistance").and_then(Variant::as_float).unwrap_or(DEFAULT_MAX_ACTIVATION_DISTANCE)
+    }
+
+    /// Set how close a character must be to click
+    pub fn set_max_activation_distance(&self, distance: f64) {
+        self.instance.borrow_mut().set_property("MaxActivationDistance", distance);
+    }
+
+    /// Get the cursor shown while hovering, or "" for the default
+    pub fn cursor_icon(&self) -> String {
+        self.instance.borrow().get_property("CursorIcon").and_then(|icon| icon.as_str().map(str::to_string)).unwrap_or_default()
+    }
+
+    /// Set the cursor shown while hovering
+    pub fn set_cursor_icon(&self, icon: &str) {
+        self.instance.borrow_mut().set_property("CursorIcon", icon);
+    }
+
+    /// Get the parts the detector makes clickable: its parent part, or the
+    /// parts of its parent model
+    pub fn parts(&self) -> Vec<BasePart> {
+        let Some(parent) = self.instance.borrow().parent() else {
+            return Vec::new();
+        };
+        if let Some(part) = BasePart::from_instance(&parent) {
+            return vec![part];
+        }
+        if !parent.borrow().is_a("Model") {
+            return Vec::new();
+        }
+        let descendants = parent.borrow().get_descendants();
+        descendants.iter().filter_map(BasePart::from_instance).collect()
+    }
+
+    /// Check if a player's character is close enough to click. Distance is
+    /// measured from the character's Head to the nearest point of any of
+    /// the detector's parts.
+    pub fn in_reach(&self, player: &Player) -> bool {
+        let head = player.character()
+            .and_then(|character| character.borrow().find_first_child("Head"))
+            .as_ref()
+            .and_then(BasePart::from_instance);
+        let Some(head) = head else {
+            return false;
+        };
+
+        let reach = self.max_activation_distance() as f32;
+        self.parts().iter().any(|part| {
+            let local = part.cframe().point_to_object_space(head.position());
+            let half_size = part.size() / 2.0;
+            (local - local.clamp(-half_size, half_size)).length() <= reach
+        })
+    }
+}
+
+impl Default for ClickDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A click to deliver to the server
+#[derive(Debug, Clone)]
+pub struct DetectorClick {
+    pub user_id: i64,
+    pub detector: ClickDetector,
+}
+
+/// The events of one click detector
+#[derive(Default)]
+struct ClickDetectorSignals {
+    mouse_click: Signal<Player>,
+    mouse_hover_enter: Signal<Player>,
+    mouse_hover_leave: Signal<Player>,
+}
+
+/// Tracks which detector each player hovers and keeps detector events.
+///
+/// Clicks fire MouseClick where they happen and are queued for the network
+/// layer to deliver to the server, which fires MouseClick again for its
+/// scripts. Hovering is only seen by the player's own client.
+#[derive(Default)]
+pub struct ClickDetectors {
+    signals: Vec<(Weak<RefCell<Instance>>, ClickDetectorSignals)>,
+    hovered: Vec<(i64, Weak<RefCell<Instance>>)>,
+    clicks: Vec<DetectorClick>,
+}
+
+impl ClickDetectors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn signals(&mut self, detector: &ClickDetector) -> &ClickDetectorSignals {
+        self.signals.retain(|(known, _)| known.strong_count() > 0);
+        let index = match self.signals.iter().position(|(known, _)| known.as_ptr() == Rc::as_ptr(detector.instance())) {
+            Some(index) => index,
+            None => {
+                self.signals.push((Rc::downgrade(detector.instance()), ClickDetectorSignals::default()));
+                self.signals.len() - 1
+            }
+        };
+        &self.signals[index].1
+    }
+
+    /// Get the MouseClick signal of a detector, fired with the clicking player
+    pub fn mouse_click(&mut self, detector: &ClickDetector) -> Signal<Player> {
+        self.signals(detector).mouse_click.clone()
+    }
+
+    /// Get the MouseHoverEnter signal of a detector
+    pub fn mouse_hover_enter(&mut self, detector: &ClickDetector) -> Signal<Player> {
+        self.signals(detector).mouse_hover_enter.clone()
+    }
+
+    /// Get the MouseHoverLeave signal of a detector
+    pub fn mouse_hover_leave(&mut self, detector: &ClickDetector) -> Signal<Player> {
+        self.signals(detector).mouse_hover_leave.clone()
+    }
+
+    /// Get the detector a player's mouse is over
+    pub fn hovered(&self, player: &Player) -> Option<ClickDetector> {
+        self.hovered
+            .iter()
+            .find(|(user_id, _)| *user_id == player.user_id())
+            .and_then(|(_, detector)| detector.upgrade())
+            .map(|instance| ClickDetector { instance })
+    }
+
+    /// Move a player's mouse onto `detector`, or off every detector with
+    /// None, firing MouseHoverLeave and MouseHoverEnter as it changes.
+    /// Detectors out of the player's reach count as None.
+    pub fn hover(&mut self, player: &Player, detector: Option<&ClickDetector>) {
+        let detector = detector.filter(|detector| detector.in_reach(player));
+        let previous = self.hovered(player);
+        let unchanged = match (&previous, detector) {
+            (Some(previous), Some(detector)) => Rc::ptr_eq(previous.instance(), detector.instance()),
+            (None, None) => true,
+            _ => false,
+        };
+        if unchanged {
+            return;
+        }
+
+        self.hovered.retain(|(user_id, _)| *user_id != player.user_id());
+        if let Some(previous) = previous {
+            self.mouse_hover_leave(&previous).fire(player);
+        }
+        if let Some(detector) = detector {
+            self.hovered.push((player.user_id(), Rc::downgrade(detector.instance())));
+            self.mouse_hover_enter(detector).fire(player);
+        }
+    }
+
+    /// Click a detector as a player, firing MouseClick and queueing the click
+    /// for the server. Returns false if the player is out of reach.
+    pub fn click(&mut self, player: &Player, detector: &ClickDetector) -> bool {
+        if !self.fire_click(player, detector) {
+            return false;
+        }
+        self.clicks.push(DetectorClick { user_id: player.user_id(), detector: detector.clone() });
+        true
+    }
+
+    fn fire_click(&mut self, player: &Player, detector: &ClickDetector) -> bool {
+        if !detector.in_reach(player) {
+            return false;
+        }
+        self.mouse_click(detector).fire(player);
+        true
+    }
+
+    /// Take the clicks queued since the last call
+    pub fn take_clicks(&mut self) -> Vec<DetectorClick> {
+        std::mem::take(&mut self.clicks)
+    }
+
+    /// Apply a click sent by a client, without queueing it again. The
+    /// server checks the reach itself rather than trusting the client.
+    pub fn apply_click(&mut self, players: &Players, user_id: i64, detector: &ClickDetector) -> bool {
+        match players.get_player_by_user_id(user_id) {
+            Some(player) => self.fire_click(&player, detector),
+            None => false,
+        }
+    }
+}
+
+/// Get the names leading from `root` down to `instance`, or None if it isn't
+/// a descendant of `root`. Used to name instances across the network.
+pub fn instance_path(root: &Rc<RefCell<Instance>>, instance: &Rc<RefCell<Instance>>) -> Option<Vec<String>> {
+    let mut path = Vec::new();
+    let mut current = instance.clone();
+    while !Rc::ptr_eq(&current, root) {
+        path.push(current.borrow().name().to_string());
+        let parent = current.borrow().parent()?;
+        current = parent;
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// Follow a path from `instance_path` down from `root`
+pub fn resolve_path(root: &Rc<RefCell<Instance>>, path: &[String]) -> Option<Rc<RefCell<Instance>>> {
+    path.iter().try_fold(root.clone(), |instance, name| instance.borrow().find_first_child(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+    use crate::debris::Debris;
+    use crate::part::create_model;
+
+    #[test]
+    fn test_detector_parts_and_paths() {
+        let workspace = Instance::new();
+        let model = create_model("Button");
+        Instance::set_parent(&model, Some(workspace.clone()));
+        let part = BasePart::new("Part");
+        Instance::set_parent(part.instance(), Some(model.clone()));
+
+        // A detector in a model covers its parts
+        let detector = ClickDetector::new();
+        Instance::set_parent(detector.instance(), Some(model.clone()));
+        assert_eq!(detector.parts().len(), 1);
+        assert!(Rc::ptr_eq(ClickDetector::for_part(part.instance()).unwrap().instance(), detector.instance()));
+
+        // The part's own detector comes first
+        let own = ClickDetector::new();
+        Instance::set_parent(own.instance(), Some(part.instance().clone()));
+        assert!(Rc::ptr_eq(ClickDetector::for_part(part.instance()).unwrap().instance(), own.instance()));
+        assert!(ClickDetector::for_part(&workspace).is_none());
+
+        let path = instance_path(&workspace, own.instance()).unwrap();
+        assert_eq!(path, vec!["Button", "Part", "ClickDetector"]);
+        assert!(Rc::ptr_eq(&resolve_path(&workspace, &path).unwrap(), own.instance()));
+        assert!(instance_path(&workspace, &Instance::new()).is_none());
+    }
+
+    #[test]
+    fn test_hover_and_click_within_reach() {
+        let workspace = Instance::new();
+        let players = Players::new();
+        let player = Players::add_player(&players, "Player1", 1);
+        let character = players.borrow_mut().load_character(&player, &workspace, &mut Debris::new().borrow_mut());
+        let head = BasePart::from_instance(&character.borrow().find_first_child("Head").unwrap()).unwrap();
+
+        let part = BasePart::new("Part");
+        part.set_position(head.position() + Vec3::new(0.0, 0.0, 20.0));
+        Instance::set_parent(part.instance(), Some(workspace.clone()));
+        let detector = ClickDetector::new();
+        Instance::set_parent(detector.instance(), Some(part.instance().clone()));
+
+        let mut detectors = ClickDetectors::new();
+        let clicks = Rc::new(RefCell::new(Vec::new()));
+        let hovers = Rc::new(RefCell::new(Vec::new()));
+        {
+            let clicks = clicks.clone();
+            detectors.mouse_click(&detector).connect(move |player: &Player| clicks.borrow_mut().push(player.user_id()));
+            let entered = hovers.clone();
+            detectors.mouse_hover_enter(&detector).connect(move |_: &Player| entered.borrow_mut().push("enter"));
+            let left = hovers.clone();
+            detectors.mouse_hover_leave(&detector).connect(move |_: &Player| left.borrow_mut().push("leave"));
+        }
+
+        detectors.hover(&player, Some(&detector));
+        detectors.hover(&player, Some(&detector));
+        assert!(detectors.hovered(&player).is_some());
+        detectors.hover(&player, None);
+        assert_eq!(*hovers.borrow(), vec!["enter", "leave"]);
+
+        assert!(detectors.click(&player, &detector));
+        assert_eq!(*clicks.borrow(), vec![1]);
+        assert_eq!(detectors.take_clicks().len(), 1);
+
+        // Too far away to hover or click
+        detector.set_max_activation_distance(5.0);
+        detectors.hover(&player, Some(&detector));
+        assert!(detectors.hovered(&player).is_none());
+        assert!(!detectors.click(&player, &detector));
+        assert!(!detectors.apply_click(&players.borrow(), 1, &detector));
+
+        // The server fires clicks from clients without queueing them
+        detector.set_max_activation_distance(DEFAULT_MAX_ACTIVATION_DISTANCE);
+        assert!(detectors.apply_click(&players.borrow(), 1, &detector));
+        assert!(!detectors.apply_click(&players.borrow(), 2, &detector));
+        assert_eq!(*clicks.borrow(), vec![1, 1]);
+        assert!(detectors.take_clicks().is_empty());
+    }
+}
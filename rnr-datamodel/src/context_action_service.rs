@@ -0,0 +1,218 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use rnr_core::instance::Instance;
+use crate::user_input_service::{InputObject, KeyCode, UserInputState, UserInputType};
+
+/// Standard priorities for BindActionAtPriority (Enum.ContextActionPriority)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextActionPriority {
+    Low,
+    Medium,
+    High,
+}
+
+impl ContextActionPriority {
+    /// Get the numeric priority value
+    pub fn value(self) -> i32 {
+        match self {
+            ContextActionPriority::Low => 1000,
+            ContextActionPriority::Medium => 2000,
+            ContextActionPriority::High => 3000,
+        }
+    }
+}
+
+/// Priority of actions bound with `bind_action`
+pub const DEFAULT_ACTION_PRIORITY: i32 = 2000;
+
+/// What an action handler did with an input (Enum.ContextActionResult)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextActionResult {
+    /// The input is used up; lower priority actions don't see it
+    Sink,
+    /// The input goes on to the next action
+    Pass,
+}
+
+/// An input an action is bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionInput {
+    Key(KeyCode),
+    InputType(UserInputType),
+}
+
+impl ActionInput {
+    fn matches(&self, input: &InputObject) -> bool {
+        match *self {
+            ActionInput::Key(key_code) => input.user_input_type() == UserInputType::Keyboard && input.key_code() == key_code,
+            ActionInput::InputType(input_type) => input.user_input_type() == input_type,
+        }
+    }
+}
+
+/// Handler called with (action name, input state, input object)
+pub type ActionHandler = dyn FnMut(&str, UserInputState, &InputObject) -> ContextActionResult;
+
+/// An action bound with BindAction
+struct ActionBinding {
+    name: String,
+    priority: i32,
+    inputs: Vec<ActionInput>,
+    handler: Rc<RefCell<ActionHandler>>,
+}
+
+/// ContextActionService - binds named actions to inputs.
+///
+/// Each input goes to the actions bound to it from the highest priority
+/// down, with the most recently bound action first among equal priorities,
+/// until one sinks it. Sunk input reaches UserInputService as game processed.
+pub struct ContextActionService {
+    instance: Rc<RefCell<Instance>>,
+    /// Bindings in the order they get input
+    bindings: Vec<ActionBinding>,
+}
+
+impl ContextActionService {
+    /// Create a new context action service
+    pub fn new() -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("ContextActionService");
+        instance.borrow_mut().set_class_name("ContextActionService");
+        instance.borrow_mut().set_archivable(false);
+
+        Rc::new(RefCell::new(Self {
+            instance,
+            bindings: Vec::new(),
+        }))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Bind an action to inputs at the default priority
+    pub fn bind_action<F>(&mut self, name: &str, handler: F, inputs: &[ActionInput])
+    where
+        F: FnMut(&str, UserInputState, &InputObject) -> ContextActionResult + 'static,
+    {
+        self.bind_action_at_priority(name, handler, DEFAULT_ACTION_PRIORITY, inputs);
+    }
+
+    /// Bind an action to inputs. Higher priorities get input first; binding
+    /// an existing name replaces it.
+    pub fn bind_action_at_priority<F>(&mut self, name: &str, handler: F, priority: i32, inputs: &[ActionInput])
+    where
+        F: FnMut(&str, UserInputState, &InputObject) -> ContextActionResult + 'static,
+    {
+        self.unbind_action(name);
+
+        let index = self.bindings
+            .iter()
+            .position(|binding| binding.priority <= priority)
+            .unwrap_or(self.bindings.len());
+
+        self.bindings.insert(index, ActionBinding {
+            name: name.to_string(),
+            priority,
+            inputs: inputs.to_vec(),
+            handler: Rc::new(RefCell::new(handler)),
+        });
+    }
+
+    /// Remove an action bound with `bind_action`
+    pub fn unbind_action(&mut self, name: &str) {
+        self.bindings.retain(|binding| binding.name != name);
+    }
+
+    /// Get the names of the bound actions, in the order they get input
+    pub fn get_bound_action_names(&self) -> Vec<String> {
+        self.bindings.iter().map(|binding| binding.name.clone()).collect()
+    }
+
+    /// Offer an input to the bound actions. Returns true if an action sank it.
+    ///
+    /// The service is not borrowed while handlers run, so they are free to
+    /// bind or unbind actions.
+    pub fn process_input(context_action_service: &Rc<RefCell<Self>>, input: &InputObject) -> bool {
+        let handlers: Vec<_> = context_action_service.borrow().bindings
+            .iter()
+            .filter(|binding| binding.inputs.iter().any(|action_input| action_input.matches(input)))
+            .map(|binding| (binding.name.clone(), binding.handler.clone()))
+            .collect();
+
+        let state = input.user_input_state();
+        for (name, handler) in handlers {
+            let Ok(mut handler) = handler.try_borrow_mut() else {
+                continue;
+            };
+            if handler(&name, state, input) == ContextActionResult::Sink {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    fn key_input(key_code: KeyCode, state: UserInputState) -> InputObject {
+        InputObject::new(UserInputType::Keyboard, state, key_code, Vec3::ZERO, Vec3::ZERO)
+    }
+
+    #[test]
+    fn test_action_priority_and_sinking() {
+        let context_action_service = ContextActionService::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let bind = |name: &'static str, priority: i32, result: ContextActionResult| {
+            let log = log.clone();
+            context_action_service.borrow_mut().bind_action_at_priority(name, move |action, state, _| {
+                log.borrow_mut().push((action.to_string(), state));
+                result
+            }, priority, &[ActionInput::Key(KeyCode::E)]);
+        };
+        bind("low", ContextActionPriority::Low.value(), ContextActionResult::Sink);
+        bind("high", ContextActionPriority::High.value(), ContextActionResult::Pass);
+        bind("medium", ContextActionPriority::Medium.value(), ContextActionResult::Sink);
+
+        assert_eq!(context_action_service.borrow().get_bound_action_names(), vec!["high", "medium", "low"]);
+        assert!(ContextActionService::process_input(&context_action_service, &key_input(KeyCode::E, UserInputState::Begin)));
+        assert_eq!(*log.borrow(), vec![("high".to_string(), UserInputState::Begin), ("medium".to_string(), UserInputState::Begin)]);
+
+        // Other keys aren't offered to the actions
+        log.borrow_mut().clear();
+        assert!(!ContextActionService::process_input(&context_action_service, &key_input(KeyCode::Q, UserInputState::Begin)));
+        assert!(log.borrow().is_empty());
+
+        context_action_service.borrow_mut().unbind_action("medium");
+        ContextActionService::process_input(&context_action_service, &key_input(KeyCode::E, UserInputState::End));
+        assert_eq!(*log.borrow(), vec![("high".to_string(), UserInputState::End), ("low".to_string(), UserInputState::End)]);
+    }
+
+    #[test]
+    fn test_rebinding_and_input_types() {
+        let context_action_service = ContextActionService::new();
+        let count = Rc::new(RefCell::new(0));
+
+        let count_clone = count.clone();
+        context_action_service.borrow_mut().bind_action("fire", move |_, _, _| {
+            *count_clone.borrow_mut() += 1;
+            ContextActionResult::Sink
+        }, &[ActionInput::InputType(UserInputType::MouseButton1), ActionInput::Key(KeyCode::F)]);
+        let first = context_action_service.borrow().get_bound_action_names();
+        context_action_service.borrow_mut().bind_action("other", |_, _, _| ContextActionResult::Pass, &[ActionInput::Key(KeyCode::F)]);
+
+        // Equal priorities: the newest binding gets input first
+        assert_eq!(first, vec!["fire"]);
+        assert_eq!(context_action_service.borrow().get_bound_action_names(), vec!["other", "fire"]);
+
+        let click = InputObject::new(UserInputType::MouseButton1, UserInputState::Begin, KeyCode::Unknown, Vec3::ZERO, Vec3::ZERO);
+        assert!(ContextActionService::process_input(&context_action_service, &click));
+        assert!(ContextActionService::process_input(&context_action_service, &key_input(KeyCode::F, UserInputState::Begin)));
+        assert_eq!(*count.borrow(), 2);
+    }
+}
@@ -0,0 +1,610 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::cell::RefCell;
+use serde_json::Value;
+use rnr_core::instance::Instance;
+use crate::data_store_backend::{DataStoreBackend, DataStoreId, MemoryDataStoreBackend};
+
+/// Scope used when a data store is requested without one
+pub const DEFAULT_SCOPE: &str = "global";
+
+/// Longest allowed data store name, scope or key
+pub const MAX_NAME_LENGTH: usize = 50;
+
+/// Largest value that can be stored, measured in characters of JSON
+pub const MAX_VALUE_SIZE: usize = 4_194_304;
+
+/// Seconds that must pass between two writes to the same key
+pub const WRITE_COOLDOWN: f64 = 6.0;
+
+/// Most entries a single page of an ordered data store can hold
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// A budget stops refilling once it holds this many minutes of requests
+const MAX_BUDGET_MINUTES: f64 = 3.0;
+
+/// Error returned by data store requests
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum DataStoreError {
+    #[error("Request was throttled: {0:?} budget exhausted")]
+    Throttled(DataStoreRequestType),
+    #[error("Request was throttled: key {0:?} was written less than 6 seconds ago")]
+    WriteCooldown(String),
+    #[error("Invalid name: {0:?}")]
+    InvalidName(String),
+    #[error("Invalid key: {0:?}")]
+    InvalidKey(String),
+    #[error("Value is too large ({0} characters)")]
+    ValueTooLarge(usize),
+    #[error("Invalid value: {0}")]
+    InvalidValue(String),
+    #[error("Invalid page size: {0}")]
+    InvalidPageSize(usize),
+    #[error("Backend error: {0}")]
+    Backend(String),
+}
+
+/// The kinds of requests budgets are tracked for (Enum.DataStoreRequestType)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataStoreRequestType {
+    GetAsync = 0,
+    SetIncrementAsync = 1,
+    UpdateAsync = 2,
+    GetSortedAsync = 3,
+    SetIncrementSortedAsync = 4,
+}
+
+impl DataStoreRequestType {
+    /// Every request type that has a budget
+    pub const ALL: [DataStoreRequestType; 5] = [
+        DataStoreRequestType::GetAsync,
+        DataStoreRequestType::SetIncrementAsync,
+        DataStoreRequestType::UpdateAsync,
+        DataStoreRequestType::GetSortedAsync,
+        DataStoreRequestType::SetIncrementSortedAsync,
+    ];
+
+    /// Requests per minute granted to a server with `num_players` players
+    pub fn requests_per_minute(self, num_players: usize) -> f64 {
+        let num_players = num_players as f64;
+        match self {
+            DataStoreRequestType::GetAsync
+            | DataStoreRequestType::SetIncrementAsync
+            | DataStoreRequestType::UpdateAsync => 60.0 + num_players * 10.0,
+            DataStoreRequestType::GetSortedAsync => 5.0 + num_players * 2.0,
+            DataStoreRequestType::SetIncrementSortedAsync => 30.0 + num_players * 5.0,
+        }
+    }
+}
+
+/// DataStoreService - persistent key/value storage for player data.
+///
+/// Budgets refill on the simulated clock passed to `update`. Requests never
+/// block: where Roblox would queue a request that is over budget, we fail it
+/// with `DataStoreError::Throttled` and the caller can retry on a later frame.
+pub struct DataStoreService {
+    instance: Rc<RefCell<Instance>>,
+    backend: Box<dyn DataStoreBackend>,
+    /// Requests left for each request type
+    budgets: HashMap<DataStoreRequestType, f64>,
+    /// Time of the last write to each key
+    last_writes: HashMap<(DataStoreId, String), f64>,
+    /// Time of the last update in seconds
+    time: f64,
+}
+
+impl DataStoreService {
+    /// Create a new service storing data in `backend`
+    pub fn new(backend: Box<dyn DataStoreBackend>) -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("DataStoreService");
+        instance.borrow_mut().set_class_name("DataStoreService");
+
+        let budgets = DataStoreRequestType::ALL
+            .iter()
+            .map(|request_type| (*request_type, request_type.requests_per_minute(0)))
+            .collect();
+
+        Rc::new(RefCell::new(Self {
+            instance,
+            backend,
+            budgets,
+            last_writes: HashMap::new(),
+            time: 0.0,
+        }))
+    }
+
+    /// Create a new service that keeps its data in memory
+    pub fn in_memory() -> Rc<RefCell<Self>> {
+        Self::new(Box::new(MemoryDataStoreBackend::new()))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get a data store (DataStoreService:GetDataStore)
+    pub fn get_data_store(service: &Rc<RefCell<Self>>, name: &str, scope: Option<&str>) -> Result<DataStore, DataStoreError> {
+        Ok(DataStore {
+            service: service.clone(),
+            id: Self::store_id(name, scope, false)?,
+        })
+    }
+
+    /// Get an ordered data store (DataStoreService:GetOrderedDataStore)
+    pub fn get_ordered_data_store(service: &Rc<RefCell<Self>>, name: &str, scope: Option<&str>) -> Result<OrderedDataStore, DataStoreError> {
+        Ok(OrderedDataStore {
+            service: service.clone(),
+            id: Self::store_id(name, scope, true)?,
+        })
+    }
+
+    /// Get the number of requests of a type that can be made right now
+    /// (DataStoreService:GetRequestBudgetForRequestType)
+    pub fn get_request_budget_for_request_type(&self, request_type: DataStoreRequestType) -> u32 {
+        self.budgets.get(&request_type).copied().unwrap_or(0.0).floor() as u32
+    }
+
+    /// Advance to `current_time`, refilling budgets for a server with
+    /// `num_players` players
+    pub fn update(&mut self, current_time: f64, num_players: usize) {
+        let delta_time = (current_time - self.time).max(0.0);
+        self.time = current_time;
+
+        for (request_type, budget) in self.budgets.iter_mut() {
+            let per_minute = request_type.requests_per_minute(num_players);
+            *budget = (*budget + per_minute * delta_time / 60.0).min(per_minute * MAX_BUDGET_MINUTES);
+        }
+
+        let time = self.time;
+        self.last_writes.retain(|_, written_at| time - *written_at < WRITE_COOLDOWN);
+    }
+
+    fn store_id(name: &str, scope: Option<&str>, ordered: bool) -> Result<DataStoreId, DataStoreError> {
+        let scope = scope.unwrap_or(DEFAULT_SCOPE);
+        for part in [name, scope] {
+            if part.is_empty() || part.chars().count() > MAX_NAME_LENGTH {
+                return Err(DataStoreError::InvalidName(part.to_string()));
+            }
+        }
+
+        Ok(DataStoreId {
+            name: name.to_string(),
+            scope: scope.to_string(),
+            ordered,
+        })
+    }
+
+    fn check_key(key: &str) -> Result<(), DataStoreError> {
+        if key.is_empty() || key.chars().count() > MAX_NAME_LENGTH {
+            return Err(DataStoreError::InvalidKey(key.to_string()));
+        }
+        Ok(())
+    }
+
+    fn check_value(value: &Value) -> Result<(), DataStoreError> {
+        let size = serde_json::to_string(value)
+            .map_err(|e| DataStoreError::InvalidValue(e.to_string()))?
+            .len();
+        if size > MAX_VALUE_SIZE {
+            return Err(DataStoreError::ValueTooLarge(size));
+        }
+        Ok(())
+    }
+
+    /// Spend one request of a type, failing if the budget is empty
+    fn consume(&mut self, request_type: DataStoreRequestType) -> Result<(), DataStoreError> {
+        let budget = self.budgets.entry(request_type).or_insert(0.0);
+        if *budget < 1.0 {
+            return Err(DataStoreError::Throttled(request_type));
+        }
+        *budget -= 1.0;
+        Ok(())
+    }
+
+    /// Check the write cooldown and spend a request for a write to `key`.
+    /// The cooldown starts once the write is made, in `finish_write`.
+    fn begin_write(&mut self, store: &DataStoreId, key: &str, request_type: DataStoreRequestType) -> Result<(), DataStoreError> {
+        Self::check_key(key)?;
+
+        let write_key = (store.clone(), key.to_string());
+        if self.last_writes.get(&write_key).is_some_and(|written_at| self.time - written_at < WRITE_COOLDOWN) {
+            return Err(DataStoreError::WriteCooldown(key.to_string()));
+        }
+
+        self.consume(request_type)
+    }
+
+    /// Start the write cooldown of a key that was just written
+    fn finish_write(&mut self, store: &DataStoreId, key: &str) {
+        self.last_writes.insert((store.clone(), key.to_string()), self.time);
+    }
+
+    fn get(&mut self, store: &DataStoreId, key: &str) -> Result<Option<Value>, DataStoreError> {
+        Self::check_key(key)?;
+        self.consume(DataStoreRequestType::GetAsync)?;
+        self.backend.get(store, key)
+    }
+
+    fn set(&mut self, store: &DataStoreId, key: &str, value: Value, request_type: DataStoreRequestType) -> Result<(), DataStoreError> {
+        Self::check_value(&value)?;
+        self.begin_write(store, key, request_type)?;
+        self.backend.set(store, key, value)?;
+        self.finish_write(store, key);
+        Ok(())
+    }
+
+    fn update_key<F>(&mut self, store: &DataStoreId, key: &str, transform: F) -> Result<Option<Value>, DataStoreError>
+    where
+        F: FnOnce(Option<Value>) -> Option<Value>,
+    {
+        self.begin_write(store, key, DataStoreRequestType::UpdateAsync)?;
+
+        let Some(value) = transform(self.backend.get(store, key)?) else {
+            return Ok(None);
+        };
+        Self::check_value(&value)?;
+        if store.ordered && value.as_i64().is_none() {
+            return Err(DataStoreError::InvalidValue("ordered data stores only hold integers".to_string()));
+        }
+
+        self.backend.set(store, key, value.clone())?;
+        self.finish_write(store, key);
+        Ok(Some(value))
+    }
+
+    fn increment(&mut self, store: &DataStoreId, key: &str, delta: i64, request_type: DataStoreRequestType) -> Result<i64, DataStoreError> {
+        self.begin_write(store, key, request_type)?;
+
+        let old = match self.backend.get(store, key)? {
+            None => 0,
+            Some(value) => value
+                .as_i64()
+                .ok_or_else(|| DataStoreError::InvalidValue(format!("can't increment non-integer value {}", value)))?,
+        };
+
+        let new = old.saturating_add(delta);
+        self.backend.set(store, key, Value::from(new))?;
+        self.finish_write(store, key);
+        Ok(new)
+    }
+
+    fn remove(&mut self, store: &DataStoreId, key: &str, request_type: DataStoreRequestType) -> Result<Option<Value>, DataStoreError> {
+        self.begin_write(store, key, request_type)?;
+        let removed = self.backend.remove(store, key)?;
+        self.finish_write(store, key);
+        Ok(removed)
+    }
+}
+
+/// A data store handle (GlobalDataStore)
+#[derive(Clone)]
+pub struct DataStore {
+    service: Rc<RefCell<DataStoreService>>,
+    id: DataStoreId,
+}
+
+impl DataStore {
+    /// Get the store's name
+    pub fn name(&self) -> &str {
+        &self.id.name
+    }
+
+    /// Get the store's scope
+    pub fn scope(&self) -> &str {
+        &self.id.scope
+    }
+
+    /// Read a key (GetAsync)
+    pub fn get_async(&self, key: &str) -> Result<Option<Value>, DataStoreError> {
+        self.service.borrow_mut().get(&self.id, key)
+    }
+
+    /// Write a key (SetAsync)
+    pub fn set_async(&self, key: &str, value: Value) -> Result<(), DataStoreError> {
+        self.service.borrow_mut().set(&self.id, key, value, DataStoreRequestType::SetIncrementAsync)
+    }
+
+    /// Read a key and write back what `transform` returns (UpdateAsync).
+    ///
+    /// Returning `None` from `transform` cancels the write, so the key's
+    /// write cooldown doesn't start. Returns the value that was written. Like
+    /// on Roblox, `transform` must not make data store requests of its own.
+    pub fn update_async<F>(&self, key: &str, transform: F) -> Result<Option<Value>, DataStoreError>
+    where
+        F: FnOnce(Option<Value>) -> Option<Value>,
+    {
+        self.service.borrow_mut().update_key(&self.id, key, transform)
+    }
+
+    /// Add `delta` to an integer key, treating a missing key as 0
+    /// (IncrementAsync). Returns the new value.
+    pub fn increment_async(&self, key: &str, delta: i64) -> Result<i64, DataStoreError> {
+        self.service.borrow_mut().increment(&self.id, key, delta, DataStoreRequestType::SetIncrementAsync)
+    }
+
+    /// Remove a key, returning its old value (RemoveAsync)
+    pub fn remove_async(&self, key: &str) -> Result<Option<Value>, DataStoreError> {
+        self.service.borrow_mut().remove(&self.id, key, DataStoreRequestType::SetIncrementAsync)
+    }
+}
+
+/// An ordered data store handle, holding integers that can be read back
+/// sorted (OrderedDataStore)
+#[derive(Clone)]
+pub struct OrderedDataStore {
+    service: Rc<RefCell<DataStoreService>>,
+    id: DataStoreId,
+}
+
+impl OrderedDataStore {
+    /// Get the store's name
+    pub fn name(&self) -> &str {
+        &self.id.name
+    }
+
+    /// Get the store's scope
+    pub fn scope(&self) -> &str {
+        &self.id.scope
+    }
+
+    /// Read a key (GetAsync)
+    pub fn get_async(&self, key: &str) -> Result<Option<i64>, DataStoreError> {
+        Ok(self.service.borrow_mut().get(&self.id, key)?.and_then(|value| value.as_i64()))
+    }
+
+    /// Write a key (SetAsync)
+    pub fn set_async(&self, key: &str, value: i64) -> Result<(), DataStoreError> {
+        self.service.borrow_mut().set(&self.id, key, Value::from(value), DataStoreRequestType::SetIncrementSortedAsync)
+    }
+
+    /// Read a key and write back what `transform` returns (UpdateAsync).
+    ///
+    /// Returning `None` from `transform` cancels the write.
+    pub fn update_async<F>(&self, key: &str, transform: F) -> Result<Option<i64>, DataStoreError>
+    where
+        F: FnOnce(Option<i64>) -> Option<i64>,
+    {
+        let value = self.service.borrow_mut().update_key(&self.id, key, |old| {
+            transform(old.and_then(|value| value.as_i64())).map(Value::from)
+        })?;
+        Ok(value.and_then(|value| value.as_i64()))
+    }
+
+    /// Add `delta` to a key, treating a missing key as 0 (IncrementAsync)
+    pub fn increment_async(&self, key: &str, delta: i64) -> Result<i64, DataStoreError> {
+        self.service.borrow_mut().increment(&self.id, key, delta, DataStoreRequestType::SetIncrementSortedAsync)
+    }
+
+    /// Remove a key, returning its old value (RemoveAsync)
+    pub fn remove_async(&self, key: &str) -> Result<Option<i64>, DataStoreError> {
+        let old = self.service.borrow_mut().remove(&self.id, key, DataStoreRequestType::SetIncrementSortedAsync)?;
+        Ok(old.and_then(|value| value.as_i64()))
+    }
+
+    /// Read the store sorted by value (GetSortedAsync).
+    ///
+    /// Only values within `min_value..=max_value` are included. Ties are
+    /// broken by key so the order is always the same.
+    pub fn get_sorted_async(
+        &self,
+        ascending: bool,
+        page_size: usize,
+        min_value: Option<i64>,
+        max_value: Option<i64>,
+    ) -> Result<DataStorePages, DataStoreError> {
+        if page_size == 0 || page_size > MAX_PAGE_SIZE {
+            return Err(DataStoreError::InvalidPageSize(page_size));
+        }
+
+        let mut service = self.service.borrow_mut();
+        service.consume(DataStoreRequestType::GetSortedAsync)?;
+
+        let mut entries: Vec<(String, i64)> = service.backend
+            .entries(&self.id)?
+            .into_iter()
+            .filter_map(|(key, value)| value.as_i64().map(|value| (key, value)))
+            .filter(|(_, value)| min_value.is_none_or(|min| *value >= min) && max_value.is_none_or(|max| *value <= max))
+            .collect();
+
+        entries.sort_by(|(key_a, a), (key_b, b)| {
+            let order = if ascending { a.cmp(b) } else { b.cmp(a) };
+            order.then_with(|| key_a.cmp(key_b))
+        });
+
+        Ok(DataStorePages {
+            service: self.service.clone(),
+            entries,
+            page_size,
+            page: 0,
+        })
+    }
+}
+
+/// Pages of sorted entries from an ordered data store.
+///
+/// The entries are read when GetSortedAsync is called. Turning a page spends a
+/// GetSortedAsync request, like on Roblox.
+pub struct DataStorePages {
+    service: Rc<RefCell<DataStoreService>>,
+    entries: Vec<(String, i64)>,
+    page_size: usize,
+    page: usize,
+}
+
+impl DataStorePages {
+    /// Get the (key, value) pairs on the current page
+    pub fn get_current_page(&self) -> &[(String, i64)] {
+        let start = (self.page * self.page_size).min(self.entries.len());
+        let end = (start + self.page_size).min(self.entries.len());
+        &self.entries[start..end]
+    }
+
+    /// Check if the current page is the last one
+    pub fn is_finished(&self) -> bool {
+        (self.page + 1) * self.page_size >= self.entries.len()
+    }
+
+    /// Move to the next page (AdvanceToNextPageAsync)
+    pub fn advance_to_next_page(&mut self) -> Result<(), DataStoreError> {
+        if self.is_finished() {
+            return Ok(());
+        }
+
+        self.service.borrow_mut().consume(DataStoreRequestType::GetSortedAsync)?;
+        self.page += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_get_set_remove() {
+        let service = DataStoreService::in_memory();
+        let store = DataStoreService::get_data_store(&service, "PlayerData", None).unwrap();
+        assert_eq!(store.scope(), DEFAULT_SCOPE);
+
+        assert_eq!(store.get_async("Player_1").unwrap(), None);
+        store.set_async("Player_1", json!({"coins": 5})).unwrap();
+        assert_eq!(store.get_async("Player_1").unwrap(), Some(json!({"coins": 5})));
+
+        service.borrow_mut().update(WRITE_COOLDOWN, 0);
+        assert_eq!(store.remove_async("Player_1").unwrap(), Some(json!({"coins": 5})));
+        assert_eq!(store.get_async("Player_1").unwrap(), None);
+    }
+
+    #[test]
+    fn test_update_and_increment() {
+        let service = DataStoreService::in_memory();
+        let store = DataStoreService::get_data_store(&service, "Stats", Some("v2")).unwrap();
+
+        assert_eq!(store.increment_async("Visits", 3).unwrap(), 3);
+
+        service.borrow_mut().update(WRITE_COOLDOWN, 0);
+        let updated = store.update_async("Visits", |old| Some(json!(old.unwrap().as_i64().unwrap() * 2))).unwrap();
+        assert_eq!(updated, Some(json!(6)));
+
+        // Cancelled updates don't write
+        service.borrow_mut().update(WRITE_COOLDOWN * 2.0, 0);
+        assert_eq!(store.update_async("Visits", |_| None).unwrap(), None);
+        assert_eq!(store.get_async("Visits").unwrap(), Some(json!(6)));
+
+        service.borrow_mut().update(WRITE_COOLDOWN * 3.0, 0);
+        store.set_async("Name", json!("Builderman")).unwrap();
+        service.borrow_mut().update(WRITE_COOLDOWN * 4.0, 0);
+        assert!(matches!(store.increment_async("Name", 1), Err(DataStoreError::InvalidValue(_))));
+    }
+
+    #[test]
+    fn test_limits() {
+        let service = DataStoreService::in_memory();
+        assert!(DataStoreService::get_data_store(&service, "", None).is_err());
+
+        let store = DataStoreService::get_data_store(&service, "Limits", None).unwrap();
+        let long_key = "k".repeat(MAX_NAME_LENGTH + 1);
+        assert_eq!(store.get_async(&long_key), Err(DataStoreError::InvalidKey(long_key.clone())));
+
+        let huge = Value::String("x".repeat(MAX_VALUE_SIZE));
+        assert!(matches!(store.set_async("Huge", huge), Err(DataStoreError::ValueTooLarge(_))));
+    }
+
+    #[test]
+    fn test_write_cooldown() {
+        let service = DataStoreService::in_memory();
+        let store = DataStoreService::get_data_store(&service, "Cooldown", None).unwrap();
+
+        store.set_async("Key", json!(1)).unwrap();
+        assert_eq!(store.set_async("Key", json!(2)), Err(DataStoreError::WriteCooldown("Key".to_string())));
+        store.set_async("OtherKey", json!(2)).unwrap();
+
+        service.borrow_mut().update(WRITE_COOLDOWN, 0);
+        store.set_async("Key", json!(3)).unwrap();
+        assert_eq!(store.get_async("Key").unwrap(), Some(json!(3)));
+    }
+
+    #[test]
+    fn test_cancelled_update_has_no_cooldown() {
+        let service = DataStoreService::in_memory();
+        let store = DataStoreService::get_data_store(&service, "Cooldown", None).unwrap();
+
+        assert_eq!(store.update_async("Key", |_| None), Ok(None));
+        store.set_async("Key", json!(1)).unwrap();
+        assert_eq!(store.get_async("Key").unwrap(), Some(json!(1)));
+        assert_eq!(store.update_async("Key", |_| None), Err(DataStoreError::WriteCooldown("Key".to_string())));
+    }
+
+    #[test]
+    fn test_budget_throttling() {
+        let service = DataStoreService::in_memory();
+        let store = DataStoreService::get_data_store(&service, "Budget", None).unwrap();
+
+        let budget = service.borrow().get_request_budget_for_request_type(DataStoreRequestType::GetAsync);
+        assert_eq!(budget, 60);
+        for _ in 0..budget {
+            store.get_async("Key").unwrap();
+        }
+        assert_eq!(store.get_async("Key"), Err(DataStoreError::Throttled(DataStoreRequestType::GetAsync)));
+
+        // With 4 players the budget refills at 100 requests a minute
+        service.borrow_mut().update(6.0, 4);
+        assert_eq!(service.borrow().get_request_budget_for_request_type(DataStoreRequestType::GetAsync), 10);
+
+        // Budgets stop growing at three minutes' worth
+        service.borrow_mut().update(3600.0, 4);
+        assert_eq!(service.borrow().get_request_budget_for_request_type(DataStoreRequestType::GetAsync), 300);
+    }
+
+    #[test]
+    fn test_ordered_pages() {
+        let service = DataStoreService::in_memory();
+        let store = DataStoreService::get_ordered_data_store(&service, "Points", None).unwrap();
+        for (key, points) in [("a", 30), ("b", 10), ("c", 50), ("d", 20), ("e", 30)] {
+            store.set_async(key, points).unwrap();
+        }
+
+        // The regular store with the same name is separate
+        let regular = DataStoreService::get_data_store(&service, "Points", None).unwrap();
+        assert_eq!(regular.get_async("a").unwrap(), None);
+
+        let mut pages = store.get_sorted_async(false, 2, None, None).unwrap();
+        assert_eq!(pages.get_current_page(), &[("c".to_string(), 50), ("a".to_string(), 30)]);
+        assert!(!pages.is_finished());
+
+        pages.advance_to_next_page().unwrap();
+        assert_eq!(pages.get_current_page(), &[("e".to_string(), 30), ("d".to_string(), 20)]);
+
+        pages.advance_to_next_page().unwrap();
+        assert_eq!(pages.get_current_page(), &[("b".to_string(), 10)]);
+        assert!(pages.is_finished());
+
+        let pages = store.get_sorted_async(true, 10, Some(15), Some(30)).unwrap();
+        let keys: Vec<&str> = pages.get_current_page().iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, vec!["d", "a", "e"]);
+
+        // GetSortedAsync has a small budget: 5 requests, 4 already spent
+        assert!(store.get_sorted_async(true, 10, None, None).is_ok());
+        assert!(matches!(
+            store.get_sorted_async(true, 10, None, None),
+            Err(DataStoreError::Throttled(DataStoreRequestType::GetSortedAsync))
+        ));
+    }
+
+    #[test]
+    fn test_file_backend_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let service = DataStoreService::new(Box::new(crate::FileDataStoreBackend::new(dir.path())));
+        let store = DataStoreService::get_data_store(&service, "Progress", None).unwrap();
+        store.set_async("Player_1", json!({"level": 7})).unwrap();
+
+        // A new server over the same directory sees the saved progress
+        let service = DataStoreService::new(Box::new(crate::FileDataStoreBackend::new(dir.path())));
+        let store = DataStoreService::get_data_store(&service, "Progress", None).unwrap();
+        assert_eq!(store.get_async("Player_1").unwrap(), Some(json!({"level": 7})));
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+use crate::data_store::DataStoreError;
+
+/// Identifies one data store: its name, its scope, and whether it is ordered.
+///
+/// Ordered and regular stores with the same name and scope are separate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DataStoreId {
+    pub name: String,
+    pub scope: String,
+    pub ordered: bool,
+}
+
+/// Storage behind DataStoreService.
+///
+/// Backends only store values. Limits, budgets and throttling are enforced by
+/// the service before a backend is called.
+pub trait DataStoreBackend {
+    /// Read a key
+    fn get(&self, store: &DataStoreId, key: &str) -> Result<Option<Value>, DataStoreError>;
+
+    /// Write a key
+    fn set(&mut self, store: &DataStoreId, key: &str, value: Value) -> Result<(), DataStoreError>;
+
+    /// Remove a key, returning its old value
+    fn remove(&mut self, store: &DataStoreId, key: &str) -> Result<Option<Value>, DataStoreError>;
+
+    /// Read every key of a store, sorted by key
+    fn entries(&self, store: &DataStoreId) -> Result<Vec<(String, Value)>, DataStoreError>;
+}
+
+/// Backend that keeps everything in memory and forgets it on shutdown
+#[derive(Debug, Default)]
+pub struct MemoryDataStoreBackend {
+    stores: HashMap<DataStoreId, BTreeMap<String, Value>>,
+}
+
+impl MemoryDataStoreBackend {
+    /// Create an empty backend
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl DataStoreBackend for MemoryDataStoreBackend {
+    fn get(&self, store: &DataStoreId, key: &str) -> Result<Option<Value>, DataStoreError> {
+        Ok(self.stores.get(store).and_then(|entries| entries.get(key)).cloned())
+    }
+
+    fn set(&mut self, store: &DataStoreId, key: &str, value: Value) -> Result<(), DataStoreError> {
+        self.stores.entry(store.clone()).or_default().insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn remove(&mut self, store: &DataStoreId, key: &str) -> Result<Option<Value>, DataStoreError> {
+        Ok(self.stores.get_mut(store).and_then(|entries| entries.remove(key)))
+    }
+
+    fn entries(&self, store: &DataStoreId) -> Result<Vec<(String, Value)>, DataStoreError> {
+        Ok(self.stores
+            .get(store)
+            .map(|entries| entries.iter().map(|(key, value)| (key.clone(), value.clone())).collect())
+            .unwrap_or_default())
+    }
+}
+
+/// Backend that keeps each store in a JSON file under a directory.
+///
+/// Stores live at `<root>/<standard|ordered>/<scope>/<name>.json`, holding one
+/// object that maps keys to values. Keys are written sorted, so the files are
+/// stable enough to diff or check in as CI fixtures.
+#[derive(Debug)]
+pub struct FileDataStoreBackend {
+    root: PathBuf,
+}
+
+impl FileDataStoreBackend {
+    /// Create a backend that stores files under `root`. The directory is
+    /// created on the first write.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Get the directory the stores are kept in
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Get the file a store is kept in
+    pub fn store_path(&self, store: &DataStoreId) -> PathBuf {
+        let kind = if store.ordered { "ordered" } else { "standard" };
+        self.root
+            .join(kind)
+            .join(encode_file_name(&store.scope))
+            .join(format!("{}.json", encode_file_name(&store.name)))
+    }
+
+    fn load(&self, store: &DataStoreId) -> Result<BTreeMap<String, Value>, DataStoreError> {
+        let path = self.store_path(store);
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+
+        let contents = fs::read_to_string(&path).map_err(|e| backend_error(&path, e))?;
+        serde_json::from_str(&contents).map_err(|e| backend_error(&path, e))
+    }
+
+    fn save(&self, store: &DataStoreId, entries: &BTreeMap<String, Value>) -> Result<(), DataStoreError> {
+        let path = self.store_path(store);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| backend_error(parent, e))?;
+        }
+
+        // Write to a temporary file first so a crash can't leave half a store behind
+        let contents = serde_json::to_string_pretty(entries).map_err(|e| backend_error(&path, e))?;
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, contents).map_err(|e| backend_error(&temp_path, e))?;
+        fs::rename(&temp_path, &path).map_err(|e| backend_error(&path, e))
+    }
+}
+
+impl DataStoreBackend for FileDataStoreBackend {
+    fn get(&self, store: &DataStoreId, key: &str) -> Result<Option<Value>, DataStoreError> {
+        Ok(self.load(store)?.remove(key))
+    }
+
+    fn set(&mut self, store: &DataStoreId, key: &str, value: Value) -> Result<(), DataStoreError> {
+        let mut entries = self.load(store)?;
+        entries.insert(key.to_string(), value);
+        self.save(store, &entries)
+    }
+
+    fn remove(&mut self, store: &DataStoreId, key: &str) -> Result<Option<Value>, DataStoreError> {
+        let mut entries = self.load(store)?;
+        let old = entries.remove(key);
+        if old.is_some() {
+            self.save(store, &entries)?;
+        }
+        Ok(old)
+    }
+
+    fn entries(&self, store: &DataStoreId) -> Result<Vec<(String, Value)>, DataStoreError> {
+        Ok(self.load(store)?.into_iter().collect())
+    }
+}
+
+/// Make a store name or scope safe to use as a file name.
+///
+/// Letters, digits, '-' and '_' are kept. Everything else is written as %XX,
+/// so different names can never map to the same file.
+fn encode_file_name(name: &str) -> String {
+    let mut encoded = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+fn backend_error(path: &Path, error: impl std::fmt::Display) -> DataStoreError {
+    DataStoreError::Backend(format!("{}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn store(name: &str, ordered: bool) -> DataStoreId {
+        DataStoreId {
+            name: name.to_string(),
+            scope: "global".to_string(),
+            ordered,
+        }
+    }
+
+    #[test]
+    fn test_memory_backend() {
+        let mut backend = MemoryDataStoreBackend::new();
+        let coins = store("Coins", false);
+
+        backend.set(&coins, "Player_1", json!(10)).unwrap();
+        assert_eq!(backend.get(&coins, "Player_1").unwrap(), Some(json!(10)));
+        assert_eq!(backend.get(&store("Coins", true), "Player_1").unwrap(), None);
+
+        assert_eq!(backend.remove(&coins, "Player_1").unwrap(), Some(json!(10)));
+        assert!(backend.entries(&coins).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_file_backend_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let progress = store("Player Progress", false);
+
+        let mut backend = FileDataStoreBackend::new(dir.path());
+        backend.set(&progress, "b", json!({"level": 2})).unwrap();
+        backend.set(&progress, "a", json!([1, 2, 3])).unwrap();
+
+        let path = backend.store_path(&progress);
+        assert!(path.ends_with("standard/global/Player%20Progress.json"));
+
+        // A new backend over the same directory sees the same data
+        let backend = FileDataStoreBackend::new(dir.path());
+        let keys: Vec<String> = backend.entries(&progress).unwrap().into_iter().map(|(key, _)| key).collect();
+        assert_eq!(keys, vec!["a", "b"]);
+        assert_eq!(backend.get(&progress, "b").unwrap(), Some(json!({"level": 2})));
+    }
+
+    #[test]
+    fn test_encode_file_name() {
+        assert_eq!(encode_file_name("Coins_v2"), "Coins_v2");
+        assert_eq!(encode_file_name("../x"), "%2E%2E%2Fx");
+    }
+}
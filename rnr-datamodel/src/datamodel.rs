@@ -43,6 +43,11 @@ impl DataModel {
         self.services.insert(service_name.to_string(), service);
     }
 
+    /// Get all registered services
+    pub fn services(&self) -> &HashMap<String, Rc<RefCell<Instance>>> {
+        &self.services
+    }
+
     /// Get instance by GUID
     pub fn get_instance_by_guid(&self, guid: &str) -> Option<Rc<RefCell<Instance>>> {
         self.guid_map.get(guid).cloned()
@@ -0,0 +1,192 @@
+use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use rnr_core::instance::Instance;
+
+/// Default number of items Debris will track before evicting the oldest
+pub const DEFAULT_MAX_ITEMS: usize = 1000;
+
+/// An instance scheduled for destruction
+struct DebrisItem {
+    instance: Weak<RefCell<Instance>>,
+    expires_at: f64,
+}
+
+/// Debris service - destroys instances after their lifetime expires.
+///
+/// Debris is driven by the simulated time passed to `update`, never by the
+/// wall clock, so cleanup is deterministic.
+pub struct Debris {
+    instance: Rc<RefCell<Instance>>,
+    /// Items in the order they were added
+    items: Vec<DebrisItem>,
+    /// Maximum number of tracked items
+    max_items: usize,
+    /// Time of the last update in seconds
+    time: f64,
+}
+
+impl Debris {
+    /// Create a new Debris service
+    pub fn new() -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("Debris");
+        instance.borrow_mut().set_class_name("Debris");
+        instance.borrow_mut().set_archivable(false);
+
+        Rc::new(RefCell::new(Self {
+            instance,
+            items: Vec::new(),
+            max_items: DEFAULT_MAX_ITEMS,
+            time: 0.0,
+        }))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Schedule an instance to be destroyed after `lifetime` seconds.
+    ///
+    /// If this pushes the number of tracked items over MaxItems, the oldest
+    /// items are destroyed immediately.
+    pub fn add_item(&mut self, instance: &Rc<RefCell<Instance>>, lifetime: f64) {
+        self.items.push(DebrisItem {
+            instance: Rc::downgrade(instance),
+            expires_at: self.time + lifetime.max(0.0),
+        });
+
+        self.evict_overflow();
+    }
+
+    /// Get the maximum number of tracked items
+    pub fn max_items(&self) -> usize {
+        self.max_items
+    }
+
+    /// Set the maximum number of tracked items
+    pub fn set_max_items(&mut self, max_items: usize) {
+        self.max_items = max_items;
+        self.evict_overflow();
+    }
+
+    /// Get the number of items waiting to be destroyed
+    pub fn num_items(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Get the current Debris time
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Advance Debris to `current_time` and destroy every expired item
+    pub fn update(&mut self, current_time: f64) {
+        self.time = current_time;
+
+        let time = self.time;
+        let mut expired = Vec::new();
+        self.items.retain(|item| {
+            if item.expires_at <= time {
+                expired.push(item.instance.clone());
+                false
+            } else {
+                true
+            }
+        });
+
+        for instance in expired {
+            Self::destroy_item(&instance);
+        }
+    }
+
+    /// Destroy the oldest items until we're within MaxItems
+    fn evict_overflow(&mut self) {
+        if self.items.len() <= self.max_items {
+            return;
+        }
+
+        let overflow = self.items.len() - self.max_items;
+        let evicted: Vec<DebrisItem> = self.items.drain(..overflow).collect();
+        for item in evicted {
+            Self::destroy_item(&item.instance);
+        }
+    }
+
+    /// Destroy an item if it is still alive
+    fn destroy_item(instance: &Weak<RefCell<Instance>>) {
+        if let Some(instance) = instance.upgrade() {
+            Instance::destroy(&instance);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parented_item(parent: &Rc<RefCell<Instance>>) -> Rc<RefCell<Instance>> {
+        let item = Instance::new();
+        Instance::set_parent(&item, Some(parent.clone()));
+        item
+    }
+
+    #[test]
+    fn test_debris_creation() {
+        let debris = Debris::new();
+
+        assert_eq!(debris.borrow().instance().borrow().class_name(), "Debris");
+        assert_eq!(debris.borrow().max_items(), DEFAULT_MAX_ITEMS);
+        assert_eq!(debris.borrow().num_items(), 0);
+    }
+
+    #[test]
+    fn test_item_expires() {
+        let debris = Debris::new();
+        let parent = Instance::new();
+        let item = parented_item(&parent);
+
+        debris.borrow_mut().add_item(&item, 1.0);
+
+        debris.borrow_mut().update(0.5);
+        assert!(parent.borrow().contains(&item));
+
+        debris.borrow_mut().update(1.0);
+        assert!(!parent.borrow().contains(&item));
+        assert!(item.borrow().is_destroyed());
+        assert_eq!(debris.borrow().num_items(), 0);
+    }
+
+    #[test]
+    fn test_max_items_evicts_oldest() {
+        let debris = Debris::new();
+        let parent = Instance::new();
+        debris.borrow_mut().set_max_items(2);
+
+        let first = parented_item(&parent);
+        let second = parented_item(&parent);
+        let third = parented_item(&parent);
+
+        debris.borrow_mut().add_item(&first, 5.0);
+        debris.borrow_mut().add_item(&second, 5.0);
+        debris.borrow_mut().add_item(&third, 5.0);
+
+        assert!(first.borrow().is_destroyed());
+        assert!(!second.borrow().is_destroyed());
+        assert!(!third.borrow().is_destroyed());
+        assert_eq!(debris.borrow().num_items(), 2);
+    }
+
+    #[test]
+    fn test_dropped_item_is_ignored() {
+        let debris = Debris::new();
+
+        {
+            let item = Instance::new();
+            debris.borrow_mut().add_item(&item, 0.1);
+        }
+
+        debris.borrow_mut().update(1.0);
+        assert_eq!(debris.borrow().num_items(), 0);
+    }
+}
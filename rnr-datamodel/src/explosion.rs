@@ -0,0 +1,151 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use glam::Vec3;
+use rnr_core::instance::Instance;
+use rnr_core::variant::Variant;
+
+/// Default radius of a new explosion, in studs
+pub const DEFAULT_BLAST_RADIUS: f64 = 4.0;
+
+/// Default pressure of a new explosion
+pub const DEFAULT_BLAST_PRESSURE: f64 = 500000.0;
+
+/// Whether an explosion digs into terrain (Enum.ExplosionType)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExplosionType {
+    NoCraters = 0,
+    Craters = 1,
+}
+
+impl ExplosionType {
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Look up an explosion type by enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(ExplosionType::NoCraters),
+            1 => Some(ExplosionType::Craters),
+            _ => None,
+        }
+    }
+}
+
+/// Typed view over an Explosion instance.
+///
+/// An explosion goes off once, on the first physics step after it is added
+/// to the workspace: it pushes bodies away, breaks joints within
+/// BlastRadius * DestroyJointRadiusPercent and fires Hit for every part
+/// within BlastRadius.
+#[derive(Debug, Clone)]
+pub struct Explosion {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl Explosion {
+    /// Create a new explosion at the origin
+    pub fn new() -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name("Explosion");
+            instance.set_class_name("Explosion");
+            instance.set_property("Position", Vec3::ZERO);
+            instance.set_property("BlastRadius", DEFAULT_BLAST_RADIUS);
+            instance.set_property("BlastPressure", DEFAULT_BLAST_PRESSURE);
+            instance.set_property("DestroyJointRadiusPercent", 1.0);
+            instance.set_property("ExplosionType", Variant::Enum(ExplosionType::Craters.value()));
+            instance.set_property("Visible", true);
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as an explosion, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("Explosion") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the center of the blast
+    pub fn position(&self) -> Vec3 {
+        self.instance.borrow().get_property("Position").and_then(Variant::as_vector3).unwrap_or_default()
+    }
+
+    /// Set the center of the blast
+    pub fn set_position(&self, position: Vec3) {
+        self.instance.borrow_mut().set_property("Position", position);
+    }
+
+    /// Get the radius the blast reaches, in studs
+    pub fn blast_radius(&self) -> f64 {
+        self.instance.borrow().get_property("BlastRadius").and_then(Variant::as_float).unwrap_or(DEFAULT_BLAST_RADIUS)
+    }
+
+    /// Set the radius the blast reaches
+    pub fn set_blast_radius(&self, radius: f64) {
+        self.instance.borrow_mut().set_property("BlastRadius", radius.max(0.0));
+    }
+
+    /// Get how hard the blast pushes
+    pub fn blast_pressure(&self) -> f64 {
+        self.instance.borrow().get_property("BlastPressure").and_then(Variant::as_float).unwrap_or(DEFAULT_BLAST_PRESSURE)
+    }
+
+    /// Set how hard the blast pushes
+    pub fn set_blast_pressure(&self, pressure: f64) {
+        self.instance.borrow_mut().set_property("BlastPressure", pressure.max(0.0));
+    }
+
+    /// Get the part of the blast radius, from 0 to 1, that breaks joints
+    pub fn destroy_joint_radius_percent(&self) -> f64 {
+        self.instance.borrow().get_property("DestroyJointRadiusPercent").and_then(Variant::as_float).unwrap_or(1.0)
+    }
+
+    /// Set the part of the blast radius that breaks joints
+    pub fn set_destroy_joint_radius_percent(&self, percent: f64) {
+        self.instance.borrow_mut().set_property("DestroyJointRadiusPercent", percent.clamp(0.0, 1.0));
+    }
+
+    /// Get the radius within which joints break, in studs
+    pub fn destroy_joint_radius(&self) -> f64 {
+        self.blast_radius() * self.destroy_joint_radius_percent()
+    }
+
+    /// Get whether the explosion digs into terrain
+    pub fn explosion_type(&self) -> ExplosionType {
+        self.instance.borrow().get_property("ExplosionType").and_then(Variant::as_int)
+            .and_then(ExplosionType::from_value).unwrap_or(ExplosionType::Craters)
+    }
+
+    /// Set whether the explosion digs into terrain
+    pub fn set_explosion_type(&self, explosion_type: ExplosionType) {
+        self.instance.borrow_mut().set_property("ExplosionType", Variant::Enum(explosion_type.value()));
+    }
+
+    /// Check if the explosion is drawn
+    pub fn visible(&self) -> bool {
+        self.instance.borrow().get_property("Visible").and_then(Variant::as_bool).unwrap_or(true)
+    }
+
+    /// Show or hide the explosion's effect
+    pub fn set_visible(&self, visible: bool) {
+        self.instance.borrow_mut().set_property("Visible", visible);
+    }
+}
+
+impl Default for Explosion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
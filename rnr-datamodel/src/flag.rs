@@ -0,0 +1,158 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use glam::Vec3;
+use rnr_core::instance::Instance;
+use rnr_core::types::{BrickColor, CFrame};
+use rnr_core::variant::Variant;
+use crate::part::{move_model, BasePart};
+use crate::tool::Tool;
+
+/// Size of the Handle of a new Flag, the pole
+pub const FLAG_POLE_SIZE: Vec3 = Vec3::new(0.4, 8.0, 0.4);
+
+/// Typed view over a Flag, a Tool belonging to a team that players of other
+/// teams pick up and carry to their own FlagStand
+#[derive(Debug, Clone)]
+pub struct Flag {
+    tool: Tool,
+}
+
+impl Flag {
+    /// Create a new flag of a team, with a pole for its Handle
+    pub fn new(team_color: BrickColor) -> Self {
+        let tool = Tool::new("Flag");
+        {
+            let mut instance = tool.instance().borrow_mut();
+            instance.set_class_name("Flag");
+            instance.set_property("TeamColor", team_color);
+        }
+        let handle = BasePart::new("Part");
+        handle.instance().borrow_mut().set_name("Handle");
+        handle.set_size(FLAG_POLE_SIZE);
+        handle.set_brick_color(team_color);
+        Instance::set_parent(handle.instance(), Some(tool.instance().clone()));
+
+        Self { tool }
+    }
+
+    /// View an existing instance as a flag, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("Flag") {
+            Tool::from_instance(instance).map(|tool| Self { tool })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        self.tool.instance()
+    }
+
+    /// Get the flag as a tool
+    pub fn tool(&self) -> &Tool {
+        &self.tool
+    }
+
+    /// Get the color of the team the flag belongs to
+    pub fn team_color(&self) -> BrickColor {
+        self.instance().borrow().get_property("TeamColor").and_then(Variant::as_brick_color).unwrap_or_default()
+    }
+
+    /// Set the color of the team the flag belongs to
+    pub fn set_team_color(&self, team_color: BrickColor) {
+        self.instance().borrow_mut().set_property("TeamColor", team_color);
+    }
+
+    /// Get where the flag's Handle stands upright on top of a stand
+    pub fn home(&self, stand: &FlagStand) -> Option<CFrame> {
+        let handle = self.tool.handle()?;
+        Some(CFrame::new(stand.part().position() + Vec3::Y * (stand.part().size().y + handle.size().y) / 2.0))
+    }
+
+    /// Check if the flag stands on top of a stand
+    pub fn is_home(&self, stand: &FlagStand) -> bool {
+        self.tool.handle().zip(self.home(stand)).is_some_and(|(handle, home)| handle.position().distance(home.position) < 0.01)
+    }
+
+    /// Put the flag back in `workspace`, upright on top of a stand
+    pub fn return_to(&self, stand: &FlagStand, workspace: &Rc<RefCell<Instance>>) {
+        Instance::set_parent(self.instance(), Some(workspace.clone()));
+        if let (Some(handle), Some(home)) = (self.tool.handle(), self.home(stand)) {
+            move_model(self.instance(), handle.cframe(), home);
+        }
+    }
+}
+
+/// Typed view over a FlagStand, the part a team's Flag is kept on and where
+/// its players bring enemy flags to capture them
+#[derive(Debug, Clone)]
+pub struct FlagStand {
+    part: BasePart,
+}
+
+impl FlagStand {
+    /// Create a new, anchored flag stand of a team
+    pub fn new(team_color: BrickColor) -> Self {
+        let part = BasePart::new("FlagStand");
+        part.set_anchored(true);
+        part.set_size(Vec3::new(4.0, 1.0, 4.0));
+        part.set_brick_color(team_color);
+        part.instance().borrow_mut().set_property("TeamColor", team_color);
+
+        Self { part }
+    }
+
+    /// View an existing instance as a flag stand, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("FlagStand") {
+            BasePart::from_instance(instance).map(|part| Self { part })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        self.part.instance()
+    }
+
+    /// Get the stand as a part
+    pub fn part(&self) -> &BasePart {
+        &self.part
+    }
+
+    /// Get the color of the team the stand belongs to
+    pub fn team_color(&self) -> BrickColor {
+        self.instance().borrow().get_property("TeamColor").and_then(Variant::as_brick_color).unwrap_or_default()
+    }
+
+    /// Set the color of the team the stand belongs to
+    pub fn set_team_color(&self, team_color: BrickColor) {
+        self.instance().borrow_mut().set_property("TeamColor", team_color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_returns_to_stand() {
+        let workspace = Instance::new();
+        let red = BrickColor::new(21);
+        let stand = FlagStand::new(red);
+        stand.part().set_position(Vec3::new(10.0, 0.5, 0.0));
+        Instance::set_parent(stand.instance(), Some(workspace.clone()));
+        let flag = Flag::new(red);
+        assert_eq!(flag.team_color(), red);
+        assert!(flag.instance().borrow().is_a("Tool"));
+        assert!(stand.instance().borrow().is_a("Part"));
+        assert!(Flag::from_instance(stand.instance()).is_none());
+
+        flag.return_to(&stand, &workspace);
+        assert!(Rc::ptr_eq(&flag.instance().borrow().parent().unwrap(), &workspace));
+        assert_eq!(flag.tool().handle().unwrap().position(), Vec3::new(10.0, 5.0, 0.0));
+        assert!(flag.is_home(&stand));
+    }
+}
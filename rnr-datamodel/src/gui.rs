@@ -0,0 +1,1215 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use glam::{Vec2, Vec3};
+use rnr_core::instance::Instance;
+use rnr_core::types::{Color3, UDim, UDim2};
+use rnr_core::variant::Variant;
+
+/// Default size of a new GUI object, in pixels
+pub const DEFAULT_GUI_SIZE: UDim2 = UDim2::from_offset(100, 100);
+
+/// Default background color of a new GUI object
+pub const DEFAULT_BACKGROUND_COLOR: Color3 = Color3::new(163.0 / 255.0, 162.0 / 255.0, 165.0 / 255.0);
+
+/// Default text size of text objects, in pixels
+pub const DEFAULT_TEXT_SIZE: i64 = 14;
+
+/// Which way a layout places its children (Enum.FillDirection)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillDirection {
+    Horizontal = 0,
+    Vertical = 1,
+}
+
+impl FillDirection {
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Look up a fill direction by enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(FillDirection::Horizontal),
+            1 => Some(FillDirection::Vertical),
+            _ => None,
+        }
+    }
+}
+
+/// Horizontal placement of laid out children (Enum.HorizontalAlignment)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HorizontalAlignment {
+    Center = 0,
+    Left = 1,
+    Right = 2,
+}
+
+impl HorizontalAlignment {
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Look up a horizontal alignment by enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(HorizontalAlignment::Center),
+            1 => Some(HorizontalAlignment::Left),
+            2 => Some(HorizontalAlignment::Right),
+            _ => None,
+        }
+    }
+}
+
+/// Vertical placement of laid out children (Enum.VerticalAlignment)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerticalAlignment {
+    Center = 0,
+    Top = 1,
+    Bottom = 2,
+}
+
+impl VerticalAlignment {
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Look up a vertical alignment by enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(VerticalAlignment::Center),
+            1 => Some(VerticalAlignment::Top),
+            2 => Some(VerticalAlignment::Bottom),
+            _ => None,
+        }
+    }
+}
+
+/// Order a layout places its children in (Enum.SortOrder)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Alphabetically by Name
+    Name = 0,
+    /// In child order
+    Custom = 1,
+    /// By LayoutOrder, then child order
+    LayoutOrder = 2,
+}
+
+impl SortOrder {
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Look up a sort order by enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(SortOrder::Name),
+            1 => Some(SortOrder::Custom),
+            2 => Some(SortOrder::LayoutOrder),
+            _ => None,
+        }
+    }
+}
+
+/// How a UIAspectRatioConstraint resizes its parent (Enum.AspectType)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AspectType {
+    /// Shrink to the largest size with the ratio that fits in Size
+    FitWithinMaxSize = 0,
+    /// Keep the dominant axis and derive the other from it
+    ScaleWithParentSize = 1,
+}
+
+impl AspectType {
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Look up an aspect type by enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(AspectType::FitWithinMaxSize),
+            1 => Some(AspectType::ScaleWithParentSize),
+            _ => None,
+        }
+    }
+}
+
+/// Axis a UIAspectRatioConstraint keeps when scaling (Enum.DominantAxis)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DominantAxis {
+    Width = 0,
+    Height = 1,
+}
+
+impl DominantAxis {
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Look up a dominant axis by enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(DominantAxis::Width),
+            1 => Some(DominantAxis::Height),
+            _ => None,
+        }
+    }
+}
+
+/// Horizontal placement of text in its object (Enum.TextXAlignment)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextXAlignment {
+    Left = 0,
+    Right = 1,
+    Center = 2,
+}
+
+impl TextXAlignment {
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Look up a text x alignment by enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(TextXAlignment::Left),
+            1 => Some(TextXAlignment::Right),
+            2 => Some(TextXAlignment::Center),
+            _ => None,
+        }
+    }
+
+    /// Get how far across the free space the text sits, from 0 to 1
+    pub fn fraction(self) -> f32 {
+        match self {
+            TextXAlignment::Left => 0.0,
+            TextXAlignment::Center => 0.5,
+            TextXAlignment::Right => 1.0,
+        }
+    }
+}
+
+/// Vertical placement of text in its object (Enum.TextYAlignment)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextYAlignment {
+    Top = 0,
+    Center = 1,
+    Bottom = 2,
+}
+
+impl TextYAlignment {
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Look up a text y alignment by enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(TextYAlignment::Top),
+            1 => Some(TextYAlignment::Center),
+            2 => Some(TextYAlignment::Bottom),
+            _ => None,
+        }
+    }
+
+    /// Get how far down the free space the text sits, from 0 to 1
+    pub fn fraction(self) -> f32 {
+        match self {
+            TextYAlignment::Top => 0.0,
+            TextYAlignment::Center => 0.5,
+            TextYAlignment::Bottom => 1.0,
+        }
+    }
+}
+
+fn get_int(instance: &Rc<RefCell<Instance>>, name: &str) -> Option<i64> {
+    instance.borrow().get_property(name).and_then(Variant::as_int)
+}
+
+fn get_float(instance: &Rc<RefCell<Instance>>, name: &str) -> Option<f64> {
+    instance.borrow().get_property(name).and_then(Variant::as_float)
+}
+
+fn get_vector2(instance: &Rc<RefCell<Instance>>, name: &str) -> Option<Vec2> {
+    instance.borrow().get_property(name).and_then(Variant::as_vector2)
+}
+
+fn get_udim(instance: &Rc<RefCell<Instance>>, name: &str) -> UDim {
+    instance.borrow().get_property(name).and_then(Variant::as_udim).unwrap_or_default()
+}
+
+fn get_udim2(instance: &Rc<RefCell<Instance>>, name: &str) -> Option<UDim2> {
+    instance.borrow().get_property(name).and_then(Variant::as_udim2)
+}
+
+fn get_bool(instance: &Rc<RefCell<Instance>>, name: &str) -> Option<bool> {
+    instance.borrow().get_property(name).and_then(Variant::as_bool)
+}
+
+fn new_instance(class_name: &str) -> Rc<RefCell<Instance>> {
+    let instance = Instance::new();
+    instance.borrow_mut().set_name(class_name);
+    instance.borrow_mut().set_class_name(class_name);
+    instance
+}
+
+/// Typed view over a ScreenGui instance, which draws its children over the
+/// whole viewport
+#[derive(Debug, Clone)]
+pub struct ScreenGui {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl ScreenGui {
+    /// Create a new, enabled screen gui
+    pub fn new() -> Self {
+        let instance = new_instance("ScreenGui");
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_property("Enabled", true);
+            instance.set_property("DisplayOrder", 0i64);
+            instance.set_property("ResetOnSpawn", true);
+            instance.set_property("AbsolutePosition", Vec2::ZERO);
+            instance.set_property("AbsoluteSize", Vec2::ZERO);
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a screen gui, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("ScreenGui") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Check if the gui is drawn
+    pub fn enabled(&self) -> bool {
+        get_bool(&self.instance, "Enabled").unwrap_or(true)
+    }
+
+    /// Show or hide the gui
+    pub fn set_enabled(&self, enabled: bool) {
+        self.instance.borrow_mut().set_property("Enabled", enabled);
+    }
+
+    /// Get the gui's draw order; higher orders are drawn over lower ones
+    pub fn display_order(&self) -> i64 {
+        get_int(&self.instance, "DisplayOrder").unwrap_or(0)
+    }
+
+    /// Set the gui's draw order
+    pub fn set_display_order(&self, display_order: i64) {
+        self.instance.borrow_mut().set_property("DisplayOrder", display_order);
+    }
+
+    /// Check if the gui is replaced from StarterGui every time the player spawns
+    pub fn reset_on_spawn(&self) -> bool {
+        get_bool(&self.instance, "ResetOnSpawn").unwrap_or(true)
+    }
+
+    /// Set whether the gui is replaced every time the player spawns
+    pub fn set_reset_on_spawn(&self, reset_on_spawn: bool) {
+        self.instance.borrow_mut().set_property("ResetOnSpawn", reset_on_spawn);
+    }
+
+    /// Get the size of the viewport the gui was last laid out for
+    pub fn absolute_size(&self) -> Vec2 {
+        get_vector2(&self.instance, "AbsoluteSize").unwrap_or_default()
+    }
+}
+
+impl Default for ScreenGui {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Typed view over a GuiObject instance (Frame, TextLabel, TextButton,
+/// TextBox, ImageLabel, ImageButton).
+///
+/// AbsolutePosition and AbsoluteSize are written by `layout_gui`.
+#[derive(Debug, Clone)]
+pub struct GuiObject {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl GuiObject {
+    /// Create a new GUI object of the given class with default properties.
+    /// Text classes also get Text properties and image classes Image ones.
+    pub fn new(class_name: &str) -> Self {
+        let instance = new_instance(class_name);
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_property("Position", UDim2::default());
+            instance.set_property("Size", DEFAULT_GUI_SIZE);
+            instance.set_property("AnchorPoint", Vec2::ZERO);
+            instance.set_property("ZIndex", 1i64);
+            instance.set_property("Visible", true);
+            instance.set_property("ClipsDescendants", false);
+            instance.set_property("LayoutOrder", 0i64);
+            instance.set_property("BackgroundColor3", DEFAULT_BACKGROUND_COLOR);
+            instance.set_property("BackgroundTransparency", 0.0);
+            instance.set_property("AbsolutePosition", Vec2::ZERO);
+            instance.set_property("AbsoluteSize", Vec2::ZERO);
+
+            if instance.is_a("TextLabel") || instance.is_a("TextButton") || instance.is_a("TextBox") {
+                instance.set_property("Text", class_name);
+                instance.set_property("TextColor3", Color3::new(0.0, 0.0, 0.0));
+                instance.set_property("TextSize", DEFAULT_TEXT_SIZE);
+                instance.set_property("TextTransparency", 0.0);
+                instance.set_property("TextWrapped", false);
+                instance.set_property("TextScaled", false);
+                instance.set_property("TextXAlignment", Variant::Enum(TextXAlignment::Center.value()));
+                instance.set_property("TextYAlignment", Variant::Enum(TextYAlignment::Center.value()));
+                instance.set_property("TextBounds", Vec2::ZERO);
+            }
+            if instance.is_a("ImageLabel") || instance.is_a("ImageButton") {
+                instance.set_property("Image", "");
+                instance.set_property("ImageColor3", Color3::new(1.0, 1.0, 1.0));
+                instance.set_property("ImageTransparency", 0.0);
+            }
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a GUI object, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("GuiObject") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the position of the anchor point within the parent
+    pub fn position(&self) -> UDim2 {
+        get_udim2(&self.instance, "Position").unwrap_or_default()
+    }
+
+    /// Set the position of the anchor point within the parent
+    pub fn set_position(&self, position: UDim2) {
+        self.instance.borrow_mut().set_property("Position", position);
+    }
+
+    /// Get the size relative to the parent
+    pub fn size(&self) -> UDim2 {
+        get_udim2(&self.instance, "Size").unwrap_or(DEFAULT_GUI_SIZE)
+    }
+
+    /// Set the size relative to the parent
+    pub fn set_size(&self, size: UDim2) {
+        self.instance.borrow_mut().set_property("Size", size);
+    }
+
+    /// Get the point of the object, as a fraction of its size, that sits at Position
+    pub fn anchor_point(&self) -> Vec2 {
+        get_vector2(&self.instance, "AnchorPoint").unwrap_or_default()
+    }
+
+    /// Set the anchor point
+    pub fn set_anchor_point(&self, anchor_point: Vec2) {
+        self.instance.borrow_mut().set_property("AnchorPoint", anchor_point);
+    }
+
+    /// Get the draw order among siblings; higher is drawn on top
+    pub fn z_index(&self) -> i64 {
+        get_int(&self.instance, "ZIndex").unwrap_or(1)
+    }
+
+    /// Set the draw order among siblings
+    pub fn set_z_index(&self, z_index: i64) {
+        self.instance.borrow_mut().set_property("ZIndex", z_index);
+    }
+
+    /// Check if the object and its descendants are drawn
+    pub fn visible(&self) -> bool {
+        get_bool(&self.instance, "Visible").unwrap_or(true)
+    }
+
+    /// Show or hide the object and its descendants
+    pub fn set_visible(&self, visible: bool) {
+        self.instance.borrow_mut().set_property("Visible", visible);
+    }
+
+    /// Check if descendants are cut off at the object's edges
+    pub fn clips_descendants(&self) -> bool {
+        get_bool(&self.instance, "ClipsDescendants").unwrap_or(false)
+    }
+
+    /// Set whether descendants are cut off at the object's edges
+    pub fn set_clips_descendants(&self, clips_descendants: bool) {
+        self.instance.borrow_mut().set_property("ClipsDescendants", clips_descendants);
+    }
+
+    /// Get the sort key used by layouts with SortOrder.LayoutOrder
+    pub fn layout_order(&self) -> i64 {
+        get_int(&self.instance, "LayoutOrder").unwrap_or(0)
+    }
+
+    /// Set the sort key used by layouts
+    pub fn set_layout_order(&self, layout_order: i64) {
+        self.instance.borrow_mut().set_property("LayoutOrder", layout_order);
+    }
+
+    /// Get the background color
+    pub fn background_color3(&self) -> Color3 {
+        self.instance.borrow().get_property("BackgroundColor3").and_then(Variant::as_color3).unwrap_or(DEFAULT_BACKGROUND_COLOR)
+    }
+
+    /// Set the background color
+    pub fn set_background_color3(&self, color: Color3) {
+        self.instance.borrow_mut().set_property("BackgroundColor3", color);
+    }
+
+    /// Get the background transparency, from 0 (opaque) to 1 (invisible)
+    pub fn background_transparency(&self) -> f64 {
+        get_float(&self.instance, "BackgroundTransparency").unwrap_or(0.0)
+    }
+
+    /// Set the background transparency, clamped to 0-1
+    pub fn set_background_transparency(&self, transparency: f64) {
+        self.instance.borrow_mut().set_property("BackgroundTransparency", transparency.clamp(0.0, 1.0));
+    }
+
+    /// Get the text of a text object
+    pub fn text(&self) -> Option<String> {
+        self.instance.borrow().get_property("Text").and_then(Variant::as_str).map(str::to_string)
+    }
+
+    /// Set the text of a text object
+    pub fn set_text(&self, text: &str) {
+        self.instance.borrow_mut().set_property("Text", text);
+    }
+
+    /// Get the text color
+    pub fn text_color3(&self) -> Color3 {
+        self.instance.borrow().get_property("TextColor3").and_then(Variant::as_color3).unwrap_or_default()
+    }
+
+    /// Set the text color
+    pub fn set_text_color3(&self, color: Color3) {
+        self.instance.borrow_mut().set_property("TextColor3", color);
+    }
+
+    /// Get the text height in pixels
+    pub fn text_size(&self) -> i64 {
+        get_int(&self.instance, "TextSize").unwrap_or(DEFAULT_TEXT_SIZE)
+    }
+
+    /// Set the text height in pixels
+    pub fn set_text_size(&self, text_size: i64) {
+        self.instance.borrow_mut().set_property("TextSize", text_size.max(1));
+    }
+
+    /// Get the text transparency, from 0 (opaque) to 1 (invisible)
+    pub fn text_transparency(&self) -> f64 {
+        get_float(&self.instance, "TextTransparency").unwrap_or(0.0)
+    }
+
+    /// Set the text transparency
+    pub fn set_text_transparency(&self, transparency: f64) {
+        self.instance.borrow_mut().set_property("TextTransparency", transparency.clamp(0.0, 1.0));
+    }
+
+    /// Check if text breaks between words to fit the object's width
+    pub fn text_wrapped(&self) -> bool {
+        get_bool(&self.instance, "TextWrapped").unwrap_or(false)
+    }
+
+    /// Set whether text wraps
+    pub fn set_text_wrapped(&self, wrapped: bool) {
+        self.instance.borrow_mut().set_property("TextWrapped", wrapped);
+    }
+
+    /// Check if text is drawn at the largest size that fits, ignoring TextSize
+    pub fn text_scaled(&self) -> bool {
+        get_bool(&self.instance, "TextScaled").unwrap_or(false)
+    }
+
+    /// Set whether text is scaled to fit
+    pub fn set_text_scaled(&self, scaled: bool) {
+        self.instance.borrow_mut().set_property("TextScaled", scaled);
+    }
+
+    /// Get the horizontal placement of the text
+    pub fn text_x_alignment(&self) -> TextXAlignment {
+        get_int(&self.instance, "TextXAlignment").and_then(TextXAlignment::from_value).unwrap_or(TextXAlignment::Center)
+    }
+
+    /// Set the horizontal placement of the text
+    pub fn set_text_x_alignment(&self, alignment: TextXAlignment) {
+        self.instance.borrow_mut().set_property("TextXAlignment", Variant::Enum(alignment.value()));
+    }
+
+    /// Get the vertical placement of the text
+    pub fn text_y_alignment(&self) -> TextYAlignment {
+        get_int(&self.instance, "TextYAlignment").and_then(TextYAlignment::from_value).unwrap_or(TextYAlignment::Center)
+    }
+
+    /// Set the vertical placement of the text
+    pub fn set_text_y_alignment(&self, alignment: TextYAlignment) {
+        self.instance.borrow_mut().set_property("TextYAlignment", Variant::Enum(alignment.value()));
+    }
+
+    /// Get the size of the text as last drawn, in pixels
+    pub fn text_bounds(&self) -> Vec2 {
+        get_vector2(&self.instance, "TextBounds").unwrap_or_default()
+    }
+
+    /// Record the size of the drawn text; called by the renderer
+    pub fn set_text_bounds(&self, bounds: Vec2) {
+        self.instance.borrow_mut().set_property("TextBounds", bounds);
+    }
+
+    /// Get the content id of an image object's image
+    pub fn image(&self) -> Option<String> {
+        self.instance.borrow().get_property("Image").and_then(Variant::as_str).map(str::to_string)
+    }
+
+    /// Set the content id of an image object's image
+    pub fn set_image(&self, image: &str) {
+        self.instance.borrow_mut().set_property("Image", image);
+    }
+
+    /// Get the on-screen position of the top left corner, in pixels
+    pub fn absolute_position(&self) -> Vec2 {
+        get_vector2(&self.instance, "AbsolutePosition").unwrap_or_default()
+    }
+
+    /// Get the on-screen size, in pixels
+    pub fn absolute_size(&self) -> Vec2 {
+        get_vector2(&self.instance, "AbsoluteSize").unwrap_or_default()
+    }
+
+    /// Record where the layout put the object
+    pub(crate) fn set_absolute(&self, position: Vec2, size: Vec2) {
+        let mut instance = self.instance.borrow_mut();
+        instance.set_property("AbsolutePosition", position);
+        instance.set_property("AbsoluteSize", size);
+    }
+}
+
+/// Typed view over a BillboardGui, which draws its children on a rectangle
+/// that floats over a part in the 3D world and always faces the camera.
+///
+/// Size scale components are in studs and offsets in pixels.
+#[derive(Debug, Clone)]
+pub struct BillboardGui {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl BillboardGui {
+    /// Create a new, enabled billboard gui with no size
+    pub fn new() -> Self {
+        let instance = new_instance("BillboardGui");
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_property("Enabled", true);
+            instance.set_property("Size", UDim2::default());
+            instance.set_property("StudsOffset", Vec3::ZERO);
+            instance.set_property("Adornee", Variant::instance_ref(None));
+            instance.set_property("AlwaysOnTop", false);
+            instance.set_property("AbsolutePosition", Vec2::ZERO);
+            instance.set_property("AbsoluteSize", Vec2::ZERO);
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a billboard gui, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("BillboardGui") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Check if the gui is drawn
+    pub fn enabled(&self) -> bool {
+        get_bool(&self.instance, "Enabled").unwrap_or(true)
+    }
+
+    /// Show or hide the gui
+    pub fn set_enabled(&self, enabled: bool) {
+        self.instance.borrow_mut().set_property("Enabled", enabled);
+    }
+
+    /// Get the size; scale is in studs and offset in pixels
+    pub fn size(&self) -> UDim2 {
+        get_udim2(&self.instance, "Size").unwrap_or_default()
+    }
+
+    /// Set the size
+    pub fn set_size(&self, size: UDim2) {
+        self.instance.borrow_mut().set_property("Size", size);
+    }
+
+    /// Get the offset from the adornee's center, in studs
+    pub fn studs_offset(&self) -> Vec3 {
+        self.instance.borrow().get_property("StudsOffset").and_then(Variant::as_vector3).unwrap_or_default()
+    }
+
+    /// Set the offset from the adornee's center
+    pub fn set_studs_offset(&self, offset: Vec3) {
+        self.instance.borrow_mut().set_property("StudsOffset", offset);
+    }
+
+    /// Get the part the gui floats over. Without one, the gui's parent is used.
+    pub fn adornee(&self) -> Option<Rc<RefCell<Instance>>> {
+        self.instance.borrow().get_property("Adornee").and_then(Variant::as_instance)
+    }
+
+    /// Set the part the gui floats over
+    pub fn set_adornee(&self, adornee: Option<&Rc<RefCell<Instance>>>) {
+        self.instance.borrow_mut().set_property("Adornee", Variant::instance_ref(adornee));
+    }
+
+    /// Check if the gui is drawn over parts that are in front of it
+    pub fn always_on_top(&self) -> bool {
+        get_bool(&self.instance, "AlwaysOnTop").unwrap_or(false)
+    }
+
+    /// Set whether the gui is drawn over parts in front of it
+    pub fn set_always_on_top(&self, always_on_top: bool) {
+        self.instance.borrow_mut().set_property("AlwaysOnTop", always_on_top);
+    }
+}
+
+impl Default for BillboardGui {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Typed view over a UIListLayout, which places its siblings in a row or
+/// column
+#[derive(Debug, Clone)]
+pub struct UIListLayout {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl UIListLayout {
+    /// Create a new list layout that stacks objects from the top left
+    pub fn new() -> Self {
+        let instance = new_instance("UIListLayout");
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_property("FillDirection", Variant::Enum(FillDirection::Vertical.value()));
+            instance.set_property("Padding", UDim::default());
+            instance.set_property("SortOrder", Variant::Enum(SortOrder::LayoutOrder.value()));
+            instance.set_property("HorizontalAlignment", Variant::Enum(HorizontalAlignment::Left.value()));
+            instance.set_property("VerticalAlignment", Variant::Enum(VerticalAlignment::Top.value()));
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a list layout, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("UIListLayout") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the direction objects are stacked in
+    pub fn fill_direction(&self) -> FillDirection {
+        get_int(&self.instance, "FillDirection").and_then(FillDirection::from_value).unwrap_or(FillDirection::Vertical)
+    }
+
+    /// Set the direction objects are stacked in
+    pub fn set_fill_direction(&self, fill_direction: FillDirection) {
+        self.instance.borrow_mut().set_property("FillDirection", Variant::Enum(fill_direction.value()));
+    }
+
+    /// Get the gap between objects, relative to the parent's length along the fill direction
+    pub fn padding(&self) -> UDim {
+        get_udim(&self.instance, "Padding")
+    }
+
+    /// Set the gap between objects
+    pub fn set_padding(&self, padding: UDim) {
+        self.instance.borrow_mut().set_property("Padding", padding);
+    }
+
+    /// Get the order objects are stacked in
+    pub fn sort_order(&self) -> SortOrder {
+        get_int(&self.instance, "SortOrder").and_then(SortOrder::from_value).unwrap_or(SortOrder::LayoutOrder)
+    }
+
+    /// Set the order objects are stacked in
+    pub fn set_sort_order(&self, sort_order: SortOrder) {
+        self.instance.borrow_mut().set_property("SortOrder", Variant::Enum(sort_order.value()));
+    }
+
+    /// Get the horizontal alignment of the objects
+    pub fn horizontal_alignment(&self) -> HorizontalAlignment {
+        get_int(&self.instance, "HorizontalAlignment").and_then(HorizontalAlignment::from_value).unwrap_or(HorizontalAlignment::Left)
+    }
+
+    /// Set the horizontal alignment of the objects
+    pub fn set_horizontal_alignment(&self, alignment: HorizontalAlignment) {
+        self.instance.borrow_mut().set_property("HorizontalAlignment", Variant::Enum(alignment.value()));
+    }
+
+    /// Get the vertical alignment of the objects
+    pub fn vertical_alignment(&self) -> VerticalAlignment {
+        get_int(&self.instance, "VerticalAlignment").and_then(VerticalAlignment::from_value).unwrap_or(VerticalAlignment::Top)
+    }
+
+    /// Set the vertical alignment of the objects
+    pub fn set_vertical_alignment(&self, alignment: VerticalAlignment) {
+        self.instance.borrow_mut().set_property("VerticalAlignment", Variant::Enum(alignment.value()));
+    }
+}
+
+impl Default for UIListLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Typed view over a UIGridLayout, which places its siblings in equally
+/// sized cells, filling rows (or columns) from the top left
+#[derive(Debug, Clone)]
+pub struct UIGridLayout {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl UIGridLayout {
+    /// Create a new grid layout with 100x100 pixel cells
+    pub fn new() -> Self {
+        let instance = new_instance("UIGridLayout");
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_property("CellSize", UDim2::from_offset(100, 100));
+            instance.set_property("CellPadding", UDim2::from_offset(5, 5));
+            instance.set_property("FillDirection", Variant::Enum(FillDirection::Horizontal.value()));
+            instance.set_property("FillDirectionMaxCells", 0i64);
+            instance.set_property("SortOrder", Variant::Enum(SortOrder::LayoutOrder.value()));
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a grid layout, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("UIGridLayout") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the size of every cell, relative to the parent
+    pub fn cell_size(&self) -> UDim2 {
+        get_udim2(&self.instance, "CellSize").unwrap_or(UDim2::from_offset(100, 100))
+    }
+
+    /// Set the size of every cell
+    pub fn set_cell_size(&self, cell_size: UDim2) {
+        self.instance.borrow_mut().set_property("CellSize", cell_size);
+    }
+
+    /// Get the gap between cells, relative to the parent
+    pub fn cell_padding(&self) -> UDim2 {
+        get_udim2(&self.instance, "CellPadding").unwrap_or_default()
+    }
+
+    /// Set the gap between cells
+    pub fn set_cell_padding(&self, cell_padding: UDim2) {
+        self.instance.borrow_mut().set_property("CellPadding", cell_padding);
+    }
+
+    /// Get whether cells fill rows (Horizontal) or columns (Vertical) first
+    pub fn fill_direction(&self) -> FillDirection {
+        get_int(&self.instance, "FillDirection").and_then(FillDirection::from_value).unwrap_or(FillDirection::Horizontal)
+    }
+
+    /// Set whether cells fill rows or columns first
+    pub fn set_fill_direction(&self, fill_direction: FillDirection) {
+        self.instance.borrow_mut().set_property("FillDirection", Variant::Enum(fill_direction.value()));
+    }
+
+    /// Get the most cells in a row (or column); 0 fits as many as the parent allows
+    pub fn fill_direction_max_cells(&self) -> i64 {
+        get_int(&self.instance, "FillDirectionMaxCells").unwrap_or(0)
+    }
+
+    /// Set the most cells in a row (or column)
+    pub fn set_fill_direction_max_cells(&self, max_cells: i64) {
+        self.instance.borrow_mut().set_property("FillDirectionMaxCells", max_cells.max(0));
+    }
+
+    /// Get the order cells are filled in
+    pub fn sort_order(&self) -> SortOrder {
+        get_int(&self.instance, "SortOrder").and_then(SortOrder::from_value).unwrap_or(SortOrder::LayoutOrder)
+    }
+
+    /// Set the order cells are filled in
+    pub fn set_sort_order(&self, sort_order: SortOrder) {
+        self.instance.borrow_mut().set_property("SortOrder", Variant::Enum(sort_order.value()));
+    }
+}
+
+impl Default for UIGridLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Typed view over a UIPadding, which insets the area its parent's children
+/// are laid out in
+#[derive(Debug, Clone)]
+pub struct UIPadding {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl UIPadding {
+    /// Create a new padding with no inset
+    pub fn new() -> Self {
+        let instance = new_instance("UIPadding");
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_property("PaddingLeft", UDim::default());
+            instance.set_property("PaddingRight", UDim::default());
+            instance.set_property("PaddingTop", UDim::default());
+            instance.set_property("PaddingBottom", UDim::default());
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a padding, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("UIPadding") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the left inset, relative to the parent's width
+    pub fn padding_left(&self) -> UDim {
+        get_udim(&self.instance, "PaddingLeft")
+    }
+
+    /// Get the right inset, relative to the parent's width
+    pub fn padding_right(&self) -> UDim {
+        get_udim(&self.instance, "PaddingRight")
+    }
+
+    /// Get the top inset, relative to the parent's height
+    pub fn padding_top(&self) -> UDim {
+        get_udim(&self.instance, "PaddingTop")
+    }
+
+    /// Get the bottom inset, relative to the parent's height
+    pub fn padding_bottom(&self) -> UDim {
+        get_udim(&self.instance, "PaddingBottom")
+    }
+
+    /// Set all four insets at once
+    pub fn set_padding(&self, left: UDim, right: UDim, top: UDim, bottom: UDim) {
+        let mut instance = self.instance.borrow_mut();
+        instance.set_property("PaddingLeft", left);
+        instance.set_property("PaddingRight", right);
+        instance.set_property("PaddingTop", top);
+        instance.set_property("PaddingBottom", bottom);
+    }
+}
+
+impl Default for UIPadding {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Typed view over a UIAspectRatioConstraint, which keeps its parent's
+/// width/height ratio fixed
+#[derive(Debug, Clone)]
+pub struct UIAspectRatioConstraint {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl UIAspectRatioConstraint {
+    /// Create a new square constraint
+    pub fn new() -> Self {
+        let instance = new_instance("UIAspectRatioConstraint");
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_property("AspectRatio", 1.0);
+            instance.set_property("AspectType", Variant::Enum(AspectType::FitWithinMaxSize.value()));
+            instance.set_property("DominantAxis", Variant::Enum(DominantAxis::Width.value()));
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as an aspect ratio constraint, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("UIAspectRatioConstraint") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the width/height ratio
+    pub fn aspect_ratio(&self) -> f64 {
+        get_float(&self.instance, "AspectRatio").unwrap_or(1.0)
+    }
+
+    /// Set the width/height ratio; must be positive
+    pub fn set_aspect_ratio(&self, aspect_ratio: f64) {
+        self.instance.borrow_mut().set_property("AspectRatio", aspect_ratio.max(f64::EPSILON));
+    }
+
+    /// Get how the parent is resized
+    pub fn aspect_type(&self) -> AspectType {
+        get_int(&self.instance, "AspectType").and_then(AspectType::from_value).unwrap_or(AspectType::FitWithinMaxSize)
+    }
+
+    /// Set how the parent is resized
+    pub fn set_aspect_type(&self, aspect_type: AspectType) {
+        self.instance.borrow_mut().set_property("AspectType", Variant::Enum(aspect_type.value()));
+    }
+
+    /// Get the axis kept when scaling with the parent size
+    pub fn dominant_axis(&self) -> DominantAxis {
+        get_int(&self.instance, "DominantAxis").and_then(DominantAxis::from_value).unwrap_or(DominantAxis::Width)
+    }
+
+    /// Set the axis kept when scaling with the parent size
+    pub fn set_dominant_axis(&self, dominant_axis: DominantAxis) {
+        self.instance.borrow_mut().set_property("DominantAxis", Variant::Enum(dominant_axis.value()));
+    }
+}
+
+impl Default for UIAspectRatioConstraint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Typed view over a UISizeConstraint, which clamps its parent's size
+#[derive(Debug, Clone)]
+pub struct UISizeConstraint {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl UISizeConstraint {
+    /// Create a new constraint that allows any size
+    pub fn new() -> Self {
+        let instance = new_instance("UISizeConstraint");
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_property("MinSize", Vec2::ZERO);
+            instance.set_property("MaxSize", Vec2::splat(f32::INFINITY));
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a size constraint, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("UISizeConstraint") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the smallest size allowed, in pixels
+    pub fn min_size(&self) -> Vec2 {
+        get_vector2(&self.instance, "MinSize").unwrap_or(Vec2::ZERO)
+    }
+
+    /// Set the smallest size allowed, in pixels
+    pub fn set_min_size(&self, min_size: Vec2) {
+        self.instance.borrow_mut().set_property("MinSize", min_size);
+    }
+
+    /// Get the largest size allowed, in pixels
+    pub fn max_size(&self) -> Vec2 {
+        get_vector2(&self.instance, "MaxSize").unwrap_or(Vec2::splat(f32::INFINITY))
+    }
+
+    /// Set the largest size allowed, in pixels
+    pub fn set_max_size(&self, max_size: Vec2) {
+        self.instance.borrow_mut().set_property("MaxSize", max_size);
+    }
+}
+
+impl Default for UISizeConstraint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// StarterGui - holds the ScreenGuis every player gets in their PlayerGui
+pub struct StarterGui {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl StarterGui {
+    /// Create a new starter gui service
+    pub fn new() -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self {
+            instance: new_instance("StarterGui"),
+        }))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Refresh a PlayerGui when its player spawns.
+    ///
+    /// ScreenGuis with ResetOnSpawn are removed and copied again from
+    /// StarterGui; the others are only copied the first time, and kept after.
+    pub fn reset_player_gui(&self, player_gui: &Rc<RefCell<Instance>>) {
+        let existing = player_gui.borrow().children().to_vec();
+        for gui in existing.iter().filter_map(ScreenGui::from_instance) {
+            if gui.reset_on_spawn() {
+                Instance::destroy(gui.instance());
+            }
+        }
+
+        let templates = self.instance.borrow().children().to_vec();
+        for template in &templates {
+            if !template.borrow().archivable() {
+                continue;
+            }
+
+            let kept = ScreenGui::from_instance(template).is_some_and(|gui| !gui.reset_on_spawn())
+                && player_gui.borrow().find_first_child(template.borrow().name()).is_some();
+            if !kept {
+                Instance::set_parent(&clone_tree(template), Some(player_gui.clone()));
+            }
+        }
+    }
+}
+
+/// Copy an instance and all of its archivable descendants
+pub fn clone_tree(instance: &Rc<RefCell<Instance>>) -> Rc<RefCell<Instance>> {
+    let copy = instance.borrow().clone();
+    let children = instance.borrow().children().to_vec();
+    for child in children.iter().filter(|child| child.borrow().archivable()) {
+        Instance::set_parent(&clone_tree(child), Some(copy.clone()));
+    }
+    copy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gui_object_defaults() {
+        let frame = GuiObject::new("Frame");
+        assert!(frame.instance().borrow().is_a("GuiObject"));
+        assert_eq!(frame.size(), DEFAULT_GUI_SIZE);
+        assert_eq!(frame.z_index(), 1);
+        assert!(frame.visible());
+        assert!(frame.text().is_none());
+
+        let button = GuiObject::new("TextButton");
+        assert!(button.instance().borrow().is_a("GuiButton"));
+        assert_eq!(button.text().as_deref(), Some("TextButton"));
+        button.set_text("Play");
+        assert_eq!(button.text().as_deref(), Some("Play"));
+
+        let image = GuiObject::new("ImageLabel");
+        assert_eq!(image.image().as_deref(), Some(""));
+
+        assert!(GuiObject::from_instance(ScreenGui::new().instance()).is_none());
+        assert!(UIListLayout::new().instance().borrow().is_a("UILayout"));
+        assert!(UISizeConstraint::new().instance().borrow().is_a("UIConstraint"));
+    }
+
+    #[test]
+    fn test_reset_player_gui() {
+        let starter_gui = StarterGui::new();
+        let hud = ScreenGui::new();
+        hud.instance().borrow_mut().set_name("Hud");
+        Instance::set_parent(GuiObject::new("Frame").instance(), Some(hud.instance().clone()));
+        Instance::set_parent(hud.instance(), Some(starter_gui.borrow().instance().clone()));
+
+        let menu = ScreenGui::new();
+        menu.instance().borrow_mut().set_name("Menu");
+        menu.set_reset_on_spawn(false);
+        Instance::set_parent(menu.instance(), Some(starter_gui.borrow().instance().clone()));
+
+        let player_gui = new_instance("PlayerGui");
+        starter_gui.borrow().reset_player_gui(&player_gui);
+        assert_eq!(player_gui.borrow().num_children(), 2);
+
+        let hud_copy = player_gui.borrow().find_first_child("Hud").unwrap();
+        assert!(!Rc::ptr_eq(&hud_copy, hud.instance()));
+        assert_eq!(hud_copy.borrow().num_children(), 1);
+        let menu_copy = player_gui.borrow().find_first_child("Menu").unwrap();
+
+        // Respawning replaces Hud but keeps Menu
+        starter_gui.borrow().reset_player_gui(&player_gui);
+        assert_eq!(player_gui.borrow().num_children(), 2);
+        assert!(hud_copy.borrow().is_destroyed());
+        assert!(Rc::ptr_eq(&player_gui.borrow().find_first_child("Menu").unwrap(), &menu_copy));
+    }
+}
@@ -0,0 +1,486 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use glam::Vec2;
+use rnr_core::instance::Instance;
+use rnr_core::types::{Color3, UDim2};
+use crate::gui::{
+    AspectType, BillboardGui, DominantAxis, FillDirection, GuiObject, HorizontalAlignment, ScreenGui, SortOrder, UIAspectRatioConstraint,
+    UIGridLayout, UIListLayout, UIPadding, UISizeConstraint, VerticalAlignment,
+};
+
+/// An axis-aligned rectangle in screen pixels, with the origin at the top left
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GuiRect {
+    pub position: Vec2,
+    pub size: Vec2,
+}
+
+impl GuiRect {
+    pub fn new(position: Vec2, size: Vec2) -> Self {
+        Self { position, size }
+    }
+
+    /// Get the bottom right corner
+    pub fn max(&self) -> Vec2 {
+        self.position + self.size
+    }
+
+    /// Get the overlap of two rectangles, which is empty if they don't touch
+    pub fn intersect(&self, other: &GuiRect) -> GuiRect {
+        let min = self.position.max(other.position);
+        let max = self.max().min(other.max());
+        GuiRect::new(min, (max - min).max(Vec2::ZERO))
+    }
+}
+
+/// A GUI object placed on screen, in the order it should be drawn
+#[derive(Debug, Clone)]
+pub struct GuiDrawItem {
+    pub object: GuiObject,
+    pub rect: GuiRect,
+    /// Area the object is cut off to, set by ancestors with ClipsDescendants
+    pub clip: Option<GuiRect>,
+    pub background_color: Color3,
+    pub background_transparency: f32,
+    /// Image content id of image objects with an image set
+    pub image: Option<String>,
+    /// Text of text objects
+    pub text: Option<String>,
+    pub text_color: Color3,
+    pub text_transparency: f32,
+    pub text_size: f32,
+    pub text_wrapped: bool,
+    pub text_scaled: bool,
+    /// Where the text sits in the rect, from (0, 0) for the top left to
+    /// (1, 1) for the bottom right
+    pub text_alignment: Vec2,
+}
+
+/// Resolve a UDim2 against a parent size in pixels
+pub fn resolve_udim2(udim2: &UDim2, parent_size: Vec2) -> Vec2 {
+    Vec2::new(udim2.x.resolve(parent_size.x), udim2.y.resolve(parent_size.y))
+}
+
+/// Lay out every ScreenGui under `root` for a viewport and return what to
+/// draw, back to front.
+///
+/// `root` is usually a PlayerGui, but a single ScreenGui works too. Every
+/// GuiObject laid out gets its AbsolutePosition and AbsoluteSize written.
+/// ScreenGuis are drawn in DisplayOrder and siblings in ZIndex order, each
+/// object before its descendants. Disabled guis and invisible objects, along
+/// with their descendants, are skipped.
+pub fn layout_gui(root: &Rc<RefCell<Instance>>, viewport: Vec2) -> Vec<GuiDrawItem> {
+    let mut screen_guis: Vec<ScreenGui> = match ScreenGui::from_instance(root) {
+        Some(screen_gui) => vec![screen_gui],
+        None => root.borrow().children().iter().filter_map(ScreenGui::from_instance).collect(),
+    };
+    screen_guis.sort_by_key(ScreenGui::display_order);
+
+    let mut items = Vec::new();
+    for screen_gui in screen_guis {
+        {
+            let mut instance = screen_gui.instance().borrow_mut();
+            instance.set_property("AbsolutePosition", Vec2::ZERO);
+            instance.set_property("AbsoluteSize", viewport);
+        }
+
+        if screen_gui.enabled() {
+            layout_children(screen_gui.instance(), GuiRect::new(Vec2::ZERO, viewport), None, &mut items);
+        }
+    }
+    items
+}
+
+/// Lay out a BillboardGui centered on a point on screen and return what to
+/// draw, back to front.
+///
+/// `pixels_per_stud` converts the scale components of the gui's Size, which
+/// are in studs, to pixels at the gui's distance from the camera.
+pub fn layout_billboard_gui(billboard: &BillboardGui, center: Vec2, pixels_per_stud: f32) -> Vec<GuiDrawItem> {
+    let size = billboard.size();
+    let size = Vec2::new(
+        size.x.scale * pixels_per_stud + size.x.offset as f32,
+        size.y.scale * pixels_per_stud + size.y.offset as f32,
+    ).max(Vec2::ZERO);
+    let rect = GuiRect::new(center - size / 2.0, size);
+    {
+        let mut instance = billboard.instance().borrow_mut();
+        instance.set_property("AbsolutePosition", rect.position);
+        instance.set_property("AbsoluteSize", rect.size);
+    }
+
+    let mut items = Vec::new();
+    if billboard.enabled() {
+        layout_children(billboard.instance(), rect, None, &mut items);
+    }
+    items
+}
+
+fn layout_children(parent: &Rc<RefCell<Instance>>, rect: GuiRect, clip: Option<GuiRect>, items: &mut Vec<GuiDrawItem>) {
+    let children = parent.borrow().children().to_vec();
+    let content = match children.iter().find_map(UIPadding::from_instance) {
+        Some(padding) => apply_padding(&padding, rect),
+        None => rect,
+    };
+
+    let objects: Vec<GuiObject> = children.iter().filter_map(GuiObject::from_instance).collect();
+    let (visible, hidden): (Vec<GuiObject>, Vec<GuiObject>) = objects.into_iter().partition(GuiObject::visible);
+
+    // Hidden objects take no space in layouts, but still get a position
+    for object in &hidden {
+        let rect = free_rect(object, content);
+        object.set_absolute(rect.position, rect.size);
+    }
+
+    let mut placed = if let Some(list) = children.iter().find_map(UIListLayout::from_instance) {
+        list_layout(&list, visible, content)
+    } else if let Some(grid) = children.iter().find_map(UIGridLayout::from_instance) {
+        grid_layout(&grid, visible, content)
+    } else {
+        visible.into_iter().map(|object| {
+            let rect = free_rect(&object, content);
+            (object, rect)
+        }).collect()
+    };
+    placed.sort_by_key(|(object, _)| object.z_index());
+
+    for (object, rect) in placed {
+        object.set_absolute(rect.position, rect.size);
+        items.push(GuiDrawItem {
+            rect,
+            clip,
+            background_color: object.background_color3(),
+            background_transparency: object.background_transparency() as f32,
+            image: object.image().filter(|image| !image.is_empty()),
+            text: object.text(),
+            text_color: object.text_color3(),
+            text_transparency: object.text_transparency() as f32,
+            text_size: object.text_size() as f32,
+            text_wrapped: object.text_wrapped(),
+            text_scaled: object.text_scaled(),
+            text_alignment: Vec2::new(object.text_x_alignment().fraction(), object.text_y_alignment().fraction()),
+            object: object.clone(),
+        });
+
+        let child_clip = if object.clips_descendants() {
+            Some(clip.map_or(rect, |clip| clip.intersect(&rect)))
+        } else {
+            clip
+        };
+        layout_children(object.instance(), rect, child_clip, items);
+    }
+}
+
+fn apply_padding(padding: &UIPadding, rect: GuiRect) -> GuiRect {
+    let left = padding.padding_left().resolve(rect.size.x);
+    let right = padding.padding_right().resolve(rect.size.x);
+    let top = padding.padding_top().resolve(rect.size.y);
+    let bottom = padding.padding_bottom().resolve(rect.size.y);
+
+    GuiRect::new(
+        rect.position + Vec2::new(left, top),
+        (rect.size - Vec2::new(left + right, top + bottom)).max(Vec2::ZERO),
+    )
+}
+
+/// Size of an object in a parent area, after its size and aspect ratio constraints
+fn constrained_size(object: &GuiObject, parent_size: Vec2) -> Vec2 {
+    let mut size = resolve_udim2(&object.size(), parent_size);
+    let children = object.instance().borrow().children().to_vec();
+
+    if let Some(constraint) = children.iter().find_map(UISizeConstraint::from_instance) {
+        let min = constraint.min_size();
+        size = size.max(min).min(constraint.max_size().max(min));
+    }
+
+    if let Some(constraint) = children.iter().find_map(UIAspectRatioConstraint::from_instance) {
+        let ratio = constraint.aspect_ratio() as f32;
+        match constraint.aspect_type() {
+            AspectType::FitWithinMaxSize => {
+                if size.x > size.y * ratio {
+                    size.x = size.y * ratio;
+                } else {
+                    size.y = size.x / ratio;
+                }
+            }
+            AspectType::ScaleWithParentSize => match constraint.dominant_axis() {
+                DominantAxis::Width => size.y = size.x / ratio,
+                DominantAxis::Height => size.x = size.y * ratio,
+            },
+        }
+    }
+    size
+}
+
+/// Place an object by its own Position and AnchorPoint
+fn free_rect(object: &GuiObject, parent: GuiRect) -> GuiRect {
+    let size = constrained_size(object, parent.size);
+    let position = parent.position + resolve_udim2(&object.position(), parent.size) - object.anchor_point() * size;
+    GuiRect::new(position, size)
+}
+
+fn sort_objects(objects: &mut [GuiObject], sort_order: SortOrder) {
+    match sort_order {
+        SortOrder::Name => objects.sort_by_key(|object| object.instance().borrow().name().to_string()),
+        SortOrder::LayoutOrder => objects.sort_by_key(GuiObject::layout_order),
+        SortOrder::Custom => {}
+    }
+}
+
+/// Fraction of the free space to put before content, for an alignment along one axis
+fn alignment_fraction(start: bool, end: bool) -> f32 {
+    if start {
+        0.0
+    } else if end {
+        1.0
+    } else {
+        0.5
+    }
+}
+
+fn list_layout(list: &UIListLayout, mut objects: Vec<GuiObject>, content: GuiRect) -> Vec<(GuiObject, GuiRect)> {
+    sort_objects(&mut objects, list.sort_order());
+
+    let horizontal = list.fill_direction() == FillDirection::Horizontal;
+    // Work in (main, cross) axes and swap back at the end
+    let axes = |v: Vec2| if horizontal { v } else { Vec2::new(v.y, v.x) };
+    let content_size = axes(content.size);
+
+    let horizontal_fraction = alignment_fraction(
+        list.horizontal_alignment() == HorizontalAlignment::Left,
+        list.horizontal_alignment() == HorizontalAlignment::Right,
+    );
+    let vertical_fraction = alignment_fraction(
+        list.vertical_alignment() == VerticalAlignment::Top,
+        list.vertical_alignment() == VerticalAlignment::Bottom,
+    );
+    let (main_fraction, cross_fraction) = if horizontal {
+        (horizontal_fraction, vertical_fraction)
+    } else {
+        (vertical_fraction, horizontal_fraction)
+    };
+
+    let padding = list.padding().resolve(content_size.x);
+    let sizes: Vec<Vec2> = objects.iter().map(|object| constrained_size(object, content.size)).collect();
+    let total: f32 = sizes.iter().map(|size| axes(*size).x).sum::<f32>() + padding * sizes.len().saturating_sub(1) as f32;
+
+    let mut main = (content_size.x - total) * main_fraction;
+    objects.into_iter().zip(sizes).map(|(object, size)| {
+        let cross = (content_size.y - axes(size).y) * cross_fraction;
+        let rect = GuiRect::new(content.position + axes(Vec2::new(main, cross)), size);
+        main += axes(size).x + padding;
+        (object, rect)
+    }).collect()
+}
+
+fn grid_layout(grid: &UIGridLayout, mut objects: Vec<GuiObject>, content: GuiRect) -> Vec<(GuiObject, GuiRect)> {
+    sort_objects(&mut objects, grid.sort_order());
+
+    let horizontal = grid.fill_direction() == FillDirection::Horizontal;
+    let axes = |v: Vec2| if horizontal { v } else { Vec2::new(v.y, v.x) };
+    let cell = resolve_udim2(&grid.cell_size(), content.size);
+    let padding = resolve_udim2(&grid.cell_padding(), content.size);
+
+    let (main_cell, main_padding, main_length) = (axes(cell).x, axes(padding).x, axes(content.size).x);
+    let mut per_line = (((main_length + main_padding) / (main_cell + main_padding).max(1.0)).floor() as usize).max(1);
+    let max_cells = grid.fill_direction_max_cells();
+    if max_cells > 0 {
+        per_line = per_line.min(max_cells as usize);
+    }
+
+    let step = axes(cell + padding);
+    objects.into_iter().enumerate().map(|(index, object)| {
+        let slot = Vec2::new((index % per_line) as f32, (index / per_line) as f32);
+        let rect = GuiRect::new(content.position + axes(slot * step), cell);
+        (object, rect)
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rnr_core::types::UDim;
+    use crate::gui::{TextXAlignment, TextYAlignment};
+
+    const VIEWPORT: Vec2 = Vec2::new(800.0, 600.0);
+
+    fn add(parent: &Rc<RefCell<Instance>>, child: &Rc<RefCell<Instance>>) {
+        Instance::set_parent(child, Some(parent.clone()));
+    }
+
+    fn frame(parent: &Rc<RefCell<Instance>>, size: UDim2) -> GuiObject {
+        let frame = GuiObject::new("Frame");
+        frame.set_size(size);
+        add(parent, frame.instance());
+        frame
+    }
+
+    #[test]
+    fn test_position_size_and_anchor_point() {
+        let screen_gui = ScreenGui::new();
+        let outer = frame(screen_gui.instance(), UDim2::new(0.5, 0, 0.5, 0));
+        outer.set_position(UDim2::new(0.5, 0, 0.5, 0));
+        outer.set_anchor_point(Vec2::new(0.5, 0.5));
+        let inner = frame(outer.instance(), UDim2::new(1.0, -20, 0.0, 30));
+        inner.set_position(UDim2::from_offset(10, 10));
+
+        let items = layout_gui(screen_gui.instance(), VIEWPORT);
+
+        assert_eq!(screen_gui.absolute_size(), VIEWPORT);
+        assert_eq!(outer.absolute_position(), Vec2::new(200.0, 150.0));
+        assert_eq!(outer.absolute_size(), Vec2::new(400.0, 300.0));
+        assert_eq!(inner.absolute_position(), Vec2::new(210.0, 160.0));
+        assert_eq!(inner.absolute_size(), Vec2::new(380.0, 30.0));
+        assert_eq!(items.len(), 2);
+    }
+
+    #[test]
+    fn test_text_properties_and_billboard() {
+        let billboard = BillboardGui::new();
+        billboard.set_size(UDim2::new(4.0, 0, 1.0, 20));
+        let label = GuiObject::new("TextLabel");
+        label.set_size(UDim2::new(1.0, 0, 1.0, 0));
+        label.set_text_wrapped(true);
+        label.set_text_x_alignment(TextXAlignment::Right);
+        label.set_text_y_alignment(TextYAlignment::Top);
+        add(billboard.instance(), label.instance());
+
+        let items = layout_billboard_gui(&billboard, Vec2::new(400.0, 300.0), 10.0);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].rect, GuiRect::new(Vec2::new(380.0, 285.0), Vec2::new(40.0, 30.0)));
+        assert_eq!(items[0].text.as_deref(), Some("TextLabel"));
+        assert!(items[0].text_wrapped && !items[0].text_scaled);
+        assert_eq!(items[0].text_alignment, Vec2::new(1.0, 0.0));
+        assert_eq!(billboard.instance().borrow().get_property("AbsoluteSize").and_then(rnr_core::variant::Variant::as_vector2), Some(Vec2::new(40.0, 30.0)));
+
+        billboard.set_enabled(false);
+        assert!(layout_billboard_gui(&billboard, Vec2::ZERO, 10.0).is_empty());
+    }
+
+    #[test]
+    fn test_draw_order_visibility_and_clipping() {
+        let player_gui = Instance::new();
+        let back = ScreenGui::new();
+        back.set_display_order(1);
+        let front = ScreenGui::new();
+        front.set_display_order(2);
+        let disabled = ScreenGui::new();
+        disabled.set_enabled(false);
+        add(&player_gui, front.instance());
+        add(&player_gui, back.instance());
+        add(&player_gui, disabled.instance());
+
+        let high = frame(back.instance(), UDim2::from_offset(50, 50));
+        high.set_z_index(5);
+        let low = frame(back.instance(), UDim2::from_offset(100, 100));
+        low.set_clips_descendants(true);
+        let clipped = frame(low.instance(), UDim2::from_offset(300, 300));
+        clipped.set_position(UDim2::from_offset(50, 0));
+        let hidden = frame(back.instance(), UDim2::from_offset(10, 10));
+        hidden.set_visible(false);
+        frame(hidden.instance(), UDim2::from_offset(10, 10));
+        let top = frame(front.instance(), UDim2::from_offset(10, 10));
+        frame(disabled.instance(), UDim2::from_offset(10, 10));
+
+        let items = layout_gui(&player_gui, VIEWPORT);
+        let order: Vec<*const RefCell<Instance>> = items.iter().map(|item| Rc::as_ptr(item.object.instance())).collect();
+        let expected: Vec<*const RefCell<Instance>> = [&low, &clipped, &high, &top].iter().map(|object| Rc::as_ptr(object.instance())).collect();
+        assert_eq!(order, expected);
+        assert_eq!(items[1].clip, Some(GuiRect::new(Vec2::ZERO, Vec2::new(100.0, 100.0))));
+        assert_eq!(items[2].clip, None);
+        assert_eq!(items[1].rect.intersect(&items[1].clip.unwrap()).size, Vec2::new(50.0, 100.0));
+    }
+
+    #[test]
+    fn test_list_layout() {
+        let screen_gui = ScreenGui::new();
+        let container = frame(screen_gui.instance(), UDim2::from_offset(200, 400));
+        let padding = UIPadding::new();
+        padding.set_padding(UDim::new(0.0, 10), UDim::new(0.0, 10), UDim::new(0.0, 5), UDim::new(0.0, 0));
+        add(container.instance(), padding.instance());
+        let list = UIListLayout::new();
+        list.set_padding(UDim::new(0.0, 4));
+        list.set_horizontal_alignment(HorizontalAlignment::Center);
+        add(container.instance(), list.instance());
+
+        let second = frame(container.instance(), UDim2::new(1.0, 0, 0.0, 20));
+        second.set_layout_order(2);
+        let first = frame(container.instance(), UDim2::from_offset(60, 30));
+        first.set_layout_order(1);
+        // Position is ignored inside a layout
+        first.set_position(UDim2::from_offset(500, 500));
+
+        layout_gui(screen_gui.instance(), VIEWPORT);
+
+        assert_eq!(first.absolute_position(), Vec2::new(70.0, 5.0));
+        assert_eq!(first.absolute_size(), Vec2::new(60.0, 30.0));
+        assert_eq!(second.absolute_position(), Vec2::new(10.0, 39.0));
+        assert_eq!(second.absolute_size(), Vec2::new(180.0, 20.0));
+
+        list.set_fill_direction(FillDirection::Horizontal);
+        list.set_horizontal_alignment(HorizontalAlignment::Right);
+        list.set_vertical_alignment(VerticalAlignment::Bottom);
+        layout_gui(screen_gui.instance(), VIEWPORT);
+
+        assert_eq!(first.absolute_position(), Vec2::new(-54.0, 370.0));
+        assert_eq!(second.absolute_position(), Vec2::new(10.0, 380.0));
+    }
+
+    #[test]
+    fn test_grid_layout() {
+        let screen_gui = ScreenGui::new();
+        let container = frame(screen_gui.instance(), UDim2::from_offset(230, 400));
+        let grid = UIGridLayout::new();
+        grid.set_cell_size(UDim2::from_offset(50, 40));
+        grid.set_cell_padding(UDim2::from_offset(10, 5));
+        grid.set_sort_order(SortOrder::Name);
+        add(container.instance(), grid.instance());
+
+        let cells: Vec<GuiObject> = (0..5).map(|i| {
+            let cell = frame(container.instance(), UDim2::from_offset(1, 1));
+            cell.instance().borrow_mut().set_name(&format!("Cell{}", 4 - i));
+            cell
+        }).collect();
+
+        layout_gui(screen_gui.instance(), VIEWPORT);
+
+        // 230 pixels fit four 50 pixel cells with 10 pixel gaps
+        assert_eq!(cells[4].absolute_position(), Vec2::new(0.0, 0.0));
+        assert_eq!(cells[1].absolute_position(), Vec2::new(180.0, 0.0));
+        assert_eq!(cells[0].absolute_position(), Vec2::new(0.0, 45.0));
+        assert_eq!(cells[0].absolute_size(), Vec2::new(50.0, 40.0));
+
+        grid.set_fill_direction(FillDirection::Vertical);
+        grid.set_fill_direction_max_cells(2);
+        layout_gui(screen_gui.instance(), VIEWPORT);
+
+        assert_eq!(cells[3].absolute_position(), Vec2::new(0.0, 45.0));
+        assert_eq!(cells[2].absolute_position(), Vec2::new(60.0, 0.0));
+    }
+
+    #[test]
+    fn test_constraints() {
+        let screen_gui = ScreenGui::new();
+        let square = frame(screen_gui.instance(), UDim2::from_scale(0.5, 0.5));
+        add(square.instance(), UIAspectRatioConstraint::new().instance());
+
+        let wide = frame(screen_gui.instance(), UDim2::from_scale(0.5, 0.5));
+        let aspect = UIAspectRatioConstraint::new();
+        aspect.set_aspect_ratio(2.0);
+        aspect.set_aspect_type(AspectType::ScaleWithParentSize);
+        aspect.set_dominant_axis(DominantAxis::Height);
+        add(wide.instance(), aspect.instance());
+
+        let clamped = frame(screen_gui.instance(), UDim2::from_scale(1.0, 0.0));
+        let size = UISizeConstraint::new();
+        size.set_min_size(Vec2::new(0.0, 20.0));
+        size.set_max_size(Vec2::new(300.0, 100.0));
+        add(clamped.instance(), size.instance());
+
+        layout_gui(screen_gui.instance(), VIEWPORT);
+
+        assert_eq!(square.absolute_size(), Vec2::new(300.0, 300.0));
+        assert_eq!(wide.absolute_size(), Vec2::new(600.0, 300.0));
+        assert_eq!(clamped.absolute_size(), Vec2::new(300.0, 20.0));
+    }
+}
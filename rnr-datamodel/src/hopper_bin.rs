@@ -0,0 +1,361 @@
+use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use glam::Vec3;
+use rnr_core::instance::Instance;
+use rnr_core::signal::Signal;
+use rnr_core::types::CFrame;
+use rnr_core::variant::Variant;
+use crate::gui::clone_tree;
+use crate::part::{move_model, BasePart};
+use crate::players::Player;
+
+/// The built-in behavior of a HopperBin (Enum.BinType)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinType {
+    /// Nothing built in; scripts listen to Selected and Deselected
+    Script = 0,
+    /// Drag whole models around
+    GameTool = 1,
+    /// Drag single parts around
+    Grab = 2,
+    /// Copy clicked parts
+    Clone = 3,
+    /// Destroy clicked parts
+    Hammer = 4,
+}
+
+impl BinType {
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Get the bin type with an enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(Self::Script),
+            1 => Some(Self::GameTool),
+            2 => Some(Self::Grab),
+            3 => Some(Self::Clone),
+            4 => Some(Self::Hammer),
+            _ => None,
+        }
+    }
+}
+
+/// Typed view over a HopperBin, the classic backpack item that works without
+/// being held
+#[derive(Debug, Clone)]
+pub struct HopperBin {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl HopperBin {
+    /// Create a new, inactive hopper bin
+    pub fn new(name: &str, bin_type: BinType) -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name(name);
+            instance.set_class_name("HopperBin");
+            instance.set_property("BinType", Variant::Enum(bin_type.value()));
+            instance.set_property("Active", false);
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a hopper bin, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("HopperBin") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get what the bin does when it clicks
+    pub fn bin_type(&self) -> BinType {
+        self.instance.borrow().get_property("BinType").and_then(Variant::as_int).and_then(BinType::from_value).unwrap_or(BinType::Script)
+    }
+
+    /// Set what the bin does when it clicks
+    pub fn set_bin_type(&self, bin_type: BinType) {
+        self.instance.borrow_mut().set_property("BinType", Variant::Enum(bin_type.value()));
+    }
+
+    /// Check if the bin is selected
+    pub fn active(&self) -> bool {
+        self.instance.borrow().get_property("Active").and_then(Variant::as_bool).unwrap_or(false)
+    }
+}
+
+/// What a hopper bin click did
+#[derive(Debug, Clone)]
+pub enum BinAction {
+    /// A Hammer destroyed the part
+    Destroyed(BasePart),
+    /// A Clone bin copied the part on top of itself
+    Cloned { original: BasePart, copy: BasePart },
+    /// A Grab or GameTool bin started dragging the part
+    Grabbed(BasePart),
+}
+
+/// The events of one hopper bin
+#[derive(Default)]
+struct BinSignals {
+    selected: Signal<Player>,
+    deselected: Signal<Player>,
+}
+
+/// A part being dragged by a player, and what moves with it
+struct Drag {
+    user_id: i64,
+    part: BasePart,
+    /// The part, or the model it is in for a GameTool
+    target: Rc<RefCell<Instance>>,
+    /// Distance from the mouse ray's origin the part is held at
+    distance: f32,
+    /// From the point the part was grabbed at to its position
+    offset: Vec3,
+}
+
+/// Selects hopper bins and carries out their built-in BinTypes.
+///
+/// A player has at most one active bin in their Backpack. Clicks with it
+/// hammer, clone or grab the part under the mouse; grabbed parts follow
+/// the mouse ray until released. Locked parts can't be changed.
+#[derive(Default)]
+pub struct HopperBins {
+    signals: Vec<(Weak<RefCell<Instance>>, BinSignals)>,
+    drags: Vec<Drag>,
+}
+
+impl HopperBins {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn signals(&mut self, bin: &HopperBin) -> &BinSignals {
+        self.signals.retain(|(known, _)| known.strong_count() > 0);
+        let index = match self.signals.iter().position(|(known, _)| known.as_ptr() == Rc::as_ptr(bin.instance())) {
+            Some(index) => index,
+            None => {
+                self.signals.push((Rc::downgrade(bin.instance()), BinSignals::default()));
+                self.signals.len() - 1
+            }
+        };
+        &self.signals[index].1
+    }
+
+    /// Get the Selected signal of a bin
+    pub fn selected(&mut self, bin: &HopperBin) -> Signal<Player> {
+        self.signals(bin).selected.clone()
+    }
+
+    /// Get the Deselected signal of a bin
+    pub fn deselected(&mut self, bin: &HopperBin) -> Signal<Player> {
+        self.signals(bin).deselected.clone()
+    }
+
+    /// Get the bin a player has selected
+    pub fn selected_bin(player: &Player) -> Option<HopperBin> {
+        let backpack = player.backpack()?;
+        let items = backpack.borrow().children().to_vec();
+        items.iter().filter_map(HopperBin::from_instance).find(HopperBin::active)
+    }
+
+    /// Select a bin in the player's Backpack, deselecting the one before.
+    /// Returns false if the bin isn't in their Backpack.
+    pub fn select(&mut self, player: &Player, bin: &HopperBin) -> bool {
+        let in_backpack = bin.instance().borrow().parent().zip(player.backpack()).is_some_and(|(parent, backpack)| Rc::ptr_eq(&parent, &backpack));
+        if !in_backpack {
+            return false;
+        }
+        if bin.active() {
+            return true;
+        }
+
+        self.deselect(player);
+        bin.instance().borrow_mut().set_property("Active", true);
+        self.selected(bin).fire(player);
+        true
+    }
+
+    /// Deselect the player's bin, letting go of anything it was dragging
+    pub fn deselect(&mut self, player: &Player) {
+        self.release(player);
+        if let Some(bin) = Self::selected_bin(player) {
+            bin.instance().borrow_mut().set_property("Active", false);
+            self.deselected(&bin).fire(player);
+        }
+    }
+
+    /// Click a part with the player's selected bin, `hit` being where the
+    /// mouse ray from `origin` hit it. Returns what the bin did, if anything.
+    pub fn click(&mut self, player: &Player, part: &BasePart, origin: Vec3, hit: Vec3) -> Option<BinAction> {
+        let bin = Self::selected_bin(player)?;
+        if part.locked() {
+            return None;
+        }
+
+        match bin.bin_type() {
+            BinType::Script => None,
+            BinType::Hammer => {
+                Instance::destroy(part.instance());
+                Some(BinAction::Destroyed(part.clone()))
+            }
+            BinType::Clone => {
+                let copy = BasePart::from_instance(&clone_tree(part.instance()))?;
+                let parent = part.instance().borrow().parent();
+                copy.set_position(part.position() + Vec3::Y * part.size().y);
+                Instance::set_parent(copy.instance(), parent);
+                Some(BinAction::Cloned { original: part.clone(), copy })
+            }
+            bin_type @ (BinType::Grab | BinType::GameTool) => {
+                self.release(player);
+                let model = part.instance().borrow().parent().filter(|parent| parent.borrow().class_name() == "Model");
+                let target = match (bin_type, model) {
+                    (BinType::GameTool, Some(model)) => model,
+                    _ => part.instance().clone(),
+                };
+                self.drags.push(Drag {
+                    user_id: player.user_id(),
+                    part: part.clone(),
+                    target,
+                    distance: hit.distance(origin),
+                    offset: part.position() - hit,
+                });
+                Some(BinAction::Grabbed(part.clone()))
+            }
+        }
+    }
+
+    /// Move what the player is dragging along a new mouse ray. Returns the
+    /// parts moved.
+    pub fn drag(&mut self, player: &Player, origin: Vec3, direction: Vec3) -> Vec<BasePart> {
+        let Some(drag) = self.drags.iter().find(|drag| drag.user_id == player.user_id()) else {
+            return Vec::new();
+        };
+        if drag.part.instance().borrow().is_destroyed() {
+            self.release(player);
+            return Vec::new();
+        }
+
+        let position = origin + direction.normalize_or_zero() * drag.distance + drag.offset;
+        let from = drag.part.cframe();
+        let to = CFrame { position, rotation: from.rotation };
+        if Rc::ptr_eq(&drag.target, drag.part.instance()) {
+            drag.part.set_cframe(to);
+            return vec![drag.part.clone()];
+        }
+        move_model(&drag.target, from, to);
+        drag.target.borrow().get_descendants().iter().filter_map(BasePart::from_instance).collect()
+    }
+
+    /// Check if the player is dragging something
+    pub fn dragging(&self, player: &Player) -> bool {
+        self.drags.iter().any(|drag| drag.user_id == player.user_id())
+    }
+
+    /// Let go of what the player is dragging
+    pub fn release(&mut self, player: &Player) {
+        self.drags.retain(|drag| drag.user_id != player.user_id());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part::create_model;
+
+    fn with_bin(bin_type: BinType) -> (Player, HopperBin) {
+        let player = Player::new("Player1", 1);
+        let bin = HopperBin::new("Bin", bin_type);
+        Instance::set_parent(bin.instance(), player.backpack());
+        (player, bin)
+    }
+
+    #[test]
+    fn test_select_and_deselect_bins() {
+        let (player, hammer) = with_bin(BinType::Hammer);
+        let clone = HopperBin::new("Clone", BinType::Clone);
+        Instance::set_parent(clone.instance(), player.backpack());
+        let mut bins = HopperBins::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        for (event, signal) in [("hammer selected", bins.selected(&hammer)), ("hammer deselected", bins.deselected(&hammer)), ("clone selected", bins.selected(&clone))] {
+            let log = log.clone();
+            signal.connect(move |_| log.borrow_mut().push(event));
+        }
+
+        assert!(bins.select(&player, &hammer));
+        assert!(bins.select(&player, &clone));
+        assert!(!bins.select(&player, &HopperBin::new("Loose", BinType::Grab)));
+        assert_eq!(*log.borrow(), vec!["hammer selected", "hammer deselected", "clone selected"]);
+        assert!(!hammer.active());
+        assert_eq!(HopperBins::selected_bin(&player).unwrap().bin_type(), BinType::Clone);
+    }
+
+    #[test]
+    fn test_hammer_clone_and_locked_parts() {
+        let workspace = Instance::new();
+        let part = BasePart::new("Part");
+        part.set_position(Vec3::new(0.0, 5.0, 0.0));
+        Instance::set_parent(part.instance(), Some(workspace.clone()));
+
+        let (player, bin) = with_bin(BinType::Clone);
+        let mut bins = HopperBins::new();
+        assert!(bins.click(&player, &part, Vec3::ZERO, part.position()).is_none());
+        bins.select(&player, &bin);
+        let Some(BinAction::Cloned { copy, .. }) = bins.click(&player, &part, Vec3::ZERO, part.position()) else {
+            panic!("expected a copy");
+        };
+        assert_eq!(copy.position(), Vec3::new(0.0, 5.0 + part.size().y, 0.0));
+        assert_eq!(workspace.borrow().children().len(), 2);
+
+        bin.set_bin_type(BinType::Hammer);
+        copy.set_locked(true);
+        assert!(bins.click(&player, &copy, Vec3::ZERO, copy.position()).is_none());
+        assert!(matches!(bins.click(&player, &part, Vec3::ZERO, part.position()), Some(BinAction::Destroyed(_))));
+        assert!(part.instance().borrow().is_destroyed());
+        assert_eq!(workspace.borrow().children().len(), 1);
+    }
+
+    #[test]
+    fn test_grab_and_game_tool_drag() {
+        let model = create_model("Car");
+        let body = BasePart::new("Part");
+        let wheel = BasePart::new("Part");
+        body.set_position(Vec3::new(0.0, 0.0, -10.0));
+        wheel.set_position(Vec3::new(2.0, 0.0, -10.0));
+        Instance::set_parent(body.instance(), Some(model.clone()));
+        Instance::set_parent(wheel.instance(), Some(model.clone()));
+
+        let (player, bin) = with_bin(BinType::Grab);
+        let mut bins = HopperBins::new();
+        bins.select(&player, &bin);
+        bins.click(&player, &body, Vec3::ZERO, Vec3::new(0.0, 0.0, -9.0));
+        assert!(bins.dragging(&player));
+        let moved = bins.drag(&player, Vec3::ZERO, Vec3::new(1.0, 0.0, -1.0));
+        assert_eq!(moved.len(), 1);
+        let expected = Vec3::new(1.0, 0.0, -1.0).normalize() * 9.0 + Vec3::new(0.0, 0.0, -1.0);
+        assert!(body.position().distance(expected) < 1e-4);
+        assert_eq!(wheel.position(), Vec3::new(2.0, 0.0, -10.0));
+
+        // A GameTool takes the whole model along
+        bin.set_bin_type(BinType::GameTool);
+        bins.click(&player, &wheel, Vec3::ZERO, wheel.position());
+        assert_eq!(bins.drag(&player, Vec3::new(0.0, 3.0, 0.0), Vec3::new(2.0, 0.0, -10.0)).len(), 2);
+        assert!(wheel.position().distance(Vec3::new(2.0, 3.0, -10.0)) < 1e-4);
+        assert!(body.position().distance(expected + Vec3::Y * 3.0) < 1e-4);
+
+        bins.deselect(&player);
+        assert!(!bins.dragging(&player));
+        assert!(bins.drag(&player, Vec3::ZERO, Vec3::X).is_empty());
+    }
+}
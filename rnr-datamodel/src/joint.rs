@@ -0,0 +1,102 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use rnr_core::instance::Instance;
+use rnr_core::types::CFrame;
+use rnr_core::variant::Variant;
+use crate::part::BasePart;
+
+/// Typed view over a JointInstance (Weld, ManualWeld, Snap, Glue, Motor,
+/// Motor6D), which holds Part1 at C1 relative to Part0's C0.
+#[derive(Debug, Clone)]
+pub struct JointInstance {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl JointInstance {
+    /// Create a new joint of the given class joining two parts. C0 is set so
+    /// the parts stay where they are now.
+    pub fn new(class_name: &str, part0: &BasePart, part1: &BasePart) -> Self {
+        let joint = Self::detached(class_name);
+        {
+            let mut instance = joint.instance.borrow_mut();
+            instance.set_property("Part0", Variant::instance_ref(Some(part0.instance())));
+            instance.set_property("Part1", Variant::instance_ref(Some(part1.instance())));
+            instance.set_property("C0", part0.cframe().inverse() * part1.cframe());
+        }
+
+        joint
+    }
+
+    /// Create a new joint of the given class that joins nothing yet
+    pub fn detached(class_name: &str) -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name(class_name);
+            instance.set_class_name(class_name);
+            instance.set_property("Part0", Variant::instance_ref(None));
+            instance.set_property("Part1", Variant::instance_ref(None));
+            instance.set_property("C0", CFrame::IDENTITY);
+            instance.set_property("C1", CFrame::IDENTITY);
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a joint, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("JointInstance") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the part the joint is attached to
+    pub fn part0(&self) -> Option<BasePart> {
+        self.instance.borrow().get_property("Part0").and_then(Variant::as_instance).as_ref().and_then(BasePart::from_instance)
+    }
+
+    /// Get the part the joint holds
+    pub fn part1(&self) -> Option<BasePart> {
+        self.instance.borrow().get_property("Part1").and_then(Variant::as_instance).as_ref().and_then(BasePart::from_instance)
+    }
+
+    /// Get the joint's offset from Part0
+    pub fn c0(&self) -> CFrame {
+        self.instance.borrow().get_property("C0").and_then(Variant::as_cframe).unwrap_or_default()
+    }
+
+    /// Get the joint's offset from Part1
+    pub fn c1(&self) -> CFrame {
+        self.instance.borrow().get_property("C1").and_then(Variant::as_cframe).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    #[test]
+    fn test_joint_offsets() {
+        let torso = BasePart::new("Part");
+        torso.set_position(Vec3::new(0.0, 3.0, 0.0));
+        let head = BasePart::new("Part");
+        head.set_position(Vec3::new(0.0, 4.5, 0.0));
+
+        let neck = JointInstance::new("Motor6D", &torso, &head);
+        assert!(neck.instance().borrow().is_a("JointInstance"));
+        assert!(Rc::ptr_eq(neck.part0().unwrap().instance(), torso.instance()));
+        assert!(Rc::ptr_eq(neck.part1().unwrap().instance(), head.instance()));
+        assert_eq!(neck.c0().position, Vec3::new(0.0, 1.5, 0.0));
+        assert_eq!((torso.cframe() * neck.c0() * neck.c1().inverse()).position, head.position());
+
+        assert!(JointInstance::from_instance(torso.instance()).is_none());
+    }
+}
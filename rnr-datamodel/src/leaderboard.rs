@@ -0,0 +1,486 @@
+use std::fmt;
+use glam::Vec2;
+use rnr_core::instance::Instance;
+use rnr_core::types::{BrickColor, Color3, UDim2};
+use rnr_core::variant::Variant;
+use crate::gui::{GuiObject, ScreenGui, TextXAlignment};
+use crate::gui_layout::{layout_gui, GuiDrawItem};
+use crate::players::{Player, Players};
+use crate::teams::Teams;
+use crate::value::ValueObject;
+
+/// Width of the leaderboard's name column, in pixels
+pub const LEADERBOARD_WIDTH: i32 = 160;
+
+/// Width of each leaderboard value column, in pixels
+pub const LEADERBOARD_COLUMN_WIDTH: i32 = 60;
+
+/// Height of each leaderboard row, in pixels
+pub const LEADERBOARD_ROW_HEIGHT: i32 = 20;
+
+/// Name of the Folder or Model under a Player whose values become
+/// leaderboard columns
+pub const LEADERSTATS_NAME: &str = "leaderstats";
+
+/// One line of the leaderboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeaderboardRow {
+    /// The names of the value columns
+    Header { columns: Vec<String> },
+    /// A team and its values, heading the team's players
+    Team { name: String, team_color: BrickColor, values: Vec<String> },
+    /// A player, under their team's row if they have one
+    Player { name: String, user_id: i64, values: Vec<String> },
+}
+
+/// Build the legacy leaderboard: each team with its score followed by its
+/// players, then the neutral players. Places without teams have no
+/// leaderboard.
+pub fn legacy_leaderboard(players: &Players, teams: &Teams) -> Vec<LeaderboardRow> {
+    if teams.get_teams().is_empty() {
+        return Vec::new();
+    }
+    let player_row = |player: &Player| LeaderboardRow::Player { name: player.name(), user_id: player.user_id(), values: Vec::new() };
+    let mut rows = Vec::new();
+    for team in teams.get_teams() {
+        rows.push(LeaderboardRow::Team {
+            name: team.instance().borrow().name().to_string(),
+            team_color: team.team_color(),
+            values: vec![team.score().to_string()],
+        });
+        rows.extend(team.get_players(players).iter().map(player_row));
+    }
+    let neutral = players.get_players().into_iter().filter(|player| player.neutral() || player.team().is_none());
+    rows.extend(neutral.map(|player| player_row(&player)));
+    rows
+}
+
+/// A value in a leaderstats folder. Ints sort before strings.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LeaderstatValue {
+    Int(i64),
+    String(String),
+}
+
+impl LeaderstatValue {
+    /// Convert an IntValue or StringValue's Value
+    pub fn from_variant(value: &Variant) -> Option<Self> {
+        match value {
+            Variant::Int(value) => Some(Self::Int(*value)),
+            Variant::String(value) => Some(Self::String(value.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LeaderstatValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(value) => write!(f, "{}", value),
+            Self::String(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// A player's named leaderstats, in the order they appear
+pub type PlayerStats = Vec<(String, LeaderstatValue)>;
+
+/// Read the IntValues and StringValues in a player's leaderstats, or None if
+/// they have no leaderstats Folder or Model
+pub fn read_leaderstats(player: &Player) -> Option<PlayerStats> {
+    let folder = player.instance().borrow().find_first_child(LEADERSTATS_NAME)?;
+    if !folder.borrow().is_a("Folder") && !folder.borrow().is_a("Model") {
+        return None;
+    }
+
+    let children = folder.borrow().children().to_vec();
+    let stats = children.iter().filter_map(|child| {
+        let value = ValueObject::from_instance(child)?.value()?;
+        Some((child.borrow().name().to_string(), LeaderstatValue::from_variant(&value)?))
+    });
+    Some(stats.collect())
+}
+
+/// A player's leaderstats changing, for the network layer to send to every
+/// client. Empty stats mean the player has none (anymore).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderstatsUpdate {
+    pub user_id: i64,
+    pub stats: PlayerStats,
+}
+
+/// Which column the leaderboard is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeaderboardSort {
+    pub column: usize,
+    pub descending: bool,
+}
+
+impl Default for LeaderboardSort {
+    /// Highest first in the first column, like the standard leaderboard
+    fn default() -> Self {
+        Self { column: 0, descending: true }
+    }
+}
+
+/// A player's line in the leaderboard view
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderboardEntry {
+    pub user_id: i64,
+    pub name: String,
+    /// The player's value in each column, if they have one
+    pub values: Vec<Option<LeaderstatValue>>,
+}
+
+/// A team's players in the leaderboard view
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderboardGroup {
+    /// Name and color of the team, or None for neutral players
+    pub team: Option<(String, BrickColor)>,
+    /// Sum of each Int column over the group's players
+    pub totals: Vec<Option<LeaderstatValue>>,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+/// The leaderboard as clients show it: leaderstats columns, with players
+/// grouped by team and sorted by one column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderboardView {
+    pub columns: Vec<String>,
+    pub sort: LeaderboardSort,
+    pub groups: Vec<LeaderboardGroup>,
+}
+
+impl LeaderboardView {
+    /// Sort the players in each group, and the teams by their totals, by a
+    /// column. Missing values sort last and ties go by name.
+    pub fn sort_by(&mut self, sort: LeaderboardSort) {
+        self.sort = sort;
+        let compare = |a: Option<&LeaderstatValue>, b: Option<&LeaderstatValue>| match (a, b) {
+            (Some(a), Some(b)) if sort.descending => b.cmp(a),
+            (Some(a), Some(b)) => a.cmp(b),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        };
+        let value = |values: &[Option<LeaderstatValue>]| values.get(sort.column).cloned().flatten();
+
+        for group in &mut self.groups {
+            group.entries.sort_by(|a, b| compare(value(&a.values).as_ref(), value(&b.values).as_ref()).then_with(|| a.name.cmp(&b.name)));
+        }
+        // Neutral players stay at the bottom
+        self.groups.sort_by(|a, b| match (a.team.is_some(), b.team.is_some()) {
+            (true, true) => compare(value(&a.totals).as_ref(), value(&b.totals).as_ref()),
+            (a, b) => b.cmp(&a),
+        });
+    }
+
+    /// Flatten the view into rows to draw: the column names, then each
+    /// team's totals followed by its players
+    pub fn rows(&self) -> Vec<LeaderboardRow> {
+        let texts = |values: &[Option<LeaderstatValue>]| values.iter().map(|value| value.as_ref().map(ToString::to_string).unwrap_or_default()).collect();
+        let mut rows = vec![LeaderboardRow::Header { columns: self.columns.clone() }];
+        for group in &self.groups {
+            if let Some((name, team_color)) = &group.team {
+                rows.push(LeaderboardRow::Team { name: name.clone(), team_color: *team_color, values: texts(&group.totals) });
+            }
+            rows.extend(group.entries.iter().map(|entry| LeaderboardRow::Player {
+                name: entry.name.clone(),
+                user_id: entry.user_id,
+                values: texts(&entry.values),
+            }));
+        }
+        rows
+    }
+}
+
+/// Tracks every player's leaderstats and builds the leaderboard from them.
+///
+/// The server reads the stats from each Player's leaderstats with `update`,
+/// queueing the ones that changed for clients. Clients apply those updates
+/// instead, so both sides build the same view.
+#[derive(Default)]
+pub struct Leaderstats {
+    /// Stats by user id, in the order players first had them
+    stats: Vec<(i64, PlayerStats)>,
+    updates: Vec<LeaderstatsUpdate>,
+}
+
+impl Leaderstats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read every player's leaderstats and queue an update for each player
+    /// whose stats changed, appeared or went away
+    pub fn update(&mut self, players: &Players) {
+        let players = players.get_players();
+        let gone: Vec<i64> = self.stats.iter()
+            .map(|(user_id, _)| *user_id)
+            .filter(|user_id| !players.iter().any(|player| player.user_id() == *user_id))
+            .collect();
+        for user_id in gone {
+            self.set(user_id, Vec::new());
+        }
+
+        for player in &players {
+            let stats = read_leaderstats(player).unwrap_or_default();
+            if self.stats(player.user_id()).unwrap_or_default() != stats.as_slice() {
+                self.set(player.user_id(), stats);
+            }
+        }
+    }
+
+    fn set(&mut self, user_id: i64, stats: PlayerStats) {
+        self.updates.push(LeaderstatsUpdate { user_id, stats: stats.clone() });
+        self.store(user_id, stats);
+    }
+
+    fn store(&mut self, user_id: i64, stats: PlayerStats) {
+        match self.stats.iter().position(|(known, _)| *known == user_id) {
+            Some(index) if stats.is_empty() => {
+                self.stats.remove(index);
+            }
+            Some(index) => self.stats[index].1 = stats,
+            None if stats.is_empty() => {}
+            None => self.stats.push((user_id, stats)),
+        }
+    }
+
+    /// Take the updates queued since the last call
+    pub fn take_updates(&mut self) -> Vec<LeaderstatsUpdate> {
+        std::mem::take(&mut self.updates)
+    }
+
+    /// Apply an update sent by the server, without queueing it again
+    pub fn apply_update(&mut self, update: &LeaderstatsUpdate) {
+        self.store(update.user_id, update.stats.clone());
+    }
+
+    /// Get a player's stats
+    pub fn stats(&self, user_id: i64) -> Option<&[(String, LeaderstatValue)]> {
+        self.stats.iter().find(|(known, _)| *known == user_id).map(|(_, stats)| stats.as_slice())
+    }
+
+    /// Get the leaderboard columns: every stat name, in the order first seen
+    pub fn columns(&self) -> Vec<String> {
+        let mut columns: Vec<String> = Vec::new();
+        for (name, _) in self.stats.iter().flat_map(|(_, stats)| stats) {
+            if !columns.contains(name) {
+                columns.push(name.clone());
+            }
+        }
+        columns
+    }
+
+    /// Build the leaderboard view: a group for each team, then one for the
+    /// neutral players if there are any, sorted by `sort`. Without teams
+    /// every player is in one group.
+    pub fn view(&self, players: &Players, teams: &Teams, sort: LeaderboardSort) -> LeaderboardView {
+        let columns = self.columns();
+        let entry = |player: &Player| {
+            let stats = self.stats(player.user_id()).unwrap_or_default();
+            LeaderboardEntry {
+                user_id: player.user_id(),
+                name: player.name(),
+                values: columns.iter().map(|column| stats.iter().find(|(name, _)| name == column).map(|(_, value)| value.clone())).collect(),
+            }
+        };
+        let group = |team: Option<(String, BrickColor)>, entries: Vec<LeaderboardEntry>| {
+            let totals = (0..columns.len())
+                .map(|column| {
+                    let ints = entries.iter().filter_map(|entry| match &entry.values[column] {
+                        Some(LeaderstatValue::Int(value)) => Some(*value),
+                        _ => None,
+                    });
+                    ints.reduce(|a, b| a + b).map(LeaderstatValue::Int)
+                })
+                .collect();
+            LeaderboardGroup { team, totals, entries }
+        };
+
+        let mut groups = Vec::new();
+        for team in teams.get_teams() {
+            let heading = (team.instance().borrow().name().to_string(), team.team_color());
+            groups.push(group(Some(heading), team.get_players(players).iter().map(entry).collect()));
+        }
+        let neutral: Vec<LeaderboardEntry> = players.get_players()
+            .iter()
+            .filter(|player| groups.is_empty() || player.neutral() || player.team().is_none())
+            .map(entry)
+            .collect();
+        if groups.is_empty() || !neutral.is_empty() {
+            groups.push(group(None, neutral));
+        }
+
+        let mut view = LeaderboardView { columns, sort, groups };
+        view.sort_by(sort);
+        view
+    }
+}
+
+/// Lay out leaderboard rows down the top right corner of a viewport and
+/// return what to draw, back to front. The name column is followed by a
+/// column for each value, and team rows are filled with the team's color.
+pub fn layout_leaderboard(rows: &[LeaderboardRow], viewport: Vec2) -> Vec<GuiDrawItem> {
+    let num_columns = rows.iter()
+        .map(|row| match row {
+            LeaderboardRow::Header { columns } => columns.len(),
+            LeaderboardRow::Team { values, .. } | LeaderboardRow::Player { values, .. } => values.len(),
+        })
+        .max()
+        .unwrap_or(0) as i32;
+    let width = LEADERBOARD_WIDTH + num_columns * LEADERBOARD_COLUMN_WIDTH;
+
+    let screen_gui = ScreenGui::new();
+    for (index, row) in rows.iter().enumerate() {
+        let y = index as i32 * LEADERBOARD_ROW_HEIGHT;
+        let (name, background, values) = match row {
+            LeaderboardRow::Header { columns } => ("", Color3::new(0.2, 0.2, 0.2), columns),
+            LeaderboardRow::Team { name, team_color, values } => (name.as_str(), team_color.color(), values),
+            LeaderboardRow::Player { name, values, .. } => (name.as_str(), Color3::new(0.0, 0.0, 0.0), values),
+        };
+
+        let label = row_label(name, UDim2::new(1.0, -width, 0.0, y), width, TextXAlignment::Left);
+        label.set_background_color3(background);
+        label.set_background_transparency(0.5);
+        Instance::set_parent(label.instance(), Some(screen_gui.instance().clone()));
+        for (column, value) in values.iter().enumerate() {
+            let x = -width + LEADERBOARD_WIDTH + column as i32 * LEADERBOARD_COLUMN_WIDTH;
+            let label = row_label(value, UDim2::new(1.0, x, 0.0, y), LEADERBOARD_COLUMN_WIDTH, TextXAlignment::Right);
+            Instance::set_parent(label.instance(), Some(screen_gui.instance().clone()));
+        }
+    }
+    layout_gui(screen_gui.instance(), viewport)
+}
+
+/// A transparent, white text label a leaderboard row high
+fn row_label(text: &str, position: UDim2, width: i32, alignment: TextXAlignment) -> GuiObject {
+    let label = GuiObject::new("TextLabel");
+    label.set_position(position);
+    label.set_size(UDim2::from_offset(width, LEADERBOARD_ROW_HEIGHT));
+    label.set_background_transparency(1.0);
+    label.set_text(text);
+    label.set_text_color3(Color3::new(1.0, 1.0, 1.0));
+    label.set_text_size(14);
+    label.set_text_x_alignment(alignment);
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part::create_model;
+
+    fn add_leaderstats(player: &Player, stats: &[ValueObject]) {
+        let folder = create_model(LEADERSTATS_NAME);
+        for stat in stats {
+            Instance::set_parent(stat.instance(), Some(folder.clone()));
+        }
+        Instance::set_parent(&folder, Some(player.instance().clone()));
+    }
+
+    #[test]
+    fn test_legacy_leaderboard_groups_players_by_team() {
+        let players = Players::new();
+        let teams = Teams::new();
+        Players::add_player(&players, "Bob", 2);
+        assert!(legacy_leaderboard(&players.borrow(), &teams.borrow()).is_empty());
+
+        let red = teams.borrow().add_team("Red", BrickColor::red());
+        teams.borrow().add_team("Blue", BrickColor::blue());
+        red.set_score(2);
+        let alice = Players::add_player(&players, "Alice", 1);
+        alice.set_team(Some(&red));
+
+        let rows = legacy_leaderboard(&players.borrow(), &teams.borrow());
+        assert_eq!(rows, vec![
+            LeaderboardRow::Team { name: "Red".to_string(), team_color: BrickColor::red(), values: vec!["2".to_string()] },
+            LeaderboardRow::Player { name: "Alice".to_string(), user_id: 1, values: Vec::new() },
+            LeaderboardRow::Team { name: "Blue".to_string(), team_color: BrickColor::blue(), values: vec!["0".to_string()] },
+            LeaderboardRow::Player { name: "Bob".to_string(), user_id: 2, values: Vec::new() },
+        ]);
+
+        // Team rows get a score label on top of their name
+        let items = layout_leaderboard(&rows, Vec2::new(800.0, 600.0));
+        let texts: Vec<&str> = items.iter().filter_map(|item| item.text.as_deref()).collect();
+        assert_eq!(texts, vec!["Red", "2", "Alice", "Blue", "0", "Bob"]);
+        assert_eq!(items[0].rect.position, Vec2::new(580.0, 0.0));
+        assert_eq!(items[0].rect.size, Vec2::new(220.0, 20.0));
+        assert_eq!(items[0].background_color, BrickColor::red().color());
+        assert_eq!(items[1].rect.position, Vec2::new(740.0, 0.0));
+        assert_eq!(items.last().unwrap().rect.position, Vec2::new(580.0, 60.0));
+    }
+
+    #[test]
+    fn test_leaderstats_updates_and_replication() {
+        let players = Players::new();
+        let alice = Players::add_player(&players, "Alice", 1);
+        let bob = Players::add_player(&players, "Bob", 2);
+        let kills = ValueObject::new_int("Kills", 3);
+        add_leaderstats(&alice, &[kills.clone(), ValueObject::new_string("Rank", "Pro")]);
+        // Only Folders and Models count
+        let junk = Instance::new();
+        junk.borrow_mut().set_name(LEADERSTATS_NAME);
+        Instance::set_parent(&junk, Some(bob.instance().clone()));
+        assert!(read_leaderstats(&bob).is_none());
+
+        let mut server = Leaderstats::new();
+        server.update(&players.borrow());
+        let updates = server.take_updates();
+        assert_eq!(updates, vec![LeaderstatsUpdate {
+            user_id: 1,
+            stats: vec![("Kills".to_string(), LeaderstatValue::Int(3)), ("Rank".to_string(), LeaderstatValue::String("Pro".to_string()))],
+        }]);
+
+        // Only changes are sent again
+        server.update(&players.borrow());
+        assert!(server.take_updates().is_empty());
+        kills.set_value(4i64);
+        server.update(&players.borrow());
+        assert_eq!(server.take_updates()[0].stats[0].1, LeaderstatValue::Int(4));
+        Players::remove_player(&players, &alice);
+        server.update(&players.borrow());
+        assert_eq!(server.take_updates(), vec![LeaderstatsUpdate { user_id: 1, stats: Vec::new() }]);
+
+        let mut client = Leaderstats::new();
+        for update in &updates {
+            client.apply_update(update);
+        }
+        assert!(client.take_updates().is_empty());
+        assert_eq!(client.columns(), vec!["Kills", "Rank"]);
+        assert_eq!(client.stats(1).unwrap()[0].1, LeaderstatValue::Int(3));
+    }
+
+    #[test]
+    fn test_leaderboard_view_groups_and_sorts() {
+        let players = Players::new();
+        let teams = Teams::new();
+        let red = teams.borrow().add_team("Red", BrickColor::red());
+        let blue = teams.borrow().add_team("Blue", BrickColor::blue());
+        let mut leaderstats = Leaderstats::new();
+        for (user_id, name, team, kills) in [(1, "Alice", &red, 2), (2, "Bob", &blue, 5), (3, "Carol", &red, 7)] {
+            let player = Players::add_player(&players, name, user_id);
+            player.set_team(Some(team));
+            add_leaderstats(&player, &[ValueObject::new_int("Kills", kills)]);
+        }
+        Players::add_player(&players, "Dave", 4);
+        leaderstats.update(&players.borrow());
+
+        let mut view = leaderstats.view(&players.borrow(), &teams.borrow(), LeaderboardSort::default());
+        assert_eq!(view.columns, vec!["Kills"]);
+        let names = |view: &LeaderboardView| -> Vec<Vec<String>> {
+            view.groups.iter().map(|group| group.entries.iter().map(|entry| entry.name.clone()).collect()).collect()
+        };
+        // Red has 9 kills to Blue's 5, and the neutral player comes last
+        assert_eq!(names(&view), vec![vec!["Carol", "Alice"], vec!["Bob"], vec!["Dave"]]);
+        assert_eq!(view.groups[0].totals, vec![Some(LeaderstatValue::Int(9))]);
+        assert!(view.groups[2].team.is_none());
+
+        view.sort_by(LeaderboardSort { column: 0, descending: false });
+        assert_eq!(names(&view), vec![vec!["Bob"], vec!["Alice", "Carol"], vec!["Dave"]]);
+
+        let rows = view.rows();
+        assert_eq!(rows[0], LeaderboardRow::Header { columns: vec!["Kills".to_string()] });
+        assert_eq!(rows[1], LeaderboardRow::Team { name: "Blue".to_string(), team_color: BrickColor::blue(), values: vec!["5".to_string()] });
+        assert_eq!(rows.last(), Some(&LeaderboardRow::Player { name: "Dave".to_string(), user_id: 4, values: vec![String::new()] }));
+    }
+}
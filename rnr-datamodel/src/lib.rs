@@ -2,25 +2,134 @@ use std::rc::Rc;
 use std::cell::RefCell;
 use rnr_core::instance::Instance;
 
+pub mod appearance;
+pub mod body_mover;
+pub mod camera;
+pub mod chat;
+pub mod click_detector;
+pub mod context_action_service;
+pub mod data_store;
+pub mod data_store_backend;
 pub mod datamodel;
+pub mod debris;
+pub mod explosion;
+pub mod flag;
+pub mod gui;
+pub mod gui_layout;
+pub mod hopper_bin;
+pub mod joint;
+pub mod leaderboard;
+pub mod message;
+pub mod part;
+pub mod players;
+pub mod run_service;
+pub mod seat;
+pub mod sound;
+pub mod spawn_location;
+pub mod teams;
+pub mod terrain;
+pub mod tool;
+pub mod tween_service;
+pub mod user_input_service;
+pub mod value;
+pub mod workspace;
+pub mod xml;
 
+pub use appearance::*;
+pub use body_mover::*;
+pub use camera::*;
+pub use chat::*;
+pub use click_detector::*;
+pub use context_action_service::*;
+pub use data_store::*;
+pub use data_store_backend::*;
 pub use datamodel::*;
+pub use debris::*;
+pub use explosion::*;
+pub use flag::*;
+pub use gui::*;
+pub use gui_layout::*;
+pub use hopper_bin::*;
+pub use joint::*;
+pub use leaderboard::*;
+pub use message::*;
+pub use part::*;
+pub use players::*;
+pub use run_service::*;
+pub use seat::*;
+pub use sound::*;
+pub use spawn_location::*;
+pub use teams::*;
+pub use terrain::*;
+pub use tool::*;
+pub use tween_service::*;
+pub use user_input_service::*;
+pub use value::*;
+pub use workspace::*;
+pub use xml::*;
 
 /// Instance factory for creating instances by class name
 pub trait InstanceFactory {
     fn create_instance(&self, class_name: &str) -> Option<Rc<RefCell<Instance>>>;
 }
 
-/// Create an instance by class name (requires factory to be set)
-pub fn create_instance(_class_name: &str) -> Option<Rc<RefCell<Instance>>> {
-    // TODO: Implement instance factory system
-    None
+/// Create an instance of a class with its default properties, the way a
+/// place loader does. Returns None for classes the engine doesn't know.
+pub fn create_instance(class_name: &str) -> Option<Rc<RefCell<Instance>>> {
+    let instance = match class_name {
+        "Part" => BasePart::new(class_name).instance().clone(),
+        "Seat" | "VehicleSeat" => Seat::new(class_name).instance().clone(),
+        "Model" => create_model("Model"),
+        "Folder" => {
+            let folder = Instance::new();
+            folder.borrow_mut().set_name("Folder");
+            folder.borrow_mut().set_class_name("Folder");
+            folder
+        }
+        "IntValue" => ValueObject::new_int("Value", 0).instance().clone(),
+        "StringValue" => ValueObject::new_string("Value", "").instance().clone(),
+        "SpawnLocation" => SpawnLocation::new().instance().clone(),
+        "Tool" => Tool::new("Tool").instance().clone(),
+        "ClickDetector" => ClickDetector::new().instance().clone(),
+        "Explosion" => Explosion::new().instance().clone(),
+        "Weld" | "ManualWeld" | "Snap" | "Glue" | "Motor" | "Motor6D" => JointInstance::detached(class_name).instance().clone(),
+        "Sound" => Sound::new().instance().clone(),
+        "Camera" => Camera::new().instance().clone(),
+        "ScreenGui" => ScreenGui::new().instance().clone(),
+        "BillboardGui" => BillboardGui::new().instance().clone(),
+        "Frame" | "TextLabel" | "TextButton" | "TextBox" | "ImageLabel" | "ImageButton" => GuiObject::new(class_name).instance().clone(),
+        // Character appearance
+        "BodyColors" => BodyColors::new().instance().clone(),
+        "Shirt" | "Pants" | "ShirtGraphic" => Clothing::new(ClothingKind::from_class_name(class_name)?, "").instance().clone(),
+        "Hat" | "Accessory" => Accessory::new(class_name, glam::Vec3::ONE).instance().clone(),
+        "CharacterMesh" => CharacterMesh::new(Limb::Head, "").instance().clone(),
+        // Classic-era classes
+        "Message" | "Hint" => Message::new(class_name).instance().clone(),
+        "HopperBin" => HopperBin::new("HopperBin", BinType::Script).instance().clone(),
+        "Flag" => Flag::new(Default::default()).instance().clone(),
+        "FlagStand" => FlagStand::new(Default::default()).instance().clone(),
+        _ => BodyMover::new(BodyMoverKind::from_class_name(class_name)?).instance().clone(),
+    };
+    Some(instance)
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn it_works() {
         assert_eq!(2 + 2, 4);
     }
+
+    #[test]
+    fn test_create_instance_knows_classic_classes() {
+        for class_name in ["Part", "Model", "Folder", "IntValue", "Message", "Hint", "HopperBin", "Flag", "FlagStand", "BodyColors", "ShirtGraphic", "Hat", "Weld"] {
+            let instance = create_instance(class_name).unwrap();
+            assert_eq!(instance.borrow().class_name(), class_name);
+        }
+        assert!(Flag::from_instance(&create_instance("Flag").unwrap()).unwrap().tool().handle().is_some());
+        assert!(Accessory::from_instance(&create_instance("Accessory").unwrap()).unwrap().handle().is_some());
+        assert!(create_instance("NotAClass").is_none());
+    }
 }
@@ -0,0 +1,142 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use glam::Vec2;
+use rnr_core::instance::Instance;
+use rnr_core::types::{Color3, UDim2};
+use rnr_core::variant::Variant;
+use crate::gui::{GuiObject, ScreenGui};
+use crate::gui_layout::{layout_gui, GuiDrawItem};
+
+/// Height of the bar a Hint is shown in, in pixels
+pub const HINT_HEIGHT: i32 = 20;
+
+/// Size of message and hint text
+const MESSAGE_TEXT_SIZE: i64 = 18;
+
+/// Typed view over a Message or Hint, the classic way of showing text.
+///
+/// A Message covers the screen with its text, and a Hint shows its text in
+/// a bar along the top. Either is shown to everyone while it is in the
+/// workspace, or to one player while it is parented to that player.
+#[derive(Debug, Clone)]
+pub struct Message {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl Message {
+    /// Create a new, empty message of the given class ("Message" or "Hint")
+    pub fn new(class_name: &str) -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name(class_name);
+            instance.set_class_name(class_name);
+            instance.set_property("Text", "");
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a message, if it is a Message or Hint
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("Message") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Check if this is a Hint
+    pub fn is_hint(&self) -> bool {
+        self.instance.borrow().is_a("Hint")
+    }
+
+    /// Get the text shown
+    pub fn text(&self) -> String {
+        self.instance.borrow().get_property("Text").and_then(Variant::as_str).map(str::to_string).unwrap_or_default()
+    }
+
+    /// Set the text shown
+    pub fn set_text(&self, text: &str) {
+        self.instance.borrow_mut().set_property("Text", text);
+    }
+}
+
+/// Find the messages and hints under each root, in tree order
+pub fn find_messages(roots: &[Rc<RefCell<Instance>>]) -> Vec<Message> {
+    roots.iter()
+        .flat_map(|root| root.borrow().get_descendants())
+        .filter_map(|instance| Message::from_instance(&instance))
+        .collect()
+}
+
+/// Lay out messages for a viewport and return what to draw, back to front.
+///
+/// Only the last Message and the last Hint with text are shown, the way
+/// newer ones used to cover older ones.
+pub fn layout_messages(messages: &[Message], viewport: Vec2) -> Vec<GuiDrawItem> {
+    let shown = |hint: bool| messages.iter().rev().find(|message| message.is_hint() == hint && !message.text().is_empty());
+    let screen_gui = ScreenGui::new();
+    if let Some(message) = shown(false) {
+        let label = message_label(message);
+        label.set_size(UDim2::from_scale(1.0, 1.0));
+        label.set_background_color3(Color3::from_rgb(128, 128, 128));
+        label.set_background_transparency(0.5);
+        Instance::set_parent(label.instance(), Some(screen_gui.instance().clone()));
+    }
+    if let Some(hint) = shown(true) {
+        let label = message_label(hint);
+        label.set_size(UDim2::new(1.0, 0, 0.0, HINT_HEIGHT));
+        label.set_background_color3(Color3::new(0.0, 0.0, 0.0));
+        Instance::set_parent(label.instance(), Some(screen_gui.instance().clone()));
+    }
+    layout_gui(screen_gui.instance(), viewport)
+}
+
+/// A centered white label showing a message's text
+fn message_label(message: &Message) -> GuiObject {
+    let label = GuiObject::new("TextLabel");
+    label.set_text(&message.text());
+    label.set_text_color3(Color3::new(1.0, 1.0, 1.0));
+    label.set_text_size(MESSAGE_TEXT_SIZE);
+    label.set_text_wrapped(true);
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_and_hints_layout() {
+        let workspace = Instance::new();
+        let player = Instance::new();
+        let message = Message::new("Message");
+        message.set_text("Welcome!");
+        Instance::set_parent(message.instance(), Some(workspace.clone()));
+        let hint = Message::new("Hint");
+        hint.set_text("Capture the flag");
+        Instance::set_parent(hint.instance(), Some(player.clone()));
+        Instance::set_parent(Message::new("Message").instance(), Some(workspace.clone()));
+        assert!(hint.is_hint() && !message.is_hint());
+
+        let messages = find_messages(&[workspace, player]);
+        assert_eq!(messages.len(), 3);
+
+        // The empty message doesn't hide the one before it
+        let viewport = Vec2::new(800.0, 600.0);
+        let items = layout_messages(&messages, viewport);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].text.as_deref(), Some("Welcome!"));
+        assert_eq!(items[0].rect.size, viewport);
+        assert_eq!(items[1].text.as_deref(), Some("Capture the flag"));
+        assert_eq!(items[1].rect.size, Vec2::new(800.0, HINT_HEIGHT as f32));
+
+        assert!(layout_messages(&[], viewport).is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use glam::Vec3;
+use rnr_core::instance::Instance;
+use rnr_core::types::{BrickColor, CFrame};
+use rnr_core::variant::Variant;
+
+/// Default size of a new part, in studs
+pub const DEFAULT_PART_SIZE: Vec3 = Vec3::new(4.0, 1.2, 2.0);
+
+/// Typed view over a BasePart instance (Part, SpawnLocation, ...).
+///
+/// All state lives in the instance's properties, so a BasePart can be created
+/// from any part instance found in the tree.
+#[derive(Debug, Clone)]
+pub struct BasePart {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl BasePart {
+    /// Create a new part of the given class with default properties
+    pub fn new(class_name: &str) -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name(class_name);
+            instance.set_class_name(class_name);
+            instance.set_property("CFrame", CFrame::IDENTITY);
+            instance.set_property("Size", DEFAULT_PART_SIZE);
+            instance.set_property("Anchored", false);
+            instance.set_property("CanCollide", true);
+            instance.set_property("BrickColor", BrickColor::default());
+            instance.set_property("Transparency", 0.0);
+            instance.set_property("Locked", false);
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a part, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("BasePart") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the part's coordinate frame
+    pub fn cframe(&self) -> CFrame {
+        self.instance.borrow().get_property("CFrame").and_then(Variant::as_cframe).unwrap_or_default()
+    }
+
+    /// Set the part's coordinate frame
+    pub fn set_cframe(&self, cframe: CFrame) {
+        self.instance.borrow_mut().set_property("CFrame", cframe);
+    }
+
+    /// Get the part's position
+    pub fn position(&self) -> Vec3 {
+        self.cframe().position
+    }
+
+    /// Move the part, keeping its rotation
+    pub fn set_position(&self, position: Vec3) {
+        let mut cframe = self.cframe();
+        cframe.position = position;
+        self.set_cframe(cframe);
+    }
+
+    /// Get the part's size
+    pub fn size(&self) -> Vec3 {
+        self.instance.borrow().get_property("Size").and_then(Variant::as_vector3).unwrap_or(DEFAULT_PART_SIZE)
+    }
+
+    /// Set the part's size
+    pub fn set_size(&self, size: Vec3) {
+        self.instance.borrow_mut().set_property("Size", size);
+    }
+
+    /// Check if the part is anchored in place
+    pub fn anchored(&self) -> bool {
+        self.instance.borrow().get_property("Anchored").and_then(Variant::as_bool).unwrap_or(false)
+    }
+
+    /// Set whether the part is anchored in place
+    pub fn set_anchored(&self, anchored: bool) {
+        self.instance.borrow_mut().set_property("Anchored", anchored);
+    }
+
+    /// Check if the part collides with other parts
+    pub fn can_collide(&self) -> bool {
+        self.instance.borrow().get_property("CanCollide").and_then(Variant::as_bool).unwrap_or(true)
+    }
+
+    /// Set whether the part collides with other parts
+    pub fn set_can_collide(&self, can_collide: bool) {
+        self.instance.borrow_mut().set_property("CanCollide", can_collide);
+    }
+
+    /// Get the part's color
+    pub fn brick_color(&self) -> BrickColor {
+        self.instance.borrow().get_property("BrickColor").and_then(Variant::as_brick_color).unwrap_or_default()
+    }
+
+    /// Set the part's color
+    pub fn set_brick_color(&self, color: BrickColor) {
+        self.instance.borrow_mut().set_property("BrickColor", color);
+    }
+
+    /// Get the part's transparency (0 is opaque)
+    pub fn transparency(&self) -> f64 {
+        self.instance.borrow().get_property("Transparency").and_then(Variant::as_float).unwrap_or(0.0)
+    }
+
+    /// Set the part's transparency (0 is opaque)
+    pub fn set_transparency(&self, transparency: f64) {
+        self.instance.borrow_mut().set_property("Transparency", transparency.clamp(0.0, 1.0));
+    }
+
+    /// Check if building tools are kept from changing the part
+    pub fn locked(&self) -> bool {
+        self.instance.borrow().get_property("Locked").and_then(Variant::as_bool).unwrap_or(false)
+    }
+
+    /// Set whether building tools are kept from changing the part
+    pub fn set_locked(&self, locked: bool) {
+        self.instance.borrow_mut().set_property("Locked", locked);
+    }
+}
+
+/// Create a Model instance with the given name
+pub fn create_model(name: &str) -> Rc<RefCell<Instance>> {
+    let model = Instance::new();
+    model.borrow_mut().set_class_name("Model");
+    model.borrow_mut().set_name(name);
+    model
+}
+
+/// Move every part under `model` rigidly, so that whatever was at `from`
+/// ends up at `to`
+pub fn move_model(model: &Rc<RefCell<Instance>>, from: CFrame, to: CFrame) {
+    let offset = to * from.inverse();
+    for descendant in model.borrow().get_descendants() {
+        if let Some(part) = BasePart::from_instance(&descendant) {
+            part.set_cframe(offset * part.cframe());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_defaults() {
+        let part = BasePart::new("Part");
+
+        assert_eq!(part.instance().borrow().class_name(), "Part");
+        assert_eq!(part.size(), DEFAULT_PART_SIZE);
+        assert_eq!(part.cframe(), CFrame::IDENTITY);
+        assert_eq!(part.brick_color(), BrickColor::default());
+        assert!(!part.anchored());
+        assert!(part.can_collide());
+    }
+
+    #[test]
+    fn test_part_properties() {
+        let part = BasePart::new("Part");
+
+        part.set_position(Vec3::new(1.0, 2.0, 3.0));
+        part.set_anchored(true);
+        part.set_transparency(2.0);
+
+        assert_eq!(part.position(), Vec3::new(1.0, 2.0, 3.0));
+        assert!(part.anchored());
+        assert_eq!(part.transparency(), 1.0);
+    }
+
+    #[test]
+    fn test_from_instance() {
+        let part = BasePart::new("Part");
+        assert!(BasePart::from_instance(part.instance()).is_some());
+
+        let model = create_model("Model");
+        assert!(BasePart::from_instance(&model).is_none());
+    }
+}
@@ -0,0 +1,483 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use glam::Vec3;
+use rnr_core::instance::Instance;
+use rnr_core::signal::Signal;
+use rnr_core::types::{BrickColor, CFrame};
+use rnr_core::variant::Variant;
+use crate::appearance::apply_appearance;
+use crate::chat::PlayerChatted;
+use crate::debris::Debris;
+use crate::gui::clone_tree;
+use crate::joint::JointInstance;
+use crate::part::{create_model, BasePart};
+use crate::spawn_location::{find_spawn_locations, SpawnLocation};
+use crate::teams::Team;
+
+/// Where characters spawn when the place has no usable SpawnLocation
+pub const DEFAULT_SPAWN_POSITION: Vec3 = Vec3::new(0.0, 100.0, 0.0);
+
+/// Typed view over a Player instance
+#[derive(Debug, Clone)]
+pub struct Player {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl Player {
+    /// Create a new, neutral player
+    pub fn new(name: &str, user_id: i64) -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name(name);
+            instance.set_class_name("Player");
+            instance.set_archivable(false);
+            instance.set_property("UserId", user_id);
+            instance.set_property("Team", Variant::instance_ref(None));
+            instance.set_property("TeamColor", BrickColor::white());
+            instance.set_property("Neutral", true);
+            instance.set_property("Character", Variant::instance_ref(None));
+        }
+
+        let player_gui = Instance::new();
+        player_gui.borrow_mut().set_name("PlayerGui");
+        player_gui.borrow_mut().set_class_name("PlayerGui");
+        Instance::set_parent(&player_gui, Some(instance.clone()));
+
+        let backpack = Instance::new();
+        backpack.borrow_mut().set_name("Backpack");
+        backpack.borrow_mut().set_class_name("Backpack");
+        Instance::set_parent(&backpack, Some(instance.clone()));
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a player, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("Player") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the player's name
+    pub fn name(&self) -> String {
+        self.instance.borrow().name().to_string()
+    }
+
+    /// Get the player's user id
+    pub fn user_id(&self) -> i64 {
+        self.instance.borrow().get_property("UserId").and_then(Variant::as_int).unwrap_or(0)
+    }
+
+    /// Get the player's team, if they are on one
+    pub fn team(&self) -> Option<Team> {
+        let team = self.instance.borrow().get_property("Team").and_then(Variant::as_instance)?;
+        Team::from_instance(&team)
+    }
+
+    /// Put the player on a team, or make them neutral with `None`.
+    ///
+    /// Team, TeamColor and Neutral are kept in sync like in Roblox.
+    pub fn set_team(&self, team: Option<&Team>) {
+        let mut instance = self.instance.borrow_mut();
+        instance.set_property("Team", Variant::instance_ref(team.map(Team::instance)));
+        instance.set_property("Neutral", team.is_none());
+        if let Some(team) = team {
+            instance.set_property("TeamColor", team.team_color());
+        }
+    }
+
+    /// Get the color of the player's team
+    pub fn team_color(&self) -> BrickColor {
+        self.instance.borrow().get_property("TeamColor").and_then(Variant::as_brick_color).unwrap_or_default()
+    }
+
+    /// Check if the player is not on any team
+    pub fn neutral(&self) -> bool {
+        self.instance.borrow().get_property("Neutral").and_then(Variant::as_bool).unwrap_or(true)
+    }
+
+    /// Check if the player is on the given team
+    pub fn is_on_team(&self, team: &Team) -> bool {
+        self.team().is_some_and(|own| Rc::ptr_eq(own.instance(), team.instance()))
+    }
+
+    /// Get the player's current character model
+    pub fn character(&self) -> Option<Rc<RefCell<Instance>>> {
+        self.instance.borrow().get_property("Character").and_then(Variant::as_instance)
+    }
+
+    /// Get the PlayerGui holding the player's ScreenGuis
+    pub fn player_gui(&self) -> Option<Rc<RefCell<Instance>>> {
+        self.instance.borrow().find_first_child_of_type("PlayerGui")
+    }
+
+    /// Get the Backpack holding the player's unequipped tools
+    pub fn backpack(&self) -> Option<Rc<RefCell<Instance>>> {
+        self.instance.borrow().find_first_child_of_type("Backpack")
+    }
+
+    fn set_character(&self, character: Option<&Rc<RefCell<Instance>>>) {
+        self.instance.borrow_mut().set_property("Character", Variant::instance_ref(character));
+    }
+}
+
+/// Players service - tracks connected players and spawns their characters
+pub struct Players {
+    instance: Rc<RefCell<Instance>>,
+    /// Fired after a player joins
+    player_added: Signal<Player>,
+    /// Fired before a player leaves
+    player_removing: Signal<Player>,
+    /// Player.Chatted for each player, by user id
+    chatted: HashMap<i64, Signal<PlayerChatted>>,
+    /// Round-robin counter used to pick between valid spawns
+    next_spawn: usize,
+    /// The player this client plays as; None on servers
+    local_player: Option<Player>,
+    /// Appearance items copied into each player's characters, by user id
+    appearances: HashMap<i64, Vec<Rc<RefCell<Instance>>>>,
+}
+
+impl Players {
+    /// Create a new players service
+    pub fn new() -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("Players");
+        instance.borrow_mut().set_class_name("Players");
+
+        Rc::new(RefCell::new(Self {
+            instance,
+            player_added: Signal::new(),
+            player_removing: Signal::new(),
+            chatted: HashMap::new(),
+            next_spawn: 0,
+            local_player: None,
+            appearances: HashMap::new(),
+        }))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the player this client plays as (Players.LocalPlayer)
+    pub fn local_player(&self) -> Option<&Player> {
+        self.local_player.as_ref()
+    }
+
+    /// Set the player this client plays as
+    pub fn set_local_player(&mut self, player: Option<&Player>) {
+        self.local_player = player.cloned();
+    }
+
+    /// Get the PlayerAdded signal
+    pub fn player_added(&self) -> &Signal<Player> {
+        &self.player_added
+    }
+
+    /// Get the PlayerRemoving signal
+    pub fn player_removing(&self) -> &Signal<Player> {
+        &self.player_removing
+    }
+
+    /// Get a player's Chatted signal, fired with every message they send
+    pub fn chatted(&self, player: &Player) -> Option<&Signal<PlayerChatted>> {
+        self.chatted.get(&player.user_id())
+    }
+
+    /// Get the appearance items copied into a player's characters
+    pub fn character_appearance(&self, player: &Player) -> &[Rc<RefCell<Instance>>] {
+        self.appearances.get(&player.user_id()).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Set the BodyColors, clothing, hats and CharacterMeshes copied into a
+    /// player's characters when they spawn
+    pub fn set_character_appearance(&mut self, player: &Player, items: Vec<Rc<RefCell<Instance>>>) {
+        self.appearances.insert(player.user_id(), items);
+    }
+
+    /// Add a player and fire PlayerAdded
+    pub fn add_player(players: &Rc<RefCell<Self>>, name: &str, user_id: i64) -> Player {
+        let player = Player::new(name, user_id);
+        let signal = {
+            let mut players = players.borrow_mut();
+            Instance::set_parent(player.instance(), Some(players.instance.clone()));
+            players.chatted.insert(user_id, Signal::new());
+            players.player_added.clone()
+        };
+
+        signal.fire(&player);
+        player
+    }
+
+    /// Fire PlayerRemoving, then remove the player and their character
+    pub fn remove_player(players: &Rc<RefCell<Self>>, player: &Player) {
+        let signal = players.borrow().player_removing.clone();
+        signal.fire(player);
+
+        if let Some(chatted) = players.borrow_mut().chatted.remove(&player.user_id()) {
+            chatted.disconnect_all();
+        }
+
+        {
+            let mut players = players.borrow_mut();
+            players.appearances.remove(&player.user_id());
+            if players.local_player.as_ref().is_some_and(|local| Rc::ptr_eq(local.instance(), player.instance())) {
+                players.local_player = None;
+            }
+        }
+
+        if let Some(character) = player.character() {
+            Instance::destroy(&character);
+        }
+        Instance::destroy(player.instance());
+    }
+
+    /// Get all players (Players:GetPlayers)
+    pub fn get_players(&self) -> Vec<Player> {
+        self.instance.borrow()
+            .children()
+            .iter()
+            .filter_map(Player::from_instance)
+            .collect()
+    }
+
+    /// Find a player by user id
+    pub fn get_player_by_user_id(&self, user_id: i64) -> Option<Player> {
+        self.get_players().into_iter().find(|player| player.user_id() == user_id)
+    }
+
+    /// Find a player by name, ignoring case
+    pub fn get_player_by_name(&self, name: &str) -> Option<Player> {
+        self.get_players().into_iter().find(|player| player.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Spawn a fresh character for the player (Player:LoadCharacter).
+    ///
+    /// Any existing character is destroyed. The character spawns on one of the
+    /// SpawnLocations in `workspace` the player is allowed to use, rotating
+    /// between them, or at DEFAULT_SPAWN_POSITION when there are none. Spawns
+    /// with a Duration give the character a ForceField that Debris removes.
+    /// The player's appearance items are copied in and applied.
+    pub fn load_character(
+        &mut self,
+        player: &Player,
+        workspace: &Rc<RefCell<Instance>>,
+        debris: &mut Debris,
+    ) -> Rc<RefCell<Instance>> {
+        if let Some(old) = player.character() {
+            Instance::destroy(&old);
+        }
+
+        let spawns: Vec<SpawnLocation> = find_spawn_locations(workspace)
+            .into_iter()
+            .filter(|spawn| spawn.can_spawn(player))
+            .collect();
+
+        let spawn = if spawns.is_empty() {
+            None
+        } else {
+            let spawn = spawns[self.next_spawn % spawns.len()].clone();
+            self.next_spawn = self.next_spawn.wrapping_add(1);
+            Some(spawn)
+        };
+
+        let cframe = spawn.as_ref()
+            .map(SpawnLocation::spawn_cframe)
+            .unwrap_or_else(|| CFrame::new(DEFAULT_SPAWN_POSITION));
+        let character = build_character(&player.name(), cframe);
+        for item in self.character_appearance(player) {
+            Instance::set_parent(&clone_tree(item), Some(character.clone()));
+        }
+        apply_appearance(&character);
+        // Characters are spawned at runtime, so they aren't saved with the place
+        character.borrow_mut().set_archivable(false);
+
+        if let Some(duration) = spawn.map(|spawn| spawn.duration()).filter(|duration| *duration > 0.0) {
+            let force_field = Instance::new();
+            force_field.borrow_mut().set_name("ForceField");
+            force_field.borrow_mut().set_class_name("ForceField");
+            Instance::set_parent(&force_field, Some(character.clone()));
+            debris.add_item(&force_field, duration);
+        }
+
+        Instance::set_parent(&character, Some(workspace.clone()));
+        player.set_character(Some(&character));
+        character
+    }
+}
+
+/// Build a classic R6 character with its torso at `cframe`
+fn build_character(name: &str, cframe: CFrame) -> Rc<RefCell<Instance>> {
+    let character = create_model(name);
+
+    let limbs = [
+        ("Head", Vec3::new(2.0, 1.0, 1.0), Vec3::new(0.0, 1.5, 0.0), BrickColor::yellow()),
+        ("Torso", Vec3::new(2.0, 2.0, 1.0), Vec3::ZERO, BrickColor::blue()),
+        ("Left Arm", Vec3::new(1.0, 2.0, 1.0), Vec3::new(-1.5, 0.0, 0.0), BrickColor::yellow()),
+        ("Right Arm", Vec3::new(1.0, 2.0, 1.0), Vec3::new(1.5, 0.0, 0.0), BrickColor::yellow()),
+        ("Left Leg", Vec3::new(1.0, 2.0, 1.0), Vec3::new(-0.5, -2.0, 0.0), BrickColor::from_name("Br. yellowish green").unwrap_or_default()),
+        ("Right Leg", Vec3::new(1.0, 2.0, 1.0), Vec3::new(0.5, -2.0, 0.0), BrickColor::from_name("Br. yellowish green").unwrap_or_default()),
+    ];
+
+    let mut parts = Vec::new();
+    for (limb_name, size, offset, color) in limbs {
+        let part = BasePart::new("Part");
+        part.instance().borrow_mut().set_name(limb_name);
+        part.set_size(size);
+        part.set_cframe(cframe * CFrame::new(offset));
+        part.set_brick_color(color);
+        Instance::set_parent(part.instance(), Some(character.clone()));
+        parts.push(part);
+    }
+
+    // R6 joints: every limb hangs off the torso
+    let torso = &parts[1];
+    let joints = [("Neck", 0), ("Left Shoulder", 2), ("Right Shoulder", 3), ("Left Hip", 4), ("Right Hip", 5)];
+    for (joint_name, limb) in joints {
+        let joint = JointInstance::new("Motor6D", torso, &parts[limb]);
+        joint.instance().borrow_mut().set_name(joint_name);
+        Instance::set_parent(joint.instance(), Some(torso.instance().clone()));
+    }
+
+    let humanoid = Instance::new();
+    {
+        let mut humanoid = humanoid.borrow_mut();
+        humanoid.set_name("Humanoid");
+        humanoid.set_class_name("Humanoid");
+        humanoid.set_property("Health", 100.0);
+        humanoid.set_property("MaxHealth", 100.0);
+        humanoid.set_property("WalkSpeed", 16.0);
+        humanoid.set_property("Sit", false);
+        humanoid.set_property("Jump", false);
+    }
+    Instance::set_parent(&humanoid, Some(character.clone()));
+
+    character
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::appearance::{Accessory, BodyColors, Limb, ACCESSORY_WELD_NAME};
+
+    #[test]
+    fn test_player_added_and_removing() {
+        let players = Players::new();
+        let added = Rc::new(RefCell::new(Vec::new()));
+        let removed = Rc::new(RefCell::new(Vec::new()));
+
+        let added_clone = added.clone();
+        players.borrow().player_added().connect(move |player: &Player| added_clone.borrow_mut().push(player.name()));
+        let removed_clone = removed.clone();
+        players.borrow().player_removing().connect(move |player: &Player| removed_clone.borrow_mut().push(player.user_id()));
+
+        let player = Players::add_player(&players, "Player1", 42);
+        assert_eq!(*added.borrow(), vec!["Player1".to_string()]);
+        assert!(players.borrow().get_player_by_user_id(42).is_some());
+
+        Players::remove_player(&players, &player);
+        assert_eq!(*removed.borrow(), vec![42]);
+        assert!(players.borrow().get_players().is_empty());
+    }
+
+    #[test]
+    fn test_load_character_without_spawns() {
+        let players = Players::new();
+        let workspace = create_model("Workspace");
+        let debris = Debris::new();
+        let player = Players::add_player(&players, "Player1", 1);
+
+        let character = players.borrow_mut().load_character(&player, &workspace, &mut debris.borrow_mut());
+
+        assert!(Rc::ptr_eq(&player.character().unwrap(), &character));
+        assert!(character.borrow().find_first_child("Humanoid").is_some());
+        assert!(character.borrow().find_first_child("ForceField").is_none());
+
+        let torso = BasePart::from_instance(&character.borrow().find_first_child("Torso").unwrap()).unwrap();
+        assert_eq!(torso.position(), DEFAULT_SPAWN_POSITION);
+    }
+
+    #[test]
+    fn test_load_character_replaces_old_and_adds_force_field() {
+        let players = Players::new();
+        let workspace = create_model("Workspace");
+        let debris = Debris::new();
+        let spawn = SpawnLocation::new();
+        spawn.set_duration(5.0);
+        Instance::set_parent(spawn.instance(), Some(workspace.clone()));
+        let player = Players::add_player(&players, "Player1", 1);
+
+        let first = players.borrow_mut().load_character(&player, &workspace, &mut debris.borrow_mut());
+        let second = players.borrow_mut().load_character(&player, &workspace, &mut debris.borrow_mut());
+
+        assert!(first.borrow().is_destroyed());
+        assert!(second.borrow().find_first_child("ForceField").is_some());
+
+        debris.borrow_mut().update(5.0);
+        assert!(second.borrow().find_first_child("ForceField").is_none());
+    }
+
+    #[test]
+    fn test_load_character_applies_appearance() {
+        let players = Players::new();
+        let workspace = create_model("Workspace");
+        let debris = Debris::new();
+        let player = Players::add_player(&players, "Player1", 1);
+        let body_colors = BodyColors::new();
+        body_colors.set_color(Limb::Torso, BrickColor::red());
+        let hat = Accessory::new("Hat", Vec3::ONE);
+        players.borrow_mut().set_character_appearance(&player, vec![body_colors.instance().clone(), hat.instance().clone()]);
+
+        let first = players.borrow_mut().load_character(&player, &workspace, &mut debris.borrow_mut());
+        let second = players.borrow_mut().load_character(&player, &workspace, &mut debris.borrow_mut());
+
+        // Every character gets its own copy of the appearance
+        assert!(first.borrow().is_destroyed());
+        assert_eq!(Limb::Torso.find_part(&second).unwrap().brick_color(), BrickColor::red());
+        let worn = Accessory::from_instance(&second.borrow().find_first_child("Hat").unwrap()).unwrap();
+        assert!(!Rc::ptr_eq(worn.instance(), hat.instance()));
+        assert!(worn.handle().unwrap().instance().borrow().find_first_child(ACCESSORY_WELD_NAME).is_some());
+        assert!(hat.handle().unwrap().instance().borrow().find_first_child(ACCESSORY_WELD_NAME).is_none());
+    }
+
+    #[test]
+    fn test_team_spawns_rotate() {
+        let players = Players::new();
+        let workspace = create_model("Workspace");
+        let debris = Debris::new();
+        let player = Players::add_player(&players, "Player1", 1);
+
+        let positions = [Vec3::new(0.0, 0.5, 0.0), Vec3::new(20.0, 0.5, 0.0)];
+        for position in positions {
+            let spawn = SpawnLocation::new();
+            spawn.part().set_position(position);
+            Instance::set_parent(spawn.instance(), Some(workspace.clone()));
+        }
+
+        // A team-only spawn the neutral player may not use
+        let team_spawn = SpawnLocation::new();
+        team_spawn.set_neutral(false);
+        team_spawn.part().set_position(Vec3::new(-50.0, 0.5, 0.0));
+        Instance::set_parent(team_spawn.instance(), Some(workspace.clone()));
+
+        let mut xs = Vec::new();
+        for _ in 0..3 {
+            let character = players.borrow_mut().load_character(&player, &workspace, &mut debris.borrow_mut());
+            let torso = BasePart::from_instance(&character.borrow().find_first_child("Torso").unwrap()).unwrap();
+            xs.push(torso.position().x);
+        }
+
+        assert_eq!(xs, vec![0.0, 20.0, 0.0]);
+    }
+}
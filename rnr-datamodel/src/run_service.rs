@@ -0,0 +1,277 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use rnr_core::instance::Instance;
+use rnr_core::signal::Signal;
+
+/// Where the world is running, which decides what RunService reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunContext {
+    Client,
+    Server,
+    /// Studio runs both sides of the game in one process
+    Studio,
+}
+
+/// Standard priorities for BindToRenderStep (Enum.RenderPriority)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderPriority {
+    First,
+    Input,
+    Camera,
+    Character,
+    Last,
+}
+
+impl RenderPriority {
+    /// Get the numeric priority value
+    pub fn value(self) -> i32 {
+        match self {
+            RenderPriority::First => 0,
+            RenderPriority::Input => 100,
+            RenderPriority::Camera => 200,
+            RenderPriority::Character => 300,
+            RenderPriority::Last => 2000,
+        }
+    }
+}
+
+/// A function bound with BindToRenderStep
+struct RenderStepBinding {
+    name: String,
+    priority: i32,
+    callback: Rc<RefCell<dyn FnMut(f64)>>,
+}
+
+/// RunService - exposes the frame signals fired by the world loop.
+///
+/// Each frame fires, in order: the render step bindings and RenderStepped
+/// (clients only), Stepped, then the physics step, then Heartbeat.
+pub struct RunService {
+    instance: Rc<RefCell<Instance>>,
+    run_context: RunContext,
+    running: bool,
+    /// Fired before physics with (time, delta time)
+    stepped: Signal<(f64, f64)>,
+    /// Fired after physics with the delta time
+    heartbeat: Signal<f64>,
+    /// Fired before rendering with the delta time (clients only)
+    render_stepped: Signal<f64>,
+    /// Functions bound with BindToRenderStep, kept sorted by priority
+    render_step_bindings: Vec<RenderStepBinding>,
+}
+
+impl RunService {
+    /// Create a new run service
+    pub fn new(run_context: RunContext) -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("RunService");
+        instance.borrow_mut().set_class_name("RunService");
+        instance.borrow_mut().set_archivable(false);
+
+        Rc::new(RefCell::new(Self {
+            instance,
+            run_context,
+            running: false,
+            stepped: Signal::new(),
+            heartbeat: Signal::new(),
+            render_stepped: Signal::new(),
+            render_step_bindings: Vec::new(),
+        }))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the run context
+    pub fn run_context(&self) -> RunContext {
+        self.run_context
+    }
+
+    /// Check if this is running as a server
+    pub fn is_server(&self) -> bool {
+        matches!(self.run_context, RunContext::Server | RunContext::Studio)
+    }
+
+    /// Check if this is running as a client
+    pub fn is_client(&self) -> bool {
+        matches!(self.run_context, RunContext::Client | RunContext::Studio)
+    }
+
+    /// Check if this is running inside Studio
+    pub fn is_studio(&self) -> bool {
+        self.run_context == RunContext::Studio
+    }
+
+    /// Check if the game simulation is running
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Set whether the game simulation is running
+    pub fn set_running(&mut self, running: bool) {
+        self.running = running;
+    }
+
+    /// Get the Stepped signal
+    pub fn stepped(&self) -> &Signal<(f64, f64)> {
+        &self.stepped
+    }
+
+    /// Get the Heartbeat signal
+    pub fn heartbeat(&self) -> &Signal<f64> {
+        &self.heartbeat
+    }
+
+    /// Get the RenderStepped signal
+    pub fn render_stepped(&self) -> &Signal<f64> {
+        &self.render_stepped
+    }
+
+    /// Bind a function to run every render step at the given priority.
+    ///
+    /// Lower priorities run first; bindings with equal priority run in the
+    /// order they were bound. Binding an existing name replaces it.
+    pub fn bind_to_render_step<F>(&mut self, name: &str, priority: i32, callback: F)
+    where
+        F: FnMut(f64) + 'static,
+    {
+        self.unbind_from_render_step(name);
+
+        let index = self.render_step_bindings
+            .iter()
+            .position(|binding| binding.priority > priority)
+            .unwrap_or(self.render_step_bindings.len());
+
+        self.render_step_bindings.insert(index, RenderStepBinding {
+            name: name.to_string(),
+            priority,
+            callback: Rc::new(RefCell::new(callback)),
+        });
+    }
+
+    /// Remove a function bound with `bind_to_render_step`
+    pub fn unbind_from_render_step(&mut self, name: &str) {
+        self.render_step_bindings.retain(|binding| binding.name != name);
+    }
+
+    /// Fire the render step bindings followed by RenderStepped.
+    ///
+    /// Does nothing when not running as a client. The service is not borrowed
+    /// while callbacks run, so they are free to bind or unbind.
+    pub fn fire_render_stepped(run_service: &Rc<RefCell<Self>>, delta_time: f64) {
+        let (callbacks, signal) = {
+            let run_service = run_service.borrow();
+            if !run_service.is_client() {
+                return;
+            }
+
+            let callbacks: Vec<_> = run_service.render_step_bindings
+                .iter()
+                .map(|binding| binding.callback.clone())
+                .collect();
+            (callbacks, run_service.render_stepped.clone())
+        };
+
+        for callback in callbacks {
+            if let Ok(mut callback) = callback.try_borrow_mut() {
+                callback(delta_time);
+            }
+        }
+        signal.fire(&delta_time);
+    }
+
+    /// Fire Stepped with the current time and delta time
+    pub fn fire_stepped(run_service: &Rc<RefCell<Self>>, time: f64, delta_time: f64) {
+        let signal = run_service.borrow().stepped.clone();
+        signal.fire(&(time, delta_time));
+    }
+
+    /// Fire Heartbeat with the delta time
+    pub fn fire_heartbeat(run_service: &Rc<RefCell<Self>>, delta_time: f64) {
+        let signal = run_service.borrow().heartbeat.clone();
+        signal.fire(&delta_time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_context() {
+        let client = RunService::new(RunContext::Client);
+        assert!(client.borrow().is_client());
+        assert!(!client.borrow().is_server());
+        assert!(!client.borrow().is_studio());
+
+        let server = RunService::new(RunContext::Server);
+        assert!(server.borrow().is_server());
+        assert!(!server.borrow().is_client());
+
+        let studio = RunService::new(RunContext::Studio);
+        assert!(studio.borrow().is_server());
+        assert!(studio.borrow().is_client());
+        assert!(studio.borrow().is_studio());
+        assert!(!studio.borrow().is_running());
+    }
+
+    #[test]
+    fn test_render_step_priority() {
+        let run_service = RunService::new(RunContext::Client);
+        let order = Rc::new(RefCell::new(Vec::new()));
+
+        let bind = |name: &'static str, priority: RenderPriority| {
+            let order = order.clone();
+            run_service.borrow_mut().bind_to_render_step(name, priority.value(), move |_| {
+                order.borrow_mut().push(name);
+            });
+        };
+        bind("character", RenderPriority::Character);
+        bind("camera", RenderPriority::Camera);
+        bind("input", RenderPriority::Input);
+
+        let order_clone = order.clone();
+        run_service.borrow().render_stepped().connect(move |_| {
+            order_clone.borrow_mut().push("render_stepped");
+        });
+
+        RunService::fire_render_stepped(&run_service, 1.0 / 60.0);
+        assert_eq!(*order.borrow(), vec!["input", "camera", "character", "render_stepped"]);
+
+        order.borrow_mut().clear();
+        run_service.borrow_mut().unbind_from_render_step("camera");
+        RunService::fire_render_stepped(&run_service, 1.0 / 60.0);
+        assert_eq!(*order.borrow(), vec!["input", "character", "render_stepped"]);
+    }
+
+    #[test]
+    fn test_render_stepped_is_client_only() {
+        let run_service = RunService::new(RunContext::Server);
+        let fired = Rc::new(RefCell::new(false));
+
+        let fired_clone = fired.clone();
+        run_service.borrow().render_stepped().connect(move |_| *fired_clone.borrow_mut() = true);
+
+        RunService::fire_render_stepped(&run_service, 1.0 / 30.0);
+        assert!(!*fired.borrow());
+    }
+
+    #[test]
+    fn test_unbind_from_callback() {
+        let run_service = RunService::new(RunContext::Client);
+        let count = Rc::new(RefCell::new(0));
+
+        let run_service_clone = run_service.clone();
+        let count_clone = count.clone();
+        run_service.borrow_mut().bind_to_render_step("once", RenderPriority::First.value(), move |_| {
+            *count_clone.borrow_mut() += 1;
+            run_service_clone.borrow_mut().unbind_from_render_step("once");
+        });
+
+        RunService::fire_render_stepped(&run_service, 0.016);
+        RunService::fire_render_stepped(&run_service, 0.016);
+        assert_eq!(*count.borrow(), 1);
+    }
+}
@@ -0,0 +1,287 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use glam::Vec3;
+use rnr_core::instance::Instance;
+use rnr_core::types::CFrame;
+use rnr_core::variant::Variant;
+use crate::joint::JointInstance;
+use crate::part::{move_model, BasePart};
+
+/// Default top speed of a VehicleSeat, in studs per second
+pub const DEFAULT_MAX_SPEED: f64 = 25.0;
+
+/// Default motor torque of a VehicleSeat
+pub const DEFAULT_TORQUE: f64 = 10.0;
+
+/// Default turn rate of a VehicleSeat, in radians per second
+pub const DEFAULT_TURN_SPEED: f64 = 1.0;
+
+/// Name of the weld holding an occupant in a seat
+pub const SEAT_WELD_NAME: &str = "SeatWeld";
+
+/// Typed view over a Seat or VehicleSeat part.
+///
+/// A humanoid sits by being welded to the seat with a SeatWeld parented to
+/// the seat. VehicleSeat also carries the occupant's Throttle and Steer,
+/// and the MaxSpeed, Torque and TurnSpeed its motors drive with.
+#[derive(Debug, Clone)]
+pub struct Seat {
+    part: BasePart,
+}
+
+impl Seat {
+    /// Create a new, empty seat of the given class ("Seat" or "VehicleSeat")
+    pub fn new(class_name: &str) -> Self {
+        let part = BasePart::new(class_name);
+        part.set_size(Vec3::new(2.0, 1.0, 2.0));
+        {
+            let mut instance = part.instance().borrow_mut();
+            instance.set_property("Disabled", false);
+            instance.set_property("Occupant", Variant::instance_ref(None));
+            if class_name == "VehicleSeat" {
+                instance.set_property("Throttle", 0i64);
+                instance.set_property("Steer", 0i64);
+                instance.set_property("MaxSpeed", DEFAULT_MAX_SPEED);
+                instance.set_property("Torque", DEFAULT_TORQUE);
+                instance.set_property("TurnSpeed", DEFAULT_TURN_SPEED);
+            }
+        }
+
+        Self { part }
+    }
+
+    /// View an existing instance as a seat, if it is a Seat or VehicleSeat
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        let is_seat = {
+            let instance = instance.borrow();
+            instance.is_a("Seat") || instance.is_a("VehicleSeat")
+        };
+        if is_seat {
+            BasePart::from_instance(instance).map(|part| Self { part })
+        } else {
+            None
+        }
+    }
+
+    /// Get the seat as a part
+    pub fn part(&self) -> &BasePart {
+        &self.part
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        self.part.instance()
+    }
+
+    /// Check if this is a VehicleSeat
+    pub fn is_vehicle_seat(&self) -> bool {
+        self.instance().borrow().is_a("VehicleSeat")
+    }
+
+    /// Check if humanoids are kept from sitting in the seat
+    pub fn disabled(&self) -> bool {
+        self.instance().borrow().get_property("Disabled").and_then(Variant::as_bool).unwrap_or(false)
+    }
+
+    /// Set whether humanoids are kept from sitting in the seat
+    pub fn set_disabled(&self, disabled: bool) {
+        self.instance().borrow_mut().set_property("Disabled", disabled);
+    }
+
+    /// Get the Humanoid sitting in the seat
+    pub fn occupant(&self) -> Option<Rc<RefCell<Instance>>> {
+        self.instance().borrow().get_property("Occupant").and_then(Variant::as_instance)
+    }
+
+    /// Get the weld holding the occupant in the seat
+    pub fn seat_weld(&self) -> Option<JointInstance> {
+        self.instance().borrow().find_first_child(SEAT_WELD_NAME).as_ref().and_then(JointInstance::from_instance)
+    }
+
+    /// Sit a humanoid in the seat (Seat:Sit).
+    ///
+    /// The humanoid's character is moved on top of the seat and welded to it
+    /// by its Torso. Returns false if the seat is disabled or taken, or the
+    /// humanoid has no Torso or is already sitting.
+    pub fn sit(&self, humanoid: &Rc<RefCell<Instance>>) -> bool {
+        if self.disabled() || self.occupant().is_some() || humanoid_sitting(humanoid) {
+            return false;
+        }
+        let Some(character) = humanoid.borrow().parent() else {
+            return false;
+        };
+        let Some(torso) = character.borrow().find_first_child("Torso").as_ref().and_then(BasePart::from_instance) else {
+            return false;
+        };
+
+        // Put the torso just above the seat, facing the way the seat faces
+        let seated = self.part.cframe() * CFrame::new(Vec3::new(0.0, self.part.size().y / 2.0 + torso.size().y / 2.0, 0.0));
+        move_model(&character, torso.cframe(), seated);
+
+        let weld = JointInstance::new("Weld", &self.part, &torso);
+        weld.instance().borrow_mut().set_name(SEAT_WELD_NAME);
+        Instance::set_parent(weld.instance(), Some(self.instance().clone()));
+
+        self.instance().borrow_mut().set_property("Occupant", Variant::instance_ref(Some(humanoid)));
+        let mut humanoid = humanoid.borrow_mut();
+        humanoid.set_property("Sit", true);
+        humanoid.set_property("SeatPart", Variant::instance_ref(Some(self.instance())));
+        true
+    }
+
+    /// Get the occupant out of the seat, breaking the SeatWeld
+    pub fn leave(&self) {
+        if let Some(weld) = self.seat_weld() {
+            Instance::destroy(weld.instance());
+        }
+        if let Some(humanoid) = self.occupant() {
+            let mut humanoid = humanoid.borrow_mut();
+            humanoid.set_property("Sit", false);
+            humanoid.set_property("SeatPart", Variant::instance_ref(None));
+        }
+        self.instance().borrow_mut().set_property("Occupant", Variant::instance_ref(None));
+    }
+
+    /// Move the occupant's character to where the SeatWeld holds its Torso
+    pub fn hold_occupant(&self) {
+        let Some(weld) = self.seat_weld() else {
+            return;
+        };
+        let Some(torso) = weld.part1() else {
+            return;
+        };
+        let Some(character) = torso.instance().borrow().parent() else {
+            return;
+        };
+
+        let held = self.part.cframe() * weld.c0() * weld.c1().inverse();
+        move_model(&character, torso.cframe(), held);
+    }
+
+    fn get_int(&self, name: &str) -> i64 {
+        self.instance().borrow().get_property(name).and_then(Variant::as_int).unwrap_or(0)
+    }
+
+    fn get_float(&self, name: &str, default: f64) -> f64 {
+        self.instance().borrow().get_property(name).and_then(Variant::as_float).unwrap_or(default)
+    }
+
+    /// Get the occupant's forward (1) or backward (-1) input (VehicleSeat only)
+    pub fn throttle(&self) -> i64 {
+        self.get_int("Throttle")
+    }
+
+    /// Set the forward or backward input, from -1 to 1 (VehicleSeat only)
+    pub fn set_throttle(&self, throttle: i64) {
+        self.instance().borrow_mut().set_property("Throttle", throttle.clamp(-1, 1));
+    }
+
+    /// Get the occupant's right (1) or left (-1) input (VehicleSeat only)
+    pub fn steer(&self) -> i64 {
+        self.get_int("Steer")
+    }
+
+    /// Set the right or left input, from -1 to 1 (VehicleSeat only)
+    pub fn set_steer(&self, steer: i64) {
+        self.instance().borrow_mut().set_property("Steer", steer.clamp(-1, 1));
+    }
+
+    /// Get the top speed, in studs per second (VehicleSeat only)
+    pub fn max_speed(&self) -> f64 {
+        self.get_float("MaxSpeed", DEFAULT_MAX_SPEED)
+    }
+
+    /// Set the top speed (VehicleSeat only)
+    pub fn set_max_speed(&self, max_speed: f64) {
+        self.instance().borrow_mut().set_property("MaxSpeed", max_speed);
+    }
+
+    /// Get the most torque the motors may use (VehicleSeat only)
+    pub fn torque(&self) -> f64 {
+        self.get_float("Torque", DEFAULT_TORQUE)
+    }
+
+    /// Set the most torque the motors may use (VehicleSeat only)
+    pub fn set_torque(&self, torque: f64) {
+        self.instance().borrow_mut().set_property("Torque", torque.max(0.0));
+    }
+
+    /// Get the turn rate, in radians per second (VehicleSeat only)
+    pub fn turn_speed(&self) -> f64 {
+        self.get_float("TurnSpeed", DEFAULT_TURN_SPEED)
+    }
+
+    /// Set the turn rate (VehicleSeat only)
+    pub fn set_turn_speed(&self, turn_speed: f64) {
+        self.instance().borrow_mut().set_property("TurnSpeed", turn_speed);
+    }
+}
+
+/// Check if a Humanoid is sitting
+pub fn humanoid_sitting(humanoid: &Rc<RefCell<Instance>>) -> bool {
+    humanoid.borrow().get_property("Sit").and_then(Variant::as_bool).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::Players;
+    use crate::debris::Debris;
+
+    #[test]
+    fn test_sit_and_leave() {
+        let workspace = Instance::new();
+        let players = Players::new();
+        let player = Players::add_player(&players, "Player1", 1);
+        let character = players.borrow_mut().load_character(&player, &workspace, &mut Debris::new().borrow_mut());
+        let humanoid = character.borrow().find_first_child("Humanoid").unwrap();
+        let head = BasePart::from_instance(&character.borrow().find_first_child("Head").unwrap()).unwrap();
+        let torso = BasePart::from_instance(&character.borrow().find_first_child("Torso").unwrap()).unwrap();
+
+        let seat = Seat::new("Seat");
+        seat.part().set_position(Vec3::new(10.0, 0.5, 0.0));
+        Instance::set_parent(seat.instance(), Some(workspace.clone()));
+        assert!(!seat.is_vehicle_seat());
+
+        assert!(seat.sit(&humanoid));
+        assert!(humanoid_sitting(&humanoid));
+        assert!(Rc::ptr_eq(&seat.occupant().unwrap(), &humanoid));
+        assert_eq!(torso.position(), Vec3::new(10.0, 2.0, 0.0));
+        // The rest of the character came along
+        assert_eq!(head.position(), Vec3::new(10.0, 3.5, 0.0));
+        assert!(Rc::ptr_eq(seat.seat_weld().unwrap().part1().unwrap().instance(), torso.instance()));
+
+        // Taken seats can't be sat in again
+        assert!(!seat.sit(&humanoid));
+
+        // The occupant follows the seat
+        seat.part().set_position(Vec3::new(20.0, 0.5, 0.0));
+        seat.hold_occupant();
+        assert_eq!(torso.position(), Vec3::new(20.0, 2.0, 0.0));
+
+        seat.leave();
+        assert!(!humanoid_sitting(&humanoid));
+        assert!(seat.occupant().is_none());
+        assert!(seat.seat_weld().is_none());
+
+        seat.set_disabled(true);
+        assert!(!seat.sit(&humanoid));
+    }
+
+    #[test]
+    fn test_vehicle_seat_controls() {
+        let seat = Seat::new("VehicleSeat");
+        assert!(seat.is_vehicle_seat());
+        assert!(Seat::from_instance(seat.instance()).is_some());
+        assert_eq!(seat.max_speed(), DEFAULT_MAX_SPEED);
+        assert_eq!(seat.torque(), DEFAULT_TORQUE);
+        assert_eq!(seat.turn_speed(), DEFAULT_TURN_SPEED);
+
+        seat.set_throttle(5);
+        seat.set_steer(-1);
+        assert_eq!(seat.throttle(), 1);
+        assert_eq!(seat.steer(), -1);
+
+        assert!(Seat::from_instance(BasePart::new("Part").instance()).is_none());
+    }
+}
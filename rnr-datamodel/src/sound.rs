@@ -0,0 +1,244 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use glam::Vec3;
+use rnr_core::instance::Instance;
+use rnr_core::variant::Variant;
+use crate::part::BasePart;
+
+/// Default volume of a new sound
+pub const DEFAULT_VOLUME: f64 = 0.5;
+
+/// Loudest volume a sound accepts
+pub const MAX_VOLUME: f64 = 10.0;
+
+/// Default distance, in studs, within which a 3D sound plays at full volume
+pub const DEFAULT_ROLL_OFF_MIN_DISTANCE: f64 = 10.0;
+
+/// Default distance, in studs, beyond which a 3D sound can't be heard
+pub const DEFAULT_ROLL_OFF_MAX_DISTANCE: f64 = 10000.0;
+
+/// Typed view over a Sound instance.
+///
+/// Play, Stop, Pause and Resume only change properties. SoundService picks
+/// the changes up on its next update and drives the mixer to match, writing
+/// TimePosition, TimeLength and Playing back as playback goes on.
+#[derive(Debug, Clone)]
+pub struct Sound {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl Sound {
+    /// Create a new, stopped sound with no SoundId
+    pub fn new() -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name("Sound");
+            instance.set_class_name("Sound");
+            instance.set_property("SoundId", "");
+            instance.set_property("Volume", DEFAULT_VOLUME);
+            instance.set_property("PlaybackSpeed", 1.0);
+            instance.set_property("Looped", false);
+            instance.set_property("Playing", false);
+            instance.set_property("TimePosition", 0.0);
+            instance.set_property("TimeLength", 0.0);
+            instance.set_property("RollOffMinDistance", DEFAULT_ROLL_OFF_MIN_DISTANCE);
+            instance.set_property("RollOffMaxDistance", DEFAULT_ROLL_OFF_MAX_DISTANCE);
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a sound, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("Sound") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the content id of the audio to play
+    pub fn sound_id(&self) -> String {
+        self.instance.borrow().get_property("SoundId").and_then(Variant::as_str).unwrap_or_default().to_string()
+    }
+
+    /// Set the content id of the audio to play
+    pub fn set_sound_id(&self, sound_id: &str) {
+        self.instance.borrow_mut().set_property("SoundId", sound_id);
+    }
+
+    /// Get the volume, from 0 to 10
+    pub fn volume(&self) -> f64 {
+        self.instance.borrow().get_property("Volume").and_then(Variant::as_float).unwrap_or(DEFAULT_VOLUME)
+    }
+
+    /// Set the volume, clamped to 0-10
+    pub fn set_volume(&self, volume: f64) {
+        self.instance.borrow_mut().set_property("Volume", volume.clamp(0.0, MAX_VOLUME));
+    }
+
+    /// Get how fast the sound plays. 2 plays twice as fast and an octave higher.
+    pub fn playback_speed(&self) -> f64 {
+        self.instance.borrow().get_property("PlaybackSpeed").and_then(Variant::as_float).unwrap_or(1.0)
+    }
+
+    /// Set how fast the sound plays. Negative speeds are treated as 0.
+    pub fn set_playback_speed(&self, playback_speed: f64) {
+        self.instance.borrow_mut().set_property("PlaybackSpeed", playback_speed.max(0.0));
+    }
+
+    /// Get the pitch, the legacy name for PlaybackSpeed
+    pub fn pitch(&self) -> f64 {
+        self.playback_speed()
+    }
+
+    /// Set the pitch, the legacy name for PlaybackSpeed
+    pub fn set_pitch(&self, pitch: f64) {
+        self.set_playback_speed(pitch);
+    }
+
+    /// Check if the sound starts over when it reaches the end
+    pub fn looped(&self) -> bool {
+        self.instance.borrow().get_property("Looped").and_then(Variant::as_bool).unwrap_or(false)
+    }
+
+    /// Set whether the sound starts over when it reaches the end
+    pub fn set_looped(&self, looped: bool) {
+        self.instance.borrow_mut().set_property("Looped", looped);
+    }
+
+    /// Check if the sound is playing
+    pub fn is_playing(&self) -> bool {
+        self.instance.borrow().get_property("Playing").and_then(Variant::as_bool).unwrap_or(false)
+    }
+
+    /// Start or stop playback without moving TimePosition
+    pub fn set_playing(&self, playing: bool) {
+        self.instance.borrow_mut().set_property("Playing", playing);
+    }
+
+    /// Get the playback position in seconds
+    pub fn time_position(&self) -> f64 {
+        self.instance.borrow().get_property("TimePosition").and_then(Variant::as_float).unwrap_or(0.0)
+    }
+
+    /// Seek to a position in seconds
+    pub fn set_time_position(&self, time_position: f64) {
+        self.instance.borrow_mut().set_property("TimePosition", time_position.max(0.0));
+    }
+
+    /// Get the length of the audio in seconds, or 0 if it hasn't loaded
+    pub fn time_length(&self) -> f64 {
+        self.instance.borrow().get_property("TimeLength").and_then(Variant::as_float).unwrap_or(0.0)
+    }
+
+    /// Set the length of the audio. This is driven by SoundService, scripts
+    /// can only read it.
+    pub fn set_time_length(&self, time_length: f64) {
+        self.instance.borrow_mut().set_property("TimeLength", time_length.max(0.0));
+    }
+
+    /// Get the distance within which a 3D sound plays at full volume
+    pub fn roll_off_min_distance(&self) -> f64 {
+        self.instance.borrow()
+            .get_property("RollOffMinDistance")
+            .and_then(Variant::as_float)
+            .unwrap_or(DEFAULT_ROLL_OFF_MIN_DISTANCE)
+    }
+
+    /// Set the distance within which a 3D sound plays at full volume
+    pub fn set_roll_off_min_distance(&self, distance: f64) {
+        self.instance.borrow_mut().set_property("RollOffMinDistance", distance.max(0.0));
+    }
+
+    /// Get the distance beyond which a 3D sound can't be heard
+    pub fn roll_off_max_distance(&self) -> f64 {
+        self.instance.borrow()
+            .get_property("RollOffMaxDistance")
+            .and_then(Variant::as_float)
+            .unwrap_or(DEFAULT_ROLL_OFF_MAX_DISTANCE)
+    }
+
+    /// Set the distance beyond which a 3D sound can't be heard
+    pub fn set_roll_off_max_distance(&self, distance: f64) {
+        self.instance.borrow_mut().set_property("RollOffMaxDistance", distance.max(0.0));
+    }
+
+    /// Get where the sound is emitted from, if it is parented to a part.
+    /// Sounds anywhere else are heard at the same volume everywhere.
+    pub fn emitter_position(&self) -> Option<Vec3> {
+        let parent = self.instance.borrow().parent()?;
+        BasePart::from_instance(&parent).map(|part| part.position())
+    }
+
+    /// Play the sound from the start (Sound:Play)
+    pub fn play(&self) {
+        self.set_time_position(0.0);
+        self.set_playing(true);
+    }
+
+    /// Stop the sound and rewind it (Sound:Stop)
+    pub fn stop(&self) {
+        self.set_playing(false);
+        self.set_time_position(0.0);
+    }
+
+    /// Stop the sound where it is (Sound:Pause)
+    pub fn pause(&self) {
+        self.set_playing(false);
+    }
+
+    /// Continue a paused sound (Sound:Resume)
+    pub fn resume(&self) {
+        self.set_playing(true);
+    }
+}
+
+impl Default for Sound {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sound_playback_properties() {
+        let sound = Sound::new();
+        sound.set_time_position(1.5);
+
+        sound.pause();
+        assert!(!sound.is_playing());
+        sound.resume();
+        assert!(sound.is_playing());
+        assert_eq!(sound.time_position(), 1.5);
+
+        sound.stop();
+        assert!(!sound.is_playing());
+        assert_eq!(sound.time_position(), 0.0);
+
+        sound.set_volume(20.0);
+        sound.set_pitch(2.0);
+        assert_eq!(sound.volume(), MAX_VOLUME);
+        assert_eq!(sound.playback_speed(), 2.0);
+    }
+
+    #[test]
+    fn test_emitter_position() {
+        let sound = Sound::new();
+        assert!(sound.emitter_position().is_none());
+
+        let part = BasePart::new("Part");
+        part.set_position(Vec3::new(5.0, 0.0, 0.0));
+        Instance::set_parent(sound.instance(), Some(part.instance().clone()));
+        assert_eq!(sound.emitter_position(), Some(Vec3::new(5.0, 0.0, 0.0)));
+    }
+}
@@ -0,0 +1,234 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use glam::Vec3;
+use rnr_core::instance::Instance;
+use rnr_core::types::{BrickColor, CFrame};
+use rnr_core::variant::Variant;
+use crate::part::BasePart;
+use crate::players::Player;
+use crate::teams::Teams;
+
+/// Default number of seconds a spawned character is protected by a ForceField
+pub const DEFAULT_SPAWN_DURATION: f64 = 10.0;
+
+/// Height above the top of a spawn where a character's torso is placed
+const SPAWN_HEIGHT_OFFSET: f32 = 3.0;
+
+/// Typed view over a SpawnLocation part
+#[derive(Debug, Clone)]
+pub struct SpawnLocation {
+    part: BasePart,
+}
+
+impl SpawnLocation {
+    /// Create a new, neutral spawn location
+    pub fn new() -> Self {
+        let part = BasePart::new("SpawnLocation");
+        part.set_anchored(true);
+        part.set_size(Vec3::new(6.0, 1.0, 6.0));
+        {
+            let mut instance = part.instance().borrow_mut();
+            instance.set_property("TeamColor", BrickColor::default());
+            instance.set_property("Neutral", true);
+            instance.set_property("AllowTeamChangeOnTouch", false);
+            instance.set_property("Duration", DEFAULT_SPAWN_DURATION);
+            instance.set_property("Enabled", true);
+        }
+
+        Self { part }
+    }
+
+    /// View an existing instance as a spawn location, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("SpawnLocation") {
+            BasePart::from_instance(instance).map(|part| Self { part })
+        } else {
+            None
+        }
+    }
+
+    /// Get the spawn as a part
+    pub fn part(&self) -> &BasePart {
+        &self.part
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        self.part.instance()
+    }
+
+    fn get_bool(&self, name: &str, default: bool) -> bool {
+        self.instance().borrow().get_property(name).and_then(Variant::as_bool).unwrap_or(default)
+    }
+
+    /// Get the color of the team allowed to spawn here
+    pub fn team_color(&self) -> BrickColor {
+        self.instance().borrow().get_property("TeamColor").and_then(Variant::as_brick_color).unwrap_or_default()
+    }
+
+    /// Set the color of the team allowed to spawn here
+    pub fn set_team_color(&self, team_color: BrickColor) {
+        self.instance().borrow_mut().set_property("TeamColor", team_color);
+    }
+
+    /// Check if any player may spawn here regardless of team
+    pub fn neutral(&self) -> bool {
+        self.get_bool("Neutral", true)
+    }
+
+    /// Set whether any player may spawn here regardless of team
+    pub fn set_neutral(&self, neutral: bool) {
+        self.instance().borrow_mut().set_property("Neutral", neutral);
+    }
+
+    /// Check if touching this spawn moves a player onto its team
+    pub fn allow_team_change_on_touch(&self) -> bool {
+        self.get_bool("AllowTeamChangeOnTouch", false)
+    }
+
+    /// Set whether touching this spawn moves a player onto its team
+    pub fn set_allow_team_change_on_touch(&self, allow: bool) {
+        self.instance().borrow_mut().set_property("AllowTeamChangeOnTouch", allow);
+    }
+
+    /// Get how long spawned characters keep their ForceField, in seconds
+    pub fn duration(&self) -> f64 {
+        self.instance().borrow().get_property("Duration").and_then(Variant::as_float).unwrap_or(DEFAULT_SPAWN_DURATION)
+    }
+
+    /// Set how long spawned characters keep their ForceField (0 disables it)
+    pub fn set_duration(&self, duration: f64) {
+        self.instance().borrow_mut().set_property("Duration", duration.max(0.0));
+    }
+
+    /// Check if players can spawn here at all
+    pub fn enabled(&self) -> bool {
+        self.get_bool("Enabled", true)
+    }
+
+    /// Set whether players can spawn here at all
+    pub fn set_enabled(&self, enabled: bool) {
+        self.instance().borrow_mut().set_property("Enabled", enabled);
+    }
+
+    /// Check if a player may spawn here.
+    ///
+    /// Neutral spawns accept everyone. Team spawns only accept players on the
+    /// team with the matching color.
+    pub fn can_spawn(&self, player: &Player) -> bool {
+        if !self.enabled() || self.instance().borrow().is_destroyed() {
+            return false;
+        }
+
+        self.neutral() || (!player.neutral() && player.team_color() == self.team_color())
+    }
+
+    /// Get where a character spawned here should be placed
+    pub fn spawn_cframe(&self) -> CFrame {
+        let cframe = self.part.cframe();
+        let top = self.part.size().y / 2.0 + SPAWN_HEIGHT_OFFSET;
+
+        CFrame {
+            position: cframe.point_to_world_space(Vec3::new(0.0, top, 0.0)),
+            rotation: cframe.rotation,
+        }
+    }
+
+    /// Handle a player's character touching the spawn.
+    ///
+    /// When AllowTeamChangeOnTouch is set on a team spawn, the player joins the
+    /// team with the spawn's color. Returns true if the player changed teams.
+    pub fn touched_by(&self, player: &Player, teams: &Teams) -> bool {
+        if !self.allow_team_change_on_touch() || self.neutral() {
+            return false;
+        }
+
+        match teams.find_team_by_color(self.team_color()) {
+            Some(team) if !player.is_on_team(&team) => {
+                player.set_team(Some(&team));
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Default for SpawnLocation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Find every spawn location below `root`, in tree order
+pub fn find_spawn_locations(root: &Rc<RefCell<Instance>>) -> Vec<SpawnLocation> {
+    root.borrow()
+        .get_descendants()
+        .iter()
+        .filter_map(SpawnLocation::from_instance)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::Players;
+
+    #[test]
+    fn test_spawn_defaults() {
+        let spawn = SpawnLocation::new();
+
+        assert!(spawn.instance().borrow().is_a("BasePart"));
+        assert!(spawn.part().anchored());
+        assert!(spawn.neutral());
+        assert!(spawn.enabled());
+        assert_eq!(spawn.duration(), DEFAULT_SPAWN_DURATION);
+    }
+
+    #[test]
+    fn test_team_spawn_filtering() {
+        let players = Players::new();
+        let teams = Teams::new();
+        let red = teams.borrow().add_team("Red", BrickColor::red());
+        let player = Players::add_player(&players, "Player1", 1);
+
+        let spawn = SpawnLocation::new();
+        spawn.set_neutral(false);
+        spawn.set_team_color(BrickColor::red());
+        assert!(!spawn.can_spawn(&player));
+
+        player.set_team(Some(&red));
+        assert!(spawn.can_spawn(&player));
+
+        spawn.set_enabled(false);
+        assert!(!spawn.can_spawn(&player));
+    }
+
+    #[test]
+    fn test_team_change_on_touch() {
+        let players = Players::new();
+        let teams = Teams::new();
+        let blue = teams.borrow().add_team("Blue", BrickColor::blue());
+        let player = Players::add_player(&players, "Player1", 1);
+
+        let spawn = SpawnLocation::new();
+        spawn.set_neutral(false);
+        spawn.set_team_color(BrickColor::blue());
+        assert!(!spawn.touched_by(&player, &teams.borrow()));
+
+        spawn.set_allow_team_change_on_touch(true);
+        assert!(spawn.touched_by(&player, &teams.borrow()));
+        assert!(player.is_on_team(&blue));
+        assert!(!spawn.touched_by(&player, &teams.borrow()));
+    }
+
+    #[test]
+    fn test_spawn_cframe_and_search() {
+        let root = Instance::new();
+        let spawn = SpawnLocation::new();
+        spawn.part().set_position(Vec3::new(10.0, 0.5, 0.0));
+        Instance::set_parent(spawn.instance(), Some(root.clone()));
+
+        assert_eq!(spawn.spawn_cframe().position, Vec3::new(10.0, 4.0, 0.0));
+        assert_eq!(find_spawn_locations(&root).len(), 1);
+    }
+}
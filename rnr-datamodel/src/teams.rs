@@ -0,0 +1,191 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use rnr_core::instance::Instance;
+use rnr_core::types::BrickColor;
+use rnr_core::variant::Variant;
+use crate::players::{Player, Players};
+
+/// Typed view over a Team instance
+#[derive(Debug, Clone)]
+pub struct Team {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl Team {
+    /// Create a new team
+    pub fn new(name: &str, team_color: BrickColor) -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name(name);
+            instance.set_class_name("Team");
+            instance.set_property("TeamColor", team_color);
+            instance.set_property("AutoAssignable", true);
+            instance.set_property("Score", 0i64);
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a team, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("Team") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the team's color, which also identifies the team
+    pub fn team_color(&self) -> BrickColor {
+        self.instance.borrow().get_property("TeamColor").and_then(Variant::as_brick_color).unwrap_or_default()
+    }
+
+    /// Set the team's color
+    pub fn set_team_color(&self, team_color: BrickColor) {
+        self.instance.borrow_mut().set_property("TeamColor", team_color);
+    }
+
+    /// Check if new players can be put on this team automatically
+    pub fn auto_assignable(&self) -> bool {
+        self.instance.borrow().get_property("AutoAssignable").and_then(Variant::as_bool).unwrap_or(true)
+    }
+
+    /// Set whether new players can be put on this team automatically
+    pub fn set_auto_assignable(&self, auto_assignable: bool) {
+        self.instance.borrow_mut().set_property("AutoAssignable", auto_assignable);
+    }
+
+    /// Get the team's score, shown on the legacy leaderboard
+    pub fn score(&self) -> i64 {
+        self.instance.borrow().get_property("Score").and_then(Variant::as_int).unwrap_or(0)
+    }
+
+    /// Set the team's score
+    pub fn set_score(&self, score: i64) {
+        self.instance.borrow_mut().set_property("Score", score);
+    }
+
+    /// Get the players on this team (Team:GetPlayers)
+    pub fn get_players(&self, players: &Players) -> Vec<Player> {
+        players.get_players()
+            .into_iter()
+            .filter(|player| player.is_on_team(self))
+            .collect()
+    }
+}
+
+/// Teams service - holds the Team instances of a place
+pub struct Teams {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl Teams {
+    /// Create a new teams service
+    pub fn new() -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("Teams");
+        instance.borrow_mut().set_class_name("Teams");
+
+        Rc::new(RefCell::new(Self { instance }))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Create a team and parent it to the service
+    pub fn add_team(&self, name: &str, team_color: BrickColor) -> Team {
+        let team = Team::new(name, team_color);
+        Instance::set_parent(team.instance(), Some(self.instance.clone()));
+        team
+    }
+
+    /// Get all teams (Teams:GetTeams)
+    pub fn get_teams(&self) -> Vec<Team> {
+        self.instance.borrow()
+            .children()
+            .iter()
+            .filter_map(Team::from_instance)
+            .collect()
+    }
+
+    /// Find the team with the given color
+    pub fn find_team_by_color(&self, team_color: BrickColor) -> Option<Team> {
+        self.get_teams().into_iter().find(|team| team.team_color() == team_color)
+    }
+
+    /// Put a player on the auto-assignable team with the fewest players.
+    ///
+    /// Ties go to the team that was added first. Players stay neutral when
+    /// there is no auto-assignable team. Returns the chosen team.
+    pub fn assign_player(&self, player: &Player, players: &Players) -> Option<Team> {
+        let mut best: Option<(Team, usize)> = None;
+        for team in self.get_teams().into_iter().filter(Team::auto_assignable) {
+            let count = team.get_players(players)
+                .iter()
+                .filter(|other| !Rc::ptr_eq(other.instance(), player.instance()))
+                .count();
+
+            if best.as_ref().is_none_or(|(_, best_count)| count < *best_count) {
+                best = Some((team, count));
+            }
+        }
+
+        let team = best.map(|(team, _)| team)?;
+        player.set_team(Some(&team));
+        Some(team)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_team_creation() {
+        let teams = Teams::new();
+        let red = teams.borrow().add_team("Red", BrickColor::red());
+
+        assert_eq!(red.team_color(), BrickColor::red());
+        assert!(red.auto_assignable());
+        assert_eq!(teams.borrow().get_teams().len(), 1);
+        assert!(teams.borrow().find_team_by_color(BrickColor::red()).is_some());
+        assert!(teams.borrow().find_team_by_color(BrickColor::blue()).is_none());
+    }
+
+    #[test]
+    fn test_auto_assign_balances_teams() {
+        let teams = Teams::new();
+        let players = Players::new();
+        let red = teams.borrow().add_team("Red", BrickColor::red());
+        let blue = teams.borrow().add_team("Blue", BrickColor::blue());
+
+        for i in 0..4 {
+            let player = Players::add_player(&players, &format!("Player{}", i), i);
+            teams.borrow().assign_player(&player, &players.borrow());
+            assert!(!player.neutral());
+        }
+
+        assert_eq!(red.get_players(&players.borrow()).len(), 2);
+        assert_eq!(blue.get_players(&players.borrow()).len(), 2);
+    }
+
+    #[test]
+    fn test_auto_assign_skips_closed_teams() {
+        let teams = Teams::new();
+        let players = Players::new();
+        let red = teams.borrow().add_team("Red", BrickColor::red());
+        red.set_auto_assignable(false);
+
+        let player = Players::add_player(&players, "Player1", 1);
+        assert!(teams.borrow().assign_player(&player, &players.borrow()).is_none());
+        assert!(player.neutral());
+    }
+}
@@ -0,0 +1,458 @@
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::cell::RefCell;
+use glam::{IVec3, Mat3, Vec3};
+use rnr_core::instance::Instance;
+use rnr_core::types::{CFrame, Color3, Region3};
+
+/// Edge length of a voxel, in studs. It is also the only resolution
+/// ReadVoxels, WriteVoxels and FillRegion accept.
+pub const VOXEL_SIZE: f32 = 4.0;
+
+/// Edge length of a chunk, in voxels. Chunks are meshed and collided as a
+/// whole.
+pub const CHUNK_SIZE: i32 = 8;
+
+const CHUNK_VOLUME: usize = (CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE) as usize;
+
+/// The material of a part or voxel (Enum.Material). Only the materials
+/// terrain can be made of are listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Material {
+    Plastic = 256,
+    Wood = 512,
+    Basalt = 788,
+    Slate = 800,
+    CrackedLava = 804,
+    Concrete = 816,
+    Limestone = 820,
+    Pavement = 836,
+    Brick = 848,
+    Cobblestone = 880,
+    Rock = 896,
+    Sandstone = 912,
+    Grass = 1280,
+    LeafyGrass = 1284,
+    Sand = 1296,
+    Snow = 1328,
+    Mud = 1344,
+    Ground = 1360,
+    Asphalt = 1376,
+    Salt = 1392,
+    Ice = 1536,
+    Glacier = 1552,
+    Air = 1792,
+    Water = 2048,
+}
+
+impl Material {
+    const ALL: [Material; 24] = [
+        Material::Plastic, Material::Wood, Material::Basalt, Material::Slate, Material::CrackedLava,
+        Material::Concrete, Material::Limestone, Material::Pavement, Material::Brick, Material::Cobblestone,
+        Material::Rock, Material::Sandstone, Material::Grass, Material::LeafyGrass, Material::Sand,
+        Material::Snow, Material::Mud, Material::Ground, Material::Asphalt, Material::Salt,
+        Material::Ice, Material::Glacier, Material::Air, Material::Water,
+    ];
+
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Look up a material by enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        Self::ALL.iter().copied().find(|material| material.value() == value)
+    }
+
+    /// Check if characters and parts collide with the material. Air and
+    /// Water can be moved through.
+    pub fn is_solid(self) -> bool {
+        !matches!(self, Material::Air | Material::Water)
+    }
+
+    /// Get the color terrain of this material is drawn with
+    pub fn color(self) -> Color3 {
+        match self {
+            Material::Plastic | Material::Concrete | Material::Pavement => Color3::from_rgb(127, 127, 127),
+            Material::Wood => Color3::from_rgb(139, 109, 79),
+            Material::Basalt => Color3::from_rgb(30, 30, 37),
+            Material::Slate => Color3::from_rgb(63, 127, 107),
+            Material::CrackedLava => Color3::from_rgb(232, 156, 74),
+            Material::Limestone => Color3::from_rgb(206, 173, 148),
+            Material::Brick => Color3::from_rgb(138, 86, 62),
+            Material::Cobblestone => Color3::from_rgb(132, 123, 90),
+            Material::Rock => Color3::from_rgb(102, 108, 111),
+            Material::Sandstone => Color3::from_rgb(137, 90, 71),
+            Material::Grass => Color3::from_rgb(106, 127, 63),
+            Material::LeafyGrass => Color3::from_rgb(115, 132, 74),
+            Material::Sand => Color3::from_rgb(143, 126, 95),
+            Material::Snow => Color3::from_rgb(195, 199, 218),
+            Material::Mud => Color3::from_rgb(58, 46, 36),
+            Material::Ground => Color3::from_rgb(102, 92, 59),
+            Material::Asphalt => Color3::from_rgb(115, 123, 107),
+            Material::Salt => Color3::from_rgb(198, 189, 181),
+            Material::Ice => Color3::from_rgb(129, 194, 224),
+            Material::Glacier => Color3::from_rgb(101, 176, 234),
+            Material::Air => Color3::from_rgb(255, 255, 255),
+            Material::Water => Color3::from_rgb(12, 84, 92),
+        }
+    }
+}
+
+/// One voxel: what it is made of and how full of it it is, from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Voxel {
+    pub material: Material,
+    pub occupancy: f32,
+}
+
+impl Voxel {
+    /// An empty voxel
+    pub const AIR: Voxel = Voxel { material: Material::Air, occupancy: 0.0 };
+
+    pub fn new(material: Material, occupancy: f32) -> Self {
+        let occupancy = occupancy.clamp(0.0, 1.0);
+        if material == Material::Air || occupancy <= 0.0 {
+            Self::AIR
+        } else {
+            Self { material, occupancy }
+        }
+    }
+}
+
+impl Default for Voxel {
+    fn default() -> Self {
+        Self::AIR
+    }
+}
+
+/// A box of voxels read from or to be written to terrain, indexed with x
+/// changing fastest
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoxelGrid {
+    size: IVec3,
+    voxels: Vec<Voxel>,
+}
+
+impl VoxelGrid {
+    /// Create a grid of empty voxels
+    pub fn new(size: IVec3) -> Self {
+        let size = size.max(IVec3::ZERO);
+        Self { size, voxels: vec![Voxel::AIR; (size.x * size.y * size.z) as usize] }
+    }
+
+    /// Get the size of the grid, in voxels
+    pub fn size(&self) -> IVec3 {
+        self.size
+    }
+
+    fn index(&self, position: IVec3) -> Option<usize> {
+        if position.cmplt(IVec3::ZERO).any() || position.cmpge(self.size).any() {
+            return None;
+        }
+        Some((position.x + self.size.x * (position.y + self.size.y * position.z)) as usize)
+    }
+
+    /// Get a voxel, or air outside the grid
+    pub fn get(&self, position: IVec3) -> Voxel {
+        self.index(position).map(|index| self.voxels[index]).unwrap_or_default()
+    }
+
+    /// Set a voxel. Positions outside the grid are ignored.
+    pub fn set(&mut self, position: IVec3, voxel: Voxel) {
+        if let Some(index) = self.index(position) {
+            self.voxels[index] = voxel;
+        }
+    }
+}
+
+/// Errors from the terrain voxel API
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum TerrainError {
+    #[error("Resolution must be {VOXEL_SIZE}, got {0}")]
+    UnsupportedResolution(f32),
+    #[error("Voxel grid is {0} but the region holds {1}")]
+    SizeMismatch(IVec3, IVec3),
+}
+
+/// Terrain - the voxel landscape of the Workspace.
+///
+/// Voxels are VOXEL_SIZE studs on a side, with voxel (0, 0, 0) spanning
+/// from the origin to (4, 4, 4). They are stored in chunks of CHUNK_SIZE
+/// voxels on a side, and every chunk whose mesh a change touches is marked
+/// dirty, for the engine to re-mesh and re-collide.
+pub struct Terrain {
+    instance: Rc<RefCell<Instance>>,
+    chunks: HashMap<IVec3, Vec<Voxel>>,
+    dirty: HashSet<IVec3>,
+}
+
+impl Terrain {
+    /// Create new, empty terrain
+    pub fn new() -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("Terrain");
+        instance.borrow_mut().set_class_name("Terrain");
+
+        Rc::new(RefCell::new(Self {
+            instance,
+            chunks: HashMap::new(),
+            dirty: HashSet::new(),
+        }))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the voxel a point in world space is in
+    pub fn voxel_at(position: Vec3) -> IVec3 {
+        (position / VOXEL_SIZE).floor().as_ivec3()
+    }
+
+    /// Get the center of a voxel in world space
+    pub fn voxel_center(voxel: IVec3) -> Vec3 {
+        (voxel.as_vec3() + 0.5) * VOXEL_SIZE
+    }
+
+    /// Get the chunk a voxel is stored in
+    pub fn chunk_of(voxel: IVec3) -> IVec3 {
+        IVec3::new(voxel.x.div_euclid(CHUNK_SIZE), voxel.y.div_euclid(CHUNK_SIZE), voxel.z.div_euclid(CHUNK_SIZE))
+    }
+
+    fn chunk_index(voxel: IVec3) -> usize {
+        let local = voxel - Self::chunk_of(voxel) * CHUNK_SIZE;
+        (local.x + CHUNK_SIZE * (local.y + CHUNK_SIZE * local.z)) as usize
+    }
+
+    /// Get a voxel
+    pub fn voxel(&self, voxel: IVec3) -> Voxel {
+        self.chunks
+            .get(&Self::chunk_of(voxel))
+            .map(|chunk| chunk[Self::chunk_index(voxel)])
+            .unwrap_or_default()
+    }
+
+    /// Set a voxel, marking the chunks whose mesh it is part of dirty
+    pub fn set_voxel(&mut self, voxel: IVec3, value: Voxel) {
+        let value = Voxel::new(value.material, value.occupancy);
+        if self.voxel(voxel) == value {
+            return;
+        }
+
+        let chunk = self.chunks.entry(Self::chunk_of(voxel)).or_insert_with(|| vec![Voxel::AIR; CHUNK_VOLUME]);
+        chunk[Self::chunk_index(voxel)] = value;
+
+        // A chunk's mesh reaches one voxel into its neighbors
+        for x in -1..=1 {
+            for y in -1..=1 {
+                for z in -1..=1 {
+                    self.dirty.insert(Self::chunk_of(voxel + IVec3::new(x, y, z)));
+                }
+            }
+        }
+    }
+
+    /// Get every chunk that holds voxels
+    pub fn chunks(&self) -> Vec<IVec3> {
+        let mut chunks: Vec<IVec3> = self.chunks.keys().copied().collect();
+        chunks.sort_by_key(|chunk| (chunk.x, chunk.y, chunk.z));
+        chunks
+    }
+
+    /// Take the chunks changed since the last call
+    pub fn take_dirty_chunks(&mut self) -> Vec<IVec3> {
+        let mut dirty: Vec<IVec3> = self.dirty.drain().collect();
+        dirty.sort_by_key(|chunk| (chunk.x, chunk.y, chunk.z));
+        dirty
+    }
+
+    /// Empty the terrain (Terrain:Clear)
+    pub fn clear(&mut self) {
+        for chunk in std::mem::take(&mut self.chunks).into_keys() {
+            for x in -1..=1 {
+                for y in -1..=1 {
+                    for z in -1..=1 {
+                        self.dirty.insert(chunk + IVec3::new(x, y, z));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fill `occupancy` of a voxel with `material`. Filling with Air takes
+    /// that much away instead.
+    fn fill_voxel(&mut self, voxel: IVec3, material: Material, occupancy: f32) {
+        if occupancy <= 0.0 {
+            return;
+        }
+        let current = self.voxel(voxel);
+        let filled = if material == Material::Air {
+            Voxel::new(current.material, current.occupancy.min(1.0 - occupancy))
+        } else {
+            Voxel::new(material, current.occupancy.max(occupancy))
+        };
+        self.set_voxel(voxel, filled);
+    }
+
+    /// Fill the voxels around a shape given by its signed distance, which is
+    /// negative inside. Voxels are filled by how far their center is inside.
+    fn fill_shape(&mut self, min: Vec3, max: Vec3, material: Material, distance: impl Fn(Vec3) -> f32) {
+        let min = Self::voxel_at(min) - 1;
+        let max = Self::voxel_at(max) + 1;
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                for z in min.z..=max.z {
+                    let voxel = IVec3::new(x, y, z);
+                    let occupancy = (0.5 - distance(Self::voxel_center(voxel)) / VOXEL_SIZE).clamp(0.0, 1.0);
+                    self.fill_voxel(voxel, material, occupancy);
+                }
+            }
+        }
+    }
+
+    /// Fill a box, which may be rotated, with a material (Terrain:FillBlock)
+    pub fn fill_block(&mut self, cframe: CFrame, size: Vec3, material: Material) {
+        let half_size = size.abs() / 2.0;
+        let rotation = cframe.rotation;
+        let absolute = Mat3::from_cols(rotation.x_axis.abs(), rotation.y_axis.abs(), rotation.z_axis.abs());
+        let extents = absolute * half_size;
+        self.fill_shape(cframe.position - extents, cframe.position + extents, material, |point| {
+            let outside = cframe.point_to_object_space(point).abs() - half_size;
+            outside.max(Vec3::ZERO).length() + outside.max_element().min(0.0)
+        });
+    }
+
+    /// Fill a sphere with a material (Terrain:FillBall)
+    pub fn fill_ball(&mut self, center: Vec3, radius: f32, material: Material) {
+        let radius = radius.abs();
+        self.fill_shape(center - radius, center + radius, material, |point| (point - center).length() - radius);
+    }
+
+    /// Get the first voxel and size in voxels of a region, after growing it
+    /// onto the voxel grid
+    fn voxel_range(region: &Region3, resolution: f32) -> Result<(IVec3, IVec3), TerrainError> {
+        if resolution != VOXEL_SIZE {
+            return Err(TerrainError::UnsupportedResolution(resolution));
+        }
+        let region = region.expand_to_grid(VOXEL_SIZE);
+        Ok(((region.min / VOXEL_SIZE).round().as_ivec3(), (region.size() / VOXEL_SIZE).round().as_ivec3()))
+    }
+
+    /// Fill every voxel of a region completely with a material
+    /// (Terrain:FillRegion)
+    pub fn fill_region(&mut self, region: &Region3, resolution: f32, material: Material) -> Result<(), TerrainError> {
+        let (min, size) = Self::voxel_range(region, resolution)?;
+        let mut grid = VoxelGrid::new(size);
+        grid.voxels.fill(Voxel::new(material, 1.0));
+        self.write_voxel_grid(min, &grid);
+        Ok(())
+    }
+
+    /// Read the voxels of a region (Terrain:ReadVoxels)
+    pub fn read_voxels(&self, region: &Region3, resolution: f32) -> Result<VoxelGrid, TerrainError> {
+        let (min, size) = Self::voxel_range(region, resolution)?;
+        let mut grid = VoxelGrid::new(size);
+        for x in 0..size.x {
+            for y in 0..size.y {
+                for z in 0..size.z {
+                    let position = IVec3::new(x, y, z);
+                    grid.set(position, self.voxel(min + position));
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Write the voxels of a region (Terrain:WriteVoxels). The grid must be
+    /// the size of the region in voxels.
+    pub fn write_voxels(&mut self, region: &Region3, resolution: f32, grid: &VoxelGrid) -> Result<(), TerrainError> {
+        let (min, size) = Self::voxel_range(region, resolution)?;
+        if grid.size() != size {
+            return Err(TerrainError::SizeMismatch(grid.size(), size));
+        }
+        self.write_voxel_grid(min, grid);
+        Ok(())
+    }
+
+    fn write_voxel_grid(&mut self, min: IVec3, grid: &VoxelGrid) {
+        let size = grid.size();
+        for x in 0..size.x {
+            for y in 0..size.y {
+                for z in 0..size.z {
+                    let position = IVec3::new(x, y, z);
+                    self.set_voxel(min + position, grid.get(position));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_ball_and_block() {
+        let terrain = Terrain::new();
+        let mut terrain = terrain.borrow_mut();
+        terrain.fill_ball(Vec3::new(16.0, 16.0, 16.0), 8.0, Material::Grass);
+
+        let center = terrain.voxel(Terrain::voxel_at(Vec3::new(16.0, 16.0, 16.0)));
+        assert_eq!(center, Voxel::new(Material::Grass, 1.0));
+        assert_eq!(terrain.voxel(Terrain::voxel_at(Vec3::new(16.0, 40.0, 16.0))), Voxel::AIR);
+        // The voxel straddling the surface is partly full
+        let edge = terrain.voxel(Terrain::voxel_at(Vec3::new(16.0, 23.0, 16.0)));
+        assert!(edge.occupancy > 0.0 && edge.occupancy < 1.0, "{edge:?}");
+
+        // Filling with air carves the ball out
+        terrain.fill_block(CFrame::new(Vec3::new(16.0, 16.0, 16.0)), Vec3::splat(40.0), Material::Air);
+        assert!(terrain.read_voxels(&Region3::new(Vec3::ZERO, Vec3::splat(32.0)), VOXEL_SIZE).unwrap() == VoxelGrid::new(IVec3::splat(8)));
+    }
+
+    #[test]
+    fn test_read_and_write_voxels() {
+        let terrain = Terrain::new();
+        let mut terrain = terrain.borrow_mut();
+        let region = Region3::new(Vec3::new(-4.0, 0.0, 0.0), Vec3::new(4.0, 4.0, 4.0));
+
+        let mut grid = VoxelGrid::new(IVec3::new(2, 1, 1));
+        grid.set(IVec3::new(0, 0, 0), Voxel::new(Material::Rock, 1.0));
+        grid.set(IVec3::new(1, 0, 0), Voxel::new(Material::Water, 0.5));
+        terrain.write_voxels(&region, VOXEL_SIZE, &grid).unwrap();
+        assert_eq!(terrain.read_voxels(&region, VOXEL_SIZE).unwrap(), grid);
+        assert_eq!(terrain.voxel(IVec3::new(-1, 0, 0)).material, Material::Rock);
+
+        assert_eq!(terrain.write_voxels(&region, 2.0, &grid), Err(TerrainError::UnsupportedResolution(2.0)));
+        assert!(matches!(terrain.write_voxels(&region, VOXEL_SIZE, &VoxelGrid::new(IVec3::ONE)), Err(TerrainError::SizeMismatch(..))));
+
+        terrain.fill_region(&Region3::new(Vec3::ZERO, Vec3::new(7.0, 4.0, 4.0)), VOXEL_SIZE, Material::Sand).unwrap();
+        assert_eq!(terrain.voxel(IVec3::new(1, 0, 0)), Voxel::new(Material::Sand, 1.0));
+        assert_eq!(Material::from_value(Material::Sand.value()), Some(Material::Sand));
+    }
+
+    #[test]
+    fn test_changes_mark_neighboring_chunks_dirty() {
+        let terrain = Terrain::new();
+        let mut terrain = terrain.borrow_mut();
+
+        // A voxel in the middle of a chunk only touches its own chunk's mesh
+        terrain.set_voxel(IVec3::new(3, 3, 3), Voxel::new(Material::Rock, 1.0));
+        assert_eq!(terrain.take_dirty_chunks(), vec![IVec3::ZERO]);
+        assert!(terrain.take_dirty_chunks().is_empty());
+
+        // Writing the same voxel again changes nothing
+        terrain.set_voxel(IVec3::new(3, 3, 3), Voxel::new(Material::Rock, 1.0));
+        assert!(terrain.take_dirty_chunks().is_empty());
+
+        // A voxel on a corner touches the chunks around the corner
+        terrain.set_voxel(IVec3::new(0, 0, 0), Voxel::new(Material::Rock, 1.0));
+        assert_eq!(terrain.take_dirty_chunks().len(), 8);
+        assert_eq!(terrain.chunks(), vec![IVec3::ZERO]);
+
+        terrain.clear();
+        assert_eq!(terrain.voxel(IVec3::new(3, 3, 3)), Voxel::AIR);
+        assert_eq!(terrain.take_dirty_chunks().len(), 27);
+    }
+}
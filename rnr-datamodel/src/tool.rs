@@ -0,0 +1,471 @@
+use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use std::f32::consts::FRAC_PI_2;
+use glam::{Quat, Vec3};
+use rnr_core::instance::Instance;
+use rnr_core::signal::Signal;
+use rnr_core::types::CFrame;
+use rnr_core::variant::Variant;
+use crate::gui::clone_tree;
+use crate::joint::JointInstance;
+use crate::part::{move_model, BasePart};
+use crate::players::{Player, Players};
+
+/// Name of the weld holding an equipped tool's Handle in the right hand
+pub const RIGHT_GRIP_NAME: &str = "RightGrip";
+
+/// Where the right hand holds a tool, relative to the Right Arm: at the
+/// bottom of the arm, turned so the tool's up points forward
+pub fn right_grip_offset() -> CFrame {
+    CFrame::from_rotation(Vec3::new(0.0, -1.0, 0.0), Quat::from_rotation_x(-FRAC_PI_2))
+}
+
+/// Typed view over a Tool instance
+#[derive(Debug, Clone)]
+pub struct Tool {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl Tool {
+    /// Create a new tool without a Handle
+    pub fn new(name: &str) -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name(name);
+            instance.set_class_name("Tool");
+            instance.set_property("Grip", CFrame::IDENTITY);
+            instance.set_property("RequiresHandle", true);
+            instance.set_property("CanBeDropped", true);
+            instance.set_property("Enabled", true);
+            instance.set_property("ToolTip", "");
+        }
+
+        Self { instance }
+    }
+
+    /// View an existing instance as a tool, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("Tool") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the part the tool is held by, its child named Handle
+    pub fn handle(&self) -> Option<BasePart> {
+        self.instance.borrow().find_first_child("Handle").as_ref().and_then(BasePart::from_instance)
+    }
+
+    fn get_bool(&self, name: &str) -> bool {
+        self.instance.borrow().get_property(name).and_then(Variant::as_bool).unwrap_or(true)
+    }
+
+    /// Get where the Handle sits in the hand, relative to the grip
+    pub fn grip(&self) -> CFrame {
+        self.instance.borrow().get_property("Grip").and_then(Variant::as_cframe).unwrap_or_default()
+    }
+
+    /// Set where the Handle sits in the hand
+    pub fn set_grip(&self, grip: CFrame) {
+        self.instance.borrow_mut().set_property("Grip", grip);
+    }
+
+    /// Check if the tool can only be equipped with a Handle
+    pub fn requires_handle(&self) -> bool {
+        self.get_bool("RequiresHandle")
+    }
+
+    /// Set whether the tool can only be equipped with a Handle
+    pub fn set_requires_handle(&self, requires_handle: bool) {
+        self.instance.borrow_mut().set_property("RequiresHandle", requires_handle);
+    }
+
+    /// Check if the holder may drop the tool into the workspace
+    pub fn can_be_dropped(&self) -> bool {
+        self.get_bool("CanBeDropped")
+    }
+
+    /// Set whether the holder may drop the tool
+    pub fn set_can_be_dropped(&self, can_be_dropped: bool) {
+        self.instance.borrow_mut().set_property("CanBeDropped", can_be_dropped);
+    }
+
+    /// Check if the tool can be activated
+    pub fn enabled(&self) -> bool {
+        self.get_bool("Enabled")
+    }
+
+    /// Set whether the tool can be activated
+    pub fn set_enabled(&self, enabled: bool) {
+        self.instance.borrow_mut().set_property("Enabled", enabled);
+    }
+}
+
+/// A change to what a player holds, for the network layer to send to every
+/// client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolUpdate {
+    /// The player took the named tool out of their backpack
+    Equipped { user_id: i64, tool_name: String },
+    /// The player put away or dropped what they held
+    Unequipped { user_id: i64 },
+}
+
+/// The events of one tool
+#[derive(Default)]
+struct ToolSignals {
+    activated: Signal<()>,
+    deactivated: Signal<()>,
+    equipped: Signal<()>,
+    unequipped: Signal<()>,
+}
+
+/// Equips, activates and drops tools, and keeps their events.
+///
+/// Equipping moves the tool from the player's Backpack into their character
+/// and welds its Handle to the Right Arm with a RightGrip at the tool's Grip.
+/// Equips and unequips are queued as updates for every client.
+#[derive(Default)]
+pub struct Tools {
+    signals: Vec<(Weak<RefCell<Instance>>, ToolSignals)>,
+    updates: Vec<ToolUpdate>,
+}
+
+impl Tools {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn signals(&mut self, tool: &Tool) -> &ToolSignals {
+        self.signals.retain(|(known, _)| known.strong_count() > 0);
+        let index = match self.signals.iter().position(|(known, _)| known.as_ptr() == Rc::as_ptr(tool.instance())) {
+            Some(index) => index,
+            None => {
+                self.signals.push((Rc::downgrade(tool.instance()), ToolSignals::default()));
+                self.signals.len() - 1
+            }
+        };
+        &self.signals[index].1
+    }
+
+    /// Get the Activated signal of a tool, fired when its holder clicks
+    pub fn activated(&mut self, tool: &Tool) -> Signal<()> {
+        self.signals(tool).activated.clone()
+    }
+
+    /// Get the Deactivated signal of a tool, fired when the click is released
+    pub fn deactivated(&mut self, tool: &Tool) -> Signal<()> {
+        self.signals(tool).deactivated.clone()
+    }
+
+    /// Get the Equipped signal of a tool
+    pub fn equipped(&mut self, tool: &Tool) -> Signal<()> {
+        self.signals(tool).equipped.clone()
+    }
+
+    /// Get the Unequipped signal of a tool
+    pub fn unequipped(&mut self, tool: &Tool) -> Signal<()> {
+        self.signals(tool).unequipped.clone()
+    }
+
+    /// Get the tool a player's character is holding
+    pub fn equipped_tool(player: &Player) -> Option<Tool> {
+        let character = player.character()?;
+        let children = character.borrow().children().to_vec();
+        children.iter().find_map(Tool::from_instance)
+    }
+
+    /// Equip a tool (Humanoid:EquipTool), putting away whatever the player
+    /// held before. Returns false if the player has no character with a
+    /// Right Arm, or the tool requires a Handle it doesn't have.
+    pub fn equip(&mut self, player: &Player, tool: &Tool) -> bool {
+        if !self.equip_tool(player, tool) {
+            return false;
+        }
+        self.updates.push(ToolUpdate::Equipped { user_id: player.user_id(), tool_name: tool.instance().borrow().name().to_string() });
+        true
+    }
+
+    fn equip_tool(&mut self, player: &Player, tool: &Tool) -> bool {
+        let Some(character) = player.character() else {
+            return false;
+        };
+        let Some(right_arm) = character.borrow().find_first_child("Right Arm").as_ref().and_then(BasePart::from_instance) else {
+            return false;
+        };
+        let handle = tool.handle();
+        if tool.requires_handle() && handle.is_none() {
+            return false;
+        }
+        if Self::equipped_tool(player).is_some_and(|held| Rc::ptr_eq(held.instance(), tool.instance())) {
+            return true;
+        }
+
+        self.put_away(player, false);
+        Instance::set_parent(tool.instance(), Some(character.clone()));
+        if let Some(handle) = handle {
+            let held = right_arm.cframe() * right_grip_offset() * tool.grip().inverse();
+            move_model(tool.instance(), handle.cframe(), held);
+
+            let weld = JointInstance::new("Weld", &right_arm, &handle);
+            {
+                let mut weld = weld.instance().borrow_mut();
+                weld.set_name(RIGHT_GRIP_NAME);
+                weld.set_property("C0", right_grip_offset());
+                weld.set_property("C1", tool.grip());
+            }
+            Instance::set_parent(weld.instance(), Some(right_arm.instance().clone()));
+        }
+
+        self.equipped(tool).fire(&());
+        true
+    }
+
+    /// Put the held tool back in the player's Backpack (Humanoid:UnequipTools)
+    pub fn unequip(&mut self, player: &Player) {
+        if self.put_away(player, false) {
+            self.updates.push(ToolUpdate::Unequipped { user_id: player.user_id() });
+        }
+    }
+
+    /// Drop the held tool into `workspace`, where it was held. Returns false
+    /// if the player holds nothing or the tool can't be dropped.
+    pub fn drop_tool(&mut self, player: &Player, workspace: &Rc<RefCell<Instance>>) -> bool {
+        let Some(tool) = Self::equipped_tool(player).filter(Tool::can_be_dropped) else {
+            return false;
+        };
+
+        self.put_away(player, true);
+        Instance::set_parent(tool.instance(), Some(workspace.clone()));
+        self.updates.push(ToolUpdate::Unequipped { user_id: player.user_id() });
+        true
+    }
+
+    /// Take the held tool out of the hand, into the Backpack unless it is
+    /// being dropped. Returns false if nothing was held.
+    fn put_away(&mut self, player: &Player, dropping: bool) -> bool {
+        let Some(tool) = Self::equipped_tool(player) else {
+            return false;
+        };
+
+        let grip = player.character()
+            .and_then(|character| character.borrow().find_first_child("Right Arm"))
+            .and_then(|right_arm| right_arm.borrow().find_first_child(RIGHT_GRIP_NAME));
+        if let Some(grip) = grip {
+            Instance::destroy(&grip);
+        }
+        if !dropping {
+            Instance::set_parent(tool.instance(), player.backpack());
+        }
+
+        self.unequipped(&tool).fire(&());
+        true
+    }
+
+    /// Activate the held tool, as its holder clicks. Disabled tools ignore it.
+    pub fn activate(&mut self, player: &Player) {
+        if let Some(tool) = Self::equipped_tool(player).filter(Tool::enabled) {
+            self.activated(&tool).fire(&());
+        }
+    }
+
+    /// Deactivate the held tool, as its holder releases the click
+    pub fn deactivate(&mut self, player: &Player) {
+        if let Some(tool) = Self::equipped_tool(player).filter(Tool::enabled) {
+            self.deactivated(&tool).fire(&());
+        }
+    }
+
+    /// Take the updates queued since the last call
+    pub fn take_updates(&mut self) -> Vec<ToolUpdate> {
+        std::mem::take(&mut self.updates)
+    }
+
+    /// Apply an update sent by the server, without queueing it again
+    pub fn apply_update(&mut self, players: &Players, update: &ToolUpdate) {
+        match update {
+            ToolUpdate::Equipped { user_id, tool_name } => {
+                let Some(player) = players.get_player_by_user_id(*user_id) else {
+                    return;
+                };
+                let tool = player.backpack()
+                    .and_then(|backpack| backpack.borrow().find_first_child(tool_name))
+                    .as_ref()
+                    .and_then(Tool::from_instance);
+                if let Some(tool) = tool {
+                    self.equip_tool(&player, &tool);
+                }
+            }
+            ToolUpdate::Unequipped { user_id } => {
+                if let Some(player) = players.get_player_by_user_id(*user_id) {
+                    self.put_away(&player, false);
+                }
+            }
+        }
+    }
+}
+
+/// StarterPack - holds the tools every player gets in their Backpack
+pub struct StarterPack {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl StarterPack {
+    /// Create a new starter pack service
+    pub fn new() -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("StarterPack");
+        instance.borrow_mut().set_class_name("StarterPack");
+
+        Rc::new(RefCell::new(Self { instance }))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Refill a Backpack when its player spawns: whatever was in it is
+    /// removed, and StarterPack's archivable contents are copied in
+    pub fn fill_backpack(&self, backpack: &Rc<RefCell<Instance>>) {
+        let existing = backpack.borrow().children().to_vec();
+        for item in &existing {
+            Instance::destroy(item);
+        }
+
+        let templates = self.instance.borrow().children().to_vec();
+        for template in templates.iter().filter(|template| template.borrow().archivable()) {
+            Instance::set_parent(&clone_tree(template), Some(backpack.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debris::Debris;
+
+    fn sword() -> Tool {
+        let tool = Tool::new("Sword");
+        let handle = BasePart::new("Part");
+        handle.instance().borrow_mut().set_name("Handle");
+        handle.set_size(Vec3::new(1.0, 0.8, 4.0));
+        Instance::set_parent(handle.instance(), Some(tool.instance().clone()));
+        tool.set_grip(CFrame::new(Vec3::new(0.0, 0.0, -1.5)));
+        tool
+    }
+
+    fn spawn(players: &Rc<RefCell<Players>>, workspace: &Rc<RefCell<Instance>>) -> Player {
+        let player = Players::add_player(players, "Player1", 1);
+        players.borrow_mut().load_character(&player, workspace, &mut Debris::new().borrow_mut());
+        player
+    }
+
+    #[test]
+    fn test_starter_pack_fills_backpack() {
+        let starter_pack = StarterPack::new();
+        Instance::set_parent(sword().instance(), Some(starter_pack.borrow().instance().clone()));
+        let player = Player::new("Player1", 1);
+        let backpack = player.backpack().unwrap();
+        assert_eq!(backpack.borrow().class_name(), "Backpack");
+
+        // Each spawn starts over with a fresh copy
+        starter_pack.borrow().fill_backpack(&backpack);
+        Instance::set_parent(Tool::new("Junk").instance(), Some(backpack.clone()));
+        starter_pack.borrow().fill_backpack(&backpack);
+        let names: Vec<String> = backpack.borrow().children().iter().map(|item| item.borrow().name().to_string()).collect();
+        assert_eq!(names, vec!["Sword"]);
+        assert!(Tool::from_instance(&backpack.borrow().children()[0]).unwrap().handle().is_some());
+    }
+
+    #[test]
+    fn test_equip_welds_handle_to_right_arm() {
+        let workspace = Instance::new();
+        let players = Players::new();
+        let player = spawn(&players, &workspace);
+        let tool = sword();
+        Instance::set_parent(tool.instance(), player.backpack());
+
+        let mut tools = Tools::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        for (event, signal) in [("equipped", tools.equipped(&tool)), ("unequipped", tools.unequipped(&tool)), ("activated", tools.activated(&tool))] {
+            let log = log.clone();
+            signal.connect(move |_| log.borrow_mut().push(event));
+        }
+
+        assert!(tools.equip(&player, &tool));
+        let character = player.character().unwrap();
+        assert!(Rc::ptr_eq(&tool.instance().borrow().parent().unwrap(), &character));
+        let right_arm = BasePart::from_instance(&character.borrow().find_first_child("Right Arm").unwrap()).unwrap();
+        let grip = JointInstance::from_instance(&right_arm.instance().borrow().find_first_child(RIGHT_GRIP_NAME).unwrap()).unwrap();
+        let handle = tool.handle().unwrap();
+        assert!(Rc::ptr_eq(grip.part1().unwrap().instance(), handle.instance()));
+        assert_eq!(grip.c1(), tool.grip());
+        let expected = right_arm.cframe() * grip.c0() * grip.c1().inverse();
+        assert!((handle.position() - expected.position).length() < 1e-4);
+
+        tools.activate(&player);
+        tool.set_enabled(false);
+        tools.activate(&player);
+        tools.unequip(&player);
+        assert!(Rc::ptr_eq(&tool.instance().borrow().parent().unwrap(), &player.backpack().unwrap()));
+        assert!(right_arm.instance().borrow().find_first_child(RIGHT_GRIP_NAME).is_none());
+        assert_eq!(*log.borrow(), vec!["equipped", "activated", "unequipped"]);
+
+        assert_eq!(tools.take_updates(), vec![
+            ToolUpdate::Equipped { user_id: 1, tool_name: "Sword".to_string() },
+            ToolUpdate::Unequipped { user_id: 1 },
+        ]);
+    }
+
+    #[test]
+    fn test_requires_handle_and_dropping() {
+        let workspace = Instance::new();
+        let players = Players::new();
+        let player = spawn(&players, &workspace);
+        let mut tools = Tools::new();
+
+        let empty = Tool::new("Empty");
+        Instance::set_parent(empty.instance(), player.backpack());
+        assert!(!tools.equip(&player, &empty));
+        empty.set_requires_handle(false);
+        assert!(tools.equip(&player, &empty));
+
+        // Equipping another tool puts the first one away
+        let tool = sword();
+        Instance::set_parent(tool.instance(), player.backpack());
+        assert!(tools.equip(&player, &tool));
+        assert!(Rc::ptr_eq(&empty.instance().borrow().parent().unwrap(), &player.backpack().unwrap()));
+
+        tool.set_can_be_dropped(false);
+        assert!(!tools.drop_tool(&player, &workspace));
+        tool.set_can_be_dropped(true);
+        assert!(tools.drop_tool(&player, &workspace));
+        assert!(Rc::ptr_eq(&tool.instance().borrow().parent().unwrap(), &workspace));
+        assert!(Tools::equipped_tool(&player).is_none());
+    }
+
+    #[test]
+    fn test_updates_apply_on_clients() {
+        let workspace = Instance::new();
+        let players = Players::new();
+        let player = spawn(&players, &workspace);
+        let tool = sword();
+        Instance::set_parent(tool.instance(), player.backpack());
+
+        let mut tools = Tools::new();
+        tools.apply_update(&players.borrow(), &ToolUpdate::Equipped { user_id: 1, tool_name: "Sword".to_string() });
+        assert!(Tools::equipped_tool(&player).is_some());
+        tools.apply_update(&players.borrow(), &ToolUpdate::Unequipped { user_id: 1 });
+        assert!(Tools::equipped_tool(&player).is_none());
+        // Applied updates aren't sent back out
+        assert!(tools.take_updates().is_empty());
+    }
+}
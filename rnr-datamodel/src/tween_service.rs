@@ -0,0 +1,624 @@
+use std::f32::consts::PI;
+use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use rnr_core::instance::Instance;
+use rnr_core::signal::Signal;
+use rnr_core::variant::Variant;
+
+/// Slack when checking if a tween has finished, so a tween stepped in frames
+/// that add up to its length finishes on the last frame despite rounding
+const TIME_EPSILON: f64 = 1e-9;
+
+/// Shape of a tween's motion (Enum.EasingStyle)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EasingStyle {
+    Linear = 0,
+    Sine = 1,
+    Back = 2,
+    Quad = 3,
+    Quart = 4,
+    Quint = 5,
+    Bounce = 6,
+    Elastic = 7,
+    Exponential = 8,
+    Circular = 9,
+    Cubic = 10,
+}
+
+/// Which end of a tween the easing style applies to (Enum.EasingDirection)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EasingDirection {
+    In = 0,
+    Out = 1,
+    InOut = 2,
+}
+
+/// State of a tween (Enum.PlaybackState)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackState {
+    Begin = 0,
+    Delayed = 1,
+    Playing = 2,
+    Paused = 3,
+    Completed = 4,
+    Cancelled = 5,
+}
+
+/// How a tween plays out over time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TweenInfo {
+    /// Length of one playthrough in seconds
+    pub time: f64,
+    pub easing_style: EasingStyle,
+    pub easing_direction: EasingDirection,
+    /// Number of extra playthroughs. Negative repeats forever.
+    pub repeat_count: i32,
+    /// Whether each playthrough plays back in reverse once it reaches the goal
+    pub reverses: bool,
+    /// Seconds to wait before each playthrough
+    pub delay_time: f64,
+}
+
+impl TweenInfo {
+    /// Create a tween info for a single playthrough
+    pub fn new(time: f64, easing_style: EasingStyle, easing_direction: EasingDirection) -> Self {
+        Self {
+            time,
+            easing_style,
+            easing_direction,
+            ..Self::default()
+        }
+    }
+
+    /// Length of one playthrough including its delay and reversal
+    fn cycle_time(&self) -> f64 {
+        let time = self.time.max(0.0);
+        self.delay_time.max(0.0) + if self.reverses { time * 2.0 } else { time }
+    }
+}
+
+impl Default for TweenInfo {
+    /// Same defaults as TweenInfo.new() in Roblox
+    fn default() -> Self {
+        Self {
+            time: 1.0,
+            easing_style: EasingStyle::Quad,
+            easing_direction: EasingDirection::Out,
+            repeat_count: 0,
+            reverses: false,
+            delay_time: 0.0,
+        }
+    }
+}
+
+/// Error returned by TweenService:Create
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum TweenError {
+    #[error("{0} is not a property of the instance")]
+    UnknownProperty(String),
+    #[error("Property {0} can't be tweened")]
+    UnsupportedType(String),
+    #[error("Goal for property {0} doesn't match the property's type")]
+    TypeMismatch(String),
+}
+
+/// Apply an easing style and direction to a linear alpha from 0 to 1
+pub fn ease(style: EasingStyle, direction: EasingDirection, alpha: f32) -> f32 {
+    let alpha = alpha.clamp(0.0, 1.0);
+    match direction {
+        EasingDirection::In => ease_in(style, alpha),
+        EasingDirection::Out => 1.0 - ease_in(style, 1.0 - alpha),
+        EasingDirection::InOut => {
+            if alpha < 0.5 {
+                ease_in(style, alpha * 2.0) / 2.0
+            } else {
+                1.0 - ease_in(style, (1.0 - alpha) * 2.0) / 2.0
+            }
+        }
+    }
+}
+
+fn ease_in(style: EasingStyle, t: f32) -> f32 {
+    match style {
+        EasingStyle::Linear => t,
+        EasingStyle::Sine => 1.0 - (t * PI / 2.0).cos(),
+        EasingStyle::Quad => t * t,
+        EasingStyle::Cubic => t * t * t,
+        EasingStyle::Quart => t * t * t * t,
+        EasingStyle::Quint => t * t * t * t * t,
+        EasingStyle::Exponential => {
+            if t == 0.0 { 0.0 } else { 2f32.powf(10.0 * (t - 1.0)) }
+        }
+        EasingStyle::Circular => 1.0 - (1.0 - t * t).max(0.0).sqrt(),
+        EasingStyle::Back => {
+            const OVERSHOOT: f32 = 1.70158;
+            t * t * ((OVERSHOOT + 1.0) * t - OVERSHOOT)
+        }
+        EasingStyle::Elastic => {
+            if t == 0.0 || t == 1.0 {
+                t
+            } else {
+                -(2f32.powf(10.0 * (t - 1.0))) * ((t - 1.075) * 2.0 * PI / 0.3).sin()
+            }
+        }
+        EasingStyle::Bounce => 1.0 - bounce_out(1.0 - t),
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// Interpolate between two property values, if their type can be tweened
+pub fn lerp_variant(start: &Variant, goal: &Variant, alpha: f32) -> Option<Variant> {
+    let value = match (start, goal) {
+        (Variant::Float(a), Variant::Float(b)) => Variant::Float(a + (b - a) * alpha as f64),
+        (Variant::Int(a), Variant::Int(b)) => Variant::Int((*a as f64 + (b - a) as f64 * alpha as f64).round() as i64),
+        (Variant::Vector2(a), Variant::Vector2(b)) => Variant::Vector2(a.lerp(*b, alpha)),
+        (Variant::Vector3(a), Variant::Vector3(b)) => Variant::Vector3(a.lerp(*b, alpha)),
+        (Variant::CFrame(a), Variant::CFrame(b)) => Variant::CFrame(a.lerp(b, alpha)),
+        (Variant::Color3(a), Variant::Color3(b)) => Variant::Color3(a.lerp(b, alpha)),
+        (Variant::UDim(a), Variant::UDim(b)) => Variant::UDim(a.lerp(b, alpha)),
+        (Variant::UDim2(a), Variant::UDim2(b)) => Variant::UDim2(a.lerp(b, alpha)),
+        _ => return None,
+    };
+    Some(value)
+}
+
+struct TweenState {
+    instance: Rc<RefCell<Instance>>,
+    target: Rc<RefCell<Instance>>,
+    info: TweenInfo,
+    /// Property names and the values to tween them to
+    goals: Vec<(String, Variant)>,
+    /// Property values captured when the tween started playing
+    starts: Vec<Variant>,
+    playback_state: PlaybackState,
+    /// Seconds played so far, including delays
+    elapsed: f64,
+    completed: Signal<PlaybackState>,
+    service: Weak<RefCell<TweenService>>,
+}
+
+/// A tween animating properties of an instance (Tween)
+#[derive(Clone)]
+pub struct Tween {
+    state: Rc<RefCell<TweenState>>,
+}
+
+impl Tween {
+    /// Get the underlying instance
+    pub fn instance(&self) -> Rc<RefCell<Instance>> {
+        self.state.borrow().instance.clone()
+    }
+
+    /// Get the instance whose properties are tweened
+    pub fn target(&self) -> Rc<RefCell<Instance>> {
+        self.state.borrow().target.clone()
+    }
+
+    /// Get how the tween plays out
+    pub fn tween_info(&self) -> TweenInfo {
+        self.state.borrow().info
+    }
+
+    /// Get the tween's state
+    pub fn playback_state(&self) -> PlaybackState {
+        self.state.borrow().playback_state
+    }
+
+    /// Get the Completed signal, fired with Completed or Cancelled
+    pub fn completed(&self) -> Signal<PlaybackState> {
+        self.state.borrow().completed.clone()
+    }
+
+    /// Start or resume the tween (Tween:Play).
+    ///
+    /// A tween that isn't paused starts over, tweening from the properties'
+    /// current values.
+    pub fn play(&self) {
+        let service = {
+            let mut state = self.state.borrow_mut();
+            match state.playback_state {
+                PlaybackState::Delayed | PlaybackState::Playing => return,
+                PlaybackState::Paused => {
+                    state.playback_state = PlaybackState::Playing;
+                }
+                PlaybackState::Begin | PlaybackState::Completed | PlaybackState::Cancelled => {
+                    let starts = {
+                        let target = state.target.borrow();
+                        state.goals
+                            .iter()
+                            .map(|(name, goal)| target.get_property(name).cloned().unwrap_or_else(|| goal.clone()))
+                            .collect()
+                    };
+                    state.starts = starts;
+                    state.elapsed = 0.0;
+                    state.playback_state = if state.info.delay_time > 0.0 {
+                        PlaybackState::Delayed
+                    } else {
+                        PlaybackState::Playing
+                    };
+                }
+            }
+            state.service.upgrade()
+        };
+
+        if let Some(service) = service {
+            service.borrow_mut().activate(self);
+        }
+    }
+
+    /// Stop the tween where it is, keeping its progress (Tween:Pause)
+    pub fn pause(&self) {
+        let mut state = self.state.borrow_mut();
+        if matches!(state.playback_state, PlaybackState::Delayed | PlaybackState::Playing) {
+            state.playback_state = PlaybackState::Paused;
+        }
+    }
+
+    /// Stop the tween and throw away its progress (Tween:Cancel).
+    ///
+    /// Properties keep their current values. Completed fires with Cancelled.
+    pub fn cancel(&self) {
+        let completed = {
+            let mut state = self.state.borrow_mut();
+            if matches!(state.playback_state, PlaybackState::Begin | PlaybackState::Completed | PlaybackState::Cancelled) {
+                return;
+            }
+            state.playback_state = PlaybackState::Cancelled;
+            state.elapsed = 0.0;
+            state.completed.clone()
+        };
+
+        completed.fire(&PlaybackState::Cancelled);
+    }
+
+    /// Advance the tween by `delta_time` seconds and write the new property
+    /// values. Returns true once the tween has finished.
+    fn step(&self, delta_time: f64) -> bool {
+        let mut state = self.state.borrow_mut();
+        match state.playback_state {
+            PlaybackState::Delayed | PlaybackState::Playing => {}
+            PlaybackState::Paused => return false,
+            _ => return true,
+        }
+
+        state.elapsed += delta_time;
+        let info = state.info;
+        let cycle_time = info.cycle_time();
+        let cycles = info.repeat_count.max(0) as f64 + 1.0;
+        let forever = info.repeat_count < 0 && cycle_time > 0.0;
+
+        let finished = !forever && (cycle_time <= 0.0 || state.elapsed + TIME_EPSILON >= cycle_time * cycles);
+        let end_alpha = if info.reverses { 0.0 } else { 1.0 };
+        let (alpha, delayed) = if finished {
+            (end_alpha, false)
+        } else {
+            let cycle = (state.elapsed / cycle_time).floor();
+            let local = state.elapsed - cycle * cycle_time - info.delay_time.max(0.0);
+            if local < 0.0 {
+                // Waiting out the delay before a playthrough. The properties
+                // hold wherever the previous playthrough left them.
+                if cycle == 0.0 {
+                    state.playback_state = PlaybackState::Delayed;
+                    return false;
+                }
+                (end_alpha, true)
+            } else {
+                let time = info.time.max(0.0);
+                let linear = if time <= 0.0 {
+                    1.0
+                } else if local <= time {
+                    local / time
+                } else {
+                    1.0 - (local - time) / time
+                };
+                (ease(info.easing_style, info.easing_direction, linear as f32), false)
+            }
+        };
+
+        state.playback_state = if finished {
+            PlaybackState::Completed
+        } else if delayed {
+            PlaybackState::Delayed
+        } else {
+            PlaybackState::Playing
+        };
+
+        let mut target = state.target.borrow_mut();
+        for ((name, goal), start) in state.goals.iter().zip(&state.starts) {
+            if let Some(value) = lerp_variant(start, goal, alpha) {
+                target.set_property(name, value);
+            }
+        }
+
+        finished
+    }
+}
+
+/// TweenService - creates tweens and plays them on the world's clock
+pub struct TweenService {
+    instance: Rc<RefCell<Instance>>,
+    /// Tweens that are playing, delayed or paused
+    active: Vec<Tween>,
+}
+
+impl TweenService {
+    /// Create a new tween service
+    pub fn new() -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("TweenService");
+        instance.borrow_mut().set_class_name("TweenService");
+
+        Rc::new(RefCell::new(Self {
+            instance,
+            active: Vec::new(),
+        }))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Create a tween that moves properties of `target` towards `goals`
+    /// (TweenService:Create).
+    ///
+    /// Every goal must name an existing property of a tweenable type: number,
+    /// Vector2, Vector3, CFrame, Color3, UDim or UDim2.
+    pub fn create(
+        service: &Rc<RefCell<Self>>,
+        target: &Rc<RefCell<Instance>>,
+        info: TweenInfo,
+        goals: Vec<(&str, Variant)>,
+    ) -> Result<Tween, TweenError> {
+        let mut checked_goals = Vec::with_capacity(goals.len());
+        {
+            let target = target.borrow();
+            for (name, goal) in goals {
+                let current = target
+                    .get_property(name)
+                    .ok_or_else(|| TweenError::UnknownProperty(name.to_string()))?;
+                if lerp_variant(current, current, 0.0).is_none() {
+                    return Err(TweenError::UnsupportedType(name.to_string()));
+                }
+
+                // Numbers may be given as either integers or floats
+                let goal = match (current, &goal) {
+                    (Variant::Float(_), Variant::Int(i)) => Variant::Float(*i as f64),
+                    _ => goal,
+                };
+                if lerp_variant(current, &goal, 0.0).is_none() {
+                    return Err(TweenError::TypeMismatch(name.to_string()));
+                }
+
+                checked_goals.push((name.to_string(), goal));
+            }
+        }
+
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("Tween");
+        instance.borrow_mut().set_class_name("Tween");
+
+        Ok(Tween {
+            state: Rc::new(RefCell::new(TweenState {
+                instance,
+                target: target.clone(),
+                info,
+                goals: checked_goals,
+                starts: Vec::new(),
+                playback_state: PlaybackState::Begin,
+                elapsed: 0.0,
+                completed: Signal::new(),
+                service: Rc::downgrade(service),
+            })),
+        })
+    }
+
+    /// Get the number of tweens that are playing, delayed or paused
+    pub fn num_active_tweens(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Keep a tween alive and stepping until it finishes
+    fn activate(&mut self, tween: &Tween) {
+        if !self.active.iter().any(|active| Rc::ptr_eq(&active.state, &tween.state)) {
+            self.active.push(tween.clone());
+        }
+    }
+
+    /// Advance every active tween by `delta_time` seconds and fire Completed
+    /// for the ones that finish
+    pub fn update(service: &Rc<RefCell<Self>>, delta_time: f64) {
+        let active = service.borrow().active.clone();
+
+        let mut finished = Vec::new();
+        for tween in &active {
+            if tween.step(delta_time) {
+                finished.push(tween.clone());
+            }
+        }
+
+        service.borrow_mut().active.retain(|tween| {
+            !matches!(tween.playback_state(), PlaybackState::Completed | PlaybackState::Cancelled)
+        });
+
+        for tween in finished {
+            if tween.playback_state() == PlaybackState::Completed {
+                tween.completed().fire(&PlaybackState::Completed);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+    use rnr_core::types::{CFrame, Color3, UDim2};
+
+    fn target() -> Rc<RefCell<Instance>> {
+        let target = Instance::new();
+        {
+            let mut target = target.borrow_mut();
+            target.set_property("Transparency", 0.0);
+            target.set_property("Position", Vec3::ZERO);
+            target.set_property("CFrame", CFrame::IDENTITY);
+            target.set_property("Color", Color3::new(0.0, 0.0, 0.0));
+            target.set_property("Size", UDim2::from_offset(0, 0));
+            target.set_property("Name", "Door");
+        }
+        target
+    }
+
+    fn linear(time: f64) -> TweenInfo {
+        TweenInfo::new(time, EasingStyle::Linear, EasingDirection::In)
+    }
+
+    #[test]
+    fn test_easing() {
+        for style in [EasingStyle::Linear, EasingStyle::Sine, EasingStyle::Back, EasingStyle::Quad, EasingStyle::Bounce, EasingStyle::Elastic, EasingStyle::Exponential, EasingStyle::Circular] {
+            for direction in [EasingDirection::In, EasingDirection::Out, EasingDirection::InOut] {
+                assert!(ease(style, direction, 0.0).abs() < 1e-3, "{:?} {:?}", style, direction);
+                assert!((ease(style, direction, 1.0) - 1.0).abs() < 1e-3, "{:?} {:?}", style, direction);
+            }
+        }
+
+        assert_eq!(ease(EasingStyle::Quad, EasingDirection::In, 0.5), 0.25);
+        assert_eq!(ease(EasingStyle::Quad, EasingDirection::Out, 0.5), 0.75);
+        assert_eq!(ease(EasingStyle::Quad, EasingDirection::InOut, 0.5), 0.5);
+    }
+
+    #[test]
+    fn test_create_validates_goals() {
+        let service = TweenService::new();
+        let target = target();
+
+        let missing = TweenService::create(&service, &target, linear(1.0), vec![("Missing", Variant::Float(1.0))]);
+        assert_eq!(missing.err(), Some(TweenError::UnknownProperty("Missing".to_string())));
+
+        let string = TweenService::create(&service, &target, linear(1.0), vec![("Name", "Open".into())]);
+        assert_eq!(string.err(), Some(TweenError::UnsupportedType("Name".to_string())));
+
+        let mismatch = TweenService::create(&service, &target, linear(1.0), vec![("Position", Variant::Float(1.0))]);
+        assert_eq!(mismatch.err(), Some(TweenError::TypeMismatch("Position".to_string())));
+    }
+
+    #[test]
+    fn test_tween_interpolates_properties() {
+        let service = TweenService::new();
+        let target = target();
+        let tween = TweenService::create(&service, &target, linear(1.0), vec![
+            ("Transparency", Variant::Int(1)),
+            ("Position", Vec3::new(10.0, 0.0, 0.0).into()),
+            ("Color", Color3::new(1.0, 1.0, 1.0).into()),
+            ("Size", UDim2::from_offset(100, 50).into()),
+        ]).unwrap();
+
+        let completed = Rc::new(RefCell::new(Vec::new()));
+        let completed_clone = completed.clone();
+        tween.completed().connect(move |state| completed_clone.borrow_mut().push(*state));
+
+        tween.play();
+        TweenService::update(&service, 0.5);
+        assert_eq!(tween.playback_state(), PlaybackState::Playing);
+        assert_eq!(target.borrow().get_property("Transparency"), Some(&Variant::Float(0.5)));
+        assert_eq!(target.borrow().get_property("Position"), Some(&Variant::Vector3(Vec3::new(5.0, 0.0, 0.0))));
+        assert_eq!(target.borrow().get_property("Size"), Some(&Variant::UDim2(UDim2::from_offset(50, 25))));
+
+        TweenService::update(&service, 0.5);
+        assert_eq!(tween.playback_state(), PlaybackState::Completed);
+        assert_eq!(target.borrow().get_property("Color"), Some(&Variant::Color3(Color3::new(1.0, 1.0, 1.0))));
+        assert_eq!(*completed.borrow(), vec![PlaybackState::Completed]);
+        assert_eq!(service.borrow().num_active_tweens(), 0);
+    }
+
+    #[test]
+    fn test_pause_and_cancel() {
+        let service = TweenService::new();
+        let target = target();
+        let tween = TweenService::create(&service, &target, linear(1.0), vec![("Transparency", Variant::Float(1.0))]).unwrap();
+
+        tween.play();
+        TweenService::update(&service, 0.25);
+        tween.pause();
+        TweenService::update(&service, 10.0);
+        assert_eq!(target.borrow().get_property("Transparency"), Some(&Variant::Float(0.25)));
+
+        tween.play();
+        TweenService::update(&service, 0.25);
+        assert_eq!(target.borrow().get_property("Transparency"), Some(&Variant::Float(0.5)));
+
+        let cancelled = Rc::new(RefCell::new(None));
+        let cancelled_clone = cancelled.clone();
+        tween.completed().connect(move |state| *cancelled_clone.borrow_mut() = Some(*state));
+        tween.cancel();
+        TweenService::update(&service, 1.0);
+
+        assert_eq!(*cancelled.borrow(), Some(PlaybackState::Cancelled));
+        assert_eq!(target.borrow().get_property("Transparency"), Some(&Variant::Float(0.5)));
+        assert_eq!(service.borrow().num_active_tweens(), 0);
+    }
+
+    #[test]
+    fn test_delay_repeat_and_reverse() {
+        let service = TweenService::new();
+        let target = target();
+        let info = TweenInfo {
+            repeat_count: 1,
+            reverses: true,
+            delay_time: 1.0,
+            ..linear(1.0)
+        };
+        let tween = TweenService::create(&service, &target, info, vec![("Transparency", Variant::Float(1.0))]).unwrap();
+        let transparency = || target.borrow().get_property("Transparency").and_then(Variant::as_float).unwrap();
+
+        tween.play();
+        assert_eq!(tween.playback_state(), PlaybackState::Delayed);
+
+        // Each playthrough: 1s delay, 1s towards the goal, 1s back
+        let mut samples = Vec::new();
+        for _ in 0..12 {
+            TweenService::update(&service, 0.5);
+            samples.push(transparency());
+        }
+        assert_eq!(samples, vec![0.0, 0.0, 0.5, 1.0, 0.5, 0.0, 0.0, 0.0, 0.5, 1.0, 0.5, 0.0]);
+        assert_eq!(tween.playback_state(), PlaybackState::Completed);
+    }
+
+    #[test]
+    fn test_cframe_tween_and_replay() {
+        let service = TweenService::new();
+        let target = target();
+        let goal = CFrame::new(Vec3::new(0.0, 10.0, 0.0));
+        let tween = TweenService::create(&service, &target, linear(2.0), vec![("CFrame", goal.into())]).unwrap();
+
+        tween.play();
+        TweenService::update(&service, 1.0);
+        let cframe = target.borrow().get_property("CFrame").and_then(Variant::as_cframe).unwrap();
+        assert_eq!(cframe.position, Vec3::new(0.0, 5.0, 0.0));
+
+        TweenService::update(&service, 1.0);
+        assert_eq!(tween.playback_state(), PlaybackState::Completed);
+
+        // Replaying starts from the current value, which is already the goal
+        tween.play();
+        TweenService::update(&service, 1.0);
+        let cframe = target.borrow().get_property("CFrame").and_then(Variant::as_cframe).unwrap();
+        assert_eq!(cframe.position, Vec3::new(0.0, 10.0, 0.0));
+    }
+}
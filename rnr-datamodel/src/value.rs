@@ -0,0 +1,78 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use rnr_core::instance::Instance;
+use rnr_core::variant::Variant;
+
+/// Typed view over an IntValue or StringValue, an instance that holds a
+/// single Value for scripts to share
+#[derive(Debug, Clone)]
+pub struct ValueObject {
+    instance: Rc<RefCell<Instance>>,
+}
+
+impl ValueObject {
+    fn new(class_name: &str, name: &str, value: Variant) -> Self {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name(name);
+            instance.set_class_name(class_name);
+            instance.set_property("Value", value);
+        }
+
+        Self { instance }
+    }
+
+    /// Create a new IntValue
+    pub fn new_int(name: &str, value: i64) -> Self {
+        Self::new("IntValue", name, Variant::Int(value))
+    }
+
+    /// Create a new StringValue
+    pub fn new_string(name: &str, value: &str) -> Self {
+        Self::new("StringValue", name, Variant::String(value.to_string()))
+    }
+
+    /// View an existing instance as a value object, if it is one
+    pub fn from_instance(instance: &Rc<RefCell<Instance>>) -> Option<Self> {
+        if instance.borrow().is_a("ValueBase") {
+            Some(Self { instance: instance.clone() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the value held
+    pub fn value(&self) -> Option<Variant> {
+        self.instance.borrow().get_property("Value").cloned()
+    }
+
+    /// Set the value held
+    pub fn set_value(&self, value: impl Into<Variant>) {
+        self.instance.borrow_mut().set_property("Value", value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_objects() {
+        let kills = ValueObject::new_int("Kills", 3);
+        assert_eq!(kills.instance().borrow().class_name(), "IntValue");
+        assert_eq!(kills.value().as_ref().and_then(Variant::as_int), Some(3));
+        kills.set_value(4i64);
+        assert_eq!(kills.value().as_ref().and_then(Variant::as_int), Some(4));
+
+        let rank = ValueObject::new_string("Rank", "Noob");
+        assert_eq!(rank.value().as_ref().and_then(Variant::as_str), Some("Noob"));
+        assert!(ValueObject::from_instance(rank.instance()).is_some());
+        assert!(ValueObject::from_instance(&Instance::new()).is_none());
+    }
+}
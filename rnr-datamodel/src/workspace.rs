@@ -0,0 +1,240 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use rnr_core::instance::Instance;
+use rnr_core::variant::Variant;
+use crate::camera::{Camera, CameraType};
+use crate::part::BasePart;
+use crate::terrain::Terrain;
+
+/// Default Workspace.Gravity, in studs/s² (Roblox's 9.81 m/s² at 20 studs
+/// to the meter)
+pub const DEFAULT_GRAVITY: f64 = 196.2;
+
+/// Default Workspace.FallenPartsDestroyHeight
+pub const DEFAULT_FALLEN_PARTS_DESTROY_HEIGHT: f64 = -500.0;
+
+/// Workspace service - holds every object that exists in the 3D world
+pub struct Workspace {
+    instance: Rc<RefCell<Instance>>,
+    terrain: Rc<RefCell<Terrain>>,
+}
+
+impl Workspace {
+    /// Create a new workspace with empty Terrain and a Custom camera as its
+    /// CurrentCamera
+    pub fn new() -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        {
+            let mut instance = instance.borrow_mut();
+            instance.set_name("Workspace");
+            instance.set_class_name("Workspace");
+            instance.set_property("Gravity", DEFAULT_GRAVITY);
+            instance.set_property("FallenPartsDestroyHeight", DEFAULT_FALLEN_PARTS_DESTROY_HEIGHT);
+            instance.set_property("DistributedGameTime", 0.0);
+            instance.set_property("FilteringEnabled", false);
+            instance.set_property("StreamingEnabled", false);
+        }
+
+        let terrain = Terrain::new();
+        Instance::set_parent(terrain.borrow().instance(), Some(instance.clone()));
+
+        let workspace = Self { instance, terrain };
+        let camera = Camera::new();
+        camera.set_camera_type(CameraType::Custom);
+        workspace.set_current_camera(Some(&camera));
+
+        Rc::new(RefCell::new(workspace))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Get the workspace's Terrain (Workspace.Terrain)
+    pub fn terrain(&self) -> &Rc<RefCell<Terrain>> {
+        &self.terrain
+    }
+
+    /// Get the camera the world is viewed through (Workspace.CurrentCamera)
+    pub fn current_camera(&self) -> Option<Camera> {
+        current_camera(&self.instance)
+    }
+
+    /// Set the camera the world is viewed through.
+    ///
+    /// Cameras that aren't in the tree yet are parented to the workspace.
+    pub fn set_current_camera(&self, camera: Option<&Camera>) {
+        if let Some(camera) = camera.filter(|camera| camera.instance().borrow().parent().is_none()) {
+            Instance::set_parent(camera.instance(), Some(self.instance.clone()));
+        }
+
+        self.instance.borrow_mut().set_property("CurrentCamera", Variant::instance_ref(camera.map(Camera::instance)));
+    }
+
+    /// Get the downward acceleration of parts, in studs/s²
+    pub fn gravity(&self) -> f64 {
+        self.float_property("Gravity", DEFAULT_GRAVITY)
+    }
+
+    /// Set the downward acceleration of parts, in studs/s²
+    pub fn set_gravity(&self, gravity: f64) {
+        self.instance.borrow_mut().set_property("Gravity", gravity);
+    }
+
+    /// Get the height below which falling parts are destroyed
+    pub fn fallen_parts_destroy_height(&self) -> f64 {
+        self.float_property("FallenPartsDestroyHeight", DEFAULT_FALLEN_PARTS_DESTROY_HEIGHT)
+    }
+
+    /// Set the height below which falling parts are destroyed
+    pub fn set_fallen_parts_destroy_height(&self, height: f64) {
+        self.instance.borrow_mut().set_property("FallenPartsDestroyHeight", height);
+    }
+
+    /// Get how long the game has been running, as kept by the server
+    pub fn distributed_game_time(&self) -> f64 {
+        self.float_property("DistributedGameTime", 0.0)
+    }
+
+    /// Set how long the game has been running
+    pub fn set_distributed_game_time(&self, time: f64) {
+        self.instance.borrow_mut().set_property("DistributedGameTime", time);
+    }
+
+    /// Check if changes made by clients are kept from the server
+    pub fn filtering_enabled(&self) -> bool {
+        filtering_enabled(&self.instance)
+    }
+
+    /// Set whether changes made by clients are kept from the server
+    pub fn set_filtering_enabled(&self, enabled: bool) {
+        self.instance.borrow_mut().set_property("FilteringEnabled", enabled);
+    }
+
+    /// Check if clients are only sent the parts of the world near them
+    pub fn streaming_enabled(&self) -> bool {
+        streaming_enabled(&self.instance)
+    }
+
+    /// Set whether clients are only sent the parts of the world near them
+    pub fn set_streaming_enabled(&self, enabled: bool) {
+        self.instance.borrow_mut().set_property("StreamingEnabled", enabled);
+    }
+
+    /// Destroy unanchored parts that fell below FallenPartsDestroyHeight.
+    /// Characters losing a part die. Returns how many parts were destroyed.
+    pub fn destroy_fallen_parts(&self) -> usize {
+        let height = self.fallen_parts_destroy_height() as f32;
+        let fallen: Vec<BasePart> = self.instance.borrow().get_descendants().iter()
+            .filter_map(BasePart::from_instance)
+            .filter(|part| !part.anchored() && part.position().y < height)
+            .collect();
+
+        for part in &fallen {
+            let humanoid = part.instance().borrow().parent().and_then(|model| model.borrow().find_first_child_of_type("Humanoid"));
+            if let Some(humanoid) = humanoid {
+                humanoid.borrow_mut().set_property("Health", 0.0);
+            }
+            Instance::destroy(part.instance());
+        }
+        fallen.len()
+    }
+
+    fn float_property(&self, name: &str, default: f64) -> f64 {
+        self.instance.borrow().get_property(name).and_then(Variant::as_float).unwrap_or(default)
+    }
+}
+
+/// Check if a workspace instance has FilteringEnabled set
+pub fn filtering_enabled(workspace: &Rc<RefCell<Instance>>) -> bool {
+    workspace.borrow().get_property("FilteringEnabled").and_then(Variant::as_bool).unwrap_or(false)
+}
+
+/// Check if a workspace instance has StreamingEnabled set
+pub fn streaming_enabled(workspace: &Rc<RefCell<Instance>>) -> bool {
+    workspace.borrow().get_property("StreamingEnabled").and_then(Variant::as_bool).unwrap_or(false)
+}
+
+/// Get the CurrentCamera of a workspace instance
+pub fn current_camera(workspace: &Rc<RefCell<Instance>>) -> Option<Camera> {
+    let camera = workspace.borrow().get_property("CurrentCamera").and_then(Variant::as_instance)?;
+    Camera::from_instance(&camera)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+    use crate::part::create_model;
+
+    #[test]
+    fn test_workspace_creation() {
+        let workspace = Workspace::new();
+
+        assert_eq!(workspace.borrow().instance().borrow().name(), "Workspace");
+        assert!(workspace.borrow().instance().borrow().is_a("Model"));
+        assert!(workspace.borrow().instance().borrow().find_first_child("Terrain").is_some());
+    }
+
+    #[test]
+    fn test_current_camera() {
+        let workspace = Workspace::new();
+        let camera = workspace.borrow().current_camera().unwrap();
+
+        assert_eq!(camera.camera_type(), CameraType::Custom);
+        assert!(workspace.borrow().instance().borrow().contains(camera.instance()));
+
+        let other = Camera::new();
+        workspace.borrow().set_current_camera(Some(&other));
+        assert!(Rc::ptr_eq(workspace.borrow().current_camera().unwrap().instance(), other.instance()));
+        assert!(workspace.borrow().instance().borrow().contains(other.instance()));
+
+        workspace.borrow().set_current_camera(None);
+        assert!(workspace.borrow().current_camera().is_none());
+    }
+
+    #[test]
+    fn test_simulation_settings() {
+        let workspace = Workspace::new();
+        let workspace = workspace.borrow();
+        assert_eq!(workspace.gravity(), DEFAULT_GRAVITY);
+        assert_eq!(workspace.fallen_parts_destroy_height(), -500.0);
+        assert!(!workspace.filtering_enabled());
+        workspace.set_filtering_enabled(true);
+        workspace.set_streaming_enabled(true);
+        assert!(filtering_enabled(workspace.instance()));
+        assert!(streaming_enabled(workspace.instance()));
+    }
+
+    #[test]
+    fn test_fallen_parts_are_destroyed() {
+        let workspace = Workspace::new();
+        let workspace = workspace.borrow();
+        workspace.set_fallen_parts_destroy_height(-100.0);
+        let falling = BasePart::new("Part");
+        falling.set_position(Vec3::new(0.0, -150.0, 0.0));
+        Instance::set_parent(falling.instance(), Some(workspace.instance().clone()));
+        let anchored = BasePart::new("Part");
+        anchored.set_anchored(true);
+        anchored.set_position(Vec3::new(0.0, -150.0, 0.0));
+        Instance::set_parent(anchored.instance(), Some(workspace.instance().clone()));
+
+        let character = create_model("Player1");
+        let humanoid = Instance::new();
+        humanoid.borrow_mut().set_name("Humanoid");
+        humanoid.borrow_mut().set_class_name("Humanoid");
+        humanoid.borrow_mut().set_property("Health", 100.0);
+        Instance::set_parent(&humanoid, Some(character.clone()));
+        let torso = BasePart::new("Part");
+        torso.set_position(Vec3::new(0.0, -101.0, 0.0));
+        Instance::set_parent(torso.instance(), Some(character.clone()));
+        Instance::set_parent(&character, Some(workspace.instance().clone()));
+
+        assert_eq!(workspace.destroy_fallen_parts(), 2);
+        assert!(falling.instance().borrow().is_destroyed());
+        assert!(torso.instance().borrow().is_destroyed());
+        assert!(!anchored.instance().borrow().is_destroyed());
+        assert_eq!(humanoid.borrow().get_property("Health").and_then(Variant::as_float), Some(0.0));
+    }
+}
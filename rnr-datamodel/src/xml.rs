@@ -0,0 +1,586 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+use glam::{Mat3, Vec2, Vec3};
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use rnr_core::instance::Instance;
+use rnr_core::types::{BrickColor, CFrame, Color3, UDim, UDim2};
+use rnr_core::variant::Variant;
+use crate::datamodel::DataModel;
+
+/// Roblox XML file errors
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum XmlError {
+    #[error("Failed to read XML file: {0}")]
+    Io(String),
+    #[error("Malformed XML at byte {position}: {message}")]
+    Parse { position: u64, message: String },
+    #[error("Not a Roblox XML file: the root element is <{0}>")]
+    NotRoblox(String),
+}
+
+/// A property value read from a Roblox XML file
+#[derive(Debug, Clone, PartialEq)]
+pub enum XmlValue {
+    Value(Variant),
+    /// A reference to the Item with this referent, or None for nil. Refs
+    /// are resolved once every Item has been created.
+    Ref(Option<String>),
+    /// A value whose type tag the engine can't read, or that didn't parse
+    Unreadable(String),
+}
+
+/// A property of an Item
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmlProperty {
+    pub name: String,
+    pub value: XmlValue,
+}
+
+/// An `<Item>` element: one instance and its descendants
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct XmlItem {
+    pub class_name: String,
+    pub referent: Option<String>,
+    pub properties: Vec<XmlProperty>,
+    pub children: Vec<XmlItem>,
+}
+
+impl XmlItem {
+    /// Count the items in this subtree, including this one
+    pub fn count(&self) -> usize {
+        1 + self.children.iter().map(XmlItem::count).sum::<usize>()
+    }
+}
+
+/// Property names older files use for properties the engine knows by
+/// another name
+const PROPERTY_ALIASES: [(&str, &str); 3] = [("size", "Size"), ("shape", "Shape"), ("formFactorRaw", "FormFactor")];
+
+/// Get the name the engine knows a file's property by
+pub fn engine_property_name(name: &str) -> &str {
+    PROPERTY_ALIASES.iter().find(|(file, _)| *file == name).map_or(name, |(_, engine)| engine)
+}
+
+/// Convert a value read from a file to the type of the property's default,
+/// for types the XML format writes as something else: BrickColors are ints,
+/// older files write some enums as ints, and numbers may be either ints or
+/// floats.
+pub fn coerce_property(value: Variant, default: Option<&Variant>) -> Variant {
+    match (value, default) {
+        (Variant::Int(number), Some(Variant::BrickColor(_))) => Variant::BrickColor(BrickColor::new(number as u16)),
+        (Variant::Int(number), Some(Variant::Float(_))) => Variant::Float(number as f64),
+        (Variant::Int(number), Some(Variant::Enum(_))) => Variant::Enum(number),
+        (Variant::Float(number), Some(Variant::Int(_))) => Variant::Int(number as i64),
+        (value, _) => value,
+    }
+}
+
+/// Read the Items of a Roblox XML place or model
+pub fn read_xml_file(path: impl AsRef<Path>) -> Result<Vec<XmlItem>, XmlError> {
+    let text = fs::read_to_string(path).map_err(|e| XmlError::Io(e.to_string()))?;
+    parse_xml(&text)
+}
+
+/// The property being read, and the text of its fields so far. Fields
+/// nested inside others, like a Font's `<Family><url>`, are keyed by their
+/// path, as in `Family/url`.
+struct PendingProperty {
+    type_name: String,
+    name: String,
+    text: String,
+    fields: Vec<(String, String)>,
+    /// The elements open inside the property
+    open: Vec<String>,
+}
+
+/// Parse the Items of a Roblox XML place or model (.rbxlx/.rbxmx).
+///
+/// Elements other than Items and their Properties, like `<Meta>` and
+/// `<External>`, are skipped.
+pub fn parse_xml(text: &str) -> Result<Vec<XmlItem>, XmlError> {
+    let mut reader = Reader::from_str(text);
+    let mut roots = Vec::new();
+    let mut items: Vec<XmlItem> = Vec::new();
+    let mut in_properties = false;
+    let mut property: Option<PendingProperty> = None;
+    let mut found_root = false;
+
+    loop {
+        let position = reader.buffer_position();
+        let error = |message: String| XmlError::Parse { position, message };
+        let event = reader.read_event().map_err(|e| error(e.to_string()))?;
+        match event {
+            Event::Start(element) => {
+                let name = element_name(&element);
+                if !found_root {
+                    if name != "roblox" {
+                        return Err(XmlError::NotRoblox(name));
+                    }
+                    found_root = true;
+                } else if let Some(property) = &mut property {
+                    property.open.push(name);
+                } else if in_properties {
+                    property = Some(start_property(&element, name).map_err(error)?);
+                } else if name == "Item" {
+                    items.push(start_item(&element).map_err(error)?);
+                } else if name == "Properties" && !items.is_empty() {
+                    in_properties = true;
+                } else {
+                    reader.read_to_end(element.name()).map_err(|e| error(e.to_string()))?;
+                }
+            }
+            Event::Empty(element) => {
+                let name = element_name(&element);
+                if !found_root {
+                    return Err(XmlError::NotRoblox(name));
+                } else if let Some(property) = &mut property {
+                    property.open.push(name);
+                    property.fields.push((property.open.join("/"), String::new()));
+                    property.open.pop();
+                } else if in_properties {
+                    let property = start_property(&element, name).map_err(error)?;
+                    push_property(&mut items, property);
+                } else if name == "Item" {
+                    let item = start_item(&element).map_err(error)?;
+                    finish_item(&mut items, &mut roots, item);
+                }
+            }
+            Event::Text(text) => {
+                if let Some(property) = &mut property {
+                    let text = text.unescape().map_err(|e| error(e.to_string()))?;
+                    append_text(property, &text);
+                }
+            }
+            Event::CData(data) => {
+                if let Some(property) = &mut property {
+                    let text = data.decode().map_err(|e| error(e.to_string()))?;
+                    append_text(property, &text);
+                }
+            }
+            Event::End(element) => {
+                let name = String::from_utf8_lossy(element.name().as_ref()).into_owned();
+                if let Some(pending) = &mut property {
+                    if pending.open.pop().is_none() {
+                        push_property(&mut items, property.take().unwrap());
+                    }
+                } else if in_properties {
+                    in_properties = false;
+                } else if name == "Item" {
+                    let item = items.pop().ok_or_else(|| error("unmatched </Item>".to_string()))?;
+                    finish_item(&mut items, &mut roots, item);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !found_root {
+        return Err(XmlError::NotRoblox(String::new()));
+    }
+    Ok(roots)
+}
+
+fn element_name(element: &BytesStart) -> String {
+    String::from_utf8_lossy(element.name().as_ref()).into_owned()
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, String> {
+    let attribute = element.try_get_attribute(name).map_err(|e| e.to_string())?;
+    attribute.map(|attribute| attribute.unescape_value().map(|value| value.into_owned()).map_err(|e| e.to_string())).transpose()
+}
+
+fn start_item(element: &BytesStart) -> Result<XmlItem, String> {
+    let class_name = attribute(element, "class")?.ok_or("<Item> without a class")?;
+    let referent = attribute(element, "referent")?.filter(|referent| !referent.is_empty() && referent != "null");
+    Ok(XmlItem { class_name, referent, ..Default::default() })
+}
+
+fn start_property(element: &BytesStart, type_name: String) -> Result<PendingProperty, String> {
+    let name = attribute(element, "name")?.ok_or_else(|| format!("<{}> property without a name", type_name))?;
+    Ok(PendingProperty { type_name, name, text: String::new(), fields: Vec::new(), open: Vec::new() })
+}
+
+fn append_text(property: &mut PendingProperty, text: &str) {
+    if property.open.is_empty() {
+        property.text.push_str(text);
+    } else {
+        property.fields.push((property.open.join("/"), text.to_string()));
+    }
+}
+
+fn push_property(items: &mut [XmlItem], property: PendingProperty) {
+    if let Some(item) = items.last_mut() {
+        let value = read_value(&property);
+        item.properties.push(XmlProperty { name: property.name, value });
+    }
+}
+
+fn finish_item(items: &mut [XmlItem], roots: &mut Vec<XmlItem>, item: XmlItem) {
+    match items.last_mut() {
+        Some(parent) => parent.children.push(item),
+        None => roots.push(item),
+    }
+}
+
+/// Read a property's value from its type tag, text and fields
+fn read_value(property: &PendingProperty) -> XmlValue {
+    let text = property.text.trim();
+    let field = |name: &str| property.fields.iter().find(|(field, _)| field == name).map(|(_, text)| text.trim());
+    let number = |name: &str| field(name).and_then(|text| text.parse::<f32>().ok());
+    if property.type_name == "Ref" {
+        return XmlValue::Ref(Some(text.to_string()).filter(|referent| !referent.is_empty() && referent != "null"));
+    }
+
+    let value = (|| match property.type_name.as_str() {
+        "bool" => Some(Variant::Bool(text == "true")),
+        "int" | "int64" => text.parse().ok().map(Variant::Int),
+        "token" => text.parse().ok().map(Variant::Enum),
+        "float" | "double" => text.parse().ok().map(Variant::Float),
+        "string" | "ProtectedString" => Some(Variant::String(property.text.clone())),
+        "Content" => Some(Variant::String(field("url").unwrap_or(text).to_string())),
+        "BrickColor" => text.parse().ok().map(|number| Variant::BrickColor(BrickColor::new(number))),
+        "Vector2" => Some(Variant::Vector2(Vec2::new(number("X")?, number("Y")?))),
+        "Vector3" => Some(Variant::Vector3(Vec3::new(number("X")?, number("Y")?, number("Z")?))),
+        "CoordinateFrame" | "CFrame" => read_cframe(&number),
+        "Color3" => match field("R") {
+            Some(_) => Some(Variant::Color3(Color3 { r: number("R")?, g: number("G")?, b: number("B")? })),
+            None => text.parse().ok().map(unpack_color),
+        },
+        "Color3uint8" => text.parse().ok().map(unpack_color),
+        "UDim" => Some(Variant::UDim(UDim::new(number("S")?, number("O")? as i32))),
+        "UDim2" => Some(Variant::UDim2(UDim2::new(number("XS")?, number("XO")? as i32, number("YS")?, number("YO")? as i32))),
+        _ => None,
+    })();
+    value.map_or_else(|| XmlValue::Unreadable(property.type_name.clone()), XmlValue::Value)
+}
+
+fn read_cframe(number: &impl Fn(&str) -> Option<f32>) -> Option<Variant> {
+    let position = Vec3::new(number("X")?, number("Y")?, number("Z")?);
+    // R00 R01 R02 is the first row; the columns are the right, up and back vectors
+    let row = |row: u8| -> Option<Vec3> {
+        Some(Vec3::new(number(&format!("R{row}0"))?, number(&format!("R{row}1"))?, number(&format!("R{row}2"))?))
+    };
+    let rotation = Mat3::from_cols(row(0)?, row(1)?, row(2)?).transpose();
+    Some(Variant::CFrame(CFrame { position, rotation }))
+}
+
+/// Unpack a Color3uint8, written as 0xAARRGGBB
+fn unpack_color(packed: u32) -> Variant {
+    Variant::Color3(Color3::from_rgb((packed >> 16) as u8, (packed >> 8) as u8, packed as u8))
+}
+
+/// The root element files are written with
+const ROBLOX_OPEN_TAG: &str = r#"<roblox xmlns:xmime="http://www.w3.org/2005/05/xmlmime" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:noNamespaceSchemaLocation="http://www.roblox.com/roblox.xsd" version="4">"#;
+
+/// Write instances and their descendants as a Roblox XML model (.rbxmx).
+///
+/// Instances that aren't Archivable are left out along with their
+/// descendants, and Refs to anything not written are nil. The output is
+/// deterministic: referents are numbered in document order and each
+/// Item's Name comes first, then its properties sorted by name.
+pub fn write_xml(instances: &[Rc<RefCell<Instance>>]) -> String {
+    let instances: Vec<_> = instances.iter().filter(|instance| instance.borrow().archivable()).cloned().collect();
+    let mut referents = HashMap::new();
+    for instance in &instances {
+        number_referents(instance, &mut referents);
+    }
+
+    let mut xml = String::new();
+    xml.push_str(ROBLOX_OPEN_TAG);
+    xml.push_str("\n\t<External>null</External>\n\t<External>nil</External>\n");
+    for instance in &instances {
+        write_item(&mut xml, instance, &referents, 1);
+    }
+    xml.push_str("</roblox>\n");
+    xml
+}
+
+/// Write a data model's services and everything in them as a Roblox XML
+/// place (.rbxlx)
+pub fn write_place(datamodel: &DataModel) -> String {
+    write_xml(datamodel.instance().borrow().children())
+}
+
+/// Write instances to a Roblox XML file. See `write_xml`.
+pub fn write_xml_file(path: impl AsRef<Path>, instances: &[Rc<RefCell<Instance>>]) -> Result<(), XmlError> {
+    fs::write(path, write_xml(instances)).map_err(|e| XmlError::Io(e.to_string()))
+}
+
+fn number_referents(instance: &Rc<RefCell<Instance>>, referents: &mut HashMap<*const RefCell<Instance>, String>) {
+    referents.insert(Rc::as_ptr(instance), format!("RBX{}", referents.len()));
+    for child in instance.borrow().children().iter().filter(|child| child.borrow().archivable()) {
+        number_referents(child, referents);
+    }
+}
+
+fn write_item(xml: &mut String, instance: &Rc<RefCell<Instance>>, referents: &HashMap<*const RefCell<Instance>, String>, depth: usize) {
+    let indent = "\t".repeat(depth);
+    let referent = &referents[&Rc::as_ptr(instance)];
+    let instance = instance.borrow();
+    let _ = writeln!(xml, "{indent}<Item class=\"{}\" referent=\"{referent}\">", escape(instance.class_name()));
+    let _ = writeln!(xml, "{indent}\t<Properties>");
+    write_property(xml, &indent, "Name", &Variant::String(instance.name().to_string()), referents);
+    for (name, value) in instance.properties() {
+        write_property(xml, &indent, name, value, referents);
+    }
+    let _ = writeln!(xml, "{indent}\t</Properties>");
+    for child in instance.children().iter().filter(|child| child.borrow().archivable()) {
+        write_item(xml, child, referents, depth + 1);
+    }
+    let _ = writeln!(xml, "{indent}</Item>");
+}
+
+/// Write a property with the type tag for its value
+fn write_property(xml: &mut String, indent: &str, name: &str, value: &Variant, referents: &HashMap<*const RefCell<Instance>, String>) {
+    let (type_name, text) = match value {
+        Variant::Bool(value) => ("bool", value.to_string()),
+        Variant::Int(value) => ("int", value.to_string()),
+        Variant::Enum(value) => ("token", value.to_string()),
+        Variant::Float(value) => ("float", format_number(*value)),
+        Variant::String(text) if name == "Source" => ("ProtectedString", escape(text.as_str()).into_owned()),
+        Variant::String(text) => ("string", escape(text.as_str()).into_owned()),
+        Variant::Vector2(vector) => ("Vector2", fields(&[("X", vector.x), ("Y", vector.y)])),
+        Variant::Vector3(vector) => ("Vector3", fields(&[("X", vector.x), ("Y", vector.y), ("Z", vector.z)])),
+        Variant::CFrame(cframe) => {
+            // R00 R01 R02 is the first row; the columns are the right, up and back vectors
+            let [r00, r01, r02, r10, r11, r12, r20, r21, r22] = cframe.rotation.transpose().to_cols_array();
+            let position = cframe.position;
+            ("CoordinateFrame", fields(&[
+                ("X", position.x), ("Y", position.y), ("Z", position.z),
+                ("R00", r00), ("R01", r01), ("R02", r02),
+                ("R10", r10), ("R11", r11), ("R12", r12),
+                ("R20", r20), ("R21", r21), ("R22", r22),
+            ]))
+        }
+        Variant::BrickColor(color) => ("BrickColor", color.number().to_string()),
+        Variant::Color3(color) => ("Color3", fields(&[("R", color.r), ("G", color.g), ("B", color.b)])),
+        Variant::UDim(udim) => ("UDim", format!("<S>{}</S><O>{}</O>", format_f32(udim.scale), udim.offset)),
+        Variant::UDim2(udim2) => ("UDim2", format!(
+            "<XS>{}</XS><XO>{}</XO><YS>{}</YS><YO>{}</YO>",
+            format_f32(udim2.x.scale), udim2.x.offset, format_f32(udim2.y.scale), udim2.y.offset
+        )),
+        Variant::Instance(_) => {
+            let referent = value.as_instance().and_then(|instance| referents.get(&Rc::as_ptr(&instance)).cloned());
+            ("Ref", referent.unwrap_or_else(|| "null".to_string()))
+        }
+    };
+    let _ = writeln!(xml, "{indent}\t\t<{type_name} name=\"{}\">{text}</{type_name}>", escape(name));
+}
+
+/// Write the fields of a value made of numbers
+fn fields(fields: &[(&str, f32)]) -> String {
+    fields.iter().map(|(field, value)| format!("<{field}>{}</{field}>", format_f32(*value))).collect()
+}
+
+/// Format a number so it reads back exactly, with infinities and NaN
+/// written the way Roblox writes them
+fn format_number(value: f64) -> String {
+    match value {
+        f64::INFINITY => "INF".to_string(),
+        f64::NEG_INFINITY => "-INF".to_string(),
+        value if value.is_nan() => "NAN".to_string(),
+        value => value.to_string(),
+    }
+}
+
+fn format_f32(value: f32) -> String {
+    if value.is_finite() { value.to_string() } else { format_number(value as f64) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLACE: &str = r#"<roblox xmlns:xmime="http://www.w3.org/2005/05/xmlmime" version="4">
+  <Meta name="ExplicitAutoJoints">true</Meta>
+  <External>null</External>
+  <Item class="Workspace" referent="RBX0">
+    <Properties>
+      <string name="Name">Workspace</string>
+      <Ref name="CurrentCamera">RBX2</Ref>
+    </Properties>
+    <Item class="Part" referent="RBX1">
+      <Properties>
+        <string name="Name">Base &amp; Plate</string>
+        <bool name="Anchored">true</bool>
+        <int name="BrickColor">194</int>
+        <CoordinateFrame name="CFrame">
+          <X>1</X><Y>2</Y><Z>3</Z>
+          <R00>0</R00><R01>0</R01><R02>1</R02>
+          <R10>0</R10><R11>1</R11><R12>0</R12>
+          <R20>-1</R20><R21>0</R21><R22>0</R22>
+        </CoordinateFrame>
+        <Vector3 name="size"><X>512</X><Y>1.2</Y><Z>512</Z></Vector3>
+        <Color3uint8 name="Color3uint8">4294901760</Color3uint8>
+        <float name="Transparency">INF</float>
+        <BinaryString name="Tags"></BinaryString>
+        <Content name="TextureId"><url>rbxasset://textures/stud.png</url></Content>
+        <ProtectedString name="Source"><![CDATA[print("<hi>")]]></ProtectedString>
+        <UDim2 name="Position"><XS>0.5</XS><XO>-10</XO><YS>1</YS><YO>4</YO></UDim2>
+        <Ref name="Target">null</Ref>
+      </Properties>
+    </Item>
+  </Item>
+  <Item class="Lighting" referent="RBX3"><Properties/></Item>
+</roblox>"#;
+
+    #[test]
+    fn test_parse_place() {
+        let roots = parse_xml(PLACE).unwrap();
+        assert_eq!(roots.len(), 2);
+        assert_eq!(roots[0].count(), 2);
+        assert_eq!(roots[1].class_name, "Lighting");
+        assert_eq!(roots[0].properties[1].value, XmlValue::Ref(Some("RBX2".to_string())));
+
+        let part = &roots[0].children[0];
+        assert_eq!(part.referent.as_deref(), Some("RBX1"));
+        let value = |name: &str| part.properties.iter().find(|property| property.name == name).unwrap().value.clone();
+        assert_eq!(value("Name"), XmlValue::Value(Variant::String("Base & Plate".to_string())));
+        assert_eq!(value("Anchored"), XmlValue::Value(Variant::Bool(true)));
+        assert_eq!(coerce_property(Variant::Int(194), Some(&Variant::BrickColor(BrickColor::white()))), Variant::BrickColor(BrickColor::new(194)));
+        let XmlValue::Value(Variant::CFrame(cframe)) = value("CFrame") else { panic!() };
+        assert_eq!(cframe.position, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(cframe.right_vector(), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(value("size"), XmlValue::Value(Variant::Vector3(Vec3::new(512.0, 1.2, 512.0))));
+        assert_eq!(engine_property_name("size"), "Size");
+        assert_eq!(value("Color3uint8"), XmlValue::Value(Variant::Color3(Color3::from_rgb(255, 0, 0))));
+        assert_eq!(value("Transparency"), XmlValue::Value(Variant::Float(f64::INFINITY)));
+        assert_eq!(value("Tags"), XmlValue::Unreadable("BinaryString".to_string()));
+        assert_eq!(value("TextureId"), XmlValue::Value(Variant::String("rbxasset://textures/stud.png".to_string())));
+        assert_eq!(value("Source"), XmlValue::Value(Variant::String("print(\"<hi>\")".to_string())));
+        assert_eq!(value("Position"), XmlValue::Value(Variant::UDim2(UDim2::new(0.5, -10, 1.0, 4))));
+        assert_eq!(value("Target"), XmlValue::Ref(None));
+    }
+
+    #[test]
+    fn test_parse_nested_property_fields() {
+        let roots = parse_xml(r#"<roblox>
+  <Item class="TextLabel">
+    <Properties>
+      <Font name="FontFace">
+        <Family><url>rbxasset://fonts/families/SourceSansPro.json</url></Family>
+        <Weight>400</Weight>
+        <Style>Normal</Style>
+      </Font>
+      <string name="Name">Title</string>
+    </Properties>
+    <Item class="Frame"><Properties><bool name="Visible">false</bool></Properties></Item>
+  </Item>
+</roblox>"#).unwrap();
+
+        let label = &roots[0];
+        assert_eq!(label.properties, vec![
+            XmlProperty { name: "FontFace".to_string(), value: XmlValue::Unreadable("Font".to_string()) },
+            XmlProperty { name: "Name".to_string(), value: XmlValue::Value(Variant::String("Title".to_string())) },
+        ]);
+        assert_eq!(label.children.len(), 1);
+        assert_eq!(label.children[0].properties[0].value, XmlValue::Value(Variant::Bool(false)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_xml("<html></html>"), Err(XmlError::NotRoblox("html".to_string())));
+        assert!(matches!(parse_xml("<roblox><Item class=\"Part\"></roblox>"), Err(XmlError::Parse { .. })));
+        assert!(matches!(parse_xml("<roblox><Item></Item></roblox>"), Err(XmlError::Parse { .. })));
+        assert!(matches!(read_xml_file("/nonexistent/place.rbxlx"), Err(XmlError::Io(_))));
+    }
+
+    #[test]
+    fn test_write_round_trips_every_type() {
+        let model = Instance::new();
+        model.borrow_mut().set_class_name("Model");
+        model.borrow_mut().set_name("Tower <1> & \"co\"");
+        let part = Instance::new();
+        part.borrow_mut().set_class_name("Part");
+        let cframe = CFrame { position: Vec3::new(1.5, -2.0, 3.25), rotation: Mat3::from_rotation_y(0.5) };
+        let values = [
+            ("Anchored", Variant::Bool(true)),
+            ("Count", Variant::Int(-7)),
+            ("Transparency", Variant::Float(0.1)),
+            ("Height", Variant::Float(f64::NEG_INFINITY)),
+            ("Source", Variant::String("print(\"<hi>\")\n".to_string())),
+            ("Offset", Variant::Vector2(Vec2::new(0.25, -1.0))),
+            ("Size", Variant::Vector3(Vec3::new(4.0, 1.2, 2.0))),
+            ("CFrame", Variant::CFrame(cframe)),
+            ("BrickColor", Variant::BrickColor(BrickColor::new(194))),
+            ("Color", Variant::Color3(Color3 { r: 0.2, g: 0.4, b: 1.0 })),
+            ("Padding", Variant::UDim(UDim::new(0.5, 4))),
+            ("Position", Variant::UDim2(UDim2::new(0.5, -10, 1.0, 4))),
+        ];
+        for (name, value) in values.clone() {
+            part.borrow_mut().set_property(name, value);
+        }
+        part.borrow_mut().set_property("Target", Variant::instance_ref(Some(&model)));
+        Instance::set_parent(&part, Some(model.clone()));
+
+        let roots = parse_xml(&write_xml(std::slice::from_ref(&model))).unwrap();
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].referent.as_deref(), Some("RBX0"));
+        assert_eq!(roots[0].properties[0].value, XmlValue::Value(Variant::String("Tower <1> & \"co\"".to_string())));
+        let part = &roots[0].children[0];
+        let value = |name: &str| part.properties.iter().find(|property| property.name == name).unwrap().value.clone();
+        for (name, expected) in values {
+            assert_eq!(value(name), XmlValue::Value(expected), "{name}");
+        }
+        assert_eq!(value("Target"), XmlValue::Ref(Some("RBX0".to_string())));
+    }
+
+    #[test]
+    fn test_write_enums_as_tokens() {
+        let part = Instance::new();
+        part.borrow_mut().set_class_name("Part");
+        part.borrow_mut().set_property("Material", Variant::Enum(288));
+        part.borrow_mut().set_property("TopSurface", Variant::Enum(3));
+        part.borrow_mut().set_property("Count", Variant::Int(288));
+
+        let xml = write_xml(std::slice::from_ref(&part));
+        assert!(xml.contains("<token name=\"Material\">288</token>"));
+        assert!(xml.contains("<token name=\"TopSurface\">3</token>"));
+        assert!(xml.contains("<int name=\"Count\">288</int>"));
+        let roots = parse_xml(&xml).unwrap();
+        assert!(roots[0].properties.contains(&XmlProperty { name: "Material".to_string(), value: XmlValue::Value(Variant::Enum(288)) }));
+        assert!(roots[0].properties.contains(&XmlProperty { name: "Count".to_string(), value: XmlValue::Value(Variant::Int(288)) }));
+        assert_eq!(coerce_property(Variant::Int(3), Some(&Variant::Enum(0))), Variant::Enum(3));
+    }
+
+    #[test]
+    fn test_write_skips_unarchivable_and_is_deterministic() {
+        let datamodel = DataModel::new();
+        let workspace = Instance::new();
+        workspace.borrow_mut().set_class_name("Workspace");
+        let lighting = Instance::new();
+        lighting.borrow_mut().set_class_name("Lighting");
+        datamodel.borrow_mut().register_service("Workspace", workspace.clone());
+        datamodel.borrow_mut().register_service("Lighting", lighting);
+        let temporary = Instance::new();
+        temporary.borrow_mut().set_archivable(false);
+        Instance::set_parent(&Instance::new(), Some(temporary.clone()));
+        Instance::set_parent(&temporary, Some(workspace.clone()));
+        let part = Instance::new();
+        part.borrow_mut().set_property("Zebra", Variant::Int(1));
+        part.borrow_mut().set_property("Apple", Variant::instance_ref(Some(&temporary)));
+        Instance::set_parent(&part, Some(workspace));
+
+        let xml = write_place(&datamodel.borrow());
+        assert_eq!(xml, write_place(&datamodel.borrow()));
+        let roots = parse_xml(&xml).unwrap();
+        let referents: Vec<_> = roots.iter().flat_map(|root| [root.referent.clone()].into_iter().chain(root.children.iter().map(|child| child.referent.clone()))).collect();
+        assert_eq!(roots.iter().map(XmlItem::count).sum::<usize>(), 3);
+        assert_eq!(referents, [Some("RBX0".to_string()), Some("RBX1".to_string()), Some("RBX2".to_string())]);
+        let names: Vec<_> = roots[0].children[0].properties.iter().map(|property| property.name.as_str()).collect();
+        assert_eq!(names, ["Name", "Apple", "Zebra"]);
+        assert_eq!(roots[0].children[0].properties[1].value, XmlValue::Ref(None));
+
+        let path = std::env::temp_dir().join(format!("rnr_write_xml_{}.rbxlx", std::process::id()));
+        write_xml_file(&path, datamodel.borrow().instance().borrow().children()).unwrap();
+        assert_eq!(read_xml_file(&path).unwrap(), roots);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,32 +1,120 @@
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::cell::RefCell;
-use rnr_datamodel::DataModel;
+use glam::{Vec2, Vec3};
+use rnr_audio::SoundService;
+use rnr_core::content::ContentProvider;
+use rnr_core::instance::Instance;
+use rnr_core::types::CFrame;
+use rnr_core::variant::Variant;
+use rnr_datamodel::{BasePart, BillboardGui, BinAction, Chat, ChatError, ClickDetector, ClickDetectors, ChatTarget, ContextActionService, DataModel, DataStoreService, Debris, FileDataStoreBackend, HopperBins, LeaderboardSort, LeaderboardView, Leaderstats, Player, Players, RunContext, RunService, Seat, StarterGui, StarterPack, Teams, Tools, TweenService, UserInputService, Workspace, GuiDrawItem, InputEvent, InputObject, KeyCode, UserInputState, UserInputType, InputReplay, find_messages, layout_billboard_gui, layout_gui, layout_leaderboard, layout_messages, legacy_leaderboard};
+use rnr_physics::PathfindingService;
+use rnr_scripting::HttpService;
 
 /// Configuration for creating a World instance
 #[derive(Debug, Clone)]
 pub struct WorldConfig {
+    pub run_context: RunContext,
     pub enable_rendering: bool,
     pub enable_networking: bool,
     pub enable_physics: bool,
     pub enable_input: bool,
+    /// Mix sounds each step. The mixed PCM is left in SoundService's output.
+    pub enable_audio: bool,
     pub target_fps: u32,
     pub viewport_width: u32,
     pub viewport_height: u32,
+    /// Directory DataStoreService keeps its JSON files in. Data stores are
+    /// kept in memory when this is None.
+    pub data_store_dir: Option<PathBuf>,
 }
 
 /// Represents the game world containing all game state
 pub struct World {
     config: WorldConfig,
     datamodel: Rc<RefCell<DataModel>>,
+    /// The physics world, which shares our data model
+    physics: rnr_physics::World,
+    run_service: Rc<RefCell<RunService>>,
+    debris: Rc<RefCell<Debris>>,
+    workspace: Rc<RefCell<Workspace>>,
+    players: Rc<RefCell<Players>>,
+    teams: Rc<RefCell<Teams>>,
+    data_store_service: Rc<RefCell<DataStoreService>>,
+    http_service: Rc<RefCell<HttpService>>,
+    tween_service: Rc<RefCell<TweenService>>,
+    sound_service: Rc<RefCell<SoundService>>,
+    chat: Rc<RefCell<Chat>>,
+    pathfinding_service: Rc<RefCell<PathfindingService>>,
+    starter_gui: Rc<RefCell<StarterGui>>,
+    starter_pack: Rc<RefCell<StarterPack>>,
+    /// Tool equipping and events
+    tools: Tools,
+    click_detectors: ClickDetectors,
+    /// HopperBin selection and dragging
+    hopper_bins: HopperBins,
+    /// Every player's leaderstats, read here on servers and replicated to
+    /// clients
+    leaderstats: Leaderstats,
+    /// How the local player sorted the leaderboard
+    leaderboard_sort: LeaderboardSort,
+    user_input_service: Rc<RefCell<UserInputService>>,
+    context_action_service: Rc<RefCell<ContextActionService>>,
+    /// Input injected since the last step
+    pending_input: Vec<InputEvent>,
+    /// Recorded input played back as the simulated clock reaches it
+    input_replay: Option<InputReplay>,
+    /// Number of steps taken so far
+    frame_count: u64,
 }
 
 impl World {
     /// Create a new world with the given configuration
     pub fn new(config: WorldConfig) -> Self {
-        let datamodel = DataModel::new();
+        let mut physics = rnr_physics::World::new(config.enable_rendering);
+        physics.set_run_physics(config.enable_physics);
+        let datamodel = physics.datamodel.clone();
+
+        let viewport = Vec2::new(config.viewport_width as f32, config.viewport_height as f32);
+        let workspace = Workspace::new();
+        if let Some(camera) = workspace.borrow().current_camera() {
+            camera.set_viewport_size(viewport);
+        }
+        let user_input_service = UserInputService::new();
+        user_input_service.borrow_mut().set_viewport_size(viewport);
+
+        let data_store_service = match &config.data_store_dir {
+            Some(dir) => DataStoreService::new(Box::new(FileDataStoreBackend::new(dir))),
+            None => DataStoreService::in_memory(),
+        };
+
         Self {
+            run_service: RunService::new(config.run_context),
             config,
             datamodel,
+            physics,
+            debris: Debris::new(),
+            workspace,
+            players: Players::new(),
+            teams: Teams::new(),
+            data_store_service,
+            http_service: HttpService::new(),
+            tween_service: TweenService::new(),
+            sound_service: SoundService::new(ContentProvider::default()),
+            chat: Chat::new(),
+            pathfinding_service: PathfindingService::new(),
+            starter_gui: StarterGui::new(),
+            starter_pack: StarterPack::new(),
+            tools: Tools::new(),
+            click_detectors: ClickDetectors::new(),
+            hopper_bins: HopperBins::new(),
+            leaderstats: Leaderstats::new(),
+            leaderboard_sort: LeaderboardSort::default(),
+            user_input_service,
+            context_action_service: ContextActionService::new(),
+            pending_input: Vec::new(),
+            input_replay: None,
+            frame_count: 0,
         }
     }
 
@@ -34,24 +122,516 @@ impl World {
     pub async fn initialize(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         // Initialize services based on configuration
         println!("Initializing world with config: {:?}", self.config);
+
+        let run_service = self.run_service.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("RunService", run_service);
+        let debris = self.debris.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("Debris", debris);
+        let workspace = self.workspace.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("Workspace", workspace);
+        let players = self.players.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("Players", players);
+        let teams = self.teams.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("Teams", teams);
+        let data_store_service = self.data_store_service.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("DataStoreService", data_store_service);
+        let http_service = self.http_service.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("HttpService", http_service);
+        let tween_service = self.tween_service.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("TweenService", tween_service);
+        let sound_service = self.sound_service.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("SoundService", sound_service);
+        let chat = self.chat.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("Chat", chat);
+        let pathfinding_service = self.pathfinding_service.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("PathfindingService", pathfinding_service);
+        let starter_gui = self.starter_gui.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("StarterGui", starter_gui);
+        let starter_pack = self.starter_pack.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("StarterPack", starter_pack);
+        let user_input_service = self.user_input_service.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("UserInputService", user_input_service);
+        let context_action_service = self.context_action_service.borrow().instance().clone();
+        self.datamodel.borrow_mut().register_service("ContextActionService", context_action_service);
+
+        self.run_service.borrow_mut().set_running(true);
         Ok(())
     }
 
-    /// Step the world forward by one frame
+    /// Step the world forward by one frame.
+    ///
+    /// Each step advances the simulated clock by `delta_time()`, independent of
+    /// how long the frame actually took, so a world stepped N times always ends
+    /// up in the same state. A frame runs in this order:
+    ///
+    /// 1. Injected input, then input due from the input replay, goes to
+    ///    ContextActionService and UserInputService, clicks the actions
+    ///    didn't sink activate the local player's tool, click with their
+    ///    selected HopperBin or click the ClickDetector under the mouse, and
+    ///    the local player's keys drive the seat they sit in (when input is
+    ///    enabled)
+    /// 2. RunService render step bindings and RenderStepped, then the camera
+    ///    is synced with Workspace.CurrentCamera (clients only)
+    /// 3. RunService.Stepped, after Workspace.DistributedGameTime is set
+    ///    (servers only)
+    /// 4. Explosions added to the workspace go off, firing Explosion.Hit
+    /// 5. Physics: terrain chunks changed since the last step are re-meshed
+    ///    and re-collided, body movers push their parts' bodies, then the
+    ///    world is stepped under Workspace.Gravity, parts follow their
+    ///    bodies and parts below FallenPartsDestroyHeight are destroyed
+    /// 6. Humanoids walk towards their MoveTo targets, firing MoveToFinished.
+    ///    Seats: occupants get up or ride along, humanoids touching free
+    ///    seats sit, and VehicleSeats set their vehicle's hinge motors. Then
+    ///    flags are dropped, picked up, returned and captured, and characters
+    ///    touching team spawns with AllowTeamChangeOnTouch join their teams
+    /// 7. Tweens
+    /// 8. RunService.Heartbeat
+    /// 9. Sounds are mixed, heard from Workspace.CurrentCamera (when audio
+    ///    is enabled)
+    /// 10. Debris cleanup, chat bubble expiry and DataStoreService budget
+    ///     refills, then players' leaderstats are read (servers only)
+    /// 11. Paths are re-checked if the workspace changed, firing Path.Blocked
+    /// 12. Terrain chunk meshes and characters, in their clothing, are
+    ///     queued for the renderer, then BillboardGuis are projected over
+    ///     their parts, the local player's PlayerGui is laid out for the
+    ///     viewport, and the leaderboard and Messages and Hints are queued
+    ///     on top (clients only)
     pub async fn step(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Update all systems
+        let delta_time = self.delta_time();
+        let time = self.elapsed_time();
+        self.frame_count += 1;
+
+        if self.config.enable_input {
+            self.process_input(time);
+        }
+        if self.config.enable_rendering {
+            RunService::fire_render_stepped(&self.run_service, delta_time);
+            self.physics.sync_camera();
+        }
+
+        if self.config.run_context != RunContext::Client {
+            self.workspace.borrow().set_distributed_game_time(time);
+        }
+        RunService::fire_stepped(&self.run_service, time, delta_time);
+        let workspace = self.workspace.borrow().instance().clone();
+        self.physics.update_explosions(&workspace);
+        let terrain = self.workspace.borrow().terrain().clone();
+        self.physics.update_terrain(&terrain);
+        if self.physics.should_run_physics() {
+            let gravity = self.workspace.borrow().gravity() as f32;
+            self.physics.physics_world.set_gravity(Vec3::new(0.0, -gravity, 0.0));
+            self.physics.apply_body_movers(&workspace, delta_time as f32);
+            self.physics.step_physics(delta_time as f32);
+            self.workspace.borrow().destroy_fallen_parts();
+        }
+        self.physics.update_humanoids(&workspace, delta_time as f32);
+        self.physics.update_seats(&workspace);
+        self.physics.update_flags(&workspace, &self.players.borrow(), &mut self.tools);
+        self.physics.update_spawn_locations(&workspace, &self.players.borrow(), &self.teams.borrow());
+        TweenService::update(&self.tween_service, delta_time);
+        RunService::fire_heartbeat(&self.run_service, delta_time);
+        if self.config.enable_audio {
+            let listener = self.workspace.borrow().current_camera().map(|camera| camera.cframe()).unwrap_or(CFrame::IDENTITY);
+            SoundService::update(&self.sound_service, delta_time, &workspace, &listener);
+        }
+
+        self.debris.borrow_mut().update(self.elapsed_time());
+        self.chat.borrow_mut().update(self.elapsed_time());
+        let num_players = self.players.borrow().get_players().len();
+        self.data_store_service.borrow_mut().update(self.elapsed_time(), num_players);
+        if self.config.run_context != RunContext::Client {
+            self.leaderstats.update(&self.players.borrow());
+        }
+        PathfindingService::update(&self.pathfinding_service, &workspace);
+
+        if self.config.enable_rendering {
+            self.physics.draw_terrain();
+            self.physics.draw_characters(&self.players.borrow());
+            self.draw_gui();
+        }
         Ok(())
     }
 
+    /// Get the fixed time step used by `step`, derived from the target FPS
+    pub fn delta_time(&self) -> f64 {
+        1.0 / self.config.target_fps.max(1) as f64
+    }
+
+    /// Dispatch injected input and replayed input due by `time`. Actions
+    /// get each input first; input they sink reaches UserInputService's
+    /// signals as game processed.
+    fn process_input(&mut self, time: f64) {
+        let mut events = std::mem::take(&mut self.pending_input);
+        if let Some(replay) = &mut self.input_replay {
+            events.extend(replay.take_due(time));
+        }
+
+        for event in &events {
+            let input = self.user_input_service.borrow_mut().apply_input(event);
+            if let Some(input) = input {
+                let sunk = ContextActionService::process_input(&self.context_action_service, &input);
+                UserInputService::fire_input(&self.user_input_service, &input, sunk);
+                if !sunk {
+                    self.activate_tool(&input);
+                    self.update_hopper_bins(&input);
+                    self.update_click_detectors(&input);
+                }
+            }
+        }
+        self.update_seat_controls();
+    }
+
+    /// Activate the local player's tool while the left mouse button is down
+    fn activate_tool(&mut self, input: &InputObject) {
+        if input.user_input_type() != UserInputType::MouseButton1 {
+            return;
+        }
+        let Some(player) = self.players.borrow().local_player().cloned() else {
+            return;
+        };
+        match input.user_input_state() {
+            UserInputState::Begin => self.tools.activate(&player),
+            UserInputState::End => self.tools.deactivate(&player),
+            _ => {}
+        }
+    }
+
+    /// Click and drag with the local player's selected HopperBin. Parts it
+    /// moves or copies have their bodies moved or copied along.
+    fn update_hopper_bins(&mut self, input: &InputObject) {
+        let Some(player) = self.players.borrow().local_player().cloned() else {
+            return;
+        };
+        if HopperBins::selected_bin(&player).is_none() {
+            return;
+        }
+        let viewport = Vec2::new(self.config.viewport_width as f32, self.config.viewport_height as f32);
+        let screen_point = input.position().truncate() / viewport;
+
+        match (input.user_input_type(), input.user_input_state()) {
+            (UserInputType::MouseButton1, UserInputState::Begin) => {
+                let (Some((origin, _)), Some((part, hit))) = (self.physics.screen_ray(screen_point), self.physics.pick_part(screen_point)) else {
+                    return;
+                };
+                let Some(part) = BasePart::from_instance(&part) else {
+                    return;
+                };
+                if let Some(BinAction::Cloned { original, copy }) = self.hopper_bins.click(&player, &part, origin, hit)
+                    && self.physics.physics_world.get_part_body(original.instance()).is_some()
+                {
+                    self.physics.physics_world.add_part(&copy);
+                }
+            }
+            (UserInputType::MouseMovement, _) if self.hopper_bins.dragging(&player) => {
+                let Some((origin, direction)) = self.physics.screen_ray(screen_point) else {
+                    return;
+                };
+                for part in self.hopper_bins.drag(&player, origin, direction) {
+                    self.physics.physics_world.teleport_part(&part);
+                }
+            }
+            (UserInputType::MouseButton1, UserInputState::End) => self.hopper_bins.release(&player),
+            _ => {}
+        }
+    }
+
+    /// Hover and click ClickDetectors with the local player's mouse. The
+    /// part under the mouse is picked through the active camera; a player
+    /// holding a tool or a selected HopperBin clicks with it instead.
+    fn update_click_detectors(&mut self, input: &InputObject) {
+        let Some(player) = self.players.borrow().local_player().cloned() else {
+            return;
+        };
+        let viewport = Vec2::new(self.config.viewport_width as f32, self.config.viewport_height as f32);
+        let pick = || {
+            let screen_point = input.position().truncate() / viewport;
+            self.physics.pick_part(screen_point).and_then(|(part, _)| ClickDetector::for_part(&part))
+        };
+
+        match (input.user_input_type(), input.user_input_state()) {
+            (UserInputType::MouseMovement, _) => {
+                let detector = pick();
+                self.click_detectors.hover(&player, detector.as_ref());
+            }
+            (UserInputType::MouseButton1, UserInputState::Begin) if Tools::equipped_tool(&player).is_none() && HopperBins::selected_bin(&player).is_none() => {
+                if let Some(detector) = pick() {
+                    self.click_detectors.click(&player, &detector);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Hand the local player's keys to the seat their character sits in. W/S
+    /// or Up/Down set a VehicleSeat's Throttle, A/D or Left/Right its Steer,
+    /// and Space makes the humanoid jump out.
+    fn update_seat_controls(&self) {
+        let Some(character) = self.players.borrow().local_player().and_then(|player| player.character()) else {
+            return;
+        };
+        let Some(humanoid) = character.borrow().find_first_child("Humanoid") else {
+            return;
+        };
+        let seat = humanoid.borrow().get_property("SeatPart").and_then(Variant::as_instance);
+        let Some(seat) = seat.as_ref().and_then(Seat::from_instance) else {
+            return;
+        };
+
+        let user_input_service = self.user_input_service.borrow();
+        let down = |keys: &[KeyCode]| keys.iter().any(|key| user_input_service.is_key_down(*key)) as i64;
+        if seat.is_vehicle_seat() {
+            seat.set_throttle(down(&[KeyCode::W, KeyCode::Up]) - down(&[KeyCode::S, KeyCode::Down]));
+            seat.set_steer(down(&[KeyCode::D, KeyCode::Right]) - down(&[KeyCode::A, KeyCode::Left]));
+        }
+        if down(&[KeyCode::Space]) != 0 {
+            humanoid.borrow_mut().set_property("Jump", true);
+        }
+    }
+
+    /// Queue input to be dispatched at the start of the next step
+    pub fn inject_input(&mut self, event: InputEvent) {
+        self.pending_input.push(event);
+    }
+
+    /// Play back recorded input, timed by the simulated clock
+    pub fn set_input_replay(&mut self, replay: InputReplay) {
+        self.input_replay = Some(replay);
+    }
+
+    /// Lay out billboards, the local player's GUI, the leaderboard and
+    /// messages and hand them to the renderer. Billboards are drawn first, so
+    /// screen GUI covers them, and messages cover everything.
+    fn draw_gui(&mut self) {
+        let local_player = self.players.borrow().local_player().cloned();
+        let player_gui = local_player.as_ref().and_then(Player::player_gui);
+        let viewport = Vec2::new(self.config.viewport_width as f32, self.config.viewport_height as f32);
+        let mut items = self.billboard_gui_items(player_gui.as_ref(), viewport);
+        if let Some(player_gui) = &player_gui {
+            items.extend(layout_gui(player_gui, viewport));
+        }
+        let rows = if self.leaderstats.columns().is_empty() {
+            legacy_leaderboard(&self.players.borrow(), &self.teams.borrow())
+        } else {
+            self.leaderboard_view().rows()
+        };
+        items.extend(layout_leaderboard(&rows, viewport));
+
+        let mut message_roots = vec![self.workspace.borrow().instance().clone()];
+        message_roots.extend(local_player.map(|player| player.instance().clone()));
+        items.extend(layout_messages(&find_messages(&message_roots), viewport));
+        self.physics.draw_gui(&items);
+    }
+
+    /// Lay out every BillboardGui in the workspace and the local PlayerGui
+    /// over its adornee (or parent part), farthest from the camera first.
+    /// Billboards behind the camera are skipped.
+    fn billboard_gui_items(&self, player_gui: Option<&Rc<RefCell<Instance>>>, viewport: Vec2) -> Vec<GuiDrawItem> {
+        let Some(camera) = self.physics.get_camera() else {
+            return Vec::new();
+        };
+        let mut camera = camera.clone();
+        camera.update_matrices();
+
+        let mut roots = vec![self.workspace.borrow().instance().clone()];
+        roots.extend(player_gui.cloned());
+        let mut billboards = Vec::new();
+        for root in &roots {
+            for billboard in root.borrow().get_descendants().iter().filter_map(BillboardGui::from_instance) {
+                let adornee = billboard.adornee().or_else(|| billboard.instance().borrow().parent());
+                let Some(part) = adornee.as_ref().and_then(BasePart::from_instance) else {
+                    continue;
+                };
+
+                let position = part.position() + billboard.studs_offset();
+                let depth = (camera.view_projection_matrix * position.extend(1.0)).w;
+                if depth <= 0.0 {
+                    continue;
+                }
+
+                // A stud straight up on screen gives the billboard's scale at this distance
+                let center = camera.world_to_screen(position).truncate() * viewport;
+                let above = camera.world_to_screen(position + camera.up()).truncate() * viewport;
+                billboards.push((depth, layout_billboard_gui(&billboard, center, (above - center).length())));
+            }
+        }
+
+        billboards.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        billboards.into_iter().flat_map(|(_, items)| items).collect()
+    }
+
+    /// Get the simulated time elapsed since the world was created
+    pub fn elapsed_time(&self) -> f64 {
+        self.frame_count as f64 * self.delta_time()
+    }
+
+    /// Add a player to the world.
+    ///
+    /// The player is put on the smallest auto-assignable team, if there is one,
+    /// and spawned into the workspace.
+    pub fn add_player(&mut self, name: &str, user_id: i64) -> Player {
+        let player = Players::add_player(&self.players, name, user_id);
+        self.teams.borrow().assign_player(&player, &self.players.borrow());
+        self.load_character(&player);
+        player
+    }
+
+    /// Respawn a player's character (Player:LoadCharacter). The player's
+    /// GUI is refreshed from StarterGui, and their Backpack from StarterPack.
+    /// The character's Humanoid gets a physics humanoid to walk it.
+    pub fn load_character(&mut self, player: &Player) -> Rc<RefCell<Instance>> {
+        let workspace = self.workspace.borrow().instance().clone();
+        let character = self.players.borrow_mut().load_character(player, &workspace, &mut self.debris.borrow_mut());
+        if let Some(humanoid) = character.borrow().find_first_child("Humanoid") {
+            self.physics.humanoid(&humanoid);
+        }
+        if let Some(player_gui) = player.player_gui() {
+            self.starter_gui.borrow().reset_player_gui(&player_gui);
+        }
+        if let Some(backpack) = player.backpack() {
+            self.starter_pack.borrow().fill_backpack(&backpack);
+        }
+        character
+    }
+
+    /// Handle a message typed by a player. Deliveries are queued on the Chat
+    /// service for the network layer to send.
+    pub fn chat(&mut self, player: &Player, text: &str, target: ChatTarget) -> Result<(), ChatError> {
+        Chat::send(&self.chat, &self.players, player, text, target)
+    }
+
+    /// Get the number of steps taken so far
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
     /// Shutdown the world
     pub async fn shutdown(&mut self) {
         println!("Shutting down world...");
+        self.run_service.borrow_mut().set_running(false);
     }
 
     /// Get the data model
     pub fn datamodel(&self) -> &Rc<RefCell<DataModel>> {
         &self.datamodel
     }
+
+    /// Get the physics world
+    pub fn physics(&self) -> &rnr_physics::World {
+        &self.physics
+    }
+
+    /// Get the physics world mutably
+    pub fn physics_mut(&mut self) -> &mut rnr_physics::World {
+        &mut self.physics
+    }
+
+    /// Get the RunService
+    pub fn run_service(&self) -> &Rc<RefCell<RunService>> {
+        &self.run_service
+    }
+
+    /// Get the Debris service
+    pub fn debris(&self) -> &Rc<RefCell<Debris>> {
+        &self.debris
+    }
+
+    /// Get the Workspace
+    pub fn workspace(&self) -> &Rc<RefCell<Workspace>> {
+        &self.workspace
+    }
+
+    /// Get the Players service
+    pub fn players(&self) -> &Rc<RefCell<Players>> {
+        &self.players
+    }
+
+    /// Get the Teams service
+    pub fn teams(&self) -> &Rc<RefCell<Teams>> {
+        &self.teams
+    }
+
+    /// Get the DataStoreService
+    pub fn data_store_service(&self) -> &Rc<RefCell<DataStoreService>> {
+        &self.data_store_service
+    }
+
+    /// Get the HttpService
+    pub fn http_service(&self) -> &Rc<RefCell<HttpService>> {
+        &self.http_service
+    }
+
+    /// Get the TweenService
+    pub fn tween_service(&self) -> &Rc<RefCell<TweenService>> {
+        &self.tween_service
+    }
+
+    /// Get the SoundService
+    pub fn sound_service(&self) -> &Rc<RefCell<SoundService>> {
+        &self.sound_service
+    }
+
+    /// Get the Chat service
+    pub fn chat_service(&self) -> &Rc<RefCell<Chat>> {
+        &self.chat
+    }
+
+    /// Get the StarterPack service
+    pub fn starter_pack(&self) -> &Rc<RefCell<StarterPack>> {
+        &self.starter_pack
+    }
+
+    /// Get the tools, to equip them and listen to their events. Equips and
+    /// unequips are queued for the network layer to send to every client.
+    pub fn tools_mut(&mut self) -> &mut Tools {
+        &mut self.tools
+    }
+
+    /// Get the click detectors, to listen to their events. Clicks are
+    /// queued for the network layer to send to the server.
+    pub fn click_detectors_mut(&mut self) -> &mut ClickDetectors {
+        &mut self.click_detectors
+    }
+
+    /// Get the leaderstats, to take the updates for clients or apply the
+    /// server's
+    pub fn leaderstats_mut(&mut self) -> &mut Leaderstats {
+        &mut self.leaderstats
+    }
+
+    /// Get the leaderboard as the local player sees it
+    pub fn leaderboard_view(&self) -> LeaderboardView {
+        self.leaderstats.view(&self.players.borrow(), &self.teams.borrow(), self.leaderboard_sort)
+    }
+
+    /// Sort the leaderboard by another column
+    pub fn set_leaderboard_sort(&mut self, sort: LeaderboardSort) {
+        self.leaderboard_sort = sort;
+    }
+
+    /// Get the hopper bins, to select them and listen to their events
+    pub fn hopper_bins_mut(&mut self) -> &mut HopperBins {
+        &mut self.hopper_bins
+    }
+
+    /// Get the StarterGui service
+    pub fn starter_gui(&self) -> &Rc<RefCell<StarterGui>> {
+        &self.starter_gui
+    }
+
+    /// Get the PathfindingService
+    pub fn pathfinding_service(&self) -> &Rc<RefCell<PathfindingService>> {
+        &self.pathfinding_service
+    }
+
+    /// Get the UserInputService
+    pub fn user_input_service(&self) -> &Rc<RefCell<UserInputService>> {
+        &self.user_input_service
+    }
+
+    /// Get the ContextActionService
+    pub fn context_action_service(&self) -> &Rc<RefCell<ContextActionService>> {
+        &self.context_action_service
+    }
 }
 
 pub fn add(left: u64, right: u64) -> u64 {
@@ -61,6 +641,15 @@ pub fn add(left: u64, right: u64) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+    use glam::Vec3;
+    use rnr_core::types::{BrickColor, CFrame, UDim2};
+    use rnr_datamodel::{ActionInput, BinType, BodyMover, BodyMoverKind, ContextActionResult, DataStoreRequestType, EasingDirection, EasingStyle, Explosion, Flag, FlagStand, GuiObject, HopperBin, LeaderstatValue, Material, Message, PlaybackState, RenderPriority, ScreenGui, Seat, Sound, SpawnLocation, TweenInfo, KeyCode, Tool, ToolUpdate, UserInputState, ValueObject};
+    use rnr_physics::{AgentParameters, PathStatus};
 
     #[test]
     fn it_works() {
@@ -68,19 +657,827 @@ mod tests {
         assert_eq!(result, 4);
     }
 
-    #[test]
-    fn test_world_creation() {
-        let config = WorldConfig {
+    fn test_config(target_fps: u32) -> WorldConfig {
+        WorldConfig {
+            run_context: RunContext::Client,
             enable_rendering: true,
             enable_networking: true,
             enable_physics: true,
             enable_input: true,
-            target_fps: 60,
+            enable_audio: true,
+            target_fps,
             viewport_width: 800,
             viewport_height: 600,
-        };
+            data_store_dir: None,
+        }
+    }
+
+    #[test]
+    fn test_world_creation() {
+        let config = test_config(60);
 
         let world = World::new(config);
         assert!(world.datamodel().borrow().services().is_empty());
     }
+
+    #[tokio::test]
+    async fn test_world_services() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+
+        assert!(world.datamodel().borrow().get_service("Debris").is_some());
+        assert!(world.datamodel().borrow().get_service("RunService").is_some());
+        assert!(world.datamodel().borrow().get_service("Workspace").is_some());
+        assert!(world.datamodel().borrow().get_service("Players").is_some());
+        assert!(world.datamodel().borrow().get_service("Teams").is_some());
+        assert!(world.datamodel().borrow().get_service("DataStoreService").is_some());
+        assert!(world.datamodel().borrow().get_service("TweenService").is_some());
+        assert!(world.datamodel().borrow().get_service("SoundService").is_some());
+        assert!(world.datamodel().borrow().get_service("Chat").is_some());
+        assert!(world.datamodel().borrow().get_service("PathfindingService").is_some());
+        assert!(world.datamodel().borrow().get_service("StarterGui").is_some());
+        assert!(world.run_service().borrow().is_running());
+    }
+
+    #[tokio::test]
+    async fn test_http_service_is_a_service_with_requests_disabled() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+
+        let http_service = world.datamodel().borrow().get_service("HttpService").unwrap();
+        assert!(Rc::ptr_eq(&http_service, world.http_service().borrow().instance()));
+        assert_eq!(http_service.borrow().get_property("HttpEnabled"), Some(&Variant::Bool(false)));
+        assert!(!world.http_service().borrow().http_enabled());
+    }
+
+    #[tokio::test]
+    async fn test_frame_signal_order() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+
+        let order = Rc::new(RefCell::new(Vec::new()));
+        {
+            let run_service = world.run_service().borrow();
+
+            let order_clone = order.clone();
+            run_service.heartbeat().connect(move |_| order_clone.borrow_mut().push("Heartbeat"));
+            let order_clone = order.clone();
+            run_service.stepped().connect(move |_| order_clone.borrow_mut().push("Stepped"));
+            let order_clone = order.clone();
+            run_service.render_stepped().connect(move |_| order_clone.borrow_mut().push("RenderStepped"));
+        }
+
+        world.step().await.unwrap();
+        assert_eq!(*order.borrow(), vec!["RenderStepped", "Stepped", "Heartbeat"]);
+    }
+
+    #[tokio::test]
+    async fn test_stepped_time() {
+        let mut config = test_config(10);
+        config.run_context = RunContext::Server;
+        config.enable_rendering = false;
+        let mut world = World::new(config);
+        world.initialize().await.unwrap();
+
+        let times = Rc::new(RefCell::new(Vec::new()));
+        let times_clone = times.clone();
+        world.run_service().borrow().stepped().connect(move |(time, delta_time)| {
+            times_clone.borrow_mut().push((*time, *delta_time));
+        });
+
+        world.step().await.unwrap();
+        world.step().await.unwrap();
+
+        assert_eq!(*times.borrow(), vec![(0.0, 0.1), (0.1, 0.1)]);
+    }
+
+    #[tokio::test]
+    async fn test_debris_runs_on_simulated_clock() {
+        let mut world = World::new(test_config(10));
+        world.initialize().await.unwrap();
+
+        let item = rnr_core::instance::Instance::new();
+        rnr_core::instance::Instance::set_parent(&item, Some(world.datamodel().borrow().instance().clone()));
+        world.debris().borrow_mut().add_item(&item, 1.0);
+
+        for _ in 0..9 {
+            world.step().await.unwrap();
+        }
+        assert!(!item.borrow().is_destroyed());
+
+        world.step().await.unwrap();
+        assert!(item.borrow().is_destroyed());
+        assert!((world.elapsed_time() - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_players_spawn_on_team_spawns() {
+        let mut world = World::new(test_config(10));
+        world.initialize().await.unwrap();
+
+        let red = world.teams().borrow().add_team("Red", BrickColor::red());
+        world.teams().borrow().add_team("Blue", BrickColor::blue());
+
+        let spawn = SpawnLocation::new();
+        spawn.set_neutral(false);
+        spawn.set_team_color(BrickColor::red());
+        spawn.part().set_position(Vec3::new(30.0, 0.5, 0.0));
+        Instance::set_parent(spawn.instance(), Some(world.workspace().borrow().instance().clone()));
+
+        let first = world.add_player("Player1", 1);
+        let second = world.add_player("Player2", 2);
+        assert!(first.is_on_team(&red));
+        assert!(!second.is_on_team(&red));
+
+        // Red spawns on its team spawn, Blue has none and uses the default
+        let torso_x = |player: &Player| {
+            let character = player.character().unwrap();
+            let torso = character.borrow().find_first_child("Torso").unwrap();
+            BasePart::from_instance(&torso).unwrap().position().x
+        };
+        assert_eq!(torso_x(&first), 30.0);
+        assert_eq!(torso_x(&second), 0.0);
+
+        // The spawn's ForceField wears off on the simulated clock
+        let character = first.character().unwrap();
+        assert!(character.borrow().find_first_child("ForceField").is_some());
+        for _ in 0..100 {
+            world.step().await.unwrap();
+        }
+        assert!(character.borrow().find_first_child("ForceField").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_touching_team_spawn_changes_team() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        let red = world.teams().borrow().add_team("Red", BrickColor::red());
+        let blue = world.teams().borrow().add_team("Blue", BrickColor::blue());
+        let workspace = world.workspace().borrow().instance().clone();
+        for (color, x) in [(BrickColor::red(), 0.0), (BrickColor::blue(), 40.0)] {
+            let spawn = SpawnLocation::new();
+            spawn.set_neutral(false);
+            spawn.set_team_color(color);
+            spawn.set_allow_team_change_on_touch(true);
+            spawn.part().set_position(Vec3::new(x, 0.5, 0.0));
+            Instance::set_parent(spawn.instance(), Some(workspace.clone()));
+        }
+        let player = world.add_player("Player1", 1);
+        world.step().await.unwrap();
+        assert!(player.is_on_team(&red));
+
+        // Walking onto the blue spawn joins Blue
+        let character = player.character().unwrap();
+        let humanoid = character.borrow().find_first_child("Humanoid").unwrap();
+        world.physics_mut().humanoid(&humanoid).borrow_mut().move_to(Vec3::new(40.0, 4.0, 0.0));
+        for _ in 0..300 {
+            world.step().await.unwrap();
+        }
+        assert!(player.is_on_team(&blue));
+    }
+
+    #[tokio::test]
+    async fn test_scripts_control_the_camera() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+
+        // A render step binding moves the camera the way a camera script would
+        let camera = world.workspace().borrow().current_camera().unwrap();
+        assert!((camera.aspect_ratio() - 800.0 / 600.0).abs() < 1e-6);
+        let camera_clone = camera.clone();
+        world.run_service().borrow_mut().bind_to_render_step("Camera", RenderPriority::Camera.value(), move |_| {
+            camera_clone.set_cframe(CFrame::look_at(Vec3::new(0.0, 10.0, 10.0), Vec3::ZERO));
+        });
+
+        world.step().await.unwrap();
+        let render_camera = world.physics().get_camera().unwrap();
+        assert_eq!(render_camera.position, Vec3::new(0.0, 10.0, 10.0));
+        assert!((render_camera.aspect_ratio - 800.0 / 600.0).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_data_store_budgets_refill_with_players() {
+        let mut config = test_config(10);
+        config.run_context = RunContext::Server;
+        config.enable_rendering = false;
+        let mut world = World::new(config);
+        world.initialize().await.unwrap();
+
+        let service = world.data_store_service().clone();
+        let store = DataStoreService::get_data_store(&service, "PlayerData", None).unwrap();
+        while store.get_async("Player_1").is_ok() {}
+
+        // 2 players give 80 GetAsync requests a minute, so 6.5 seconds refills
+        // 8.67 requests, 8 of which can be spent
+        world.add_player("Player1", 1);
+        world.add_player("Player2", 2);
+        for _ in 0..65 {
+            world.step().await.unwrap();
+        }
+        let budget = service.borrow().get_request_budget_for_request_type(DataStoreRequestType::GetAsync);
+        assert_eq!(budget, 8);
+    }
+
+    #[tokio::test]
+    async fn test_tweens_run_on_world_clock() {
+        let mut world = World::new(test_config(10));
+        world.initialize().await.unwrap();
+
+        // A door sliding open over one second
+        let door = BasePart::new("Part");
+        Instance::set_parent(door.instance(), Some(world.workspace().borrow().instance().clone()));
+        let info = TweenInfo::new(1.0, EasingStyle::Linear, EasingDirection::In);
+        let goal = CFrame::new(Vec3::new(0.0, 8.0, 0.0));
+        let tween = TweenService::create(world.tween_service(), door.instance(), info, vec![("CFrame", goal.into())]).unwrap();
+        tween.play();
+
+        // Heartbeat sees the tweened value for the frame
+        let heights = Rc::new(RefCell::new(Vec::new()));
+        let heights_clone = heights.clone();
+        let door_clone = door.clone();
+        world.run_service().borrow().heartbeat().connect(move |_| heights_clone.borrow_mut().push(door_clone.position().y));
+
+        for _ in 0..10 {
+            world.step().await.unwrap();
+        }
+        assert!((heights.borrow()[4] - 4.0).abs() < 1e-4);
+        assert_eq!(door.position(), Vec3::new(0.0, 8.0, 0.0));
+        assert_eq!(tween.playback_state(), PlaybackState::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_audio_is_mixed_each_step() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+
+        let sound = Sound::new();
+        sound.set_sound_id("rbxasset://sounds/does-not-exist.ogg");
+        Instance::set_parent(sound.instance(), Some(world.workspace().borrow().instance().clone()));
+        sound.play();
+
+        world.step().await.unwrap();
+
+        // One 60th of a second of stereo audio at 44.1 kHz
+        assert_eq!(world.sound_service().borrow().output().len(), 735 * 2);
+        // Audio that can't be loaded stops the sound instead of retrying
+        assert!(!sound.is_playing());
+    }
+
+    #[tokio::test]
+    async fn test_chat_bubbles_expire_on_world_clock() {
+        let mut world = World::new(test_config(1));
+        world.initialize().await.unwrap();
+        let player = world.add_player("Builderman", 1);
+
+        world.chat(&player, "hello world", ChatTarget::All).unwrap();
+        assert_eq!(world.chat_service().borrow_mut().take_deliveries().len(), 1);
+        assert_eq!(world.chat_service().borrow().bubbles().len(), 1);
+
+        for _ in 0..16 {
+            world.step().await.unwrap();
+        }
+        assert!(world.chat_service().borrow().bubbles().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_paths_blocked_on_world_step() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        let workspace = world.workspace().borrow().instance().clone();
+
+        let floor = BasePart::new("Part");
+        floor.set_cframe(CFrame::new(Vec3::new(0.0, -0.5, 0.0)));
+        floor.set_size(Vec3::new(128.0, 1.0, 128.0));
+        Instance::set_parent(floor.instance(), Some(workspace.clone()));
+
+        let path = world.pathfinding_service().borrow_mut().compute_path(
+            &workspace,
+            Vec3::new(-20.0, 0.0, 2.0),
+            Vec3::new(20.0, 0.0, 2.0),
+            AgentParameters::default(),
+        );
+        assert_eq!(path.status(), PathStatus::Success);
+
+        let blocked = Rc::new(RefCell::new(0));
+        let blocked_clone = blocked.clone();
+        path.blocked().connect(move |_| *blocked_clone.borrow_mut() += 1);
+
+        world.step().await.unwrap();
+        let wall = BasePart::new("Part");
+        wall.set_cframe(CFrame::new(Vec3::new(0.0, 5.0, 2.0)));
+        wall.set_size(Vec3::new(2.0, 10.0, 10.0));
+        Instance::set_parent(wall.instance(), Some(workspace));
+        world.step().await.unwrap();
+
+        assert_eq!(*blocked.borrow(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_character_walks_path_waypoint_with_move_to() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        let workspace = world.workspace().borrow().instance().clone();
+
+        let floor = BasePart::new("Part");
+        floor.set_cframe(CFrame::new(Vec3::new(0.0, -0.5, 0.0)));
+        floor.set_size(Vec3::new(128.0, 1.0, 128.0));
+        Instance::set_parent(floor.instance(), Some(workspace.clone()));
+
+        let player = world.add_player("Player1", 1);
+        let character = player.character().unwrap();
+        let torso = BasePart::from_instance(&character.borrow().find_first_child("Torso").unwrap()).unwrap();
+        let start = torso.position();
+        let path = world.pathfinding_service().borrow_mut().compute_path(
+            &workspace,
+            Vec3::new(start.x, 0.0, start.z),
+            Vec3::new(start.x + 12.0, 0.0, start.z),
+            AgentParameters::default(),
+        );
+        assert_eq!(path.status(), PathStatus::Success);
+        let waypoint = path.get_waypoints()[1].position;
+
+        let instance = character.borrow().find_first_child("Humanoid").unwrap();
+        let humanoid = world.physics_mut().humanoid(&instance);
+        let finished = Rc::new(RefCell::new(Vec::new()));
+        let finished_clone = finished.clone();
+        humanoid.borrow().move_to_finished().connect(move |reached| finished_clone.borrow_mut().push(*reached));
+        humanoid.borrow_mut().move_to(waypoint);
+
+        for _ in 0..60 {
+            world.step().await.unwrap();
+        }
+
+        assert_eq!(*finished.borrow(), vec![true]);
+        assert_eq!((torso.position().x, torso.position().z), (waypoint.x, waypoint.z));
+        assert_eq!(torso.position().y, start.y);
+    }
+
+    #[tokio::test]
+    async fn test_local_player_gui_is_drawn() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+
+        let hud = ScreenGui::new();
+        let bar = GuiObject::new("Frame");
+        bar.set_size(UDim2::new(1.0, 0, 0.0, 40));
+        Instance::set_parent(bar.instance(), Some(hud.instance().clone()));
+        Instance::set_parent(hud.instance(), Some(world.starter_gui().borrow().instance().clone()));
+
+        let player = world.add_player("Builderman", 1);
+        world.players().borrow_mut().set_local_player(Some(&player));
+        world.step().await.unwrap();
+
+        let copy = player.player_gui().unwrap().borrow().find_first_child("ScreenGui").unwrap();
+        let copied_bar = GuiObject::from_instance(&copy.borrow().children()[0]).unwrap();
+        let viewport_width = test_config(60).viewport_width as f32;
+        assert_eq!(copied_bar.absolute_size(), Vec2::new(viewport_width, 40.0));
+        assert_eq!(bar.absolute_size(), Vec2::ZERO);
+        assert_eq!(world.physics().get_render_stats().queued_gui_quads, 1);
+    }
+
+    #[tokio::test]
+    async fn test_injected_and_replayed_input() {
+        let mut world = World::new(test_config(10));
+        world.initialize().await.unwrap();
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let log_clone = log.clone();
+        world.user_input_service().borrow().input_began().connect(move |(input, processed)| {
+            log_clone.borrow_mut().push((input.key_code(), *processed));
+        });
+        let jumps = Rc::new(RefCell::new(0));
+        let jumps_clone = jumps.clone();
+        world.context_action_service().borrow_mut().bind_action("Jump", move |_, state, _| {
+            if state == UserInputState::Begin {
+                *jumps_clone.borrow_mut() += 1;
+            }
+            ContextActionResult::Sink
+        }, &[ActionInput::Key(KeyCode::Space)]);
+
+        world.inject_input(InputEvent::Key { key_code: KeyCode::Space, pressed: true });
+        world.set_input_replay(InputReplay::parse("0.0 key_down W\n0.2 key_up W\n").unwrap());
+        world.step().await.unwrap();
+
+        assert_eq!(*jumps.borrow(), 1);
+        assert_eq!(*log.borrow(), vec![(KeyCode::Space, true), (KeyCode::W, false)]);
+        assert!(world.user_input_service().borrow().is_key_down(KeyCode::W));
+
+        // The release is due at 0.2s, which is the third step at 10 FPS
+        world.step().await.unwrap();
+        assert!(world.user_input_service().borrow().is_key_down(KeyCode::W));
+        world.step().await.unwrap();
+        assert!(!world.user_input_service().borrow().is_key_down(KeyCode::W));
+        assert!(world.datamodel().borrow().get_service("UserInputService").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_billboard_text_is_drawn() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        world.physics_mut().renderer.load_fonts(concat!(env!("CARGO_MANIFEST_DIR"), "/../../Content/RNR/fonts")).unwrap();
+        let camera = world.workspace().borrow().current_camera().unwrap();
+        camera.set_cframe(CFrame::look_at(Vec3::new(0.0, 5.0, 20.0), Vec3::new(0.0, 5.0, 0.0)));
+
+        let part = BasePart::new("Part");
+        part.set_position(Vec3::new(0.0, 5.0, 0.0));
+        Instance::set_parent(part.instance(), Some(world.workspace().borrow().instance().clone()));
+        let billboard = BillboardGui::new();
+        billboard.set_size(UDim2::new(4.0, 0, 1.0, 0));
+        Instance::set_parent(billboard.instance(), Some(part.instance().clone()));
+        let label = GuiObject::new("TextLabel");
+        label.set_size(UDim2::new(1.0, 0, 1.0, 0));
+        label.set_background_transparency(1.0);
+        label.set_text("Hi");
+        Instance::set_parent(label.instance(), Some(billboard.instance().clone()));
+
+        world.step().await.unwrap();
+
+        // Centered on the part, which is in the middle of the screen
+        let center = label.absolute_position() + label.absolute_size() / 2.0;
+        assert!((center - Vec2::new(400.0, 300.0)).length() < 1.0);
+        assert!(label.absolute_size().x > 0.0);
+        assert!(label.text_bounds().x > 0.0);
+        assert_eq!(world.physics().get_render_stats().queued_gui_quads, 2);
+    }
+
+    #[tokio::test]
+    async fn test_explosions_go_off_on_world_step() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        let player = world.add_player("Player1", 1);
+        let character = player.character().unwrap();
+        let torso = BasePart::from_instance(&character.borrow().find_first_child("Torso").unwrap()).unwrap();
+
+        let explosion = Explosion::new();
+        explosion.set_position(torso.position());
+        let hits = Rc::new(RefCell::new(0));
+        let hits_clone = hits.clone();
+        world.physics_mut().explosion_hit(&explosion).connect(move |_| *hits_clone.borrow_mut() += 1);
+        Instance::set_parent(explosion.instance(), Some(world.workspace().borrow().instance().clone()));
+
+        world.step().await.unwrap();
+        assert!(*hits.borrow() > 0);
+        let humanoid = character.borrow().find_first_child("Humanoid").unwrap();
+        assert_eq!(humanoid.borrow().get_property("Health").and_then(Variant::as_float), Some(0.0));
+
+        // It only goes off once
+        let first = *hits.borrow();
+        world.step().await.unwrap();
+        assert_eq!(*hits.borrow(), first);
+    }
+
+    #[tokio::test]
+    async fn test_body_movers_push_parts_on_world_step() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        let part = BasePart::new("Part");
+        part.set_position(Vec3::new(0.0, 20.0, 0.0));
+        Instance::set_parent(part.instance(), Some(world.workspace().borrow().instance().clone()));
+        world.physics_mut().physics_world.add_part(&part);
+
+        // A stiff mover, so the part's weight only costs it a small velocity
+        // error under Workspace.Gravity
+        let mover = BodyMover::new(BodyMoverKind::Velocity);
+        mover.set_velocity(Vec3::new(10.0, 0.0, 0.0));
+        mover.set_p(50000.0);
+        mover.set_max_force(Vec3::splat(50000.0));
+        Instance::set_parent(mover.instance(), Some(part.instance().clone()));
+
+        for _ in 0..60 {
+            world.step().await.unwrap();
+        }
+        // The part follows its body, held up against gravity by the mover
+        assert!(part.position().x > 5.0);
+        assert!((part.position().y - 20.0).abs() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_parts_fall_under_workspace_gravity_and_are_destroyed() {
+        let mut config = test_config(60);
+        config.run_context = RunContext::Server;
+        config.enable_rendering = false;
+        let mut world = World::new(config);
+        world.initialize().await.unwrap();
+        world.workspace().borrow().set_fallen_parts_destroy_height(-50.0);
+        let part = BasePart::new("Part");
+        Instance::set_parent(part.instance(), Some(world.workspace().borrow().instance().clone()));
+        world.physics_mut().physics_world.add_part(&part);
+
+        // Half a second at 196.2 studs/s² is about 25 studs
+        for _ in 0..30 {
+            world.step().await.unwrap();
+        }
+        assert_eq!(world.physics().physics_world.get_gravity(), Vec3::new(0.0, -196.2, 0.0));
+        assert!((part.position().y + 24.5).abs() < 2.0, "{}", part.position().y);
+        assert!(world.workspace().borrow().distributed_game_time() > 0.4);
+
+        world.workspace().borrow().set_gravity(400.0);
+        for _ in 0..30 {
+            world.step().await.unwrap();
+        }
+        assert!(part.instance().borrow().is_destroyed());
+        assert!(world.physics().physics_world.get_part_body(part.instance()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_local_player_drives_vehicle_seat() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        let player = world.add_player("Player1", 1);
+        world.players().borrow_mut().set_local_player(Some(&player));
+        let character = player.character().unwrap();
+        let humanoid = character.borrow().find_first_child("Humanoid").unwrap();
+        let torso = BasePart::from_instance(&character.borrow().find_first_child("Torso").unwrap()).unwrap();
+
+        let seat = Seat::new("VehicleSeat");
+        seat.part().set_anchored(true);
+        seat.part().set_position(torso.position() + Vec3::new(0.0, -1.5, 0.0));
+        Instance::set_parent(seat.instance(), Some(world.workspace().borrow().instance().clone()));
+        world.step().await.unwrap();
+        assert!(Rc::ptr_eq(&seat.occupant().unwrap(), &humanoid));
+
+        world.inject_input(InputEvent::Key { key_code: KeyCode::W, pressed: true });
+        world.inject_input(InputEvent::Key { key_code: KeyCode::Left, pressed: true });
+        world.step().await.unwrap();
+        assert_eq!((seat.throttle(), seat.steer()), (1, -1));
+
+        world.inject_input(InputEvent::Key { key_code: KeyCode::W, pressed: false });
+        world.inject_input(InputEvent::Key { key_code: KeyCode::Space, pressed: true });
+        world.step().await.unwrap();
+        assert_eq!(seat.throttle(), 0);
+        assert!(seat.occupant().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_starter_pack_tools_equip_and_activate() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        let sword = Tool::new("Sword");
+        let handle = BasePart::new("Part");
+        handle.instance().borrow_mut().set_name("Handle");
+        Instance::set_parent(handle.instance(), Some(sword.instance().clone()));
+        Instance::set_parent(sword.instance(), Some(world.starter_pack().borrow().instance().clone()));
+
+        let player = world.add_player("Player1", 1);
+        world.players().borrow_mut().set_local_player(Some(&player));
+        let tool = Tool::from_instance(&player.backpack().unwrap().borrow().find_first_child("Sword").unwrap()).unwrap();
+        let activations = Rc::new(RefCell::new(0));
+        let activations_clone = activations.clone();
+        world.tools_mut().activated(&tool).connect(move |_| *activations_clone.borrow_mut() += 1);
+        assert!(world.tools_mut().equip(&player, &tool));
+
+        world.inject_input(InputEvent::MouseButton { button: UserInputType::MouseButton1, pressed: true });
+        world.step().await.unwrap();
+        assert_eq!(*activations.borrow(), 1);
+        assert_eq!(world.tools_mut().take_updates(), vec![ToolUpdate::Equipped { user_id: 1, tool_name: "Sword".to_string() }]);
+
+        // Respawning starts over from StarterPack
+        world.load_character(&player);
+        assert_eq!(player.backpack().unwrap().borrow().children().len(), 1);
+        assert!(Tools::equipped_tool(&player).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_mouse_hovers_and_clicks_click_detectors() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        let player = world.add_player("Player1", 1);
+        world.players().borrow_mut().set_local_player(Some(&player));
+        let character = world.load_character(&player);
+        let head = BasePart::from_instance(&character.borrow().find_first_child("Head").unwrap()).unwrap();
+        let target = head.position() + Vec3::new(0.0, 0.0, 10.0);
+        let camera = world.workspace().borrow().current_camera().unwrap();
+        camera.set_cframe(CFrame::look_at(target + Vec3::new(0.0, 0.0, 20.0), target));
+
+        let button = BasePart::new("Part");
+        button.set_anchored(true);
+        button.set_position(target);
+        Instance::set_parent(button.instance(), Some(world.workspace().borrow().instance().clone()));
+        world.physics_mut().physics_world.add_part(&button);
+        let detector = ClickDetector::new();
+        Instance::set_parent(detector.instance(), Some(button.instance().clone()));
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        for (event, signal) in [
+            ("enter", world.click_detectors_mut().mouse_hover_enter(&detector)),
+            ("click", world.click_detectors_mut().mouse_click(&detector)),
+            ("leave", world.click_detectors_mut().mouse_hover_leave(&detector)),
+        ] {
+            let events = events.clone();
+            signal.connect(move |player: &Player| events.borrow_mut().push((event, player.user_id())));
+        }
+        world.step().await.unwrap();
+
+        // The button is in the middle of the screen
+        world.inject_input(InputEvent::MouseMove(Vec2::new(400.0, 300.0)));
+        world.inject_input(InputEvent::MouseButton { button: UserInputType::MouseButton1, pressed: true });
+        world.step().await.unwrap();
+        world.inject_input(InputEvent::MouseMove(Vec2::new(10.0, 10.0)));
+        world.step().await.unwrap();
+
+        assert_eq!(*events.borrow(), vec![("enter", 1), ("click", 1), ("leave", 1)]);
+        let clicks = world.click_detectors_mut().take_clicks();
+        assert_eq!(clicks.len(), 1);
+        assert!(Rc::ptr_eq(clicks[0].detector.instance(), detector.instance()));
+    }
+
+    #[tokio::test]
+    async fn test_hopper_bins_clone_and_drag_parts() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        let player = world.add_player("Player1", 1);
+        world.players().borrow_mut().set_local_player(Some(&player));
+        let target = Vec3::new(0.0, 10.0, 0.0);
+        let camera = world.workspace().borrow().current_camera().unwrap();
+        camera.set_cframe(CFrame::look_at(target + Vec3::new(0.0, 0.0, 20.0), target));
+
+        let brick = BasePart::new("Part");
+        brick.set_anchored(true);
+        brick.set_position(target);
+        Instance::set_parent(brick.instance(), Some(world.workspace().borrow().instance().clone()));
+        world.physics_mut().physics_world.add_part(&brick);
+        let bin = HopperBin::new("Clone", BinType::Clone);
+        Instance::set_parent(bin.instance(), player.backpack());
+        assert!(world.hopper_bins_mut().select(&player, &bin));
+        world.step().await.unwrap();
+
+        // Clicking the brick in the middle of the screen copies it, body and all
+        world.inject_input(InputEvent::MouseMove(Vec2::new(400.0, 300.0)));
+        world.inject_input(InputEvent::MouseButton { button: UserInputType::MouseButton1, pressed: true });
+        world.inject_input(InputEvent::MouseButton { button: UserInputType::MouseButton1, pressed: false });
+        world.step().await.unwrap();
+        let workspace = world.workspace().borrow().instance().clone();
+        let bricks: Vec<BasePart> = workspace.borrow().children().iter().filter_map(BasePart::from_instance).collect();
+        assert_eq!(bricks.len(), 2);
+        assert!(world.physics().physics_world.get_part_body(bricks[1].instance()).is_some());
+
+        // Grabbing drags it along the mouse ray
+        bin.set_bin_type(BinType::Grab);
+        world.inject_input(InputEvent::MouseButton { button: UserInputType::MouseButton1, pressed: true });
+        world.inject_input(InputEvent::MouseMove(Vec2::new(500.0, 300.0)));
+        world.step().await.unwrap();
+        assert!(brick.position().x > 1.0, "{:?}", brick.position());
+        let body = world.physics().physics_world.get_part_body(brick.instance()).unwrap();
+        assert!((world.physics().physics_world.get_rigid_body(body).unwrap().translation().x - brick.position().x).abs() < 1e-4);
+    }
+
+    #[tokio::test]
+    async fn test_flags_messages_and_leaderboard() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        let red = world.teams().borrow().add_team("Red", BrickColor::red());
+        world.teams().borrow().add_team("Blue", BrickColor::blue());
+        let player = world.add_player("Player1", 1);
+        player.set_team(Some(&red));
+        world.players().borrow_mut().set_local_player(Some(&player));
+        let character = world.load_character(&player);
+        let torso = BasePart::from_instance(&character.borrow().find_first_child("Torso").unwrap()).unwrap();
+        torso.set_anchored(true);
+
+        let workspace = world.workspace().borrow().instance().clone();
+        let stand = FlagStand::new(BrickColor::blue());
+        stand.part().set_position(Vec3::new(0.0, 0.0, -100.0));
+        Instance::set_parent(stand.instance(), Some(workspace.clone()));
+        let flag = Flag::new(BrickColor::blue());
+        Instance::set_parent(flag.instance(), Some(workspace.clone()));
+        flag.tool().handle().unwrap().set_position(torso.position());
+        world.step().await.unwrap();
+        assert!(Tools::equipped_tool(&player).is_some_and(|tool| Rc::ptr_eq(tool.instance(), flag.instance())));
+
+        // Team rows and the player are drawn, then the hint on top
+        let quads = world.physics().get_render_stats().queued_gui_quads;
+        let hint = Message::new("Hint");
+        hint.set_text("Capture the blue flag!");
+        Instance::set_parent(hint.instance(), Some(workspace.clone()));
+        world.step().await.unwrap();
+        assert!(quads >= 3);
+        assert_eq!(world.physics().get_render_stats().queued_gui_quads, quads + 1);
+    }
+
+    #[tokio::test]
+    async fn test_leaderstats_replicate_to_client_leaderboard() {
+        let mut config = test_config(60);
+        config.run_context = RunContext::Server;
+        config.enable_rendering = false;
+        let mut server = World::new(config);
+        server.initialize().await.unwrap();
+        let player = server.add_player("Player1", 1);
+        let leaderstats = rnr_datamodel::create_instance("Folder").unwrap();
+        leaderstats.borrow_mut().set_name("leaderstats");
+        Instance::set_parent(ValueObject::new_int("Kills", 5).instance(), Some(leaderstats.clone()));
+        Instance::set_parent(&leaderstats, Some(player.instance().clone()));
+        server.step().await.unwrap();
+        let updates = server.leaderstats_mut().take_updates();
+        assert_eq!(updates.len(), 1);
+
+        let mut client = World::new(test_config(60));
+        client.initialize().await.unwrap();
+        let local_player = client.add_player("Player1", 1);
+        client.players().borrow_mut().set_local_player(Some(&local_player));
+        client.step().await.unwrap();
+        assert_eq!(client.physics().get_render_stats().queued_gui_quads, 0);
+
+        // Clients show what the server sent, and don't read stats themselves
+        for update in &updates {
+            client.leaderstats_mut().apply_update(update);
+        }
+        client.step().await.unwrap();
+        let view = client.leaderboard_view();
+        assert_eq!(view.columns, vec!["Kills"]);
+        assert_eq!(view.groups[0].entries[0].values, vec![Some(LeaderstatValue::Int(5))]);
+        assert!(client.leaderstats_mut().take_updates().is_empty());
+        assert_eq!(client.physics().get_render_stats().queued_gui_quads, 2);
+    }
+
+    #[tokio::test]
+    async fn test_parts_land_on_terrain() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        let terrain = world.workspace().borrow().terrain().clone();
+        terrain.borrow_mut().fill_block(CFrame::new(Vec3::new(0.0, -4.0, 0.0)), Vec3::new(64.0, 8.0, 64.0), Material::Grass);
+
+        let crate_part = BasePart::new("Part");
+        crate_part.set_size(Vec3::new(2.0, 2.0, 2.0));
+        crate_part.set_position(Vec3::new(2.0, 10.0, 2.0));
+        Instance::set_parent(crate_part.instance(), Some(world.workspace().borrow().instance().clone()));
+        world.physics_mut().physics_world.add_part(&crate_part);
+
+        for _ in 0..180 {
+            world.step().await.unwrap();
+        }
+        assert!((crate_part.position().y - 1.0).abs() < 0.2, "{:?}", crate_part.position());
+        assert!(!world.physics().terrain_chunks().meshed_chunks().is_empty());
+
+        // Digging under the part drops it again
+        terrain.borrow_mut().fill_ball(Vec3::new(2.0, 0.0, 2.0), 12.0, Material::Air);
+        for _ in 0..60 {
+            world.step().await.unwrap();
+        }
+        assert!(crate_part.position().y < -1.0, "{:?}", crate_part.position());
+    }
+
+    #[tokio::test]
+    async fn test_saved_place_leaves_out_players_and_runtime_services() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        let brick = BasePart::new("Part");
+        brick.instance().borrow_mut().set_name("Brick");
+        Instance::set_parent(brick.instance(), Some(world.workspace().borrow().instance().clone()));
+        world.add_player("Player1", 1);
+        world.players().borrow().instance().borrow_mut().set_property("CharacterAutoLoads", false);
+        world.step().await.unwrap();
+
+        let path = std::env::temp_dir().join(format!("rnr_world_runtime_save_{}.rbxlx", std::process::id()));
+        world.physics().save(path.to_str().unwrap()).unwrap();
+        let xml = std::fs::read_to_string(&path).unwrap();
+        assert!(xml.contains("class=\"Players\""));
+        for class_name in ["Player", "Debris", "RunService", "UserInputService"] {
+            assert!(!xml.contains(&format!("class=\"{class_name}\"")), "{class_name} was saved");
+        }
+
+        let mut loaded = World::new(test_config(60));
+        loaded.initialize().await.unwrap();
+        loaded.physics_mut().load(path.to_str().unwrap(), None).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let workspace = loaded.workspace().borrow().instance().clone();
+        assert!(workspace.borrow().find_first_child("Brick").is_some());
+        assert!(workspace.borrow().find_first_child("Player1").is_none());
+        assert_eq!(workspace.borrow().num_children(), world.workspace().borrow().instance().borrow().num_children() - 1);
+        // The Players service keeps its settings, but not the players
+        assert_eq!(loaded.players().borrow().instance().borrow().num_children(), 0);
+        assert_eq!(loaded.players().borrow().instance().borrow().get_property("CharacterAutoLoads"), Some(&Variant::Bool(false)));
+        assert_eq!(loaded.datamodel().borrow().instance().borrow().children().len(), world.datamodel().borrow().instance().borrow().children().len());
+    }
+
+    #[tokio::test]
+    async fn test_http_requests_do_not_hold_up_steps() {
+        let mut world = World::new(test_config(60));
+        world.initialize().await.unwrap();
+        world.http_service().borrow_mut().set_http_enabled(true);
+
+        // The server only answers once the world has stepped, which never
+        // happens in time if the request blocks the step's thread
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/motd", listener.local_addr().unwrap());
+        let (stepped, wait_for_step) = mpsc::channel();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            while line != "\r\n" {
+                line.clear();
+                reader.read_line(&mut line).unwrap();
+            }
+            let answered_after_step = wait_for_step.recv_timeout(Duration::from_secs(5)).is_ok();
+            write!(reader.into_inner(), "HTTP/1.1 200 OK\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello").unwrap();
+            answered_after_step
+        });
+
+        let response = world.http_service().borrow().get_async(&url);
+        world.step().await.unwrap();
+        stepped.send(()).unwrap();
+        assert_eq!(response.await.unwrap(), "hello");
+        assert!(server.join().unwrap());
+    }
 }
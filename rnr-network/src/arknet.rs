@@ -167,6 +167,11 @@ impl ArkStream {
         self.data.extend_from_slice(&value.to_be_bytes());
     }
 
+    /// Write i64 (big endian)
+    pub fn write_i64(&mut self, value: i64) {
+        self.data.extend_from_slice(&value.to_be_bytes());
+    }
+
     /// Write f32 (big endian)
     pub fn write_f32(&mut self, value: f32) {
         self.data.extend_from_slice(&value.to_be_bytes());
@@ -226,6 +231,16 @@ impl ArkStream {
         }
     }
 
+    /// Read i64 (big endian)
+    pub fn read_i64(&mut self) -> Option<i64> {
+        if self.data.len() < 8 {
+            None
+        } else {
+            let bytes: Vec<u8> = self.data.drain(0..8).collect();
+            Some(i64::from_be_bytes(bytes.try_into().ok()?))
+        }
+    }
+
     /// Read f32 (big endian)
     pub fn read_f32(&mut self) -> Option<f32> {
         if self.data.len() < 4 {
@@ -292,10 +307,12 @@ mod tests {
         stream.write_u16(1337);
         stream.write_u32(0xDEADBEEF);
         stream.write_string("Hello World");
+        stream.write_i64(-5_000_000_000);
 
         assert_eq!(stream.read_u8(), Some(42));
         assert_eq!(stream.read_u16(), Some(1337));
         assert_eq!(stream.read_u32(), Some(0xDEADBEEF));
         assert_eq!(stream.read_string(), Some("Hello World".to_string()));
+        assert_eq!(stream.read_i64(), Some(-5_000_000_000));
     }
 }
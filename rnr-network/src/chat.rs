@@ -0,0 +1,233 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::rc::Rc;
+use rnr_core::instance::Instance;
+use rnr_core::types::BrickColor;
+use rnr_datamodel::{filtering_enabled, ChatDelivery, ChatMessage, ChatTarget};
+use crate::arknet::{ArkPacket, ArkStream};
+use crate::peer::ArkPeer;
+
+/// Packet id of a message typed by a player (client to server)
+pub const ID_CHAT_POST: u8 = 0x90;
+
+/// Packet id of a routed message (server to client)
+pub const ID_CHAT_MESSAGE: u8 = 0x91;
+
+/// Chat traffic between clients and the server
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatPacket {
+    /// A player typed a message
+    Post { text: String, target: ChatTarget },
+    /// The server delivers a filtered message to a client
+    Message(ChatMessage),
+}
+
+impl ChatPacket {
+    /// Serialize the packet
+    pub fn to_packet(&self) -> ArkPacket {
+        let mut stream = ArkStream::new();
+        match self {
+            ChatPacket::Post { text, target } => {
+                stream.write_u8(ID_CHAT_POST);
+                write_target(&mut stream, target);
+                stream.write_string(text);
+            }
+            ChatPacket::Message(message) => {
+                stream.write_u8(ID_CHAT_MESSAGE);
+                write_target(&mut stream, &message.target);
+                stream.write_i64(message.speaker_user_id);
+                stream.write_string(&message.speaker_name);
+                stream.write_string(&message.text);
+            }
+        }
+        stream.to_packet()
+    }
+
+    /// Parse a packet, returning None if it isn't a well-formed chat packet
+    pub fn from_packet(packet: ArkPacket) -> Option<Self> {
+        let mut stream = ArkStream::from_packet(packet);
+        match stream.read_u8()? {
+            ID_CHAT_POST => {
+                let target = read_target(&mut stream)?;
+                let text = stream.read_string()?;
+                Some(ChatPacket::Post { text, target })
+            }
+            ID_CHAT_MESSAGE => {
+                let target = read_target(&mut stream)?;
+                Some(ChatPacket::Message(ChatMessage {
+                    target,
+                    speaker_user_id: stream.read_i64()?,
+                    speaker_name: stream.read_string()?,
+                    text: stream.read_string()?,
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Send each delivery to its recipient's peer, returning how many were sent.
+/// Deliveries to players without a peer are dropped.
+pub fn send_chat_deliveries(deliveries: &[ChatDelivery], peers: &HashMap<i64, ArkPeer>) -> io::Result<usize> {
+    let mut sent = 0;
+    for delivery in deliveries {
+        if let Some(peer) = peers.get(&delivery.recipient_user_id) {
+            peer.send_packet(&ChatPacket::Message(delivery.message.clone()).to_packet())?;
+            sent += 1;
+        }
+    }
+    Ok(sent)
+}
+
+/// Relay a message a client sent straight to other clients, as legacy
+/// clients did, returning how many peers it was sent to. Only places with
+/// FilteringEnabled off trust clients with this; otherwise, or if the
+/// client speaks as someone else, the message is dropped and the client
+/// has to post it through the server. Whispers only reach their target,
+/// and team and system messages are left to the server.
+pub fn relay_chat_message(
+    packet: &ChatPacket,
+    user_id: i64,
+    workspace: &Rc<RefCell<Instance>>,
+    peers: &HashMap<i64, ArkPeer>,
+) -> io::Result<usize> {
+    let ChatPacket::Message(message) = packet else {
+        return Ok(0);
+    };
+    if filtering_enabled(workspace) || message.speaker_user_id != user_id {
+        return Ok(0);
+    }
+
+    let recipients: Vec<&ArkPeer> = match message.target {
+        ChatTarget::All => peers.iter().filter(|(id, _)| **id != user_id).map(|(_, peer)| peer).collect(),
+        ChatTarget::Whisper(target) => peers.get(&target).into_iter().collect(),
+        ChatTarget::Team(_) | ChatTarget::System => Vec::new(),
+    };
+    let packet = packet.to_packet();
+    for peer in &recipients {
+        peer.send_packet(&packet)?;
+    }
+    Ok(recipients.len())
+}
+
+fn write_target(stream: &mut ArkStream, target: &ChatTarget) {
+    match target {
+        ChatTarget::All => stream.write_u8(0),
+        ChatTarget::Team(color) => {
+            stream.write_u8(1);
+            stream.write_u16(color.number());
+        }
+        ChatTarget::Whisper(user_id) => {
+            stream.write_u8(2);
+            stream.write_i64(*user_id);
+        }
+        ChatTarget::System => stream.write_u8(3),
+    }
+}
+
+fn read_target(stream: &mut ArkStream) -> Option<ChatTarget> {
+    match stream.read_u8()? {
+        0 => Some(ChatTarget::All),
+        1 => Some(ChatTarget::Team(BrickColor::new(stream.read_u16()?))),
+        2 => Some(ChatTarget::Whisper(stream.read_i64()?)),
+        3 => Some(ChatTarget::System),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    use rnr_datamodel::Workspace;
+    use crate::arknet::ArkSocket;
+
+    fn message(target: ChatTarget) -> ChatMessage {
+        ChatMessage {
+            speaker_user_id: 42,
+            speaker_name: "Builderman".to_string(),
+            text: "hello".to_string(),
+            target,
+        }
+    }
+
+    #[test]
+    fn test_chat_packet_round_trip() {
+        let packets = [
+            ChatPacket::Post { text: "/w bob hi".to_string(), target: ChatTarget::All },
+            ChatPacket::Message(message(ChatTarget::Team(BrickColor::red()))),
+            ChatPacket::Message(message(ChatTarget::Whisper(7))),
+            ChatPacket::Message(message(ChatTarget::System)),
+        ];
+
+        for packet in packets {
+            assert_eq!(ChatPacket::from_packet(packet.to_packet()), Some(packet));
+        }
+    }
+
+    #[test]
+    fn test_rejects_malformed_packets() {
+        assert_eq!(ChatPacket::from_packet(ArkPacket::from_data(vec![0x01])), None);
+        assert_eq!(ChatPacket::from_packet(ArkPacket::from_data(vec![ID_CHAT_MESSAGE, 9])), None);
+
+        let mut truncated = ChatPacket::Message(message(ChatTarget::All)).to_packet();
+        truncated.data.truncate(truncated.size() - 1);
+        assert_eq!(ChatPacket::from_packet(truncated), None);
+    }
+
+    #[test]
+    fn test_deliveries_reach_clients() {
+        let server = Rc::new(RefCell::new(ArkSocket::new("127.0.0.1:0".parse().unwrap()).unwrap()));
+        let client = ArkSocket::new("127.0.0.1:0".parse().unwrap()).unwrap();
+
+        let mut peers = HashMap::new();
+        peers.insert(1, ArkPeer::with_remote(client.local_addr().unwrap(), server.clone()));
+
+        let deliveries = vec![
+            ChatDelivery { recipient_user_id: 1, message: message(ChatTarget::All) },
+            ChatDelivery { recipient_user_id: 2, message: message(ChatTarget::All) },
+        ];
+        assert_eq!(send_chat_deliveries(&deliveries, &peers).unwrap(), 1);
+
+        // The client socket is non-blocking, so poll until the packet lands
+        let mut buf = [0u8; 1024];
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let size = loop {
+            match client.recv_from(&mut buf) {
+                Ok((size, _)) => break size,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock && Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                Err(e) => panic!("no chat packet received: {}", e),
+            }
+        };
+
+        let packet = ChatPacket::from_packet(ArkPacket::from_data(buf[..size].to_vec()));
+        assert_eq!(packet, Some(ChatPacket::Message(message(ChatTarget::All))));
+    }
+
+    #[test]
+    fn test_client_messages_are_relayed_without_filtering() {
+        let server = Rc::new(RefCell::new(ArkSocket::new("127.0.0.1:0".parse().unwrap()).unwrap()));
+        let client = ArkSocket::new("127.0.0.1:0".parse().unwrap()).unwrap();
+        let mut peers = HashMap::new();
+        for user_id in [7, 42, 99] {
+            peers.insert(user_id, ArkPeer::with_remote(client.local_addr().unwrap(), server.clone()));
+        }
+
+        let workspace = Workspace::new();
+        let workspace = workspace.borrow();
+        let relay = |packet: &ChatPacket, user_id| relay_chat_message(packet, user_id, workspace.instance(), &peers).unwrap();
+
+        // Everyone but the speaker hears it, and whispers only reach their target
+        assert_eq!(relay(&ChatPacket::Message(message(ChatTarget::All)), 42), 2);
+        assert_eq!(relay(&ChatPacket::Message(message(ChatTarget::Whisper(7))), 42), 1);
+        assert_eq!(relay(&ChatPacket::Message(message(ChatTarget::System)), 42), 0);
+        // Clients can't speak for others
+        assert_eq!(relay(&ChatPacket::Message(message(ChatTarget::All)), 7), 0);
+
+        workspace.set_filtering_enabled(true);
+        assert_eq!(relay(&ChatPacket::Message(message(ChatTarget::All)), 42), 0);
+    }
+}
@@ -0,0 +1,106 @@
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+use rnr_core::instance::Instance;
+use rnr_datamodel::{instance_path, resolve_path, ClickDetector, ClickDetectors, DetectorClick, Players};
+use crate::arknet::{ArkPacket, ArkStream};
+use crate::peer::ArkPeer;
+
+/// Packet id of a player clicking a ClickDetector (client to server)
+pub const ID_CLICK_DETECTOR_CLICKED: u8 = 0x94;
+
+/// A click sent to the server. The detector is named by its path from the
+/// workspace, since both sides hold their own copy of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClickPacket {
+    pub path: Vec<String>,
+}
+
+impl ClickPacket {
+    /// Serialize the packet
+    pub fn to_packet(&self) -> ArkPacket {
+        let mut stream = ArkStream::new();
+        stream.write_u8(ID_CLICK_DETECTOR_CLICKED);
+        stream.write_u32(self.path.len() as u32);
+        for name in &self.path {
+            stream.write_string(name);
+        }
+        stream.to_packet()
+    }
+
+    /// Parse a packet, returning None if it isn't a well-formed click packet
+    pub fn from_packet(packet: ArkPacket) -> Option<Self> {
+        let mut stream = ArkStream::from_packet(packet);
+        if stream.read_u8()? != ID_CLICK_DETECTOR_CLICKED {
+            return None;
+        }
+        let len = stream.read_u32()?;
+        let path = (0..len).map(|_| stream.read_string()).collect::<Option<Vec<_>>>()?;
+        Some(ClickPacket { path })
+    }
+}
+
+/// Send clicks to the server, returning how many were sent. Clicks on
+/// detectors outside `workspace` are dropped.
+pub fn send_detector_clicks(clicks: &[DetectorClick], workspace: &Rc<RefCell<Instance>>, server: &ArkPeer) -> io::Result<usize> {
+    let mut sent = 0;
+    for click in clicks {
+        if let Some(path) = instance_path(workspace, click.detector.instance()) {
+            server.send_packet(&ClickPacket { path }.to_packet())?;
+            sent += 1;
+        }
+    }
+    Ok(sent)
+}
+
+/// Fire MouseClick on the server for a click packet from the player with
+/// `user_id`. Returns false if the detector is gone or out of the
+/// player's reach.
+pub fn receive_detector_click(
+    packet: &ClickPacket,
+    user_id: i64,
+    workspace: &Rc<RefCell<Instance>>,
+    players: &Players,
+    click_detectors: &mut ClickDetectors,
+) -> bool {
+    match resolve_path(workspace, &packet.path).as_ref().and_then(ClickDetector::from_instance) {
+        Some(detector) => click_detectors.apply_click(players, user_id, &detector),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rnr_datamodel::{BasePart, Debris, Player};
+
+    #[test]
+    fn test_clicks_reach_server_scripts() {
+        let packet = ClickPacket { path: vec!["Button".to_string(), "ClickDetector".to_string()] };
+        assert_eq!(ClickPacket::from_packet(packet.to_packet()), Some(packet.clone()));
+        assert_eq!(ClickPacket::from_packet(ArkPacket::from_data(vec![ID_CLICK_DETECTOR_CLICKED, 1])), None);
+
+        let workspace = Instance::new();
+        let players = Players::new();
+        let player = Players::add_player(&players, "Player1", 1);
+        let character = players.borrow_mut().load_character(&player, &workspace, &mut Debris::new().borrow_mut());
+        let head = BasePart::from_instance(&character.borrow().find_first_child("Head").unwrap()).unwrap();
+        let button = BasePart::new("Part");
+        button.instance().borrow_mut().set_name("Button");
+        button.set_cframe(head.cframe());
+        Instance::set_parent(button.instance(), Some(workspace.clone()));
+        let detector = ClickDetector::new();
+        Instance::set_parent(detector.instance(), Some(button.instance().clone()));
+
+        let mut click_detectors = ClickDetectors::new();
+        let clicked_by = Rc::new(RefCell::new(Vec::new()));
+        let log = clicked_by.clone();
+        click_detectors.mouse_click(&detector).connect(move |player: &Player| log.borrow_mut().push(player.name()));
+
+        assert!(receive_detector_click(&packet, 1, &workspace, &players.borrow(), &mut click_detectors));
+        assert_eq!(*clicked_by.borrow(), vec!["Player1".to_string()]);
+
+        let missing = ClickPacket { path: vec!["Door".to_string()] };
+        assert!(!receive_detector_click(&missing, 1, &workspace, &players.borrow(), &mut click_detectors));
+    }
+}
@@ -0,0 +1,145 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::rc::Rc;
+use rnr_core::instance::Instance;
+use rnr_datamodel::{filtering_enabled, LeaderstatValue, LeaderstatsUpdate};
+use crate::arknet::{ArkPacket, ArkStream};
+use crate::peer::ArkPeer;
+
+/// Packet id of a player's leaderstats changing (server to client)
+pub const ID_LEADERSTATS: u8 = 0x95;
+
+/// Tag of an IntValue stat
+const STAT_INT: u8 = 0;
+
+/// Tag of a StringValue stat
+const STAT_STRING: u8 = 1;
+
+/// A leaderstats update replicated to clients
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderstatsPacket(pub LeaderstatsUpdate);
+
+impl LeaderstatsPacket {
+    /// Serialize the packet
+    pub fn to_packet(&self) -> ArkPacket {
+        let mut stream = ArkStream::new();
+        stream.write_u8(ID_LEADERSTATS);
+        stream.write_i64(self.0.user_id);
+        stream.write_u16(self.0.stats.len() as u16);
+        for (name, value) in &self.0.stats {
+            stream.write_string(name);
+            match value {
+                LeaderstatValue::Int(value) => {
+                    stream.write_u8(STAT_INT);
+                    stream.write_i64(*value);
+                }
+                LeaderstatValue::String(value) => {
+                    stream.write_u8(STAT_STRING);
+                    stream.write_string(value);
+                }
+            }
+        }
+        stream.to_packet()
+    }
+
+    /// Parse a packet, returning None if it isn't a well-formed leaderstats
+    /// packet
+    pub fn from_packet(packet: ArkPacket) -> Option<Self> {
+        let mut stream = ArkStream::from_packet(packet);
+        if stream.read_u8()? != ID_LEADERSTATS {
+            return None;
+        }
+        let user_id = stream.read_i64()?;
+        let count = stream.read_u16()?;
+        let mut stats = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let name = stream.read_string()?;
+            let value = match stream.read_u8()? {
+                STAT_INT => LeaderstatValue::Int(stream.read_i64()?),
+                STAT_STRING => LeaderstatValue::String(stream.read_string()?),
+                _ => return None,
+            };
+            stats.push((name, value));
+        }
+        Some(LeaderstatsPacket(LeaderstatsUpdate { user_id, stats }))
+    }
+}
+
+/// Send every update to every peer, returning how many packets were sent
+pub fn send_leaderstats_updates(updates: &[LeaderstatsUpdate], peers: &HashMap<i64, ArkPeer>) -> io::Result<usize> {
+    let mut sent = 0;
+    for update in updates {
+        let packet = LeaderstatsPacket(update.clone()).to_packet();
+        for peer in peers.values() {
+            peer.send_packet(&packet)?;
+            sent += 1;
+        }
+    }
+    Ok(sent)
+}
+
+/// Relay a leaderstats packet a client sent about its own player to every
+/// other peer, returning how many packets were sent. Nothing is sent when
+/// FilteringEnabled keeps client changes from the server, or when the
+/// packet is about someone else.
+pub fn relay_leaderstats_packet(
+    packet: &LeaderstatsPacket,
+    user_id: i64,
+    workspace: &Rc<RefCell<Instance>>,
+    peers: &HashMap<i64, ArkPeer>,
+) -> io::Result<usize> {
+    if filtering_enabled(workspace) || packet.0.user_id != user_id {
+        return Ok(0);
+    }
+
+    let packet = packet.to_packet();
+    let mut sent = 0;
+    for (_, peer) in peers.iter().filter(|(id, _)| **id != user_id) {
+        peer.send_packet(&packet)?;
+        sent += 1;
+    }
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rnr_datamodel::Workspace;
+    use crate::arknet::ArkSocket;
+
+    #[test]
+    fn test_leaderstats_packet_round_trip() {
+        let packets = [
+            LeaderstatsPacket(LeaderstatsUpdate {
+                user_id: 7,
+                stats: vec![("KOs".to_string(), LeaderstatValue::Int(-3)), ("Rank".to_string(), LeaderstatValue::String("Noob".to_string()))],
+            }),
+            LeaderstatsPacket(LeaderstatsUpdate { user_id: 7, stats: Vec::new() }),
+        ];
+        for packet in packets {
+            assert_eq!(LeaderstatsPacket::from_packet(packet.to_packet()), Some(packet));
+        }
+
+        assert_eq!(LeaderstatsPacket::from_packet(ArkPacket::from_data(vec![ID_LEADERSTATS, 1])), None);
+    }
+
+    #[test]
+    fn test_client_leaderstats_need_filtering_disabled() {
+        let server = Rc::new(RefCell::new(ArkSocket::new("127.0.0.1:0".parse().unwrap()).unwrap()));
+        let client = ArkSocket::new("127.0.0.1:0".parse().unwrap()).unwrap();
+        let mut peers = HashMap::new();
+        for user_id in [7, 8] {
+            peers.insert(user_id, ArkPeer::with_remote(client.local_addr().unwrap(), server.clone()));
+        }
+
+        let workspace = Workspace::new();
+        let workspace = workspace.borrow();
+        let packet = LeaderstatsPacket(LeaderstatsUpdate { user_id: 7, stats: vec![("KOs".to_string(), LeaderstatValue::Int(1000))] });
+        assert_eq!(relay_leaderstats_packet(&packet, 7, workspace.instance(), &peers).unwrap(), 1);
+        assert_eq!(relay_leaderstats_packet(&packet, 8, workspace.instance(), &peers).unwrap(), 0);
+
+        workspace.set_filtering_enabled(true);
+        assert_eq!(relay_leaderstats_packet(&packet, 7, workspace.instance(), &peers).unwrap(), 0);
+    }
+}
@@ -1,11 +1,20 @@
 pub mod arknet;
+pub mod chat;
+pub mod click_detector;
+pub mod leaderstats;
 pub mod peer;
+pub mod server;
+pub mod tool;
 // pub mod replicator; // TODO: Implement later
 // pub mod client; // TODO: Implement later
-// pub mod server; // TODO: Implement later
 
 pub use arknet::*;
+pub use chat::*;
+pub use click_detector::*;
+pub use leaderstats::*;
 pub use peer::*;
+pub use server::*;
+pub use tool::*;
 // pub use replicator::*;
 
 #[cfg(test)]
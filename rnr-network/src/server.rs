@@ -0,0 +1,146 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::rc::Rc;
+use rnr_core::instance::Instance;
+use crate::arknet::{ArkAddress, ArkPacket, ArkSocket};
+use crate::chat::{relay_chat_message, ChatPacket, ID_CHAT_MESSAGE};
+use crate::leaderstats::{relay_leaderstats_packet, LeaderstatsPacket, ID_LEADERSTATS};
+use crate::peer::ArkPeer;
+use crate::tool::{relay_tool_packet, ToolPacket, ID_TOOL_EQUIPPED, ID_TOOL_UNEQUIPPED};
+
+/// Server end of the game's connections: one socket, and a peer for each
+/// connected player keyed by user id
+pub struct NetworkServer {
+    socket: Rc<RefCell<ArkSocket>>,
+    peers: HashMap<i64, ArkPeer>,
+}
+
+impl NetworkServer {
+    /// Create a server listening on `bind_addr`
+    pub fn new(bind_addr: std::net::SocketAddr) -> io::Result<Self> {
+        Ok(Self {
+            socket: Rc::new(RefCell::new(ArkSocket::new(bind_addr)?)),
+            peers: HashMap::new(),
+        })
+    }
+
+    /// Get the address the server listens on
+    pub fn local_addr(&self) -> io::Result<ArkAddress> {
+        self.socket.borrow().local_addr()
+    }
+
+    /// Connect the player with `user_id` at `remote`
+    pub fn add_peer(&mut self, user_id: i64, remote: ArkAddress) {
+        self.peers.insert(user_id, ArkPeer::with_remote(remote, self.socket.clone()));
+    }
+
+    /// Disconnect the player with `user_id`
+    pub fn remove_peer(&mut self, user_id: i64) {
+        self.peers.remove(&user_id);
+    }
+
+    /// Get the peers of the connected players
+    pub fn peers(&self) -> &HashMap<i64, ArkPeer> {
+        &self.peers
+    }
+
+    /// Handle a packet from the player with `user_id`, returning how many
+    /// packets it caused to be sent. Chat messages, tool and leaderstats
+    /// changes made by the client are relayed to the other clients, which
+    /// `workspace`'s FilteringEnabled can forbid. Other packets are left to
+    /// the caller.
+    pub fn handle_packet(&self, user_id: i64, packet: ArkPacket, workspace: &Rc<RefCell<Instance>>) -> io::Result<usize> {
+        match packet.data.first().copied() {
+            Some(ID_CHAT_MESSAGE) => match ChatPacket::from_packet(packet) {
+                Some(chat) => relay_chat_message(&chat, user_id, workspace, &self.peers),
+                None => Ok(0),
+            },
+            Some(ID_TOOL_EQUIPPED | ID_TOOL_UNEQUIPPED) => match ToolPacket::from_packet(packet) {
+                Some(tool) => relay_tool_packet(&tool, user_id, workspace, &self.peers),
+                None => Ok(0),
+            },
+            Some(ID_LEADERSTATS) => match LeaderstatsPacket::from_packet(packet) {
+                Some(stats) => relay_leaderstats_packet(&stats, user_id, workspace, &self.peers),
+                None => Ok(0),
+            },
+            _ => Ok(0),
+        }
+    }
+
+    /// Handle every packet waiting on the socket, returning how many were
+    /// received. Packets from addresses with no player are dropped.
+    pub fn poll(&self, workspace: &Rc<RefCell<Instance>>) -> io::Result<usize> {
+        let mut buf = [0u8; 65535];
+        let mut received = 0;
+        loop {
+            let (size, addr) = match self.socket.borrow().recv_from(&mut buf) {
+                Ok(packet) => packet,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(received),
+                Err(e) => return Err(e),
+            };
+            let Some((&user_id, _)) = self.peers.iter().find(|(_, peer)| *peer.remote_addr() == addr) else {
+                continue;
+            };
+            self.handle_packet(user_id, ArkPacket::from_data(buf[..size].to_vec()), workspace)?;
+            received += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    use rnr_datamodel::{ToolUpdate, Workspace};
+
+    /// Poll the server until it has received a packet
+    fn poll_one(server: &NetworkServer, workspace: &Rc<RefCell<Instance>>) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while server.poll(workspace).unwrap() == 0 {
+            assert!(Instant::now() < deadline, "no packet reached the server");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    /// Wait for the next packet on a client socket
+    fn receive(client: &ArkSocket) -> ArkPacket {
+        let mut buf = [0u8; 1024];
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            match client.recv_from(&mut buf) {
+                Ok((size, _)) => return ArkPacket::from_data(buf[..size].to_vec()),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock && Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                Err(e) => panic!("no packet received: {}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn test_client_packets_are_relayed_unless_filtering_enabled() {
+        let mut server = NetworkServer::new("127.0.0.1:0".parse().unwrap()).unwrap();
+        let sender = ArkSocket::new("127.0.0.1:0".parse().unwrap()).unwrap();
+        let watcher = ArkSocket::new("127.0.0.1:0".parse().unwrap()).unwrap();
+        server.add_peer(1, sender.local_addr().unwrap());
+        server.add_peer(2, watcher.local_addr().unwrap());
+        let server_addr = server.local_addr().unwrap();
+
+        let workspace = Workspace::new();
+        let workspace = workspace.borrow();
+        let equipped = ToolPacket(ToolUpdate::Equipped { user_id: 1, tool_name: "LinkedSword".to_string() });
+        let unequipped = ToolPacket(ToolUpdate::Unequipped { user_id: 1 });
+
+        // With FilteringEnabled, the equip never leaves the server...
+        workspace.set_filtering_enabled(true);
+        sender.send_to(&server_addr, &equipped.to_packet().data).unwrap();
+        poll_one(&server, workspace.instance());
+
+        // ...so the first thing the other client hears is the later unequip
+        workspace.set_filtering_enabled(false);
+        sender.send_to(&server_addr, &unequipped.to_packet().data).unwrap();
+        poll_one(&server, workspace.instance());
+        assert_eq!(ToolPacket::from_packet(receive(&watcher)), Some(unequipped));
+    }
+}
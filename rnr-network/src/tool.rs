@@ -0,0 +1,126 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::rc::Rc;
+use rnr_core::instance::Instance;
+use rnr_datamodel::{filtering_enabled, ToolUpdate};
+use crate::arknet::{ArkPacket, ArkStream};
+use crate::peer::ArkPeer;
+
+/// Packet id of a player equipping a tool (server to client)
+pub const ID_TOOL_EQUIPPED: u8 = 0x92;
+
+/// Packet id of a player putting away their tool (server to client)
+pub const ID_TOOL_UNEQUIPPED: u8 = 0x93;
+
+/// A tool update replicated to clients
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolPacket(pub ToolUpdate);
+
+impl ToolPacket {
+    /// Serialize the packet
+    pub fn to_packet(&self) -> ArkPacket {
+        let mut stream = ArkStream::new();
+        match &self.0 {
+            ToolUpdate::Equipped { user_id, tool_name } => {
+                stream.write_u8(ID_TOOL_EQUIPPED);
+                stream.write_i64(*user_id);
+                stream.write_string(tool_name);
+            }
+            ToolUpdate::Unequipped { user_id } => {
+                stream.write_u8(ID_TOOL_UNEQUIPPED);
+                stream.write_i64(*user_id);
+            }
+        }
+        stream.to_packet()
+    }
+
+    /// Parse a packet, returning None if it isn't a well-formed tool packet
+    pub fn from_packet(packet: ArkPacket) -> Option<Self> {
+        let mut stream = ArkStream::from_packet(packet);
+        match stream.read_u8()? {
+            ID_TOOL_EQUIPPED => Some(ToolPacket(ToolUpdate::Equipped {
+                user_id: stream.read_i64()?,
+                tool_name: stream.read_string()?,
+            })),
+            ID_TOOL_UNEQUIPPED => Some(ToolPacket(ToolUpdate::Unequipped { user_id: stream.read_i64()? })),
+            _ => None,
+        }
+    }
+}
+
+/// Send every update to every peer, returning how many packets were sent
+pub fn send_tool_updates(updates: &[ToolUpdate], peers: &HashMap<i64, ArkPeer>) -> io::Result<usize> {
+    let mut sent = 0;
+    for update in updates {
+        let packet = ToolPacket(update.clone()).to_packet();
+        for peer in peers.values() {
+            peer.send_packet(&packet)?;
+            sent += 1;
+        }
+    }
+    Ok(sent)
+}
+
+/// Relay a tool packet a client sent about its own player to every other
+/// peer, returning how many packets were sent. Nothing is sent when
+/// FilteringEnabled keeps client changes from the server, or when the
+/// packet is about someone else.
+pub fn relay_tool_packet(
+    packet: &ToolPacket,
+    user_id: i64,
+    workspace: &Rc<RefCell<Instance>>,
+    peers: &HashMap<i64, ArkPeer>,
+) -> io::Result<usize> {
+    let (ToolUpdate::Equipped { user_id: subject, .. } | ToolUpdate::Unequipped { user_id: subject }) = packet.0;
+    if filtering_enabled(workspace) || subject != user_id {
+        return Ok(0);
+    }
+
+    let packet = packet.to_packet();
+    let mut sent = 0;
+    for (_, peer) in peers.iter().filter(|(id, _)| **id != user_id) {
+        peer.send_packet(&packet)?;
+        sent += 1;
+    }
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rnr_datamodel::Workspace;
+    use crate::arknet::ArkSocket;
+
+    #[test]
+    fn test_tool_packet_round_trip() {
+        let packets = [
+            ToolPacket(ToolUpdate::Equipped { user_id: 7, tool_name: "LinkedSword".to_string() }),
+            ToolPacket(ToolUpdate::Unequipped { user_id: 7 }),
+        ];
+        for packet in packets {
+            assert_eq!(ToolPacket::from_packet(packet.to_packet()), Some(packet));
+        }
+
+        assert_eq!(ToolPacket::from_packet(ArkPacket::from_data(vec![ID_TOOL_EQUIPPED, 1])), None);
+    }
+
+    #[test]
+    fn test_client_tool_changes_need_filtering_disabled() {
+        let server = Rc::new(RefCell::new(ArkSocket::new("127.0.0.1:0".parse().unwrap()).unwrap()));
+        let client = ArkSocket::new("127.0.0.1:0".parse().unwrap()).unwrap();
+        let mut peers = HashMap::new();
+        for user_id in [7, 8, 9] {
+            peers.insert(user_id, ArkPeer::with_remote(client.local_addr().unwrap(), server.clone()));
+        }
+
+        let workspace = Workspace::new();
+        let workspace = workspace.borrow();
+        let packet = ToolPacket(ToolUpdate::Unequipped { user_id: 7 });
+        assert_eq!(relay_tool_packet(&packet, 7, workspace.instance(), &peers).unwrap(), 2);
+        assert_eq!(relay_tool_packet(&packet, 8, workspace.instance(), &peers).unwrap(), 0);
+
+        workspace.set_filtering_enabled(true);
+        assert_eq!(relay_tool_packet(&packet, 7, workspace.instance(), &peers).unwrap(), 0);
+    }
+}
@@ -0,0 +1,144 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use glam::{Mat4, Vec3, Vec4};
+use rnr_core::instance::Instance;
+use rnr_datamodel::{limb_appearances, BasePart, LimbAppearance, Players};
+use rnr_rendering::{Material, MaterialProperty, Renderer, Texture, PLACEHOLDER_TEXTURE};
+
+/// Mesh drawn for limbs without a CharacterMesh
+pub const LIMB_MESH: &str = "Cube";
+
+/// Material limbs without textures are drawn with
+pub const LIMB_MATERIAL: &str = "InstancedMaterial";
+
+/// Get the name of the material drawing a stack of textures
+pub fn clothing_material_name(textures: &[String]) -> String {
+    format!("Clothing:{}", textures.join("|"))
+}
+
+/// Get the material a limb is drawn with, registering it the first time
+/// its textures are seen. Clothing templates are loaded through the
+/// renderer's TextureManager; ones that can't be loaded are drawn with the
+/// white placeholder texture instead. The material is named after the
+/// textures it ends up with, so a template registered later replaces the
+/// placeholder.
+pub fn prepare_limb_material(renderer: &mut Renderer, appearance: &LimbAppearance) -> String {
+    if appearance.textures.is_empty() {
+        return LIMB_MATERIAL.to_string();
+    }
+
+    let textures: Vec<String> = appearance.textures.iter()
+        .map(|texture| match renderer.texture_manager.load_texture(texture) {
+            Ok(_) => texture.clone(),
+            Err(_) => PLACEHOLDER_TEXTURE.to_string(),
+        })
+        .collect();
+    if textures.iter().any(|texture| texture == PLACEHOLDER_TEXTURE) && renderer.texture_manager.get_texture(PLACEHOLDER_TEXTURE).is_none() {
+        renderer.texture_manager.register_texture(Texture::solid_color(PLACEHOLDER_TEXTURE, [255, 255, 255, 255]));
+    }
+
+    let name = clothing_material_name(&textures);
+    if renderer.material_manager.get_material(&name).is_none() {
+        let mut material = Material::new(&name).with_specular(Vec3::ONE, 12.5);
+        for (layer, texture) in textures.iter().enumerate() {
+            material = material.with_property(&format!("Texture{layer}"), MaterialProperty::Texture(texture.clone()));
+        }
+        renderer.material_manager.register_material(material);
+    }
+    name
+}
+
+/// Queue a character's limbs for drawing with their meshes, colors and
+/// clothing. Returns how many limbs were queued.
+pub fn draw_character(renderer: &mut Renderer, character: &Rc<RefCell<Instance>>) -> usize {
+    let appearances = limb_appearances(character);
+    for appearance in &appearances {
+        let material = prepare_limb_material(renderer, appearance);
+        let mesh = appearance.mesh_id.as_deref()
+            .filter(|mesh_id| renderer.mesh_manager.get_mesh(mesh_id).is_some())
+            .unwrap_or(LIMB_MESH);
+        renderer.draw_mesh(mesh, &material, part_transform(&appearance.part), part_color(&appearance.part));
+    }
+    appearances.len()
+}
+
+/// Queue every player's character for drawing
+pub fn draw_characters(renderer: &mut Renderer, players: &Players) -> usize {
+    players.get_players().iter()
+        .filter_map(|player| player.character())
+        .map(|character| draw_character(renderer, &character))
+        .sum()
+}
+
+fn part_transform(part: &BasePart) -> Mat4 {
+    let cframe = part.cframe();
+    Mat4::from_scale_rotation_translation(part.size(), cframe.to_quat(), cframe.position)
+}
+
+fn part_color(part: &BasePart) -> Vec4 {
+    let color = part.brick_color().color();
+    Vec4::new(color.r, color.g, color.b, 1.0 - part.transparency() as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rnr_datamodel::{create_model, Clothing, ClothingKind, Debris};
+
+    #[test]
+    fn test_clothing_materials_and_textures() {
+        use rnr_core::content::ContentProvider;
+
+        let dir = std::env::temp_dir().join(format!("rnr_clothing_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut png_bytes = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut png_bytes, 1, 1);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.write_header().unwrap().write_image_data(&[10, 20, 30, 255]).unwrap();
+        }
+        std::fs::write(dir.join("shirt.png"), &png_bytes).unwrap();
+
+        let workspace = create_model("Workspace");
+        let players = Players::new();
+        let debris = Debris::new();
+        let player = Players::add_player(&players, "Player1", 1);
+        let shirt = Clothing::new(ClothingKind::Shirt, "rbxasset://shirt.png");
+        let pants = Clothing::new(ClothingKind::Pants, "rbxasset://pants.png");
+        players.borrow_mut().set_character_appearance(&player, vec![shirt.instance().clone(), pants.instance().clone()]);
+        players.borrow_mut().load_character(&player, &workspace, &mut debris.borrow_mut());
+
+        let mut renderer = Renderer::new();
+        renderer.texture_manager.set_content_provider(ContentProvider::new(&dir));
+        assert_eq!(draw_characters(&mut renderer, &players.borrow()), 6);
+        assert_eq!(renderer.render_queue.len(), 6);
+
+        // The shirt template is decoded from the content folder
+        assert_eq!(renderer.texture_manager.get_texture("rbxasset://shirt.png").unwrap().data, vec![10, 20, 30, 255]);
+        let drawn_with = |renderer: &Renderer, textures: &[&str]| {
+            let material = clothing_material_name(&textures.iter().map(|texture| texture.to_string()).collect::<Vec<_>>());
+            renderer.render_queue.iter().filter(|command| command.material_name == material).count()
+        };
+        let shirt_material = clothing_material_name(&["rbxasset://shirt.png".to_string()]);
+        assert_eq!(drawn_with(&renderer, &["rbxasset://shirt.png"]), 2);
+        assert!(matches!(
+            renderer.material_manager.get_material(&shirt_material).unwrap().get_property("Texture0"),
+            Some(MaterialProperty::Texture(texture)) if texture == "rbxasset://shirt.png"
+        ));
+        assert!(renderer.render_queue.iter().all(|command| command.mesh_name == LIMB_MESH));
+
+        // The missing pants template is drawn with the placeholder, which
+        // never takes the template's name
+        assert_eq!(drawn_with(&renderer, &[PLACEHOLDER_TEXTURE]), 2);
+        assert_eq!(drawn_with(&renderer, &[PLACEHOLDER_TEXTURE, "rbxasset://shirt.png"]), 1);
+        assert!(renderer.texture_manager.get_texture("rbxasset://pants.png").is_none());
+
+        // Once the template is available, the legs switch to it
+        renderer.texture_manager.register_texture(Texture::solid_color("rbxasset://pants.png", [0, 0, 255, 255]));
+        renderer.render_queue.clear();
+        draw_characters(&mut renderer, &players.borrow());
+        assert_eq!(drawn_with(&renderer, &["rbxasset://pants.png"]), 2);
+        assert_eq!(drawn_with(&renderer, &["rbxasset://pants.png", "rbxasset://shirt.png"]), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
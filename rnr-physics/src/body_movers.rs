@@ -0,0 +1,275 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use glam::{Quat, Vec3};
+use rapier3d::prelude::*;
+use rnr_core::instance::Instance;
+use rnr_datamodel::{BodyMover, BodyMoverKind};
+use crate::physics::PhysicsWorld;
+
+/// The state of a rigid body a body mover reads
+#[derive(Debug, Clone, Copy)]
+pub struct BodyState {
+    pub position: Vec3,
+    pub rotation: Quat,
+    pub linear_velocity: Vec3,
+    pub angular_velocity: Vec3,
+    pub mass: f32,
+    /// Smallest principal moment of inertia
+    pub inertia: f32,
+    /// Acceleration of gravity on the body
+    pub gravity: Vec3,
+}
+
+impl BodyState {
+    /// Read the state of a rapier body falling under `gravity`
+    pub fn from_rigid_body(body: &RigidBody, gravity: Vec3) -> Self {
+        let translation = body.translation();
+        let rotation = body.rotation().quaternion();
+        let linvel = body.linvel();
+        let angvel = body.angvel();
+        let principal_inertia = body.mass_properties().local_mprops.principal_inertia();
+        Self {
+            position: Vec3::new(translation.x, translation.y, translation.z),
+            rotation: Quat::from_xyzw(rotation.i, rotation.j, rotation.k, rotation.w),
+            linear_velocity: Vec3::new(linvel.x, linvel.y, linvel.z),
+            angular_velocity: Vec3::new(angvel.x, angvel.y, angvel.z),
+            mass: body.mass(),
+            inertia: principal_inertia.x.min(principal_inertia.y).min(principal_inertia.z),
+            gravity: gravity * body.gravity_scale(),
+        }
+    }
+}
+
+/// Work out the world space force and torque a body mover applies to a
+/// body over a step of `delta_time`.
+///
+/// Movers chasing a target are PD controllers limited per axis by MaxForce
+/// or MaxTorque. So that stiff gains stay stable at our step rate, they
+/// never push harder than it takes to reach the target within one step,
+/// counting what gravity takes away over that step.
+pub fn body_mover_force(mover: &BodyMover, state: &BodyState, delta_time: f32) -> (Vec3, Vec3) {
+    let p = mover.p() as f32;
+    let d = mover.d() as f32;
+    match mover.kind() {
+        BodyMoverKind::Force => (mover.force(), Vec3::ZERO),
+        BodyMoverKind::Thrust => {
+            let force = state.rotation * mover.force();
+            let arm = state.rotation * mover.location();
+            (force, arm.cross(force))
+        }
+        BodyMoverKind::Velocity => {
+            let error = mover.velocity() - state.linear_velocity;
+            let force = clamp_axes(error * p, mover.max_force());
+            let needed = error - state.gravity * delta_time;
+            (limit_overshoot(force, needed, state.mass, delta_time), Vec3::ZERO)
+        }
+        BodyMoverKind::Position => {
+            let error = mover.position() - state.position;
+            let force = clamp_axes(error * p - state.linear_velocity * d, mover.max_force());
+            let needed = error / delta_time - state.linear_velocity - state.gravity * delta_time;
+            (limit_overshoot(force, needed, state.mass, delta_time), Vec3::ZERO)
+        }
+        BodyMoverKind::AngularVelocity => {
+            let error = mover.angular_velocity() - state.angular_velocity;
+            let torque = clamp_axes(error * p, mover.max_torque());
+            (Vec3::ZERO, limit_overshoot(torque, error, state.inertia, delta_time))
+        }
+        BodyMoverKind::Gyro => {
+            let error = rotation_vector(mover.cframe().to_quat() * state.rotation.inverse());
+            let torque = clamp_axes(error * p - state.angular_velocity * d, mover.max_torque());
+            let needed = error / delta_time - state.angular_velocity;
+            (Vec3::ZERO, limit_overshoot(torque, needed, state.inertia, delta_time))
+        }
+    }
+}
+
+/// Clamp each component of `value` to `[-limit, limit]`
+fn clamp_axes(value: Vec3, limit: Vec3) -> Vec3 {
+    value.clamp(-limit.abs(), limit.abs())
+}
+
+/// Scale `force` down so the change in velocity it causes over a step
+/// doesn't go past `needed`. `response` is the mass or moment of inertia.
+fn limit_overshoot(force: Vec3, needed: Vec3, response: f32, delta_time: f32) -> Vec3 {
+    if response <= 0.0 {
+        return force;
+    }
+
+    let change = force * delta_time / response;
+    let along = change.dot(needed);
+    let needed_squared = needed.length_squared();
+    if along > needed_squared {
+        force * (needed_squared / along)
+    } else {
+        force
+    }
+}
+
+/// Get the shortest rotation of a quaternion as axis * angle
+fn rotation_vector(rotation: Quat) -> Vec3 {
+    let rotation = if rotation.w < 0.0 { -rotation } else { rotation };
+    let (axis, angle) = rotation.normalize().to_axis_angle();
+    if angle.abs() < 1e-6 {
+        Vec3::ZERO
+    } else {
+        axis * angle
+    }
+}
+
+/// Apply every body mover in `workspace` to the rigid body of the part it
+/// is parented to, for a step of `delta_time`. Movers whose part has no
+/// body, or an anchored one, do nothing. Returns how many movers acted.
+pub fn apply_body_movers(workspace: &Rc<RefCell<Instance>>, physics_world: &mut PhysicsWorld, delta_time: f32) -> usize {
+    let movers: Vec<BodyMover> = workspace.borrow()
+        .get_descendants()
+        .iter()
+        .filter_map(BodyMover::from_instance)
+        .collect();
+
+    let gravity = physics_world.get_gravity();
+    let mut applied = 0;
+    for mover in movers {
+        let Some(handle) = mover.part().and_then(|part| physics_world.get_part_body(part.instance())) else {
+            continue;
+        };
+        let Some(body) = physics_world.get_rigid_body_mut(handle).filter(|body| body.is_dynamic()) else {
+            continue;
+        };
+
+        let (force, torque) = body_mover_force(&mover, &BodyState::from_rigid_body(body, gravity), delta_time);
+        let impulse = force * delta_time;
+        let torque_impulse = torque * delta_time;
+        body.apply_impulse(vector![impulse.x, impulse.y, impulse.z], true);
+        body.apply_torque_impulse(vector![torque_impulse.x, torque_impulse.y, torque_impulse.z], true);
+        applied += 1;
+    }
+    applied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rnr_core::types::CFrame;
+    use rnr_datamodel::BasePart;
+
+    const STEP: f32 = 1.0 / 60.0;
+
+    /// A workspace holding one unanchored part simulated without gravity
+    fn setup() -> (Rc<RefCell<Instance>>, BasePart, PhysicsWorld, RigidBodyHandle) {
+        let workspace = Instance::new();
+        let part = BasePart::new("Part");
+        part.set_position(Vec3::new(0.0, 10.0, 0.0));
+        Instance::set_parent(part.instance(), Some(workspace.clone()));
+
+        let mut physics_world = PhysicsWorld::new();
+        physics_world.set_gravity(Vec3::ZERO);
+        let handle = physics_world.add_part(&part);
+        (workspace, part, physics_world, handle)
+    }
+
+    fn add_mover(part: &BasePart, kind: BodyMoverKind) -> BodyMover {
+        let mover = BodyMover::new(kind);
+        Instance::set_parent(mover.instance(), Some(part.instance().clone()));
+        mover
+    }
+
+    fn run(workspace: &Rc<RefCell<Instance>>, physics_world: &mut PhysicsWorld, steps: usize) {
+        for _ in 0..steps {
+            apply_body_movers(workspace, physics_world, STEP);
+            physics_world.step(STEP);
+            physics_world.sync_parts();
+        }
+    }
+
+    #[test]
+    fn test_body_velocity_and_position_reach_targets() {
+        let (workspace, part, mut physics_world, handle) = setup();
+        let velocity = add_mover(&part, BodyMoverKind::Velocity);
+        velocity.set_velocity(Vec3::new(5.0, 0.0, 0.0));
+        run(&workspace, &mut physics_world, 120);
+
+        let linvel = physics_world.get_rigid_body(handle).unwrap().linvel();
+        assert!((linvel.x - 5.0).abs() < 0.1, "{linvel:?}");
+        assert!(part.position().x > 5.0);
+
+        Instance::destroy(velocity.instance());
+        let position = add_mover(&part, BodyMoverKind::Position);
+        position.set_position(Vec3::new(0.0, 20.0, 0.0));
+        run(&workspace, &mut physics_world, 600);
+        assert!((part.position() - Vec3::new(0.0, 20.0, 0.0)).length() < 0.5, "{:?}", part.position());
+    }
+
+    #[test]
+    fn test_movers_hold_parts_against_gravity() {
+        let (workspace, part, mut physics_world, _) = setup();
+        physics_world.set_gravity(Vec3::new(0.0, -196.2, 0.0));
+        let position = add_mover(&part, BodyMoverKind::Position);
+        position.set_position(Vec3::new(0.0, 10.0, 0.0));
+        position.set_max_force(Vec3::splat(50000.0));
+        run(&workspace, &mut physics_world, 120);
+        assert!((part.position().y - 10.0).abs() < 0.5, "{:?}", part.position());
+
+        Instance::destroy(position.instance());
+        let velocity = add_mover(&part, BodyMoverKind::Velocity);
+        velocity.set_p(50000.0);
+        velocity.set_max_force(Vec3::splat(50000.0));
+        run(&workspace, &mut physics_world, 60);
+        assert!((part.position().y - 10.0).abs() < 1.0, "{:?}", part.position());
+    }
+
+    #[test]
+    fn test_max_force_limits_each_axis() {
+        let (workspace, part, mut physics_world, handle) = setup();
+        let velocity = add_mover(&part, BodyMoverKind::Velocity);
+        velocity.set_velocity(Vec3::new(50.0, 50.0, 0.0));
+        velocity.set_max_force(Vec3::new(4000.0, 0.0, 4000.0));
+        run(&workspace, &mut physics_world, 10);
+
+        let linvel = physics_world.get_rigid_body(handle).unwrap().linvel();
+        assert!(linvel.x > 0.0);
+        assert_eq!(linvel.y, 0.0);
+    }
+
+    #[test]
+    fn test_gyro_and_angular_velocity_turn_the_part() {
+        let (workspace, part, mut physics_world, handle) = setup();
+        let spin = add_mover(&part, BodyMoverKind::AngularVelocity);
+        spin.set_angular_velocity(Vec3::new(0.0, 3.0, 0.0));
+        run(&workspace, &mut physics_world, 120);
+        let angvel = physics_world.get_rigid_body(handle).unwrap().angvel();
+        assert!((angvel.y - 3.0).abs() < 0.1, "{angvel:?}");
+
+        Instance::destroy(spin.instance());
+        let gyro = add_mover(&part, BodyMoverKind::Gyro);
+        gyro.set_max_torque(Vec3::splat(400000.0));
+        let target = Quat::from_rotation_y(1.0);
+        gyro.set_cframe(CFrame::from_rotation(Vec3::ZERO, target));
+        run(&workspace, &mut physics_world, 600);
+        assert!(part.cframe().to_quat().angle_between(target) < 0.05);
+    }
+
+    #[test]
+    fn test_force_and_thrust() {
+        let (workspace, part, mut physics_world, handle) = setup();
+        let force = add_mover(&part, BodyMoverKind::Force);
+        force.set_force(Vec3::new(0.0, 100.0, 0.0));
+        let thrust = add_mover(&part, BodyMoverKind::Thrust);
+        thrust.set_force(Vec3::new(0.0, 0.0, -10.0));
+        thrust.set_location(Vec3::new(1.0, 0.0, 0.0));
+        run(&workspace, &mut physics_world, 10);
+
+        let body = physics_world.get_rigid_body(handle).unwrap();
+        assert!(body.linvel().y > 0.0);
+        assert!(body.linvel().z < 0.0);
+        // Pushing the +X end towards -Z turns the part about +Y
+        assert!(body.angvel().y > 0.0);
+
+        // Anchored parts don't move
+        let anchored = BasePart::new("Part");
+        anchored.set_anchored(true);
+        Instance::set_parent(anchored.instance(), Some(workspace.clone()));
+        physics_world.add_part(&anchored);
+        add_mover(&anchored, BodyMoverKind::Force).set_force(Vec3::splat(100.0));
+        assert_eq!(apply_body_movers(&workspace, &mut physics_world, STEP), 2);
+    }
+}
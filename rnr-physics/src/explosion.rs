@@ -0,0 +1,235 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use rnr_core::instance::Instance;
+use rnr_core::signal::Signal;
+use rnr_core::variant::Variant;
+use rnr_datamodel::{BasePart, Explosion, JointInstance};
+use crate::physics::PhysicsWorld;
+
+/// Impulse given to a body at the center of a blast, per unit of BlastPressure
+pub const BLAST_IMPULSE_PER_PRESSURE: f32 = 1.0 / 2000.0;
+
+/// Arguments of Explosion.Hit: a part the blast reached and its distance
+/// from the center
+pub type ExplosionHit = (Rc<RefCell<Instance>>, f32);
+
+/// What an explosion did when it went off
+#[derive(Debug, Clone, Default)]
+pub struct ExplosionResult {
+    /// Parts within BlastRadius, nearest first
+    pub parts_hit: Vec<ExplosionHit>,
+    /// Dynamic bodies pushed in the physics world
+    pub bodies_pushed: usize,
+    /// JointInstances destroyed
+    pub joints_broken: usize,
+    /// Impulse joints removed from the physics world
+    pub physics_joints_broken: usize,
+    /// Humanoids whose Health was set to 0
+    pub humanoids_killed: usize,
+}
+
+/// Set off an explosion in `workspace`.
+///
+/// Dynamic bodies within BlastRadius are pushed away from the center.
+/// Joints attached to a part or body within the destroy radius break, and
+/// any living Humanoid whose model lost a joint dies. Hit is not fired here;
+/// see `Explosions`.
+pub fn detonate(explosion: &Explosion, workspace: &Rc<RefCell<Instance>>, physics_world: &mut PhysicsWorld) -> ExplosionResult {
+    let center = explosion.position();
+    let radius = explosion.blast_radius() as f32;
+    let destroy_radius = explosion.destroy_joint_radius() as f32;
+    let descendants = workspace.borrow().get_descendants();
+
+    let mut parts_hit: Vec<ExplosionHit> = descendants
+        .iter()
+        .filter_map(BasePart::from_instance)
+        .map(|part| (part.instance().clone(), (part.position() - center).length()))
+        .filter(|(_, distance)| *distance <= radius)
+        .collect();
+    parts_hit.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+    let within_destroy_radius = |part: Option<BasePart>| part.is_some_and(|part| (part.position() - center).length() <= destroy_radius);
+    let broken: Vec<JointInstance> = descendants
+        .iter()
+        .filter_map(JointInstance::from_instance)
+        .filter(|joint| within_destroy_radius(joint.part0()) || within_destroy_radius(joint.part1()))
+        .collect();
+
+    // Find the owners before destroying the joints unparents them
+    let mut humanoids: Vec<Rc<RefCell<Instance>>> = Vec::new();
+    for joint in &broken {
+        if let Some(humanoid) = find_humanoid(joint.instance()) {
+            if !humanoids.iter().any(|known| Rc::ptr_eq(known, &humanoid)) {
+                humanoids.push(humanoid);
+            }
+        }
+        Instance::destroy(joint.instance());
+    }
+
+    let mut humanoids_killed = 0;
+    for humanoid in &humanoids {
+        let health = humanoid.borrow().get_property("Health").and_then(Variant::as_float).unwrap_or(0.0);
+        if health > 0.0 {
+            humanoid.borrow_mut().set_property("Health", 0.0);
+            humanoids_killed += 1;
+        }
+    }
+
+    let impulse = explosion.blast_pressure() as f32 * BLAST_IMPULSE_PER_PRESSURE;
+    ExplosionResult {
+        parts_hit,
+        bodies_pushed: physics_world.apply_blast_impulse(center, radius, impulse),
+        joints_broken: broken.len(),
+        physics_joints_broken: physics_world.break_joints_near(center, destroy_radius),
+        humanoids_killed,
+    }
+}
+
+/// Find the Humanoid in the nearest ancestor of an instance that has one
+fn find_humanoid(instance: &Rc<RefCell<Instance>>) -> Option<Rc<RefCell<Instance>>> {
+    let mut ancestor = instance.borrow().parent();
+    while let Some(current) = ancestor {
+        if let Some(humanoid) = current.borrow().find_first_child_of_type("Humanoid") {
+            return Some(humanoid);
+        }
+        ancestor = current.borrow().parent();
+    }
+    None
+}
+
+/// Sets off explosions as they appear in the workspace, once each, and
+/// keeps their Hit signals.
+#[derive(Default)]
+pub struct Explosions {
+    /// Explosions that have gone off
+    detonated: Vec<Weak<RefCell<Instance>>>,
+    hit_signals: Vec<(Weak<RefCell<Instance>>, Signal<ExplosionHit>)>,
+}
+
+impl Explosions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the Hit signal of an explosion, fired once for each part within
+    /// BlastRadius when it goes off
+    pub fn hit(&mut self, explosion: &Explosion) -> Signal<ExplosionHit> {
+        let instance = explosion.instance();
+        if let Some((_, signal)) = self.hit_signals.iter().find(|(known, _)| known.as_ptr() == Rc::as_ptr(instance)) {
+            return signal.clone();
+        }
+
+        let signal = Signal::new();
+        self.hit_signals.push((Rc::downgrade(instance), signal.clone()));
+        signal
+    }
+
+    /// Set off every explosion in the workspace that hasn't gone off yet,
+    /// in tree order, and fire their Hit signals
+    pub fn update(&mut self, workspace: &Rc<RefCell<Instance>>, physics_world: &mut PhysicsWorld) -> Vec<ExplosionResult> {
+        self.detonated.retain(|explosion| explosion.strong_count() > 0);
+        self.hit_signals.retain(|(explosion, _)| explosion.strong_count() > 0);
+
+        let explosions: Vec<Explosion> = workspace.borrow()
+            .get_descendants()
+            .iter()
+            .filter_map(Explosion::from_instance)
+            .filter(|explosion| !self.detonated.iter().any(|known| known.as_ptr() == Rc::as_ptr(explosion.instance())))
+            .collect();
+
+        let mut results = Vec::new();
+        for explosion in explosions {
+            self.detonated.push(Rc::downgrade(explosion.instance()));
+            let result = detonate(&explosion, workspace, physics_world);
+
+            let signal = self.hit(&explosion);
+            for hit in &result.parts_hit {
+                signal.fire(hit);
+            }
+            results.push(result);
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+    use rapier3d::prelude::*;
+    use rnr_datamodel::{Debris, Players};
+
+    fn add_part(parent: &Rc<RefCell<Instance>>, position: Vec3) -> BasePart {
+        let part = BasePart::new("Part");
+        part.set_position(position);
+        Instance::set_parent(part.instance(), Some(parent.clone()));
+        part
+    }
+
+    fn add_body(physics_world: &mut PhysicsWorld, position: Vec3, dynamic: bool) -> RigidBodyHandle {
+        let builder = if dynamic { RigidBodyBuilder::dynamic() } else { RigidBodyBuilder::fixed() };
+        let handle = physics_world.add_rigid_body(builder.translation(vector![position.x, position.y, position.z]).build());
+        physics_world.colliders.insert_with_parent(PhysicsWorld::create_sphere_collider(0.5), handle, &mut physics_world.rigid_bodies);
+        handle
+    }
+
+    #[test]
+    fn test_blast_pushes_bodies_and_breaks_physics_joints() {
+        let workspace = Instance::new();
+        let mut physics_world = PhysicsWorld::new();
+        let near = add_body(&mut physics_world, Vec3::new(2.0, 0.0, 0.0), true);
+        let far = add_body(&mut physics_world, Vec3::new(20.0, 0.0, 0.0), true);
+        let farther = add_body(&mut physics_world, Vec3::new(25.0, 0.0, 0.0), true);
+        let anchor = add_body(&mut physics_world, Vec3::new(-2.0, 0.0, 0.0), false);
+        let joint = FixedJointBuilder::new().build();
+        physics_world.impulse_joints.insert(near, far, joint, true);
+        physics_world.impulse_joints.insert(far, farther, joint, true);
+
+        let explosion = Explosion::new();
+        explosion.set_blast_radius(8.0);
+        explosion.set_destroy_joint_radius_percent(0.5);
+        let result = detonate(&explosion, &workspace, &mut physics_world);
+
+        assert_eq!(result.bodies_pushed, 1);
+        assert_eq!(result.physics_joints_broken, 1);
+        assert_eq!(physics_world.impulse_joints.len(), 1);
+        let velocity = physics_world.get_rigid_body(near).unwrap().linvel();
+        assert!(velocity.x > 0.0 && velocity.y.abs() < 1e-4);
+        assert_eq!(physics_world.get_rigid_body(far).unwrap().linvel().norm(), 0.0);
+        assert_eq!(physics_world.get_rigid_body(anchor).unwrap().linvel().norm(), 0.0);
+    }
+
+    #[test]
+    fn test_blast_kills_characters_and_fires_hit_once() {
+        let workspace = Instance::new();
+        let players = Players::new();
+        let player = Players::add_player(&players, "Player1", 1);
+        let character = players.borrow_mut().load_character(&player, &workspace, &mut Debris::new().borrow_mut());
+        let torso = BasePart::from_instance(&character.borrow().find_first_child("Torso").unwrap()).unwrap();
+        add_part(&workspace, torso.position() + Vec3::new(30.0, 0.0, 0.0));
+
+        let explosion = Explosion::new();
+        explosion.set_position(torso.position() + Vec3::new(0.0, 0.0, 2.0));
+        let mut explosions = Explosions::new();
+        let hits = Rc::new(RefCell::new(Vec::new()));
+        let hits_clone = hits.clone();
+        explosions.hit(&explosion).connect(move |(part, _)| hits_clone.borrow_mut().push(part.borrow().name().to_string()));
+        Instance::set_parent(explosion.instance(), Some(workspace.clone()));
+
+        let mut physics_world = PhysicsWorld::new();
+        let results = explosions.update(&workspace, &mut physics_world);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].humanoids_killed, 1);
+        assert!(results[0].joints_broken > 0);
+        let humanoid = character.borrow().find_first_child("Humanoid").unwrap();
+        assert_eq!(humanoid.borrow().get_property("Health").and_then(Variant::as_float), Some(0.0));
+        assert!(torso.instance().borrow().find_first_child("Neck").is_none());
+
+        // The torso is nearest, and the far away part isn't hit
+        assert_eq!(hits.borrow().first().map(String::as_str), Some("Torso"));
+        assert!(!hits.borrow().iter().any(|name| name == "Part"));
+
+        // Each explosion goes off once
+        assert!(explosions.update(&workspace, &mut physics_world).is_empty());
+    }
+}
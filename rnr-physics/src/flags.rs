@@ -0,0 +1,183 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use rnr_core::instance::Instance;
+use rnr_core::signal::Signal;
+use rnr_core::types::BrickColor;
+use rnr_datamodel::{Flag, FlagStand, Player, Players, Tools};
+use crate::seats::{alive, touching};
+
+/// A flag carried onto an enemy team's stand
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlagCapture {
+    /// The player who brought the flag
+    pub user_id: i64,
+    /// Color of the team the flag was taken from
+    pub flag_color: BrickColor,
+    /// Color of the team that scored
+    pub team_color: BrickColor,
+}
+
+/// Runs capture the flag between Flags and FlagStands, and keeps the
+/// stands' FlagCaptured signals.
+#[derive(Default)]
+pub struct Flags {
+    captured_signals: Vec<(Weak<RefCell<Instance>>, Signal<Player>)>,
+}
+
+impl Flags {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the FlagCaptured signal of a stand, fired with the player who
+    /// brought an enemy flag to it
+    pub fn flag_captured(&mut self, stand: &FlagStand) -> Signal<Player> {
+        let instance = stand.instance();
+        if let Some((_, signal)) = self.captured_signals.iter().find(|(known, _)| known.as_ptr() == Rc::as_ptr(instance)) {
+            return signal.clone();
+        }
+
+        let signal = Signal::new();
+        self.captured_signals.push((Rc::downgrade(instance), signal.clone()));
+        signal
+    }
+
+    /// Update every flag in the workspace, in this order:
+    ///
+    /// 1. Carriers that died drop the flag where they fell
+    /// 2. Carriers touching their own team's stand with an enemy flag
+    ///    capture it: the stand's FlagCaptured fires, their team scores a
+    ///    point and the flag goes home
+    /// 3. Loose flags touched by their own team go home, and loose flags
+    ///    touched by an enemy are picked up
+    pub fn update(&mut self, workspace: &Rc<RefCell<Instance>>, players: &Players, tools: &mut Tools) -> Vec<FlagCapture> {
+        self.captured_signals.retain(|(stand, _)| stand.strong_count() > 0);
+        let descendants = workspace.borrow().get_descendants();
+        let stands: Vec<FlagStand> = descendants.iter().filter_map(FlagStand::from_instance).collect();
+        let home_of = |flag: &Flag| stands.iter().find(|stand| stand.team_color() == flag.team_color());
+        let mut captures = Vec::new();
+
+        for player in players.get_players() {
+            let Some(flag) = Tools::equipped_tool(&player).and_then(|tool| Flag::from_instance(tool.instance())) else {
+                continue;
+            };
+            let Some(humanoid) = humanoid_of(&player) else {
+                continue;
+            };
+            if !alive(&humanoid) {
+                tools.drop_tool(&player, workspace);
+                continue;
+            }
+
+            let Some(team) = player.team() else {
+                continue;
+            };
+            let own_stand = stands.iter().find(|stand| stand.team_color() == team.team_color() && touching(stand.part(), &humanoid));
+            if let Some(stand) = own_stand.filter(|_| flag.team_color() != team.team_color()) {
+                tools.unequip(&player);
+                if let Some(home) = home_of(&flag) {
+                    flag.return_to(home, workspace);
+                }
+                team.set_score(team.score() + 1);
+                self.flag_captured(stand).fire(&player);
+                captures.push(FlagCapture { user_id: player.user_id(), flag_color: flag.team_color(), team_color: team.team_color() });
+            }
+        }
+
+        let loose_flags: Vec<Flag> = workspace.borrow().children().iter().filter_map(Flag::from_instance).collect();
+        for flag in &loose_flags {
+            let Some(handle) = flag.tool().handle() else {
+                continue;
+            };
+            let toucher = players.get_players().into_iter().find_map(|player| {
+                let humanoid = humanoid_of(&player).filter(alive)?;
+                let team = player.team()?;
+                touching(&handle, &humanoid).then_some((player, team))
+            });
+            let Some((player, team)) = toucher else {
+                continue;
+            };
+
+            if team.team_color() != flag.team_color() {
+                tools.equip(&player, flag.tool());
+            } else if let Some(home) = home_of(flag).filter(|home| !flag.is_home(home)) {
+                flag.return_to(home, workspace);
+            }
+        }
+
+        captures
+    }
+}
+
+/// Get the Humanoid of a player's character
+fn humanoid_of(player: &Player) -> Option<Rc<RefCell<Instance>>> {
+    player.character().and_then(|character| character.borrow().find_first_child("Humanoid"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+    use rnr_core::types::CFrame;
+    use rnr_datamodel::{move_model, BasePart, Debris, Teams};
+
+    fn torso_of(player: &Player) -> BasePart {
+        BasePart::from_instance(&player.character().unwrap().borrow().find_first_child("Torso").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_capture_the_flag() {
+        let workspace = Instance::new();
+        let players = Players::new();
+        let teams = Teams::new();
+        let red = teams.borrow().add_team("Red", BrickColor::red());
+        let blue = teams.borrow().add_team("Blue", BrickColor::blue());
+        let debris = Debris::new();
+        let alice = Players::add_player(&players, "Alice", 1);
+        let bob = Players::add_player(&players, "Bob", 2);
+        alice.set_team(Some(&red));
+        bob.set_team(Some(&blue));
+        players.borrow_mut().load_character(&alice, &workspace, &mut debris.borrow_mut());
+        let bob_character = players.borrow_mut().load_character(&bob, &workspace, &mut debris.borrow_mut());
+        move_model(&bob_character, CFrame::IDENTITY, CFrame::new(Vec3::new(50.0, 0.0, 0.0)));
+
+        let red_stand = FlagStand::new(BrickColor::red());
+        red_stand.part().set_position(torso_of(&alice).position() + Vec3::new(0.0, 0.0, 20.0));
+        Instance::set_parent(red_stand.instance(), Some(workspace.clone()));
+        let blue_stand = FlagStand::new(BrickColor::blue());
+        blue_stand.part().set_position(Vec3::new(0.0, 0.0, -100.0));
+        Instance::set_parent(blue_stand.instance(), Some(workspace.clone()));
+        let blue_flag = Flag::new(BrickColor::blue());
+        Instance::set_parent(blue_flag.instance(), Some(workspace.clone()));
+        blue_flag.tool().handle().unwrap().set_position(torso_of(&alice).position());
+
+        let mut flags = Flags::new();
+        let mut tools = Tools::new();
+        let captured_by = Rc::new(RefCell::new(Vec::new()));
+        let captured_by_clone = captured_by.clone();
+        flags.flag_captured(&red_stand).connect(move |player| captured_by_clone.borrow_mut().push(player.name()));
+
+        // Alice picks up the enemy flag, then carries it to her stand
+        assert!(flags.update(&workspace, &players.borrow(), &mut tools).is_empty());
+        assert!(Tools::equipped_tool(&alice).is_some_and(|tool| Rc::ptr_eq(tool.instance(), blue_flag.instance())));
+        red_stand.part().set_position(torso_of(&alice).position());
+        let captures = flags.update(&workspace, &players.borrow(), &mut tools);
+        assert_eq!(captures, vec![FlagCapture { user_id: 1, flag_color: BrickColor::blue(), team_color: BrickColor::red() }]);
+        assert_eq!(*captured_by.borrow(), vec!["Alice"]);
+        assert_eq!(red.score(), 1);
+        assert!(blue_flag.is_home(&blue_stand));
+        assert!(Tools::equipped_tool(&alice).is_none());
+
+        // A carrier who dies drops the flag, and the owners return it
+        red_stand.part().set_position(Vec3::new(0.0, 0.0, 100.0));
+        assert!(tools.equip(&alice, blue_flag.tool()));
+        alice.character().unwrap().borrow().find_first_child("Humanoid").unwrap().borrow_mut().set_property("Health", 0.0);
+        flags.update(&workspace, &players.borrow(), &mut tools);
+        assert!(Rc::ptr_eq(&blue_flag.instance().borrow().parent().unwrap(), &workspace));
+        assert!(!blue_flag.is_home(&blue_stand));
+        blue_flag.tool().handle().unwrap().set_position(torso_of(&bob).position());
+        flags.update(&workspace, &players.borrow(), &mut tools);
+        assert!(blue_flag.is_home(&blue_stand));
+        assert_eq!(blue.score(), 0);
+    }
+}
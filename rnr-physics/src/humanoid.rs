@@ -1,7 +1,15 @@
 use glam::{Vec3, Quat};
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::cell::RefCell;
 use rnr_core::instance::Instance;
+use rnr_core::signal::Signal;
+use rnr_core::types::CFrame;
+use rnr_core::variant::Variant;
+use rnr_datamodel::{humanoid_sitting, move_model, BasePart};
+use crate::seats::alive;
+
+/// How long MoveTo keeps walking before giving up, in seconds
+pub const MOVE_TO_TIMEOUT: f32 = 8.0;
 
 /// Humanoid character controller
 pub struct Humanoid {
@@ -26,6 +34,13 @@ pub struct Humanoid {
     pub health: f32,
     /// Maximum health
     pub max_health: f32,
+    /// Where MoveTo is walking to
+    move_to_target: Option<Vec3>,
+    /// Time spent walking to the current MoveTo target
+    move_to_elapsed: f32,
+    /// Fired with true when MoveTo arrives, or false when it times out or is
+    /// replaced by a new target
+    move_to_finished: Signal<bool>,
 }
 
 impl Humanoid {
@@ -33,8 +48,20 @@ impl Humanoid {
     pub fn new() -> Rc<RefCell<Self>> {
         let instance = Instance::new();
         instance.borrow_mut().set_class_name("Humanoid");
+        Rc::new(RefCell::new(Self::with_instance(instance)))
+    }
+
+    /// Create a humanoid that drives an existing Humanoid instance, reading
+    /// its WalkSpeed, Health and MaxHealth and starting at its character's
+    /// root part
+    pub fn attach(instance: &Rc<RefCell<Instance>>) -> Rc<RefCell<Self>> {
+        let mut humanoid = Self::with_instance(instance.clone());
+        humanoid.sync_from_instance();
+        Rc::new(RefCell::new(humanoid))
+    }
 
-        Rc::new(RefCell::new(Self {
+    fn with_instance(instance: Rc<RefCell<Instance>>) -> Self {
+        Self {
             instance,
             position: Vec3::ZERO,
             rotation: Quat::IDENTITY,
@@ -46,7 +73,38 @@ impl Humanoid {
             speed_multiplier: 1.0,
             health: 100.0,
             max_health: 100.0,
-        }))
+            move_to_target: None,
+            move_to_elapsed: 0.0,
+            move_to_finished: Signal::new(),
+        }
+    }
+
+    /// Read the instance's properties and its root part's position
+    fn sync_from_instance(&mut self) {
+        let float = |name: &str| self.instance.borrow().get_property(name).and_then(Variant::as_float);
+        if let Some(walk_speed) = float("WalkSpeed") {
+            self.walk_speed = walk_speed as f32;
+        }
+        if let Some(health) = float("Health") {
+            self.health = health as f32;
+        }
+        if let Some(max_health) = float("MaxHealth") {
+            self.max_health = max_health as f32;
+        }
+        if let Some(root) = self.root_part() {
+            self.position = root.position();
+        }
+    }
+
+    /// Get the part the humanoid moves its character by: the
+    /// HumanoidRootPart, or the Torso of characters without one
+    pub fn root_part(&self) -> Option<BasePart> {
+        let character = self.instance.borrow().parent()?;
+        let character = character.borrow();
+        ["HumanoidRootPart", "Torso"].iter()
+            .find_map(|name| character.find_first_child(name))
+            .as_ref()
+            .and_then(BasePart::from_instance)
     }
 
     /// Get the underlying instance
@@ -73,6 +131,67 @@ impl Humanoid {
         }
     }
 
+    /// Walk to a position (Humanoid:MoveTo).
+    ///
+    /// Only the horizontal position matters. The walk happens in
+    /// `update_move_to`, and gives up after MOVE_TO_TIMEOUT seconds. Calling
+    /// MoveTo again while walking restarts the timeout without firing
+    /// MoveToFinished, so paths can be followed waypoint by waypoint.
+    pub fn move_to(&mut self, position: Vec3) {
+        self.move_to_target = Some(position);
+        self.move_to_elapsed = 0.0;
+    }
+
+    /// Get the position MoveTo is walking to
+    pub fn move_to_target(&self) -> Option<Vec3> {
+        self.move_to_target
+    }
+
+    /// Get the MoveToFinished signal
+    pub fn move_to_finished(&self) -> &Signal<bool> {
+        &self.move_to_finished
+    }
+
+    /// Walk towards the MoveTo target for `delta_time` seconds, firing
+    /// MoveToFinished when it is reached or the walk times out
+    pub fn update_move_to(humanoid: &Rc<RefCell<Self>>, delta_time: f32) {
+        let finished = {
+            let mut humanoid = humanoid.borrow_mut();
+            let Some(target) = humanoid.move_to_target else {
+                return;
+            };
+
+            let mut offset = target - humanoid.position;
+            offset.y = 0.0;
+            let step = humanoid.get_current_speed() * delta_time;
+            humanoid.move_to_elapsed += delta_time;
+
+            if offset.length() <= step {
+                humanoid.position.x = target.x;
+                humanoid.position.z = target.z;
+                humanoid.velocity.x = 0.0;
+                humanoid.velocity.z = 0.0;
+                Some(true)
+            } else if humanoid.move_to_elapsed >= MOVE_TO_TIMEOUT {
+                humanoid.velocity.x = 0.0;
+                humanoid.velocity.z = 0.0;
+                Some(false)
+            } else {
+                humanoid.move_direction(offset, delta_time);
+                None
+            }
+        };
+
+        if let Some(reached) = finished {
+            let signal = {
+                let mut humanoid = humanoid.borrow_mut();
+                humanoid.move_to_target = None;
+                humanoid.move_to_finished.clone()
+            };
+            signal.fire(&reached);
+        }
+    }
+
     /// Make the humanoid jump
     pub fn jump(&mut self) {
         if self.on_ground {
@@ -147,6 +266,78 @@ impl Humanoid {
     }
 }
 
+/// A Humanoid instance and the physics humanoid driving it
+type AttachedHumanoid = (Weak<RefCell<Instance>>, Rc<RefCell<Humanoid>>);
+
+/// Physics humanoids attached to the Humanoid instances in the workspace.
+/// They walk their characters to their MoveTo targets.
+#[derive(Default)]
+pub struct Humanoids {
+    attached: Vec<AttachedHumanoid>,
+}
+
+impl Humanoids {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the physics humanoid driving a Humanoid instance, attaching one
+    /// the first time
+    pub fn attach(&mut self, instance: &Rc<RefCell<Instance>>) -> Rc<RefCell<Humanoid>> {
+        if let Some((_, humanoid)) = self.attached.iter().find(|(attached, _)| attached.as_ptr() == Rc::as_ptr(instance)) {
+            return humanoid.clone();
+        }
+        let humanoid = Humanoid::attach(instance);
+        self.attached.push((Rc::downgrade(instance), humanoid.clone()));
+        humanoid
+    }
+
+    /// Get how many humanoids are attached
+    pub fn len(&self) -> usize {
+        self.attached.len()
+    }
+
+    /// Check if no humanoids are attached
+    pub fn is_empty(&self) -> bool {
+        self.attached.is_empty()
+    }
+
+    /// Attach humanoids to new Humanoids in `workspace` and walk each living,
+    /// standing one towards its MoveTo target for `delta_time` seconds,
+    /// moving its character along. Humanoids whose instance is gone are
+    /// dropped.
+    pub fn update(&mut self, workspace: &Rc<RefCell<Instance>>, delta_time: f32) {
+        self.attached.retain(|(instance, _)| instance.upgrade().is_some_and(|instance| !instance.borrow().is_destroyed()));
+        let descendants = workspace.borrow().get_descendants();
+        for instance in descendants.iter().filter(|instance| instance.borrow().is_a("Humanoid")) {
+            self.attach(instance);
+        }
+
+        for (instance, humanoid) in self.attached.clone() {
+            let Some(instance) = instance.upgrade() else {
+                continue;
+            };
+            if !alive(&instance) || humanoid_sitting(&instance) {
+                continue;
+            }
+            humanoid.borrow_mut().sync_from_instance();
+            let Some(root) = humanoid.borrow().root_part() else {
+                continue;
+            };
+            let Some(character) = instance.borrow().parent() else {
+                continue;
+            };
+
+            Humanoid::update_move_to(&humanoid, delta_time);
+            let from = root.cframe();
+            let position = humanoid.borrow().position;
+            if position != from.position {
+                move_model(&character, from, CFrame { position, rotation: from.rotation });
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,6 +428,75 @@ mod tests {
         assert_eq!(humanoid_ref.get_current_speed(), 40.0);
     }
 
+    #[test]
+    fn test_humanoid_move_to() {
+        let humanoid = Humanoid::new();
+        let results = Rc::new(RefCell::new(Vec::new()));
+        let results_clone = results.clone();
+        humanoid.borrow().move_to_finished().connect(move |reached| results_clone.borrow_mut().push(*reached));
+
+        // 16 studs/s for 0.25 s per step reaches 10 studs on the third step
+        humanoid.borrow_mut().move_to(Vec3::new(10.0, 3.0, 0.0));
+        for _ in 0..3 {
+            Humanoid::update_move_to(&humanoid, 0.25);
+        }
+
+        assert_eq!(*results.borrow(), vec![true]);
+        assert_eq!(humanoid.borrow().position, Vec3::new(10.0, 0.0, 0.0));
+        assert!(humanoid.borrow().move_to_target().is_none());
+    }
+
+    #[test]
+    fn test_humanoid_move_to_times_out() {
+        let humanoid = Humanoid::new();
+        let results = Rc::new(RefCell::new(Vec::new()));
+        let results_clone = results.clone();
+        humanoid.borrow().move_to_finished().connect(move |reached| results_clone.borrow_mut().push(*reached));
+
+        humanoid.borrow_mut().set_walk_speed(1.0);
+        humanoid.borrow_mut().move_to(Vec3::new(100.0, 0.0, 0.0));
+        for _ in 0..9 {
+            Humanoid::update_move_to(&humanoid, 1.0);
+        }
+
+        assert_eq!(*results.borrow(), vec![false]);
+        assert_eq!(humanoid.borrow().position.x, 7.0);
+    }
+
+    #[test]
+    fn test_humanoids_walk_characters_to_move_to_targets() {
+        use rnr_datamodel::{create_model, Debris, Players};
+
+        let workspace = create_model("Workspace");
+        let players = Players::new();
+        let debris = Debris::new();
+        let player = Players::add_player(&players, "Player1", 1);
+        let character = players.borrow_mut().load_character(&player, &workspace, &mut debris.borrow_mut());
+        let instance = character.borrow().find_first_child("Humanoid").unwrap();
+        let torso = BasePart::from_instance(&character.borrow().find_first_child("Torso").unwrap()).unwrap();
+        let head = BasePart::from_instance(&character.borrow().find_first_child("Head").unwrap()).unwrap();
+        let start = torso.position();
+
+        let mut humanoids = Humanoids::new();
+        let humanoid = humanoids.attach(&instance);
+        assert!(Rc::ptr_eq(&humanoids.attach(&instance), &humanoid));
+        assert_eq!(humanoid.borrow().position, start);
+        humanoid.borrow_mut().move_to(start + Vec3::new(4.0, 0.0, 0.0));
+
+        humanoids.update(&workspace, 0.125);
+        assert_eq!(torso.position(), start + Vec3::new(2.0, 0.0, 0.0));
+        assert_eq!(head.position(), torso.position() + Vec3::new(0.0, 1.5, 0.0));
+
+        // Sitting humanoids stay put
+        instance.borrow_mut().set_property("Sit", true);
+        humanoids.update(&workspace, 0.125);
+        assert_eq!(torso.position(), start + Vec3::new(2.0, 0.0, 0.0));
+
+        Instance::destroy(&character);
+        humanoids.update(&workspace, 0.125);
+        assert!(humanoids.is_empty());
+    }
+
     #[test]
     fn test_humanoid_health_percentage() {
         let mut humanoid = Humanoid::new();
@@ -2,8 +2,23 @@ pub mod world;
 pub mod physics;
 pub mod joints;
 pub mod humanoid;
+pub mod pathfinding;
+pub mod explosion;
+pub mod body_movers;
+pub mod seats;
+pub mod flags;
+pub mod spawns;
+pub mod terrain;
+pub mod appearance;
 
 pub use world::*;
 pub use physics::*;
 pub use joints::*;
-pub use humanoid::*;
\ No newline at end of file
+pub use humanoid::*;
+pub use pathfinding::*;
+pub use explosion::*;
+pub use body_movers::*;
+pub use seats::*;
+pub use flags::*;
+pub use spawns::*;
+pub use appearance::*;
\ No newline at end of file
@@ -0,0 +1,704 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::rc::{Rc, Weak};
+use glam::Vec3;
+use rnr_core::instance::Instance;
+use rnr_core::signal::Signal;
+use rnr_datamodel::BasePart;
+
+/// Height an agent can step up without jumping, in studs
+pub const MAX_STEP_HEIGHT: f32 = 2.0;
+
+/// How far past the start and goal the navigation grid reaches, in studs
+pub const SEARCH_MARGIN: f32 = 64.0;
+
+/// Largest navigation grid built for one path, in cells
+const MAX_GRID_CELLS: usize = 1 << 20;
+
+/// Extra cost of a jump, so flat detours of similar length are preferred
+const JUMP_COST: f32 = 4.0;
+
+/// Tolerance when comparing surface heights
+const HEIGHT_EPSILON: f32 = 0.01;
+
+/// Size and abilities of the agent a path is computed for (the
+/// agentParameters table of PathfindingService:CreatePath)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AgentParameters {
+    /// Minimum distance kept from walls, in studs
+    pub agent_radius: f32,
+    /// Minimum headroom needed to pass, in studs
+    pub agent_height: f32,
+    /// Whether the path may jump up ledges
+    pub agent_can_jump: bool,
+    /// Highest ledge the agent can jump onto, in studs
+    pub agent_jump_height: f32,
+    /// Distance between waypoints, which is also the navigation grid's cell size
+    pub waypoint_spacing: f32,
+}
+
+impl Default for AgentParameters {
+    /// A standard character: 50 JumpPower jumps about 7.2 studs
+    fn default() -> Self {
+        Self {
+            agent_radius: 2.0,
+            agent_height: 5.0,
+            agent_can_jump: true,
+            agent_jump_height: 7.2,
+            waypoint_spacing: 4.0,
+        }
+    }
+}
+
+/// What an agent does to reach a waypoint (Enum.PathWaypointAction)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathWaypointAction {
+    Walk = 0,
+    Jump = 1,
+}
+
+/// A point along a path, on the surface the agent stands on (PathWaypoint)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathWaypoint {
+    pub position: Vec3,
+    pub action: PathWaypointAction,
+}
+
+/// Result of computing a path (Enum.PathStatus)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStatus {
+    Success = 0,
+    NoPath = 5,
+}
+
+/// World-space bounding box of a collidable part
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Obstacle {
+    min: Vec3,
+    max: Vec3,
+}
+
+impl Obstacle {
+    fn from_part(part: &BasePart) -> Self {
+        let cframe = part.cframe();
+        let half = part.size() * 0.5;
+        let rotation = cframe.rotation;
+        let extents = rotation.x_axis.abs() * half.x + rotation.y_axis.abs() * half.y + rotation.z_axis.abs() * half.z;
+        Self {
+            min: cframe.position - extents,
+            max: cframe.position + extents,
+        }
+    }
+
+    /// Whether the point's XZ position is over the box
+    fn covers(&self, x: f32, z: f32) -> bool {
+        x >= self.min.x && x <= self.max.x && z >= self.min.z && z <= self.max.z
+    }
+
+    /// Whether the box overlaps the space an agent standing at `feet` needs
+    fn blocks(&self, feet: Vec3, agent: &AgentParameters) -> bool {
+        let r = agent.agent_radius;
+        self.min.x < feet.x + r && self.max.x > feet.x - r
+            && self.min.z < feet.z + r && self.max.z > feet.z - r
+            && self.min.y < feet.y + agent.agent_height && self.max.y > feet.y + HEIGHT_EPSILON
+    }
+}
+
+/// Collect the collidable parts in the workspace.
+///
+/// Parts are treated as their world-space bounding boxes. Parts of characters
+/// (models with a Humanoid) are skipped so agents don't block themselves.
+fn collect_obstacles(workspace: &Rc<RefCell<Instance>>) -> Vec<Obstacle> {
+    workspace.borrow().get_descendants()
+        .iter()
+        .filter_map(BasePart::from_instance)
+        .filter(|part| part.can_collide() && !is_character_part(part))
+        .map(|part| Obstacle::from_part(&part))
+        .collect()
+}
+
+fn is_character_part(part: &BasePart) -> bool {
+    let parent = part.instance().borrow().parent();
+    parent.is_some_and(|parent| parent.borrow().find_first_child_of_type("Humanoid").is_some())
+}
+
+/// 2.5D navigation grid: each XZ cell holds the heights of every surface an
+/// agent fits on above it
+struct NavGrid {
+    origin_x: f32,
+    origin_z: f32,
+    spacing: f32,
+    width: usize,
+    depth: usize,
+    /// Surface heights of all cells, grouped by cell
+    floors: Vec<f32>,
+    /// Where each cell's floors start in `floors`; has one extra entry at the end
+    cell_start: Vec<usize>,
+    /// Cell of each floor
+    floor_cell: Vec<usize>,
+}
+
+impl NavGrid {
+    /// Build a grid covering `start` and `goal`, or None if it would be too big
+    fn build(obstacles: &[Obstacle], agent: &AgentParameters, start: Vec3, goal: Vec3) -> Option<Self> {
+        let spacing = agent.waypoint_spacing.max(0.5);
+        // Snap the grid to multiples of the spacing so cell centers don't
+        // depend on exactly where the agent starts
+        let origin_x = ((start.x.min(goal.x) - SEARCH_MARGIN) / spacing).floor() * spacing;
+        let origin_z = ((start.z.min(goal.z) - SEARCH_MARGIN) / spacing).floor() * spacing;
+        let width = ((start.x.max(goal.x) + SEARCH_MARGIN - origin_x) / spacing).ceil() as usize;
+        let depth = ((start.z.max(goal.z) + SEARCH_MARGIN - origin_z) / spacing).ceil() as usize;
+        if width.saturating_mul(depth) > MAX_GRID_CELLS {
+            return None;
+        }
+
+        let max_x = origin_x + width as f32 * spacing;
+        let max_z = origin_z + depth as f32 * spacing;
+        let nearby: Vec<Obstacle> = obstacles
+            .iter()
+            .filter(|o| o.max.x >= origin_x && o.min.x <= max_x && o.max.z >= origin_z && o.min.z <= max_z)
+            .copied()
+            .collect();
+
+        let mut grid = Self {
+            origin_x,
+            origin_z,
+            spacing,
+            width,
+            depth,
+            floors: Vec::new(),
+            cell_start: Vec::with_capacity(width * depth + 1),
+            floor_cell: Vec::new(),
+        };
+
+        for z in 0..depth {
+            for x in 0..width {
+                let cell = z * width + x;
+                let (cx, cz) = grid.cell_center(cell);
+                grid.cell_start.push(grid.floors.len());
+
+                let mut heights: Vec<f32> = nearby
+                    .iter()
+                    .filter(|o| o.covers(cx, cz))
+                    .map(|o| o.max.y)
+                    .filter(|&y| !nearby.iter().any(|o| o.blocks(Vec3::new(cx, y, cz), agent)))
+                    .collect();
+                heights.sort_by(f32::total_cmp);
+                heights.dedup_by(|a, b| (*a - *b).abs() < HEIGHT_EPSILON);
+
+                grid.floor_cell.extend(std::iter::repeat_n(cell, heights.len()));
+                grid.floors.extend(heights);
+            }
+        }
+        grid.cell_start.push(grid.floors.len());
+        Some(grid)
+    }
+
+    fn cell_center(&self, cell: usize) -> (f32, f32) {
+        let x = (cell % self.width) as f32;
+        let z = (cell / self.width) as f32;
+        (self.origin_x + (x + 0.5) * self.spacing, self.origin_z + (z + 0.5) * self.spacing)
+    }
+
+    fn cell_at(&self, x: f32, z: f32) -> Option<usize> {
+        let cx = ((x - self.origin_x) / self.spacing).floor();
+        let cz = ((z - self.origin_z) / self.spacing).floor();
+        if cx < 0.0 || cz < 0.0 || cx as usize >= self.width || cz as usize >= self.depth {
+            return None;
+        }
+        Some(cz as usize * self.width + cx as usize)
+    }
+
+    fn cell_floors(&self, cell: usize) -> std::ops::Range<usize> {
+        self.cell_start[cell]..self.cell_start[cell + 1]
+    }
+
+    fn floor_position(&self, floor: usize) -> Vec3 {
+        let (x, z) = self.cell_center(self.floor_cell[floor]);
+        Vec3::new(x, self.floors[floor], z)
+    }
+
+    /// Find the floor an agent at `position` stands on: the highest one no
+    /// more than a step above it
+    fn floor_under(&self, position: Vec3) -> Option<usize> {
+        let cell = self.cell_at(position.x, position.z)?;
+        self.cell_floors(cell).rev().find(|&floor| self.floors[floor] <= position.y + MAX_STEP_HEIGHT)
+    }
+
+    /// How an agent on `from` gets onto `to`, if it can
+    fn traverse(&self, from: usize, to: usize, agent: &AgentParameters) -> Option<PathWaypointAction> {
+        let rise = self.floors[to] - self.floors[from];
+        if rise <= MAX_STEP_HEIGHT {
+            Some(PathWaypointAction::Walk)
+        } else if agent.agent_can_jump && rise <= agent.agent_jump_height {
+            Some(PathWaypointAction::Jump)
+        } else {
+            None
+        }
+    }
+
+    /// Whether any floor of `cell` can be reached from `from`
+    fn reachable(&self, from: usize, cell: usize, agent: &AgentParameters) -> bool {
+        self.cell_floors(cell).any(|floor| self.traverse(from, floor, agent).is_some())
+    }
+
+    /// Floors reachable from `from` with how they are reached and their cost
+    fn neighbors(&self, from: usize, agent: &AgentParameters) -> Vec<(usize, PathWaypointAction, f32)> {
+        let cell = self.floor_cell[from];
+        let x = (cell % self.width) as i64;
+        let z = (cell / self.width) as i64;
+        let in_bounds = |x: i64, z: i64| x >= 0 && z >= 0 && (x as usize) < self.width && (z as usize) < self.depth;
+        let index = |x: i64, z: i64| z as usize * self.width + x as usize;
+
+        let mut neighbors = Vec::new();
+        for dz in -1..=1 {
+            for dx in -1..=1 {
+                if (dx == 0 && dz == 0) || !in_bounds(x + dx, z + dz) {
+                    continue;
+                }
+
+                // Don't cut corners past walls
+                if dx != 0 && dz != 0
+                    && !(self.reachable(from, index(x + dx, z), agent) && self.reachable(from, index(x, z + dz), agent))
+                {
+                    continue;
+                }
+
+                for floor in self.cell_floors(index(x + dx, z + dz)) {
+                    if let Some(action) = self.traverse(from, floor, agent) {
+                        let mut cost = self.floor_position(from).distance(self.floor_position(floor));
+                        if action == PathWaypointAction::Jump {
+                            cost += JUMP_COST;
+                        }
+                        neighbors.push((floor, action, cost));
+                    }
+                }
+            }
+        }
+        neighbors
+    }
+
+    /// Run A* between two floors, returning the floors along the way and how
+    /// each is entered
+    fn search(&self, start: usize, goal: usize, agent: &AgentParameters) -> Option<Vec<(usize, PathWaypointAction)>> {
+        let goal_position = self.floor_position(goal);
+        let heuristic = |floor: usize| self.floor_position(floor).distance(goal_position);
+
+        let mut cost = vec![f32::INFINITY; self.floors.len()];
+        let mut came_from: Vec<Option<(usize, PathWaypointAction)>> = vec![None; self.floors.len()];
+        let mut open = BinaryHeap::new();
+        cost[start] = 0.0;
+        open.push(OpenNode { estimate: heuristic(start), floor: start });
+
+        while let Some(OpenNode { estimate, floor }) = open.pop() {
+            if floor == goal {
+                let mut path = Vec::new();
+                let mut current = goal;
+                while let Some((previous, action)) = came_from[current] {
+                    path.push((current, action));
+                    current = previous;
+                }
+                path.push((start, PathWaypointAction::Walk));
+                path.reverse();
+                return Some(path);
+            }
+
+            // Skip entries superseded by a cheaper route
+            if estimate > cost[floor] + heuristic(floor) + HEIGHT_EPSILON {
+                continue;
+            }
+
+            for (next, action, step) in self.neighbors(floor, agent) {
+                let next_cost = cost[floor] + step;
+                if next_cost < cost[next] {
+                    cost[next] = next_cost;
+                    came_from[next] = Some((floor, action));
+                    open.push(OpenNode { estimate: next_cost + heuristic(next), floor: next });
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Entry in the A* open set, ordered so the heap pops the lowest estimate
+struct OpenNode {
+    estimate: f32,
+    floor: usize,
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate).then_with(|| other.floor.cmp(&self.floor))
+    }
+}
+
+/// Find the first waypoint from `start_index` on that the obstacles now
+/// block, either by filling the space the agent needs or by removing the
+/// surface it stands on
+fn first_blocked(waypoints: &[PathWaypoint], obstacles: &[Obstacle], agent: &AgentParameters, start_index: usize) -> Option<usize> {
+    (start_index..waypoints.len()).find(|&i| {
+        let position = waypoints[i].position;
+        let supported = obstacles.iter().any(|o| o.covers(position.x, position.z) && (o.max.y - position.y).abs() < HEIGHT_EPSILON);
+        if !supported || obstacles.iter().any(|o| o.blocks(position, agent)) {
+            return true;
+        }
+
+        // Check halfway along the way in, at the higher end so ledges don't count
+        if i > start_index {
+            let previous = waypoints[i - 1].position;
+            let mut midpoint = (previous + position) * 0.5;
+            midpoint.y = previous.y.max(position.y);
+            return obstacles.iter().any(|o| o.blocks(midpoint, agent));
+        }
+        false
+    })
+}
+
+struct PathState {
+    instance: Rc<RefCell<Instance>>,
+    agent: AgentParameters,
+    status: PathStatus,
+    waypoints: Vec<PathWaypoint>,
+    /// Waypoint the Blocked event last fired for, so it fires once per change
+    blocked_at: Option<usize>,
+    blocked: Signal<usize>,
+}
+
+/// A path between two points, created by PathfindingService (Path)
+#[derive(Clone)]
+pub struct Path {
+    state: Rc<RefCell<PathState>>,
+}
+
+impl Path {
+    fn new(agent: AgentParameters) -> Self {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("Path");
+        instance.borrow_mut().set_class_name("Path");
+
+        Self {
+            state: Rc::new(RefCell::new(PathState {
+                instance,
+                agent,
+                status: PathStatus::NoPath,
+                waypoints: Vec::new(),
+                blocked_at: None,
+                blocked: Signal::new(),
+            })),
+        }
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> Rc<RefCell<Instance>> {
+        self.state.borrow().instance.clone()
+    }
+
+    /// Get the agent the path is computed for
+    pub fn agent_parameters(&self) -> AgentParameters {
+        self.state.borrow().agent
+    }
+
+    /// Get the result of the last ComputeAsync
+    pub fn status(&self) -> PathStatus {
+        self.state.borrow().status
+    }
+
+    /// Get the waypoints from start to goal (Path:GetWaypoints).
+    ///
+    /// The first waypoint is the start and the last is the goal, both moved
+    /// down onto the surface under them. A Jump waypoint means the agent has
+    /// to jump to reach it.
+    pub fn get_waypoints(&self) -> Vec<PathWaypoint> {
+        self.state.borrow().waypoints.clone()
+    }
+
+    /// Get the Blocked signal, fired with the index of the first waypoint
+    /// that a change to the workspace has made unreachable
+    pub fn blocked(&self) -> Signal<usize> {
+        self.state.borrow().blocked.clone()
+    }
+
+    /// Find a path from `start` to `goal` through the workspace's parts
+    /// (Path:ComputeAsync)
+    pub fn compute(&self, workspace: &Rc<RefCell<Instance>>, start: Vec3, goal: Vec3) -> PathStatus {
+        let agent = self.agent_parameters();
+        let obstacles = collect_obstacles(workspace);
+        let waypoints = Self::search(&obstacles, &agent, start, goal);
+
+        let mut state = self.state.borrow_mut();
+        state.status = if waypoints.is_some() { PathStatus::Success } else { PathStatus::NoPath };
+        state.waypoints = waypoints.unwrap_or_default();
+        state.blocked_at = None;
+        state.status
+    }
+
+    fn search(obstacles: &[Obstacle], agent: &AgentParameters, start: Vec3, goal: Vec3) -> Option<Vec<PathWaypoint>> {
+        let grid = NavGrid::build(obstacles, agent, start, goal)?;
+        let start_floor = grid.floor_under(start)?;
+        let goal_floor = grid.floor_under(goal)?;
+        let floors = grid.search(start_floor, goal_floor, agent)?;
+
+        let mut waypoints: Vec<PathWaypoint> = floors
+            .iter()
+            .map(|&(floor, action)| PathWaypoint { position: grid.floor_position(floor), action })
+            .collect();
+
+        // Start and end exactly where asked rather than at cell centers
+        let first = waypoints.first_mut().unwrap();
+        first.position = Vec3::new(start.x, first.position.y, start.z);
+        let last = waypoints.last_mut().unwrap();
+        last.position = Vec3::new(goal.x, last.position.y, goal.z);
+        Some(waypoints)
+    }
+
+    /// Find the first waypoint from `start_index` on that the workspace now
+    /// blocks (Path:CheckOcclusionAsync)
+    pub fn check_occlusion(&self, workspace: &Rc<RefCell<Instance>>, start_index: usize) -> Option<usize> {
+        let state = self.state.borrow();
+        first_blocked(&state.waypoints, &collect_obstacles(workspace), &state.agent, start_index)
+    }
+
+    /// Re-check the path against changed obstacles, firing Blocked if a
+    /// different waypoint became blocked
+    fn check_blocked(&self, obstacles: &[Obstacle]) {
+        let (signal, index) = {
+            let mut state = self.state.borrow_mut();
+            if state.status != PathStatus::Success {
+                return;
+            }
+
+            let blocked_at = first_blocked(&state.waypoints, obstacles, &state.agent, 0);
+            if blocked_at == state.blocked_at {
+                return;
+            }
+            state.blocked_at = blocked_at;
+            match blocked_at {
+                Some(index) => (state.blocked.clone(), index),
+                None => return,
+            }
+        };
+        signal.fire(&index);
+    }
+}
+
+/// PathfindingService - computes paths through the workspace and watches them
+/// for obstructions
+pub struct PathfindingService {
+    instance: Rc<RefCell<Instance>>,
+    /// Paths to re-check when the workspace changes; dropped paths are pruned
+    paths: Vec<Weak<RefCell<PathState>>>,
+    /// Obstacles as of the last update
+    obstacles: Vec<Obstacle>,
+}
+
+impl PathfindingService {
+    /// Create a new pathfinding service
+    pub fn new() -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("PathfindingService");
+        instance.borrow_mut().set_class_name("PathfindingService");
+
+        Rc::new(RefCell::new(Self {
+            instance,
+            paths: Vec::new(),
+            obstacles: Vec::new(),
+        }))
+    }
+
+    /// Get the underlying instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Create a path for an agent (PathfindingService:CreatePath)
+    pub fn create_path(&mut self, agent: AgentParameters) -> Path {
+        let path = Path::new(agent);
+        self.paths.push(Rc::downgrade(&path.state));
+        path
+    }
+
+    /// Create a path and compute it straight away
+    pub fn compute_path(&mut self, workspace: &Rc<RefCell<Instance>>, start: Vec3, goal: Vec3, agent: AgentParameters) -> Path {
+        let path = self.create_path(agent);
+        path.compute(workspace, start, goal);
+        path
+    }
+
+    /// Get the number of paths still in use
+    pub fn num_paths(&self) -> usize {
+        self.paths.iter().filter(|path| path.strong_count() > 0).count()
+    }
+
+    /// Re-check live paths if any part moved, appeared or disappeared since
+    /// the last update, firing Blocked on those that became obstructed
+    pub fn update(service: &Rc<RefCell<Self>>, workspace: &Rc<RefCell<Instance>>) {
+        let (paths, obstacles) = {
+            let mut service = service.borrow_mut();
+            service.paths.retain(|path| path.strong_count() > 0);
+            if service.paths.is_empty() {
+                return;
+            }
+
+            let obstacles = collect_obstacles(workspace);
+            if obstacles == service.obstacles {
+                return;
+            }
+            service.obstacles = obstacles.clone();
+
+            let paths: Vec<Path> = service.paths
+                .iter()
+                .filter_map(Weak::upgrade)
+                .map(|state| Path { state })
+                .collect();
+            (paths, obstacles)
+        };
+
+        for path in paths {
+            path.check_blocked(&obstacles);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rnr_core::types::CFrame;
+
+    fn add_part(workspace: &Rc<RefCell<Instance>>, position: Vec3, size: Vec3) -> BasePart {
+        let part = BasePart::new("Part");
+        part.set_cframe(CFrame::new(position));
+        part.set_size(size);
+        part.set_anchored(true);
+        Instance::set_parent(part.instance(), Some(workspace.clone()));
+        part
+    }
+
+    fn workspace_with_floor() -> Rc<RefCell<Instance>> {
+        let workspace = Instance::new();
+        workspace.borrow_mut().set_class_name("Workspace");
+        add_part(&workspace, Vec3::new(0.0, -0.5, 0.0), Vec3::new(128.0, 1.0, 128.0));
+        workspace
+    }
+
+    #[test]
+    fn test_compute_straight_path() {
+        let workspace = workspace_with_floor();
+        let service = PathfindingService::new();
+        let start = Vec3::new(-20.0, 3.0, 2.0);
+        let goal = Vec3::new(20.0, 3.0, 2.0);
+        let path = service.borrow_mut().compute_path(&workspace, start, goal, AgentParameters::default());
+
+        assert_eq!(path.status(), PathStatus::Success);
+        let waypoints = path.get_waypoints();
+        assert_eq!(waypoints.first().unwrap().position, Vec3::new(-20.0, 0.0, 2.0));
+        assert_eq!(waypoints.last().unwrap().position, Vec3::new(20.0, 0.0, 2.0));
+        assert!(waypoints.iter().all(|waypoint| waypoint.action == PathWaypointAction::Walk));
+        assert!(waypoints.iter().all(|waypoint| waypoint.position.y == 0.0));
+    }
+
+    #[test]
+    fn test_path_goes_around_wall() {
+        let workspace = workspace_with_floor();
+        add_part(&workspace, Vec3::new(0.0, 10.0, 0.0), Vec3::new(4.0, 20.0, 64.0));
+
+        let service = PathfindingService::new();
+        let path = service.borrow_mut().compute_path(
+            &workspace,
+            Vec3::new(-20.0, 0.0, 2.0),
+            Vec3::new(20.0, 0.0, 2.0),
+            AgentParameters::default(),
+        );
+
+        assert_eq!(path.status(), PathStatus::Success);
+        let waypoints = path.get_waypoints();
+        assert!(waypoints.iter().any(|waypoint| waypoint.position.z.abs() > 32.0));
+        assert!(waypoints.iter().all(|waypoint| waypoint.position.y == 0.0));
+    }
+
+    #[test]
+    fn test_jump_waypoints() {
+        let workspace = workspace_with_floor();
+        // A 5 stud ledge: too high to step onto, low enough to jump onto
+        add_part(&workspace, Vec3::new(24.0, 2.5, 0.0), Vec3::new(32.0, 5.0, 128.0));
+        let start = Vec3::new(-20.0, 0.0, 2.0);
+        let goal = Vec3::new(30.0, 5.0, 2.0);
+
+        let service = PathfindingService::new();
+        let path = service.borrow_mut().compute_path(&workspace, start, goal, AgentParameters::default());
+        assert_eq!(path.status(), PathStatus::Success);
+
+        let waypoints = path.get_waypoints();
+        let jumps: Vec<&PathWaypoint> = waypoints.iter().filter(|w| w.action == PathWaypointAction::Jump).collect();
+        assert_eq!(jumps.len(), 1);
+        assert_eq!(jumps[0].position.y, 5.0);
+        assert_eq!(waypoints.last().unwrap().position, goal);
+
+        let agent = AgentParameters { agent_can_jump: false, ..AgentParameters::default() };
+        let path = service.borrow_mut().compute_path(&workspace, start, goal, agent);
+        assert_eq!(path.status(), PathStatus::NoPath);
+        assert!(path.get_waypoints().is_empty());
+    }
+
+    #[test]
+    fn test_no_path_over_void() {
+        let workspace = workspace_with_floor();
+        let service = PathfindingService::new();
+        let path = service.borrow_mut().compute_path(
+            &workspace,
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(200.0, 0.0, 0.0),
+            AgentParameters::default(),
+        );
+        assert_eq!(path.status(), PathStatus::NoPath);
+    }
+
+    #[test]
+    fn test_blocked_fires_when_world_changes() {
+        let workspace = workspace_with_floor();
+        let service = PathfindingService::new();
+        let path = service.borrow_mut().compute_path(
+            &workspace,
+            Vec3::new(-20.0, 0.0, 2.0),
+            Vec3::new(20.0, 0.0, 2.0),
+            AgentParameters::default(),
+        );
+
+        let blocked = Rc::new(RefCell::new(Vec::new()));
+        let blocked_clone = blocked.clone();
+        path.blocked().connect(move |index| blocked_clone.borrow_mut().push(*index));
+
+        PathfindingService::update(&service, &workspace);
+        assert!(blocked.borrow().is_empty());
+
+        let wall = add_part(&workspace, Vec3::new(0.0, 5.0, 2.0), Vec3::new(2.0, 10.0, 10.0));
+        PathfindingService::update(&service, &workspace);
+        PathfindingService::update(&service, &workspace);
+        assert_eq!(blocked.borrow().len(), 1);
+
+        let index = blocked.borrow()[0];
+        assert_eq!(path.check_occlusion(&workspace, 0), Some(index));
+        assert!(path.get_waypoints()[index].position.x.abs() <= 4.0);
+
+        Instance::set_parent(wall.instance(), None);
+        assert_eq!(path.check_occlusion(&workspace, 0), None);
+        drop(path);
+        PathfindingService::update(&service, &workspace);
+        assert_eq!(service.borrow().num_paths(), 0);
+    }
+}
@@ -1,6 +1,11 @@
 use rapier3d::prelude::*;
-use glam::Vec3;
+use glam::{Quat, Vec3};
 use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use rnr_core::instance::Instance;
+use rnr_core::types::CFrame;
+use rnr_datamodel::{BasePart, DEFAULT_GRAVITY};
 
 /// Physics world using Rapier3D (replaces Bullet)
 pub struct PhysicsWorld {
@@ -32,6 +37,8 @@ pub struct PhysicsWorld {
     pub query_pipeline: QueryPipeline,
     /// Physics hooks
     pub hooks: (),
+    /// Parts simulated by a rigid body, and their bodies
+    parts: Vec<(Weak<RefCell<Instance>>, RigidBodyHandle)>,
 }
 
 impl PhysicsWorld {
@@ -39,7 +46,7 @@ impl PhysicsWorld {
     pub fn new() -> Self {
         Self {
             pipeline: PhysicsPipeline::new(),
-            gravity: Vec3::new(0.0, -9.81, 0.0), // Standard gravity
+            gravity: Vec3::new(0.0, -DEFAULT_GRAVITY as f32, 0.0),
             integration_parameters: IntegrationParameters::default(),
             islands: IslandManager::new(),
             broad_phase: BroadPhase::new(),
@@ -52,6 +59,7 @@ impl PhysicsWorld {
             event_handler: (),
             query_pipeline: QueryPipeline::new(),
             hooks: (),
+            parts: Vec::new(),
         }
     }
 
@@ -106,6 +114,107 @@ impl PhysicsWorld {
         self.rigid_bodies.get_mut(handle)
     }
 
+    /// Simulate a part with a box shaped rigid body at its CFrame, fixed if
+    /// the part is anchored
+    pub fn add_part(&mut self, part: &BasePart) -> RigidBodyHandle {
+        let cframe = part.cframe();
+        let builder = if part.anchored() { RigidBodyBuilder::fixed() } else { RigidBodyBuilder::dynamic() };
+        let handle = self.add_rigid_body(builder.position(cframe_to_isometry(&cframe)).build());
+        let collider = ColliderBuilder::cuboid(part.size().x / 2.0, part.size().y / 2.0, part.size().z / 2.0)
+            .sensor(!part.can_collide())
+            .build();
+        self.colliders.insert_with_parent(collider, handle, &mut self.rigid_bodies);
+        self.parts.push((Rc::downgrade(part.instance()), handle));
+        handle
+    }
+
+    /// Get the rigid body simulating a part
+    pub fn get_part_body(&self, part: &Rc<RefCell<Instance>>) -> Option<RigidBodyHandle> {
+        self.parts
+            .iter()
+            .find(|(known, _)| known.as_ptr() == Rc::as_ptr(part))
+            .map(|(_, handle)| *handle)
+    }
+
+    /// Move a part's body to the part's CFrame and stop it, for parts moved
+    /// outside of physics. Returns false if the part has no body.
+    pub fn teleport_part(&mut self, part: &BasePart) -> bool {
+        let Some(body) = self.get_part_body(part.instance()).and_then(|handle| self.rigid_bodies.get_mut(handle)) else {
+            return false;
+        };
+        body.set_position(cframe_to_isometry(&part.cframe()), true);
+        body.set_linvel(vector![0.0, 0.0, 0.0], true);
+        body.set_angvel(vector![0.0, 0.0, 0.0], true);
+        true
+    }
+
+    /// Get the part a collider belongs to, if it simulates a part
+    pub fn get_collider_part(&self, collider: ColliderHandle) -> Option<Rc<RefCell<Instance>>> {
+        let body = self.colliders.get(collider)?.parent()?;
+        self.parts
+            .iter()
+            .find(|(_, handle)| *handle == body)
+            .and_then(|(part, _)| part.upgrade())
+    }
+
+    /// Cast a ray and get the first part it hits, and where
+    pub fn cast_ray_part(&self, origin: Vec3, direction: Vec3, max_distance: f32) -> Option<(Rc<RefCell<Instance>>, Vec3)> {
+        let direction = direction.normalize_or_zero();
+        let (collider, distance) = self.cast_ray(origin, direction, max_distance)?;
+        let part = self.get_collider_part(collider)?;
+        Some((part, origin + direction * distance))
+    }
+
+    /// Hinge two part bodies together about `axis` through `anchor`, both
+    /// in world space. Part1 turns relative to Part0, so for a wheel the
+    /// wheel should be Part1.
+    pub fn add_part_hinge(&mut self, part0: &BasePart, part1: &BasePart, anchor: Vec3, axis: Vec3) -> Option<ImpulseJointHandle> {
+        let body0 = self.get_part_body(part0.instance())?;
+        let body1 = self.get_part_body(part1.instance())?;
+        let anchor = point![anchor.x, anchor.y, anchor.z];
+        let axis = vector![axis.x, axis.y, axis.z];
+        let position0 = *self.rigid_bodies.get(body0)?.position();
+        let position1 = *self.rigid_bodies.get(body1)?.position();
+
+        let mut joint = RevoluteJointBuilder::new(UnitVector::new_normalize(position0.inverse_transform_vector(&axis)))
+            .local_anchor1(position0.inverse_transform_point(&anchor))
+            .local_anchor2(position1.inverse_transform_point(&anchor))
+            .build();
+        joint.data.set_local_axis2(UnitVector::new_normalize(position1.inverse_transform_vector(&axis)));
+        Some(self.impulse_joints.insert(body0, body1, joint, true))
+    }
+
+    /// Weld two part bodies together, holding Part1 at `c1` from the point
+    /// `c0` of Part0, the way a Weld holds its parts
+    pub fn add_part_weld(&mut self, part0: &BasePart, part1: &BasePart, c0: CFrame, c1: CFrame) -> Option<ImpulseJointHandle> {
+        let body0 = self.get_part_body(part0.instance())?;
+        let body1 = self.get_part_body(part1.instance())?;
+        let joint = FixedJointBuilder::new()
+            .local_frame1(cframe_to_isometry(&c0))
+            .local_frame2(cframe_to_isometry(&c1))
+            .build();
+        Some(self.impulse_joints.insert(body0, body1, joint, true))
+    }
+
+    /// Copy the pose of each dynamic part body back into its part's CFrame.
+    /// Bodies of parts that no longer exist, or were destroyed, are removed.
+    pub fn sync_parts(&mut self) {
+        let parts = std::mem::take(&mut self.parts);
+        for (part, handle) in parts {
+            let Some(part) = part.upgrade().filter(|part| !part.borrow().is_destroyed()) else {
+                self.remove_rigid_body(handle);
+                continue;
+            };
+
+            if let (Some(body), Some(part)) = (self.rigid_bodies.get(handle), BasePart::from_instance(&part)) {
+                if body.is_dynamic() {
+                    part.set_cframe(isometry_to_cframe(body.position()));
+                }
+            }
+            self.parts.push((Rc::downgrade(&part), handle));
+        }
+    }
+
     /// Add a collider
     pub fn add_collider(&mut self, collider: Collider) -> ColliderHandle {
         self.colliders.insert(collider)
@@ -144,6 +253,54 @@ impl PhysicsWorld {
         self.query_pipeline.project_point(&self.rigid_bodies, &self.colliders, &nalgebra::Point3::new(point.x, point.y, point.z), true, filter)
     }
 
+    /// Push dynamic bodies within `radius` of `center` straight away from it.
+    /// The impulse is `impulse` at the center and falls off linearly to
+    /// nothing at the edge. Returns how many bodies were pushed.
+    pub fn apply_blast_impulse(&mut self, center: Vec3, radius: f32, impulse: f32) -> usize {
+        let mut pushed = 0;
+        for (_, body) in self.rigid_bodies.iter_mut() {
+            if !body.is_dynamic() {
+                continue;
+            }
+
+            let translation = body.translation();
+            let offset = Vec3::new(translation.x, translation.y, translation.z) - center;
+            let distance = offset.length();
+            if distance > radius {
+                continue;
+            }
+
+            // Bodies at the very center are thrown upwards
+            let direction = if distance > 1e-4 { offset / distance } else { Vec3::Y };
+            let push = direction * impulse * (1.0 - distance / radius.max(1e-4));
+            body.apply_impulse(vector![push.x, push.y, push.z], true);
+            pushed += 1;
+        }
+        pushed
+    }
+
+    /// Remove every impulse joint with a body within `radius` of `center`.
+    /// Returns how many joints were removed.
+    pub fn break_joints_near(&mut self, center: Vec3, radius: f32) -> usize {
+        let rigid_bodies = &self.rigid_bodies;
+        let near = |handle: RigidBodyHandle| {
+            rigid_bodies.get(handle).is_some_and(|body| {
+                let translation = body.translation();
+                (Vec3::new(translation.x, translation.y, translation.z) - center).length() <= radius
+            })
+        };
+
+        let broken: Vec<ImpulseJointHandle> = self.impulse_joints
+            .iter()
+            .filter(|(_, joint)| near(joint.body1) || near(joint.body2))
+            .map(|(handle, _)| handle)
+            .collect();
+        for handle in &broken {
+            self.impulse_joints.remove(*handle, true);
+        }
+        broken.len()
+    }
+
     /// Create a box collider
     pub fn create_box_collider(half_extents: Vec3) -> Collider {
         ColliderBuilder::cuboid(half_extents.x, half_extents.y, half_extents.z).build()
@@ -175,6 +332,25 @@ impl PhysicsWorld {
     }
 }
 
+/// Convert a CFrame to a rapier position
+pub fn cframe_to_isometry(cframe: &CFrame) -> Isometry<Real> {
+    let rotation = cframe.to_quat();
+    Isometry::from_parts(
+        nalgebra::Translation3::new(cframe.position.x, cframe.position.y, cframe.position.z),
+        nalgebra::UnitQuaternion::from_quaternion(nalgebra::Quaternion::new(rotation.w, rotation.x, rotation.y, rotation.z)),
+    )
+}
+
+/// Convert a rapier position to a CFrame
+pub fn isometry_to_cframe(isometry: &Isometry<Real>) -> CFrame {
+    let translation = isometry.translation.vector;
+    let rotation = isometry.rotation.quaternion();
+    CFrame::from_rotation(
+        Vec3::new(translation.x, translation.y, translation.z),
+        Quat::from_xyzw(rotation.i, rotation.j, rotation.k, rotation.w),
+    )
+}
+
 /// Computational Physics Engine (ComPlicitNgine) - handles complex physics calculations
 pub struct ComPlicitNgine {
     /// Last physics delta time
@@ -247,7 +423,7 @@ mod tests {
     fn test_physics_world_creation() {
         let world = PhysicsWorld::new();
 
-        assert_eq!(world.get_gravity(), Vec3::new(0.0, -9.81, 0.0));
+        assert_eq!(world.get_gravity(), Vec3::new(0.0, -196.2, 0.0));
         assert!(world.rigid_bodies.is_empty());
         assert!(world.colliders.is_empty());
     }
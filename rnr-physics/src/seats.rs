@@ -0,0 +1,276 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use glam::{Mat3, Vec3};
+use rapier3d::prelude::*;
+use rnr_core::instance::Instance;
+use rnr_core::variant::Variant;
+use rnr_datamodel::{humanoid_sitting, BasePart, Seat};
+use crate::physics::PhysicsWorld;
+
+/// How far apart a character's part and a seat may be and still touch
+pub const SEAT_TOUCH_MARGIN: f32 = 0.1;
+
+/// Damping of the hinge motors a VehicleSeat drives. High enough that the
+/// seat's Torque is what limits them.
+const WHEEL_MOTOR_DAMPING: f32 = 1000.0;
+
+/// A seat and a humanoid that got up from it
+type LeftSeat = (Weak<RefCell<Instance>>, Weak<RefCell<Instance>>);
+
+/// Seats humanoids as they touch them and drives vehicles from their
+/// VehicleSeats.
+#[derive(Default)]
+pub struct Seats {
+    /// Seats and the humanoids that got up from them, kept from sitting
+    /// again until they stop touching
+    left: Vec<LeftSeat>,
+}
+
+impl Seats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update every seat in the workspace, in this order:
+    ///
+    /// 1. Occupants that jumped, died or lost their SeatWeld get up
+    /// 2. Occupants are moved along with their seats
+    /// 3. Living humanoids touching a free seat sit in it
+    /// 4. VehicleSeats set the motors of their vehicle's hinges
+    pub fn update(&mut self, workspace: &Rc<RefCell<Instance>>, physics_world: &mut PhysicsWorld) {
+        let descendants = workspace.borrow().get_descendants();
+        let seats: Vec<Seat> = descendants.iter().filter_map(Seat::from_instance).collect();
+
+        for seat in &seats {
+            let Some(humanoid) = seat.occupant() else {
+                continue;
+            };
+            let jumped = humanoid.borrow().get_property("Jump").and_then(Variant::as_bool).unwrap_or(false);
+            if jumped || !alive(&humanoid) || seat.seat_weld().is_none() {
+                seat.leave();
+                humanoid.borrow_mut().set_property("Jump", false);
+                self.left.push((Rc::downgrade(seat.instance()), Rc::downgrade(&humanoid)));
+            } else {
+                seat.hold_occupant();
+            }
+        }
+
+        let humanoids: Vec<Rc<RefCell<Instance>>> = descendants
+            .iter()
+            .filter(|instance| instance.borrow().is_a("Humanoid"))
+            .cloned()
+            .collect();
+        self.left.retain(|(seat, humanoid)| match (seat.upgrade(), humanoid.upgrade()) {
+            (Some(seat), Some(humanoid)) => BasePart::from_instance(&seat).is_some_and(|seat| touching(&seat, &humanoid)),
+            _ => false,
+        });
+
+        for seat in &seats {
+            if seat.disabled() || seat.occupant().is_some() {
+                continue;
+            }
+            let sitter = humanoids.iter().find(|humanoid| {
+                alive(humanoid)
+                    && !humanoid_sitting(humanoid)
+                    && !self.left.iter().any(|(left_seat, left_humanoid)| {
+                        left_seat.as_ptr() == Rc::as_ptr(seat.instance()) && left_humanoid.as_ptr() == Rc::as_ptr(humanoid)
+                    })
+                    && touching(seat.part(), humanoid)
+            });
+            if let Some(humanoid) = sitter {
+                seat.sit(humanoid);
+            }
+        }
+
+        for seat in seats.iter().filter(|seat| seat.is_vehicle_seat()) {
+            drive_vehicle(seat, physics_world);
+        }
+    }
+}
+
+/// Check if a Humanoid has health left
+pub(crate) fn alive(humanoid: &Rc<RefCell<Instance>>) -> bool {
+    humanoid.borrow().get_property("Health").and_then(Variant::as_float).unwrap_or(0.0) > 0.0
+}
+
+/// Check if any part of a humanoid's character touches a part
+pub(crate) fn touching(part: &BasePart, humanoid: &Rc<RefCell<Instance>>) -> bool {
+    let Some(character) = humanoid.borrow().parent() else {
+        return false;
+    };
+    let (center, half_extents) = world_bounds(part);
+    let descendants = character.borrow().get_descendants();
+    descendants.iter().filter_map(BasePart::from_instance).any(|limb| {
+        let (limb_center, limb_half_extents) = world_bounds(&limb);
+        let gap = (limb_center - center).abs() - half_extents - limb_half_extents;
+        gap.max_element() <= SEAT_TOUCH_MARGIN
+    })
+}
+
+/// Get the center and half size of a part's world space bounding box
+fn world_bounds(part: &BasePart) -> (Vec3, Vec3) {
+    let cframe = part.cframe();
+    let rotation = cframe.rotation;
+    let absolute = Mat3::from_cols(rotation.x_axis.abs(), rotation.y_axis.abs(), rotation.z_axis.abs());
+    (cframe.position, absolute * (part.size() / 2.0))
+}
+
+/// Set the motors of the hinges holding the wheels of a VehicleSeat's
+/// vehicle, the seat's parent.
+///
+/// A hinge is a wheel if its Part1 is in the vehicle. Wheels roll towards
+/// the seat's look vector at Throttle * MaxSpeed, and Steer turns the
+/// vehicle by speeding up the wheels on one side and slowing the other.
+fn drive_vehicle(seat: &Seat, physics_world: &mut PhysicsWorld) {
+    let Some(vehicle) = seat.instance().borrow().parent() else {
+        return;
+    };
+    let wheels: Vec<(RigidBodyHandle, f32)> = vehicle.borrow()
+        .get_descendants()
+        .iter()
+        .filter_map(BasePart::from_instance)
+        .filter_map(|part| physics_world.get_part_body(part.instance()).map(|handle| (handle, (part.size().y / 2.0).max(0.1))))
+        .collect();
+
+    let seat_cframe = seat.part().cframe();
+    let right = seat_cframe.right_vector();
+    let throttle = seat.throttle() as f32 * seat.max_speed() as f32;
+    let turn = seat.steer() as f32 * seat.turn_speed() as f32;
+    let torque = seat.torque() as f32;
+
+    let mut driven = Vec::new();
+    for (_, joint) in physics_world.impulse_joints.iter_mut() {
+        if joint.data.locked_axes != JointAxesMask::LOCKED_REVOLUTE_AXES {
+            continue;
+        }
+        let Some(&(_, radius)) = wheels.iter().find(|(handle, _)| *handle == joint.body2) else {
+            continue;
+        };
+        let Some(body1) = physics_world.rigid_bodies.get(joint.body1) else {
+            continue;
+        };
+
+        let axis = body1.position() * joint.data.local_axis1();
+        let anchor = body1.position() * joint.data.local_anchor1();
+        let lateral = seat_cframe.point_to_object_space(Vec3::new(anchor.x, anchor.y, anchor.z)).x;
+
+        // Rolling towards the look vector is a negative turn about the right
+        // vector. Wheels on the left (-X) speed up to steer right.
+        let speed = throttle - turn * lateral;
+        let spin = -right * (speed / radius);
+        let target = spin.dot(Vec3::new(axis.x, axis.y, axis.z));
+        joint.data.set_motor_velocity(JointAxis::AngX, target, WHEEL_MOTOR_DAMPING);
+        joint.data.set_motor_max_force(JointAxis::AngX, torque);
+        driven.push((joint.body1, joint.body2));
+    }
+
+    for (body1, body2) in driven {
+        for handle in [body1, body2] {
+            if let Some(body) = physics_world.rigid_bodies.get_mut(handle) {
+                body.wake_up(true);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rnr_datamodel::{create_model, Debris, Players};
+
+    #[test]
+    fn test_touching_seats_and_jumping_out() {
+        let workspace = Instance::new();
+        let players = Players::new();
+        let player = Players::add_player(&players, "Player1", 1);
+        let character = players.borrow_mut().load_character(&player, &workspace, &mut Debris::new().borrow_mut());
+        let humanoid = character.borrow().find_first_child("Humanoid").unwrap();
+        let torso = BasePart::from_instance(&character.borrow().find_first_child("Torso").unwrap()).unwrap();
+        let mut physics_world = PhysicsWorld::new();
+        let mut seats = Seats::new();
+
+        let seat = Seat::new("Seat");
+        seat.part().set_position(torso.position() + Vec3::new(0.0, 0.0, 10.0));
+        Instance::set_parent(seat.instance(), Some(workspace.clone()));
+        seats.update(&workspace, &mut physics_world);
+        assert!(seat.occupant().is_none());
+
+        // Walking into the seat sits the humanoid down
+        seat.part().set_position(torso.position() + Vec3::new(0.0, 0.0, 1.5));
+        seats.update(&workspace, &mut physics_world);
+        assert!(seat.occupant().is_some());
+        assert!(humanoid_sitting(&humanoid));
+
+        // The occupant rides along
+        seat.part().set_position(seat.part().position() + Vec3::new(5.0, 0.0, 0.0));
+        seats.update(&workspace, &mut physics_world);
+        assert!((torso.position().x - seat.part().position().x).abs() < 1e-4);
+
+        // Jumping gets up, and the humanoid doesn't sit again while touching
+        humanoid.borrow_mut().set_property("Jump", true);
+        seats.update(&workspace, &mut physics_world);
+        assert!(seat.occupant().is_none());
+        assert!(!humanoid_sitting(&humanoid));
+        seats.update(&workspace, &mut physics_world);
+        assert!(seat.occupant().is_none());
+
+        // Dead humanoids don't sit
+        humanoid.borrow_mut().set_property("Health", 0.0);
+        let other = Seat::new("Seat");
+        other.part().set_position(torso.position());
+        Instance::set_parent(other.instance(), Some(workspace.clone()));
+        seats.update(&workspace, &mut physics_world);
+        assert!(other.occupant().is_none());
+    }
+
+    #[test]
+    fn test_vehicle_seat_drives_wheel_hinges() {
+        let workspace = Instance::new();
+        let car = create_model("Car");
+        Instance::set_parent(&car, Some(workspace.clone()));
+        let mut physics_world = PhysicsWorld::new();
+        physics_world.set_gravity(Vec3::ZERO);
+
+        let seat = Seat::new("VehicleSeat");
+        seat.part().set_size(Vec3::new(4.0, 1.0, 6.0));
+        seat.set_torque(100000.0);
+        seat.set_max_speed(10.0);
+        Instance::set_parent(seat.instance(), Some(car.clone()));
+        physics_world.add_part(seat.part());
+
+        let mut wheels = Vec::new();
+        for x in [-3.0, 3.0] {
+            let wheel = BasePart::new("Part");
+            wheel.set_size(Vec3::new(1.0, 2.0, 2.0));
+            wheel.set_position(Vec3::new(x, 0.0, 0.0));
+            Instance::set_parent(wheel.instance(), Some(car.clone()));
+            physics_world.add_part(&wheel);
+            physics_world.add_part_hinge(seat.part(), &wheel, Vec3::new(x, 0.0, 0.0), Vec3::X).unwrap();
+            wheels.push(physics_world.get_part_body(wheel.instance()).unwrap());
+        }
+        let chassis = physics_world.get_part_body(seat.instance()).unwrap();
+        let mut seats = Seats::new();
+        let spin = |physics_world: &PhysicsWorld, wheel: RigidBodyHandle| {
+            let relative = physics_world.get_rigid_body(wheel).unwrap().angvel() - physics_world.get_rigid_body(chassis).unwrap().angvel();
+            relative.x
+        };
+        let run = |seats: &mut Seats, physics_world: &mut PhysicsWorld| {
+            for _ in 0..30 {
+                seats.update(&workspace, physics_world);
+                physics_world.step(1.0 / 60.0);
+            }
+        };
+
+        // Full throttle spins both wheels forward at MaxSpeed / radius
+        seat.set_throttle(1);
+        run(&mut seats, &mut physics_world);
+        for wheel in &wheels {
+            assert!((spin(&physics_world, *wheel) + 10.0).abs() < 0.5, "{}", spin(&physics_world, *wheel));
+        }
+
+        // Steering right speeds up the left wheel
+        seat.set_steer(1);
+        run(&mut seats, &mut physics_world);
+        assert!(spin(&physics_world, wheels[0]) < spin(&physics_world, wheels[1]) - 1.0);
+    }
+}
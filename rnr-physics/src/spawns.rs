@@ -0,0 +1,73 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rnr_core::instance::Instance;
+use rnr_datamodel::{find_spawn_locations, Players, SpawnLocation, Teams};
+use crate::seats::{alive, touching};
+
+/// Move players whose living characters touch a team SpawnLocation with
+/// AllowTeamChangeOnTouch onto its team. Returns how many players changed
+/// teams.
+pub fn update_spawn_locations(workspace: &Rc<RefCell<Instance>>, players: &Players, teams: &Teams) -> usize {
+    let spawns: Vec<SpawnLocation> = find_spawn_locations(workspace)
+        .into_iter()
+        .filter(SpawnLocation::allow_team_change_on_touch)
+        .collect();
+    if spawns.is_empty() {
+        return 0;
+    }
+
+    let mut changed = 0;
+    for player in players.get_players() {
+        let Some(humanoid) = player.character().and_then(|character| character.borrow().find_first_child("Humanoid")) else {
+            continue;
+        };
+        if !alive(&humanoid) {
+            continue;
+        }
+        if spawns.iter().any(|spawn| touching(spawn.part(), &humanoid) && spawn.touched_by(&player, teams)) {
+            changed += 1;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+    use rnr_core::types::{BrickColor, CFrame};
+    use rnr_datamodel::{move_model, BasePart, Debris};
+
+    #[test]
+    fn test_touching_a_team_spawn_changes_team() {
+        let workspace = Instance::new();
+        let players = Players::new();
+        let teams = Teams::new();
+        let red = teams.borrow().add_team("Red", BrickColor::red());
+        let blue = teams.borrow().add_team("Blue", BrickColor::blue());
+        let player = Players::add_player(&players, "Player1", 1);
+        player.set_team(Some(&red));
+        let character = players.borrow_mut().load_character(&player, &workspace, &mut Debris::new().borrow_mut());
+
+        let spawn = SpawnLocation::new();
+        spawn.set_neutral(false);
+        spawn.set_team_color(BrickColor::blue());
+        // Level with the character's feet, off to one side
+        let torso = character.borrow().find_first_child("Torso").unwrap();
+        let feet = BasePart::from_instance(&torso).unwrap().position() - Vec3::new(0.0, 3.5, 0.0);
+        spawn.part().set_position(feet + Vec3::new(50.0, 0.0, 0.0));
+        Instance::set_parent(spawn.instance(), Some(workspace.clone()));
+
+        // Out of reach, or without AllowTeamChangeOnTouch, nothing happens
+        assert_eq!(update_spawn_locations(&workspace, &players.borrow(), &teams.borrow()), 0);
+        move_model(&character, CFrame::IDENTITY, CFrame::new(Vec3::new(50.0, 0.0, 0.0)));
+        spawn.set_allow_team_change_on_touch(false);
+        assert_eq!(update_spawn_locations(&workspace, &players.borrow(), &teams.borrow()), 0);
+        assert!(player.is_on_team(&red));
+
+        spawn.set_allow_team_change_on_touch(true);
+        assert_eq!(update_spawn_locations(&workspace, &players.borrow(), &teams.borrow()), 1);
+        assert!(player.is_on_team(&blue));
+        assert_eq!(update_spawn_locations(&workspace, &players.borrow(), &teams.borrow()), 0);
+    }
+}
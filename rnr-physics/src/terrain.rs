@@ -0,0 +1,299 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use glam::{IVec3, Vec2, Vec3, Vec4};
+use rapier3d::prelude::*;
+use rnr_datamodel::{Material, Terrain, CHUNK_SIZE, VOXEL_SIZE};
+use rnr_rendering::{Mesh, Renderer, Vertex};
+use crate::physics::PhysicsWorld;
+
+/// Material terrain chunk meshes are drawn with
+pub const TERRAIN_MATERIAL: &str = "InstancedMaterial";
+
+/// Occupancy a voxel must be over to count as inside the surface
+const SURFACE_LEVEL: f32 = 0.5;
+
+/// Get the name a terrain chunk's mesh is registered under
+pub fn terrain_chunk_mesh_name(chunk: IVec3) -> String {
+    format!("Terrain_{}_{}_{}", chunk.x, chunk.y, chunk.z)
+}
+
+/// Mesh one chunk of terrain with surface nets, a simple form of dual
+/// contouring.
+///
+/// Voxel centers are the samples. Every cell between eight samples that
+/// the surface passes through gets one vertex, at the average of where the
+/// surface crosses the cell's edges, and every sample edge the surface
+/// crosses gets a quad joining the four cells around it. A chunk owns the
+/// edges starting at its own voxels, so neighboring chunks meet without
+/// gaps. Vertex colors (uv4) come from the voxel materials.
+///
+/// Only voxels of materials `include` accepts count as filled, so the same
+/// terrain can be meshed for drawing (every material) and for collision
+/// (solid materials only).
+pub fn mesh_terrain_chunk(terrain: &Terrain, chunk: IVec3, include: impl Fn(Material) -> bool) -> Mesh {
+    let mut mesher = ChunkMesher {
+        terrain,
+        include,
+        mesh: Mesh::new(&terrain_chunk_mesh_name(chunk)),
+        cells: HashMap::new(),
+    };
+
+    let origin = chunk * CHUNK_SIZE;
+    for x in 0..CHUNK_SIZE {
+        for y in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                let sample = origin + IVec3::new(x, y, z);
+                for axis in 0..3 {
+                    mesher.add_edge_quad(sample, axis);
+                }
+            }
+        }
+    }
+
+    let index_count = mesher.mesh.indices.len() as u32;
+    let mut mesh = mesher.mesh;
+    if index_count > 0 {
+        mesh.add_submesh("Terrain", 0, index_count, TERRAIN_MATERIAL);
+    }
+    mesh
+}
+
+struct ChunkMesher<'a, F> {
+    terrain: &'a Terrain,
+    include: F,
+    mesh: Mesh,
+    /// Vertex of each cell meshed so far, or None if the surface misses it
+    cells: HashMap<IVec3, Option<u32>>,
+}
+
+impl<F: Fn(Material) -> bool> ChunkMesher<'_, F> {
+    fn density(&self, sample: IVec3) -> f32 {
+        let voxel = self.terrain.voxel(sample);
+        if (self.include)(voxel.material) { voxel.occupancy } else { 0.0 }
+    }
+
+    /// Add the quad for the edge from `sample` one voxel along `axis`, if the
+    /// surface crosses it
+    fn add_edge_quad(&mut self, sample: IVec3, axis: usize) {
+        let inside = self.density(sample) > SURFACE_LEVEL;
+        if inside == (self.density(sample + unit(axis)) > SURFACE_LEVEL) {
+            return;
+        }
+
+        // The other two axes, in the order that makes their cross product
+        // point along `axis`
+        let (u, v) = (unit((axis + 1) % 3), unit((axis + 2) % 3));
+        let corners = [sample - u - v, sample - v, sample, sample - u];
+        let mut quad = [0u32; 4];
+        for (vertex, cell) in quad.iter_mut().zip(corners) {
+            match self.cell_vertex(cell) {
+                Some(index) => *vertex = index,
+                None => return,
+            }
+        }
+
+        // Face away from the filled side
+        if !inside {
+            quad.reverse();
+        }
+        self.mesh.indices.extend_from_slice(&[quad[0], quad[1], quad[2], quad[0], quad[2], quad[3]]);
+    }
+
+    /// Get the vertex of the cell whose lowest sample is `cell`, adding it
+    /// the first time
+    fn cell_vertex(&mut self, cell: IVec3) -> Option<u32> {
+        if let Some(vertex) = self.cells.get(&cell) {
+            return *vertex;
+        }
+
+        let mut densities = [0.0; 8];
+        for (corner, density) in densities.iter_mut().enumerate() {
+            *density = self.density(cell + corner_offset(corner));
+        }
+
+        let mut crossings = Vec3::ZERO;
+        let mut count = 0;
+        for (a, b) in CELL_EDGES {
+            let (da, db) = (densities[a], densities[b]);
+            if (da > SURFACE_LEVEL) != (db > SURFACE_LEVEL) {
+                let t = (SURFACE_LEVEL - da) / (db - da);
+                crossings += corner_offset(a).as_vec3().lerp(corner_offset(b).as_vec3(), t);
+                count += 1;
+            }
+        }
+
+        let vertex = if count == 0 {
+            None
+        } else {
+            let local = crossings / count as f32;
+            let position = (cell.as_vec3() + local + 0.5) * VOXEL_SIZE;
+
+            // The surface faces down the density gradient
+            let mut gradient = Vec3::ZERO;
+            for (corner, density) in densities.iter().enumerate() {
+                gradient += (corner_offset(corner).as_vec3() * 2.0 - 1.0) * *density;
+            }
+            let normal = (-gradient).normalize_or_zero();
+
+            // Color by the fullest filled corner
+            let material = (0..8)
+                .map(|corner| self.terrain.voxel(cell + corner_offset(corner)))
+                .filter(|voxel| (self.include)(voxel.material))
+                .max_by(|a, b| a.occupancy.total_cmp(&b.occupancy))
+                .map(|voxel| voxel.material)
+                .unwrap_or(Material::Air);
+            let color = material.color();
+
+            let mut vertex = Vertex::new(position, normal, Vec2::new(position.x, position.z) / VOXEL_SIZE);
+            vertex.uv4 = Vec4::new(color.r, color.g, color.b, 1.0);
+            self.mesh.add_vertex(vertex);
+            Some(self.mesh.vertices.len() as u32 - 1)
+        };
+        self.cells.insert(cell, vertex);
+        vertex
+    }
+}
+
+/// The twelve edges of a cell, as pairs of corners
+const CELL_EDGES: [(usize, usize); 12] = [
+    (0, 1), (2, 3), (4, 5), (6, 7),
+    (0, 2), (1, 3), (4, 6), (5, 7),
+    (0, 4), (1, 5), (2, 6), (3, 7),
+];
+
+/// Offset of corner `index` of a cell, with bit 0 for x, 1 for y and 2 for z
+fn corner_offset(index: usize) -> IVec3 {
+    IVec3::new((index & 1) as i32, ((index >> 1) & 1) as i32, ((index >> 2) & 1) as i32)
+}
+
+fn unit(axis: usize) -> IVec3 {
+    let mut unit = IVec3::ZERO;
+    unit[axis] = 1;
+    unit
+}
+
+/// Keeps terrain chunk meshes and colliders up to date with the voxels.
+///
+/// Only chunks the terrain marked dirty are re-meshed. Each chunk's drawn
+/// mesh is registered with the renderer, and the solid part of it becomes a
+/// fixed trimesh collider.
+#[derive(Default)]
+pub struct TerrainChunks {
+    colliders: HashMap<IVec3, ColliderHandle>,
+    meshes: Vec<IVec3>,
+}
+
+impl TerrainChunks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Re-mesh and re-collide the chunks changed since the last update,
+    /// returning how many were updated
+    pub fn update(&mut self, terrain: &Rc<RefCell<Terrain>>, physics_world: &mut PhysicsWorld, renderer: &mut Renderer) -> usize {
+        let dirty = terrain.borrow_mut().take_dirty_chunks();
+        let terrain = terrain.borrow();
+        for &chunk in &dirty {
+            let mesh = mesh_terrain_chunk(&terrain, chunk, |material| material != Material::Air);
+            let name = mesh.name.clone();
+            self.meshes.retain(|known| *known != chunk);
+            if mesh.indices.is_empty() {
+                renderer.mesh_manager.meshes.remove(&name);
+            } else {
+                self.meshes.push(chunk);
+                renderer.mesh_manager.register_mesh(mesh);
+            }
+
+            if let Some(collider) = self.colliders.remove(&chunk) {
+                physics_world.remove_collider(collider);
+            }
+            let solid = mesh_terrain_chunk(&terrain, chunk, Material::is_solid);
+            if !solid.indices.is_empty() {
+                let vertices = solid.vertices.iter().map(|vertex| point![vertex.position.x, vertex.position.y, vertex.position.z]).collect();
+                let indices = solid.indices.chunks(3).map(|triangle| [triangle[0], triangle[1], triangle[2]]).collect();
+                let collider = physics_world.colliders.insert(ColliderBuilder::trimesh(vertices, indices).build());
+                self.colliders.insert(chunk, collider);
+            }
+        }
+        dirty.len()
+    }
+
+    /// Get the chunks that have a mesh to draw
+    pub fn meshed_chunks(&self) -> &[IVec3] {
+        &self.meshes
+    }
+
+    /// Get the collider of a chunk, if it has anything solid in it
+    pub fn chunk_collider(&self, chunk: IVec3) -> Option<ColliderHandle> {
+        self.colliders.get(&chunk).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rnr_core::types::{CFrame, Region3};
+    use rnr_datamodel::Voxel;
+
+    #[test]
+    fn test_meshes_closed_surface() {
+        let terrain = Terrain::new();
+        terrain.borrow_mut().set_voxel(IVec3::new(3, 3, 3), Voxel::new(Material::Grass, 1.0));
+        let mesh = mesh_terrain_chunk(&terrain.borrow(), IVec3::ZERO, |material| material != Material::Air);
+
+        // One voxel gives a closed octahedron-like shell: a quad per face
+        assert_eq!(mesh.indices.len(), 6 * 6);
+        assert_eq!(mesh.vertices.len(), 8);
+        let center = Terrain::voxel_center(IVec3::new(3, 3, 3));
+        for vertex in &mesh.vertices {
+            assert!((vertex.position - center).abs().max_element() < VOXEL_SIZE);
+            // Normals point out of the voxel
+            assert!(vertex.normal.dot(vertex.position - center) > 0.0);
+            assert_eq!(vertex.uv4.truncate(), Vec3::new(Material::Grass.color().r, Material::Grass.color().g, Material::Grass.color().b));
+        }
+        // Triangles wind outwards
+        for triangle in mesh.indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[triangle[i] as usize].position);
+            assert!((b - a).cross(c - a).dot(a - center) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_chunk_colliders_follow_voxel_changes() {
+        let terrain = Terrain::new();
+        let mut physics_world = PhysicsWorld::new();
+        let mut renderer = Renderer::new();
+        let mut chunks = TerrainChunks::new();
+
+        // A flat floor 8 studs thick across two chunks, under a pond
+        terrain.borrow_mut().fill_block(CFrame::new(Vec3::new(32.0, 4.0, 16.0)), Vec3::new(64.0, 8.0, 32.0), Material::Grass);
+        terrain.borrow_mut().fill_region(&Region3::new(Vec3::new(0.0, 8.0, 0.0), Vec3::new(8.0, 12.0, 8.0)), VOXEL_SIZE, Material::Water).unwrap();
+        assert!(chunks.update(&terrain, &mut physics_world, &mut renderer) > 0);
+        assert!(renderer.mesh_manager.get_mesh(&terrain_chunk_mesh_name(IVec3::ZERO)).is_some());
+        assert!(chunks.meshed_chunks().contains(&IVec3::ZERO));
+        physics_world.step(1.0 / 60.0);
+
+        // Rays land on the floor, through the water
+        for x in [4.0, 40.0] {
+            let (_, distance) = physics_world.cast_ray(Vec3::new(x, 50.0, 4.0), -Vec3::Y, 100.0).unwrap();
+            assert!((50.0 - distance - 8.0).abs() < 1.0, "{distance}");
+        }
+
+        // Nothing changed, so nothing is re-meshed
+        assert_eq!(chunks.update(&terrain, &mut physics_world, &mut renderer), 0);
+
+        // Digging a hole only re-meshes the chunks around it
+        terrain.borrow_mut().fill_block(CFrame::new(Vec3::new(40.0, 4.0, 16.0)), Vec3::new(8.0, 20.0, 8.0), Material::Air);
+        let updated = chunks.update(&terrain, &mut physics_world, &mut renderer);
+        assert!(updated > 0 && updated < 27);
+        physics_world.step(1.0 / 60.0);
+        assert!(physics_world.cast_ray(Vec3::new(40.0, 50.0, 16.0), -Vec3::Y, 100.0).is_none());
+        assert!(physics_world.cast_ray(Vec3::new(4.0, 50.0, 16.0), -Vec3::Y, 100.0).is_some());
+
+        terrain.borrow_mut().clear();
+        chunks.update(&terrain, &mut physics_world, &mut renderer);
+        assert!(chunks.meshed_chunks().is_empty());
+        assert!(chunks.chunk_collider(IVec3::ZERO).is_none());
+    }
+}
@@ -1,12 +1,25 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::rc::Rc;
 use std::cell::RefCell;
-use glam::Mat4;
+use glam::{Mat4, Quat, Vec2, Vec3, Vec4};
 use rnr_core::instance::Instance;
-use rnr_datamodel::DataModel;
-use rnr_rendering::{Renderer, Camera};
-
+use rnr_core::signal::Signal;
+use rnr_core::types::CFrame;
+use rnr_core::variant::Variant;
+use rnr_datamodel::{
+    coerce_property, create_instance, engine_property_name, parse_xml, read_xml_file, write_xml_file, BasePart, DataModel, Explosion, FlagStand,
+    GuiDrawItem, JointInstance, Player, Players, Teams, Terrain, Tools, XmlError, XmlItem, XmlProperty, XmlValue,
+};
+use rnr_rendering::{Renderer, Camera, GuiQuad, TextLayoutOptions, DEFAULT_FONT, FONT_DIRECTORY};
+
+use crate::appearance::draw_characters;
+use crate::body_movers::apply_body_movers;
+use crate::explosion::{ExplosionHit, ExplosionResult, Explosions};
+use crate::flags::{FlagCapture, Flags};
+use crate::humanoid::{Humanoid, Humanoids};
 use crate::physics::{PhysicsWorld, ComPlicitNgine};
+use crate::seats::Seats;
+use crate::terrain::{terrain_chunk_mesh_name, TerrainChunks, TERRAIN_MATERIAL};
 
 /// Loading states for the world
 #[derive(Debug, Clone, PartialEq)]
@@ -52,25 +65,73 @@ pub struct World {
     pub scene_has_render: bool,
     /// Last physics delta time
     pub last_physics_delta: f32,
+    /// Camera state as of the last sync with Workspace.CurrentCamera
+    camera_sync: Option<CameraSync>,
+    /// Explosions that have gone off and their Hit signals
+    explosions: Explosions,
+    /// Seat occupancy and vehicle driving
+    seats: Seats,
+    /// Physics humanoids walking their characters
+    humanoids: Humanoids,
+    /// Capture the flag and the stands' FlagCaptured signals
+    flags: Flags,
+    /// Terrain chunk meshes and colliders
+    terrain_chunks: TerrainChunks,
+}
+
+/// Snapshot of both sides of the camera bridge, taken at the last sync.
+///
+/// Each side is compared against its own snapshot, so converting between the
+/// CFrame and quaternion representations never looks like a change.
+struct CameraSync {
+    instance: Rc<RefCell<Instance>>,
+    cframe: CFrame,
+    field_of_view: f64,
+    aspect_ratio: f32,
+    position: Vec3,
+    rotation: Quat,
+    fov: f32,
+    render_aspect_ratio: f32,
 }
 
+/// An instance created by a load whose properties haven't been set yet.
+/// Properties are set once every instance exists, so Refs can be resolved.
 #[derive(Debug)]
 pub struct WorldUndeserialized {
     pub instance: Rc<RefCell<Instance>>,
     pub parent: Option<Rc<RefCell<Instance>>>,
-    pub xml_node: String, // Simplified, would be proper XML in real implementation
+    pub properties: Vec<XmlProperty>,
+}
+
+/// What a load couldn't make sense of. None of it stops the load.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadReport {
+    /// Classes the engine doesn't know, loaded as plain instances
+    pub unknown_classes: BTreeSet<String>,
+    /// Properties that couldn't be read or that their class doesn't have,
+    /// as (class, property)
+    pub unknown_properties: BTreeSet<(String, String)>,
+    /// Referents that Refs pointed at but no Item had
+    pub unresolved_refs: BTreeSet<String>,
+    /// How many instances were loaded
+    pub instances: usize,
 }
 
 impl World {
     /// Create a new world
     pub fn new(has_render: bool) -> Self {
         let datamodel = DataModel::new();
+        let mut renderer = Renderer::new();
+        if has_render {
+            // Text is simply not drawn when the fonts can't be found
+            let _ = renderer.load_fonts(FONT_DIRECTORY);
+        }
 
         Self {
             datamodel: datamodel.clone(),
             physics_world: PhysicsWorld::new(),
             com_plicit_ngine: ComPlicitNgine::new(),
-            renderer: Renderer::new(),
+            renderer,
             camera: None,
             refs: HashMap::new(),
             undeserialized: Vec::new(),
@@ -81,49 +142,331 @@ impl World {
             run_physics: true,
             scene_has_render: has_render,
             last_physics_delta: 0.0,
+            camera_sync: None,
+            explosions: Explosions::new(),
+            seats: Seats::new(),
+            humanoids: Humanoids::new(),
+            flags: Flags::new(),
+            terrain_chunks: TerrainChunks::new(),
         }
     }
 
-    /// Set the active camera
+    /// Set the active camera.
+    ///
+    /// Workspace.CurrentCamera is updated to match, so scripts see the view
+    /// the engine chose.
     pub fn set_camera(&mut self, camera: Camera) {
+        self.apply_camera(camera);
+        if let Some(current_camera) = self.current_camera() {
+            self.write_camera_instance(&current_camera);
+            self.snapshot_camera(&current_camera);
+        }
+    }
+
+    /// Get Workspace.CurrentCamera, if the workspace has been registered
+    pub fn current_camera(&self) -> Option<rnr_datamodel::Camera> {
+        let workspace = self.datamodel.borrow().get_service("Workspace")?;
+        rnr_datamodel::current_camera(&workspace)
+    }
+
+    /// Reconcile the active camera with Workspace.CurrentCamera.
+    ///
+    /// Whichever side changed since the last sync wins. If both changed, the
+    /// instance wins, since that means a script moved the camera this frame.
+    /// Switching CurrentCamera to another instance also makes the instance win,
+    /// and a missing engine camera is created from the instance.
+    ///
+    /// Call this once per frame, after scripts have had a chance to move the
+    /// camera.
+    pub fn sync_camera(&mut self) {
+        let Some(current_camera) = self.current_camera() else {
+            self.camera_sync = None;
+            return;
+        };
+
+        let (instance_changed, camera_changed) = match (&self.camera_sync, &self.camera) {
+            (Some(sync), Some(camera)) => (
+                !Rc::ptr_eq(&sync.instance, current_camera.instance())
+                    || sync.cframe != current_camera.cframe()
+                    || sync.field_of_view != current_camera.field_of_view()
+                    || sync.aspect_ratio != current_camera.aspect_ratio(),
+                sync.position != camera.position
+                    || sync.rotation != camera.rotation
+                    || sync.fov != camera.fov
+                    || sync.render_aspect_ratio != camera.aspect_ratio,
+            ),
+            // An engine camera set before the workspace existed is kept
+            (None, Some(_)) => (false, true),
+            (_, None) => (true, false),
+        };
+
+        if instance_changed {
+            let mut camera = self.camera.clone()
+                .unwrap_or_else(|| Camera::perspective(current_camera.field_of_view() as f32, current_camera.aspect_ratio(), 0.1, 1000.0));
+            let cframe = current_camera.cframe();
+            camera.set_position(cframe.position);
+            camera.set_rotation(cframe.to_quat());
+            camera.set_fov(current_camera.field_of_view() as f32);
+            camera.set_aspect_ratio(current_camera.aspect_ratio());
+            camera.update_matrices();
+            self.apply_camera(camera);
+        } else if camera_changed {
+            self.write_camera_instance(&current_camera);
+            if let Some(camera) = self.camera.clone() {
+                self.renderer.set_camera(camera);
+            }
+        }
+
+        self.snapshot_camera(&current_camera);
+    }
+
+    /// Make a camera active without touching the instance side
+    fn apply_camera(&mut self, camera: Camera) {
         self.camera = Some(camera.clone());
         self.renderer.set_camera(camera);
     }
 
+    /// Copy the active camera onto a Camera instance
+    fn write_camera_instance(&self, current_camera: &rnr_datamodel::Camera) {
+        let Some(camera) = &self.camera else {
+            return;
+        };
+
+        current_camera.set_cframe(CFrame::from_rotation(camera.position, camera.rotation));
+        current_camera.set_field_of_view(camera.fov as f64);
+
+        // The viewport keeps its height and takes its width from the aspect ratio
+        let viewport_size = current_camera.viewport_size();
+        if camera.aspect_ratio != current_camera.aspect_ratio() {
+            current_camera.set_viewport_size(glam::Vec2::new(viewport_size.y * camera.aspect_ratio, viewport_size.y));
+        }
+    }
+
+    /// Remember both sides of the camera bridge as they are now
+    fn snapshot_camera(&mut self, current_camera: &rnr_datamodel::Camera) {
+        self.camera_sync = self.camera.as_ref().map(|camera| CameraSync {
+            instance: current_camera.instance().clone(),
+            cframe: current_camera.cframe(),
+            field_of_view: current_camera.field_of_view(),
+            aspect_ratio: current_camera.aspect_ratio(),
+            position: camera.position,
+            rotation: camera.rotation,
+            fov: camera.fov,
+            render_aspect_ratio: camera.aspect_ratio,
+        });
+    }
+
     /// Get the active camera
     pub fn get_camera(&self) -> Option<&Camera> {
         self.camera.as_ref()
     }
 
-    /// Load a world from XML/path (simplified implementation)
-    pub fn load(&mut self, _path: &str, load_listener: Option<Box<dyn LoadListener>>) {
+    /// Get the ray from the active camera through a point on the screen,
+    /// from (0, 0) at the top left to (1, 1) at the bottom right, as an
+    /// origin and direction
+    pub fn screen_ray(&self, screen_point: Vec2) -> Option<(Vec3, Vec3)> {
+        let mut camera = self.camera.clone()?;
+        camera.update_matrices();
+        Some(camera.screen_to_world_ray(screen_point.x, screen_point.y))
+    }
+
+    /// Find the part under a point on the screen, and where the ray from the
+    /// active camera hits it
+    pub fn pick_part(&self, screen_point: Vec2) -> Option<(Rc<RefCell<Instance>>, Vec3)> {
+        let far_plane = self.camera.as_ref()?.far_plane;
+        let (origin, direction) = self.screen_ray(screen_point)?;
+        self.physics_world.cast_ray_part(origin, direction, far_plane)
+    }
+
+    /// Load a Roblox XML place or model (.rbxlx, .rbxmx) into the data
+    /// model. See `load_xml`.
+    pub fn load(&mut self, path: &str, load_listener: Option<Box<dyn LoadListener>>) -> Result<LoadReport, XmlError> {
+        let items = read_xml_file(path)?;
+        Ok(self.load_items(items, load_listener))
+    }
+
+    /// Load Roblox XML text into the data model, moving through the load
+    /// states and telling `load_listener` after each instance:
+    ///
+    /// 1. LoadingDataModel: an instance is created for each Item through the
+    ///    class factory and its referent is kept in `refs`. Top level Items
+    ///    naming a service registered in the data model load into it; other
+    ///    top level classes the engine doesn't know become new services,
+    ///    and models' top level Items go into the Workspace.
+    /// 2. LoadingDataModelProperties: properties are set, with Refs
+    ///    resolved through `refs`, then each instance is parented
+    /// 3. LoadingMakeJoints: parts in the Workspace get bodies and Welds
+    ///    hold their parts' bodies together
+    ///
+    /// Unknown classes and properties are reported, not treated as errors.
+    pub fn load_xml(&mut self, text: &str, load_listener: Option<Box<dyn LoadListener>>) -> Result<LoadReport, XmlError> {
+        let items = parse_xml(text)?;
+        Ok(self.load_items(items, load_listener))
+    }
+
+    /// Save the data model as a Roblox XML place (.rbxlx). See
+    /// `rnr_datamodel::write_xml`.
+    pub fn save(&self, path: &str) -> Result<(), XmlError> {
+        write_xml_file(path, self.datamodel.borrow().instance().borrow().children())
+    }
+
+    fn load_items(&mut self, items: Vec<XmlItem>, load_listener: Option<Box<dyn LoadListener>>) -> LoadReport {
         self.load_listener = load_listener;
-        self.load_state = WorldLoadState::LoadingDataModel;
-        self.max_load_progress = 100;
+        self.refs.clear();
+        self.undeserialized.clear();
+        let count: usize = items.iter().map(XmlItem::count).sum();
+        self.max_load_progress = (count * 3) as i32;
         self.load_progress = 0;
+        let mut report = LoadReport { instances: count, ..Default::default() };
+
+        self.set_load_state(WorldLoadState::LoadingDataModel);
+        let workspace = self.datamodel.borrow().get_service("Workspace");
+        for item in items {
+            let (instance, parent) = match self.datamodel.borrow().get_service(&item.class_name) {
+                Some(service) => (Some(service), None),
+                None if create_instance(&item.class_name).is_some() => {
+                    (None, Some(workspace.clone().unwrap_or_else(|| self.datamodel.borrow().instance().clone())))
+                }
+                None => (None, None),
+            };
+            let service = instance.is_none() && parent.is_none();
+            let instance = self.create_loaded_instance(item, instance, parent, &mut report);
+            if service {
+                let name = instance.borrow().class_name().to_string();
+                self.datamodel.borrow_mut().register_service(&name, instance);
+            }
+        }
 
-        // TODO: Implement actual XML loading
-        // For now, just simulate loading
-        self.load_progress = 50;
-        if let Some(ref mut listener) = self.load_listener {
-            listener.update_world_load();
+        self.set_load_state(WorldLoadState::LoadingDataModelProperties);
+        let undeserialized = std::mem::take(&mut self.undeserialized);
+        let loaded: Vec<Rc<RefCell<Instance>>> = undeserialized.iter().map(|pending| pending.instance.clone()).collect();
+        for pending in undeserialized {
+            self.deserialize_properties(&pending, &mut report);
+            if let Some(parent) = pending.parent {
+                Instance::set_parent(&pending.instance, Some(parent));
+            }
+            self.advance_load();
         }
 
-        self.load_state = WorldLoadState::LoadingDataModelProperties;
-        self.load_progress = 75;
-        if let Some(ref mut listener) = self.load_listener {
-            listener.update_world_load();
+        self.set_load_state(WorldLoadState::LoadingMakeJoints);
+        for instance in &loaded {
+            let in_workspace = workspace.as_ref().is_some_and(|workspace| Instance::is_ancestor_of(workspace, instance));
+            if let Some(part) = BasePart::from_instance(instance).filter(|_| in_workspace) {
+                if self.physics_world.get_part_body(instance).is_none() {
+                    self.physics_world.add_part(&part);
+                }
+            }
+            self.advance_load();
+        }
+        for joint in loaded.iter().filter(|instance| !instance.borrow().is_a("Motor")).filter_map(JointInstance::from_instance) {
+            if let (Some(part0), Some(part1)) = (joint.part0(), joint.part1()) {
+                self.physics_world.add_part_weld(&part0, &part1, joint.c0(), joint.c1());
+            }
+        }
+
+        self.load_progress = self.max_load_progress;
+        self.set_load_state(WorldLoadState::Finished);
+        report
+    }
+
+    /// Create the instance for an Item and its descendants, or use
+    /// `existing` for services, queueing their properties. Items under an
+    /// existing instance that match one of its children by class and name,
+    /// like the Workspace's Terrain and Camera, load into that child.
+    fn create_loaded_instance(
+        &mut self,
+        item: XmlItem,
+        existing: Option<Rc<RefCell<Instance>>>,
+        parent: Option<Rc<RefCell<Instance>>>,
+        report: &mut LoadReport,
+    ) -> Rc<RefCell<Instance>> {
+        let created = existing.is_none();
+        let instance = existing.or_else(|| create_instance(&item.class_name)).unwrap_or_else(|| {
+            report.unknown_classes.insert(item.class_name.clone());
+            let instance = Instance::new();
+            instance.borrow_mut().set_name(&item.class_name);
+            instance.borrow_mut().set_class_name(&item.class_name);
+            instance
+        });
+        // Factory defaults like a Tool's Handle are replaced by the file's
+        let mut existing_children = instance.borrow().children().to_vec();
+        if created {
+            for child in existing_children.drain(..) {
+                Instance::destroy(&child);
+            }
+        }
+        if let Some(referent) = &item.referent {
+            self.refs.insert(referent.clone(), instance.clone());
+        }
+        self.undeserialized.push(WorldUndeserialized { instance: instance.clone(), parent, properties: item.properties });
+        self.advance_load();
+
+        for child in item.children {
+            let name = child.properties.iter().find_map(|property| match &property.value {
+                XmlValue::Value(Variant::String(name)) if property.name == "Name" => Some(name.as_str()),
+                _ => None,
+            });
+            let matching = existing_children.iter().position(|existing| {
+                let existing = existing.borrow();
+                existing.class_name() == child.class_name && Some(existing.name()) == name
+            });
+            match matching {
+                Some(index) => self.create_loaded_instance(child, Some(existing_children.remove(index)), None, report),
+                None => self.create_loaded_instance(child, None, Some(instance.clone()), report),
+            };
+        }
+        instance
+    }
+
+    /// Set the properties read for an instance
+    fn deserialize_properties(&self, pending: &WorldUndeserialized, report: &mut LoadReport) {
+        let class_name = pending.instance.borrow().class_name().to_string();
+        let known_class = !report.unknown_classes.contains(&class_name);
+        for property in &pending.properties {
+            let name = engine_property_name(&property.name);
+            let mut instance = pending.instance.borrow_mut();
+            let value = match &property.value {
+                XmlValue::Value(Variant::String(text)) if name == "Name" => {
+                    instance.set_name(text);
+                    continue;
+                }
+                XmlValue::Value(Variant::Bool(archivable)) if name == "Archivable" => {
+                    instance.set_archivable(*archivable);
+                    continue;
+                }
+                XmlValue::Value(value) => coerce_property(value.clone(), instance.get_property(name)),
+                XmlValue::Ref(referent) => {
+                    let target = referent.as_ref().and_then(|referent| {
+                        let target = self.refs.get(referent);
+                        if target.is_none() {
+                            report.unresolved_refs.insert(referent.clone());
+                        }
+                        target
+                    });
+                    Variant::instance_ref(target)
+                }
+                XmlValue::Unreadable(_) => {
+                    report.unknown_properties.insert((class_name.clone(), property.name.clone()));
+                    continue;
+                }
+            };
+            if known_class && instance.get_property(name).is_none() {
+                report.unknown_properties.insert((class_name.clone(), property.name.clone()));
+            }
+            instance.set_property(name, value);
         }
+    }
 
-        self.load_state = WorldLoadState::LoadingMakeJoints;
-        self.load_progress = 90;
-        if let Some(ref mut listener) = self.load_listener {
+    fn set_load_state(&mut self, load_state: WorldLoadState) {
+        self.load_state = load_state;
+        if let Some(listener) = &mut self.load_listener {
             listener.update_world_load();
         }
+    }
 
-        self.load_state = WorldLoadState::Finished;
-        self.load_progress = 100;
-        if let Some(ref mut listener) = self.load_listener {
+    fn advance_load(&mut self) {
+        self.load_progress += 1;
+        if let Some(listener) = &mut self.load_listener {
             listener.update_world_load();
         }
     }
@@ -137,14 +480,90 @@ impl World {
 
         // Update renderer
         if self.scene_has_render {
-            // TODO: Update camera matrices, etc.
+            self.sync_camera();
+            if let Some(camera) = &mut self.camera {
+                camera.update_matrices();
+            }
+        }
+    }
+
+    /// Set off explosions that were added to `workspace` since the last call
+    pub fn update_explosions(&mut self, workspace: &Rc<RefCell<Instance>>) -> Vec<ExplosionResult> {
+        self.explosions.update(workspace, &mut self.physics_world)
+    }
+
+    /// Get the Hit signal of an explosion
+    pub fn explosion_hit(&mut self, explosion: &Explosion) -> Signal<ExplosionHit> {
+        self.explosions.hit(explosion)
+    }
+
+    /// Get the physics humanoid driving a Humanoid instance, attaching one
+    /// the first time. Use it to walk the character with MoveTo.
+    pub fn humanoid(&mut self, instance: &Rc<RefCell<Instance>>) -> Rc<RefCell<Humanoid>> {
+        self.humanoids.attach(instance)
+    }
+
+    /// Walk the humanoids in `workspace` towards their MoveTo targets
+    pub fn update_humanoids(&mut self, workspace: &Rc<RefCell<Instance>>, delta_time: f32) {
+        self.humanoids.update(workspace, delta_time);
+    }
+
+    /// Get up, seat and carry the occupants of the seats in `workspace`, and
+    /// drive vehicles from their VehicleSeats
+    pub fn update_seats(&mut self, workspace: &Rc<RefCell<Instance>>) {
+        self.seats.update(workspace, &mut self.physics_world);
+    }
+
+    /// Pick up, return and capture the flags in `workspace`
+    pub fn update_flags(&mut self, workspace: &Rc<RefCell<Instance>>, players: &Players, tools: &mut Tools) -> Vec<FlagCapture> {
+        self.flags.update(workspace, players, tools)
+    }
+
+    /// Move players touching team spawns in `workspace` that allow it onto
+    /// the spawns' teams. Returns how many players changed teams.
+    pub fn update_spawn_locations(&self, workspace: &Rc<RefCell<Instance>>, players: &Players, teams: &Teams) -> usize {
+        crate::spawns::update_spawn_locations(workspace, players, teams)
+    }
+
+    /// Get the FlagCaptured signal of a flag stand
+    pub fn flag_captured(&mut self, stand: &FlagStand) -> Signal<Player> {
+        self.flags.flag_captured(stand)
+    }
+
+    /// Re-mesh and re-collide the terrain chunks changed since the last
+    /// update, returning how many were updated
+    pub fn update_terrain(&mut self, terrain: &Rc<RefCell<Terrain>>) -> usize {
+        self.terrain_chunks.update(terrain, &mut self.physics_world, &mut self.renderer)
+    }
+
+    /// Get the terrain chunk meshes and colliders
+    pub fn terrain_chunks(&self) -> &TerrainChunks {
+        &self.terrain_chunks
+    }
+
+    /// Queue every terrain chunk mesh for drawing
+    pub fn draw_terrain(&mut self) {
+        for chunk in self.terrain_chunks.meshed_chunks().to_vec() {
+            self.renderer.draw_mesh(&terrain_chunk_mesh_name(chunk), TERRAIN_MATERIAL, Mat4::IDENTITY, Vec4::ONE);
         }
     }
 
-    /// Step physics simulation
+    /// Queue every player's character for drawing with its appearance
+    pub fn draw_characters(&mut self, players: &Players) -> usize {
+        draw_characters(&mut self.renderer, players)
+    }
+
+    /// Apply the body movers in `workspace` to their parts' bodies, ahead of
+    /// a physics step of `timestep`
+    pub fn apply_body_movers(&mut self, workspace: &Rc<RefCell<Instance>>, timestep: f32) -> usize {
+        apply_body_movers(workspace, &mut self.physics_world, timestep)
+    }
+
+    /// Step physics simulation. Parts with bodies are moved to match them.
     pub fn step_physics(&mut self, timestep: f32) {
         self.last_physics_delta = timestep;
         self.physics_world.step(timestep);
+        self.physics_world.sync_parts();
         self.com_plicit_ngine.step(timestep);
     }
 
@@ -184,6 +603,53 @@ impl World {
         self.renderer.draw_mesh(mesh_name, material_name, transform, color);
     }
 
+    /// Replace the queued GUI with laid out objects, drawing each object's
+    /// background, image and text. Fully transparent backgrounds are skipped.
+    /// Text objects get their TextBounds written.
+    pub fn draw_gui(&mut self, items: &[GuiDrawItem]) {
+        self.renderer.clear_gui_queue();
+        for item in items {
+            let clip = item.clip.map(|clip| (clip.position, clip.size));
+            let color = item.background_color;
+            if item.background_transparency < 1.0 {
+                self.renderer.draw_gui_quad(GuiQuad {
+                    position: item.rect.position,
+                    size: item.rect.size,
+                    color: Vec4::new(color.r, color.g, color.b, 1.0 - item.background_transparency),
+                    texture_name: None,
+                    uv_min: Vec2::ZERO,
+                    uv_max: Vec2::ONE,
+                    clip,
+                });
+            }
+            if let Some(image) = &item.image {
+                self.renderer.draw_gui_quad(GuiQuad {
+                    position: item.rect.position,
+                    size: item.rect.size,
+                    color: Vec4::ONE,
+                    texture_name: Some(image.clone()),
+                    uv_min: Vec2::ZERO,
+                    uv_max: Vec2::ONE,
+                    clip,
+                });
+            }
+            if let Some(text) = &item.text {
+                let options = TextLayoutOptions {
+                    text_size: item.text_size,
+                    bounds: item.rect.size,
+                    wrapped: item.text_wrapped,
+                    scaled: item.text_scaled,
+                    alignment: item.text_alignment,
+                };
+                let color = item.text_color;
+                let color = Vec4::new(color.r, color.g, color.b, 1.0 - item.text_transparency);
+                if let Some(layout) = self.renderer.draw_text(DEFAULT_FONT, text, item.rect.position, &options, color, clip) {
+                    item.object.set_text_bounds(layout.text_bounds);
+                }
+            }
+        }
+    }
+
     /// Render the current frame
     pub fn render_frame(&mut self) -> Result<(), rnr_rendering::RenderError> {
         self.renderer.render_frame()
@@ -218,26 +684,147 @@ mod tests {
     #[test]
     fn test_world_loading() {
         let mut world = World::new(true);
+        let workspace = rnr_datamodel::Workspace::new();
+        let workspace_instance = workspace.borrow().instance().clone();
+        world.datamodel.borrow_mut().register_service("Workspace", workspace_instance.clone());
 
-        // Create a simple mock load listener
         struct MockLoadListener {
-            pub update_count: std::cell::RefCell<i32>,
+            updates: Rc<std::cell::Cell<usize>>,
         }
 
         impl LoadListener for MockLoadListener {
             fn update_world_load(&mut self) {
-                *self.update_count.borrow_mut() += 1;
+                self.updates.set(self.updates.get() + 1);
             }
         }
 
-        let listener = MockLoadListener {
-            update_count: std::cell::RefCell::new(0),
-        };
-
-        world.load("test_path", Some(Box::new(listener)));
+        let place = r#"<roblox version="4">
+  <Item class="Workspace" referent="RBX0">
+    <Properties><float name="Gravity">50</float></Properties>
+    <Item class="Part" referent="RBX1">
+      <Properties>
+        <string name="Name">Base</string>
+        <bool name="Anchored">true</bool>
+        <int name="BrickColor">194</int>
+        <Vector3 name="size"><X>64</X><Y>1</Y><Z>64</Z></Vector3>
+        <float name="Wobble">3</float>
+      </Properties>
+    </Item>
+    <Item class="Part" referent="RBX2">
+      <Properties><string name="Name">Brick</string><bool name="Archivable">false</bool></Properties>
+      <Item class="Weld" referent="RBX3">
+        <Properties>
+          <Ref name="Part0">RBX1</Ref>
+          <Ref name="Part1">RBX2</Ref>
+          <Ref name="Target">RBX9</Ref>
+        </Properties>
+      </Item>
+    </Item>
+  </Item>
+  <Item class="Gadget" referent="RBX4"><Properties><SharedString name="Blob">abc</SharedString></Properties></Item>
+</roblox>"#;
+        let path = std::env::temp_dir().join(format!("rnr_world_loading_{}.rbxlx", std::process::id()));
+        std::fs::write(&path, place).unwrap();
+
+        let updates = Rc::new(std::cell::Cell::new(0));
+        let listener = MockLoadListener { updates: updates.clone() };
+        let report = world.load(path.to_str().unwrap(), Some(Box::new(listener))).unwrap();
+        std::fs::remove_file(&path).unwrap();
 
         assert_eq!(world.load_state, WorldLoadState::Finished);
-        assert_eq!(world.load_progress, 100);
+        assert_eq!(report.instances, 5);
+        assert_eq!(world.max_load_progress, 15);
+        assert_eq!(world.load_progress, world.max_load_progress);
+        // One update per instance in each of the three passes, and one per state
+        assert_eq!(updates.get(), 15 + 4);
+        assert!(world.undeserialized.is_empty());
+
+        // The Workspace item loads into the registered service
+        assert_eq!(workspace.borrow().gravity(), 50.0);
+        let base = BasePart::from_instance(&workspace_instance.borrow().find_first_child("Base").unwrap()).unwrap();
+        assert!(base.anchored());
+        assert_eq!(base.size(), Vec3::new(64.0, 1.0, 64.0));
+        let brick = workspace_instance.borrow().find_first_child("Brick").unwrap();
+        assert!(!brick.borrow().archivable());
+        let weld = JointInstance::from_instance(&brick.borrow().find_first_child("Weld").unwrap()).unwrap();
+        assert!(Rc::ptr_eq(weld.part0().unwrap().instance(), base.instance()));
+        assert!(Rc::ptr_eq(weld.part1().unwrap().instance(), &brick));
+        assert!(Rc::ptr_eq(world.refs.get("RBX2").unwrap(), &brick));
+
+        // Unknown top level classes become services
+        let gadget = world.datamodel.borrow().get_service("Gadget").unwrap();
+        assert_eq!(gadget.borrow().class_name(), "Gadget");
+        assert_eq!(report.unknown_classes, BTreeSet::from(["Gadget".to_string()]));
+        assert_eq!(
+            report.unknown_properties,
+            BTreeSet::from([
+                ("Gadget".to_string(), "Blob".to_string()),
+                ("Part".to_string(), "Wobble".to_string()),
+                ("Weld".to_string(), "Target".to_string()),
+            ])
+        );
+        assert_eq!(report.unresolved_refs, BTreeSet::from(["RBX9".to_string()]));
+
+        // Parts got bodies and the weld joins them
+        assert!(world.physics_world.get_part_body(base.instance()).is_some());
+        assert!(world.physics_world.get_part_body(&brick).is_some());
+        assert_eq!(world.physics_world.impulse_joints.len(), 1);
+
+        assert!(matches!(world.load("no_such_place.rbxlx", None), Err(XmlError::Io(_))));
+    }
+
+    #[test]
+    fn test_world_loading_reuses_workspace_terrain_and_camera() {
+        let mut world = World::new(true);
+        let workspace = rnr_datamodel::Workspace::new().borrow().instance().clone();
+        world.datamodel.borrow_mut().register_service("Workspace", workspace.clone());
+        let terrain = workspace.borrow().find_first_child("Terrain").unwrap();
+
+        let place = r#"<roblox version="4">
+  <Item class="Workspace" referent="RBX0">
+    <Properties><Ref name="CurrentCamera">RBX2</Ref></Properties>
+    <Item class="Terrain" referent="RBX1"><Properties><string name="Name">Terrain</string></Properties></Item>
+    <Item class="Camera" referent="RBX2"><Properties><string name="Name">Camera</string></Properties></Item>
+  </Item>
+</roblox>"#;
+        let report = world.load_xml(place, None).unwrap();
+
+        assert!(report.unknown_classes.is_empty());
+        assert_eq!(workspace.borrow().num_children(), 2);
+        assert!(Rc::ptr_eq(&workspace.borrow().find_first_child("Terrain").unwrap(), &terrain));
+        let camera = rnr_datamodel::current_camera(&workspace).unwrap();
+        assert!(Rc::ptr_eq(&camera.instance().borrow().parent().unwrap(), &workspace));
+    }
+
+    #[test]
+    fn test_world_save_round_trips_through_load() {
+        let world = World::new(true);
+        let workspace = rnr_datamodel::Workspace::new();
+        let workspace_instance = workspace.borrow().instance().clone();
+        world.datamodel.borrow_mut().register_service("Workspace", workspace_instance.clone());
+        let part = BasePart::new("Part");
+        part.instance().borrow_mut().set_name("Brick");
+        part.set_cframe(CFrame::new(Vec3::new(0.0, 10.0, 5.0)));
+        Instance::set_parent(part.instance(), Some(workspace_instance));
+
+        let path = std::env::temp_dir().join(format!("rnr_world_save_{}.rbxlx", std::process::id()));
+        world.save(path.to_str().unwrap()).unwrap();
+
+        let mut loaded = World::new(true);
+        let loaded_workspace = rnr_datamodel::Workspace::new().borrow().instance().clone();
+        loaded.datamodel.borrow_mut().register_service("Workspace", loaded_workspace.clone());
+        let report = loaded.load(path.to_str().unwrap(), None).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // The Workspace's own Terrain and Camera are loaded into, not duplicated
+        assert_eq!(report, LoadReport { instances: 4, ..Default::default() });
+        assert_eq!(loaded_workspace.borrow().num_children(), 3);
+        let camera = rnr_datamodel::current_camera(&loaded_workspace).unwrap();
+        assert!(Rc::ptr_eq(&camera.instance().borrow().parent().unwrap(), &loaded_workspace));
+        let brick = BasePart::from_instance(&loaded_workspace.borrow().find_first_child("Brick").unwrap()).unwrap();
+        assert_eq!(brick.cframe().position, Vec3::new(0.0, 10.0, 5.0));
+        assert_eq!(brick.size(), part.size());
+        assert!(loaded.physics_world.get_part_body(brick.instance()).is_some());
     }
 
     #[test]
@@ -250,6 +837,31 @@ mod tests {
         assert!(world.get_camera().is_some());
     }
 
+    #[test]
+    fn test_camera_syncs_with_current_camera() {
+        let mut world = World::new(true);
+        let workspace = rnr_datamodel::Workspace::new();
+        let workspace_instance = workspace.borrow().instance().clone();
+        world.datamodel.borrow_mut().register_service("Workspace", workspace_instance);
+
+        // The engine camera is created from CurrentCamera
+        world.sync_camera();
+        let current_camera = world.current_camera().unwrap();
+        assert_eq!(world.get_camera().unwrap().position, current_camera.cframe().position);
+
+        // Scripts move the engine camera through the instance
+        current_camera.set_cframe(CFrame::new(glam::Vec3::new(1.0, 2.0, 3.0)));
+        current_camera.set_field_of_view(90.0);
+        world.sync_camera();
+        assert_eq!(world.get_camera().unwrap().position, glam::Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(world.get_camera().unwrap().fov, 90.0);
+
+        // The engine moves the instance through set_camera
+        world.set_camera(Camera::new(glam::Vec3::new(0.0, 0.0, 5.0), glam::Vec3::ZERO));
+        assert_eq!(current_camera.cframe().position, glam::Vec3::new(0.0, 0.0, 5.0));
+        assert_eq!(current_camera.field_of_view(), 60.0);
+    }
+
     #[test]
     fn test_physics_control() {
         let mut world = World::new(true);
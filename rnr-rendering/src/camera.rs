@@ -185,24 +185,21 @@ impl Camera {
         )
     }
 
-    /// Convert screen point to world ray
+    /// Convert screen point (0 to 1) to a world ray starting at the camera
     pub fn screen_to_world_ray(&self, screen_x: f32, screen_y: f32) -> (Vec3, Vec3) {
         // Convert screen coordinates to NDC (-1 to 1)
         let ndc_x = screen_x * 2.0 - 1.0;
         let ndc_y = (1.0 - screen_y) * 2.0 - 1.0; // Flip Y axis
 
-        // Create ray in clip space
-        let clip_ray = Vec3::new(ndc_x, ndc_y, -1.0); // -1 for forward direction
-
-        // Transform to world space
+        // Unproject the point on the near plane (depth 0) and far plane (depth 1)
         let inv_view_proj = self.view_projection_matrix.inverse();
-        let world_near = inv_view_proj * clip_ray.extend(1.0);
-        let world_far = inv_view_proj * clip_ray.extend(0.0); // Point at far plane
+        let world_near = inv_view_proj * glam::Vec4::new(ndc_x, ndc_y, 0.0, 1.0);
+        let world_far = inv_view_proj * glam::Vec4::new(ndc_x, ndc_y, 1.0, 1.0);
 
-        let origin = world_near.truncate() / world_near.w;
-        let direction = (world_far.truncate() / world_far.w - origin).normalize();
+        let near = world_near.truncate() / world_near.w;
+        let far = world_far.truncate() / world_far.w;
 
-        (origin, direction)
+        (self.position, (far - near).normalize())
     }
 
     /// Check if a point is visible by the camera
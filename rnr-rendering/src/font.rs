@@ -0,0 +1,567 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use ab_glyph::{point, Font as _, FontVec, PxScale, ScaleFont};
+use glam::Vec2;
+use crate::texture::{Texture, TextureFormat, TextureManager};
+
+/// Directory the engine's fonts are loaded from
+pub const FONT_DIRECTORY: &str = "Content/RNR/fonts";
+
+/// Font used for GUI text
+pub const DEFAULT_FONT: &str = "ComicSans";
+
+/// Largest text size TextScaled picks, in pixels
+pub const MAX_TEXT_SIZE: u32 = 100;
+
+/// Width of a glyph atlas in pixels; atlases grow downwards as glyphs are added
+const ATLAS_WIDTH: u32 = 512;
+
+/// Empty pixels kept around each glyph so neighbours don't bleed when sampled
+const ATLAS_PADDING: u32 = 1;
+
+/// Font loading errors
+#[derive(Debug, thiserror::Error)]
+pub enum FontError {
+    #[error("Failed to read font {0}: {1}")]
+    Io(String, String),
+    #[error("Invalid font data in {0}")]
+    InvalidFont(String),
+}
+
+/// A TrueType font
+pub struct Font {
+    name: String,
+    font: FontVec,
+}
+
+impl Font {
+    /// Parse a font from the contents of a .ttf file
+    pub fn from_bytes(name: &str, data: Vec<u8>) -> Result<Self, FontError> {
+        let font = FontVec::try_from_vec(data).map_err(|_| FontError::InvalidFont(name.to_string()))?;
+        Ok(Self {
+            name: name.to_string(),
+            font,
+        })
+    }
+
+    /// Load a .ttf file, naming the font after the file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, FontError> {
+        let path = path.as_ref();
+        let name = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+        let data = fs::read(path).map_err(|e| FontError::Io(path.display().to_string(), e.to_string()))?;
+        Self::from_bytes(name, data)
+    }
+
+    /// Get the font's name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the distance from the top of a line to its baseline
+    pub fn ascent(&self, text_size: f32) -> f32 {
+        self.font.as_scaled(PxScale::from(text_size)).ascent()
+    }
+
+    /// Get the distance between the baselines of two lines
+    pub fn line_height(&self, text_size: f32) -> f32 {
+        let font = self.font.as_scaled(PxScale::from(text_size));
+        font.ascent() - font.descent() + font.line_gap()
+    }
+
+    /// Get the width of a single line of text, including kerning
+    pub fn measure_line(&self, text: &str, text_size: f32) -> f32 {
+        let font = self.font.as_scaled(PxScale::from(text_size));
+        let mut width = 0.0;
+        let mut previous = None;
+        for ch in text.chars() {
+            let id = font.glyph_id(ch);
+            if let Some(previous) = previous {
+                width += font.kern(previous, id);
+            }
+            width += font.h_advance(id);
+            previous = Some(id);
+        }
+        width
+    }
+}
+
+/// How text is fitted into a box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextLayoutOptions {
+    /// Text height in pixels; ignored when `scaled`
+    pub text_size: f32,
+    /// Size of the box the text is laid out in
+    pub bounds: Vec2,
+    /// Break lines between words to fit the box's width (TextWrapped)
+    pub wrapped: bool,
+    /// Use the largest whole size up to MAX_TEXT_SIZE that fits the box,
+    /// wrapping lines as needed (TextScaled)
+    pub scaled: bool,
+    /// Where the text sits in the box, from (0, 0) for the top left to (1, 1)
+    /// for the bottom right
+    pub alignment: Vec2,
+}
+
+/// A character placed by `layout_text`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LaidOutGlyph {
+    pub ch: char,
+    /// Pen position on the baseline, relative to the box's top left
+    pub position: Vec2,
+}
+
+/// Text broken into lines and placed in a box
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+    pub glyphs: Vec<LaidOutGlyph>,
+    pub lines: Vec<String>,
+    /// Text size the layout was made at
+    pub text_size: f32,
+    /// Size of the laid out text (TextBounds)
+    pub text_bounds: Vec2,
+}
+
+/// Shape text into lines and place them in a box.
+///
+/// Lines break at newlines and, when wrapping, between words; words too
+/// long for a line on their own break between characters. Text that doesn't
+/// fit is not cut off.
+pub fn layout_text(font: &Font, text: &str, options: &TextLayoutOptions) -> TextLayout {
+    if !options.scaled {
+        return layout_at(font, text, options.text_size, options, options.wrapped);
+    }
+
+    // Binary search whole sizes so the result doesn't depend on float rounding
+    let fits = |size: u32| {
+        let bounds = layout_at(font, text, size as f32, options, true).text_bounds;
+        bounds.x <= options.bounds.x && bounds.y <= options.bounds.y
+    };
+    let (mut low, mut high) = (1, MAX_TEXT_SIZE);
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        if fits(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    layout_at(font, text, low as f32, options, true)
+}
+
+fn layout_at(font: &Font, text: &str, text_size: f32, options: &TextLayoutOptions, wrapped: bool) -> TextLayout {
+    let max_width = if wrapped { Some(options.bounds.x) } else { None };
+    let lines = wrap_lines(font, text, text_size, max_width);
+    let widths: Vec<f32> = lines.iter().map(|line| font.measure_line(line, text_size)).collect();
+
+    let line_height = font.line_height(text_size);
+    let text_bounds = Vec2::new(widths.iter().copied().fold(0.0, f32::max), line_height * lines.len() as f32);
+    let top = (options.bounds.y - text_bounds.y) * options.alignment.y;
+
+    let scaled = font.font.as_scaled(PxScale::from(text_size));
+    let mut glyphs = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let baseline = top + scaled.ascent() + line_height * index as f32;
+        let mut x = (options.bounds.x - widths[index]) * options.alignment.x;
+        let mut previous = None;
+        for ch in line.chars() {
+            let id = scaled.glyph_id(ch);
+            if let Some(previous) = previous {
+                x += scaled.kern(previous, id);
+            }
+            glyphs.push(LaidOutGlyph { ch, position: Vec2::new(x, baseline) });
+            x += scaled.h_advance(id);
+            previous = Some(id);
+        }
+    }
+
+    TextLayout {
+        glyphs,
+        lines,
+        text_size,
+        text_bounds,
+    }
+}
+
+fn wrap_lines(font: &Font, text: &str, text_size: f32, max_width: Option<f32>) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let Some(max_width) = max_width else {
+            lines.push(paragraph.to_string());
+            continue;
+        };
+
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if font.measure_line(&candidate, text_size) <= max_width {
+                line = candidate;
+                continue;
+            }
+
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            for ch in word.chars() {
+                let mut next = line.clone();
+                next.push(ch);
+                if !line.is_empty() && font.measure_line(&next, text_size) > max_width {
+                    lines.push(std::mem::replace(&mut line, ch.to_string()));
+                } else {
+                    line = next;
+                }
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Where a glyph's bitmap sits in an atlas
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasGlyph {
+    pub uv_min: Vec2,
+    pub uv_max: Vec2,
+    /// Top left of the bitmap relative to the pen position on the baseline
+    pub offset: Vec2,
+    /// Size of the bitmap in pixels
+    pub size: Vec2,
+}
+
+/// Pixel rectangle of a rasterized glyph
+#[derive(Debug, Clone, Copy)]
+struct AtlasSlot {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    offset: Vec2,
+}
+
+/// Glyphs of one font at one size, rasterized into a single-channel texture.
+///
+/// Printable ASCII is rasterized up front in character order and other
+/// characters as they are first drawn, so the same text always produces the
+/// same pixels.
+pub struct GlyphAtlas {
+    font_name: String,
+    text_size: u32,
+    width: u32,
+    height: u32,
+    /// Coverage, one byte per pixel
+    data: Vec<u8>,
+    /// None for characters without an outline, like spaces
+    slots: BTreeMap<char, Option<AtlasSlot>>,
+    /// Top left of the free space in the current shelf
+    cursor_x: u32,
+    cursor_y: u32,
+    shelf_height: u32,
+}
+
+impl GlyphAtlas {
+    /// Rasterize printable ASCII for a font at a size in pixels
+    pub fn new(font: &Font, text_size: u32) -> Self {
+        let mut atlas = Self {
+            font_name: font.name().to_string(),
+            text_size: text_size.max(1),
+            width: ATLAS_WIDTH,
+            height: 64,
+            data: vec![0; (ATLAS_WIDTH * 64) as usize],
+            slots: BTreeMap::new(),
+            cursor_x: 0,
+            cursor_y: 0,
+            shelf_height: 0,
+        };
+        for ch in ' '..='~' {
+            atlas.add_glyph(font, ch);
+        }
+        atlas
+    }
+
+    /// Get the name of the atlas texture for a font and size
+    pub fn texture_name(font_name: &str, text_size: u32) -> String {
+        format!("font:{}:{}", font_name, text_size)
+    }
+
+    /// Get the name of this atlas's texture
+    pub fn name(&self) -> String {
+        Self::texture_name(&self.font_name, self.text_size)
+    }
+
+    /// Get the size glyphs are rasterized at, in pixels
+    pub fn text_size(&self) -> u32 {
+        self.text_size
+    }
+
+    /// Get the atlas size in pixels
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Get the coverage bytes, row by row
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Check if a character has been rasterized
+    pub fn contains(&self, ch: char) -> bool {
+        self.slots.contains_key(&ch)
+    }
+
+    /// Get where a glyph is, or None if it hasn't been rasterized or has no outline
+    pub fn glyph(&self, ch: char) -> Option<AtlasGlyph> {
+        let slot = (*self.slots.get(&ch)?)?;
+        let atlas_size = Vec2::new(self.width as f32, self.height as f32);
+        let min = Vec2::new(slot.x as f32, slot.y as f32);
+        let size = Vec2::new(slot.width as f32, slot.height as f32);
+        Some(AtlasGlyph {
+            uv_min: min / atlas_size,
+            uv_max: (min + size) / atlas_size,
+            offset: slot.offset,
+            size,
+        })
+    }
+
+    /// Rasterize a character if it isn't in the atlas yet
+    pub fn add_glyph(&mut self, font: &Font, ch: char) {
+        if self.contains(ch) {
+            return;
+        }
+
+        let glyph = font.font.glyph_id(ch).with_scale_and_position(PxScale::from(self.text_size as f32), point(0.0, 0.0));
+        let Some(outlined) = font.font.outline_glyph(glyph) else {
+            self.slots.insert(ch, None);
+            return;
+        };
+
+        let bounds = outlined.px_bounds();
+        let (width, height) = (bounds.width() as u32, bounds.height() as u32);
+        let (x, y) = self.allocate(width, height);
+
+        let atlas_width = self.width;
+        let data = &mut self.data;
+        outlined.draw(|gx, gy, coverage| {
+            if gx < width && gy < height {
+                data[((y + gy) * atlas_width + x + gx) as usize] = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+            }
+        });
+
+        self.slots.insert(ch, Some(AtlasSlot {
+            x,
+            y,
+            width,
+            height,
+            offset: Vec2::new(bounds.min.x, bounds.min.y),
+        }));
+    }
+
+    /// Find space for a bitmap, growing the atlas if it is full
+    fn allocate(&mut self, width: u32, height: u32) -> (u32, u32) {
+        if self.cursor_x + width + ATLAS_PADDING > self.width {
+            self.cursor_x = 0;
+            self.cursor_y += self.shelf_height + ATLAS_PADDING;
+            self.shelf_height = 0;
+        }
+        while self.cursor_y + height + ATLAS_PADDING > self.height {
+            self.height *= 2;
+            self.data.resize((self.width * self.height) as usize, 0);
+        }
+
+        let position = (self.cursor_x, self.cursor_y);
+        self.cursor_x += width + ATLAS_PADDING;
+        self.shelf_height = self.shelf_height.max(height);
+        position
+    }
+
+    /// Copy the atlas into a texture
+    pub fn to_texture(&self) -> Texture {
+        Texture::new(&self.name(), self.width, self.height, self.data.clone(), TextureFormat::R8)
+    }
+}
+
+/// Loaded fonts and the atlases rasterized from them
+#[derive(Default)]
+pub struct FontManager {
+    fonts: HashMap<String, Font>,
+    atlases: HashMap<(String, u32), GlyphAtlas>,
+}
+
+impl FontManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a font, replacing any font with the same name
+    pub fn register_font(&mut self, font: Font) {
+        self.atlases.retain(|(name, _), _| name != font.name());
+        self.fonts.insert(font.name().to_string(), font);
+    }
+
+    /// Load every .ttf file in a directory, returning how many were loaded
+    pub fn load_directory(&mut self, directory: impl AsRef<Path>) -> Result<usize, FontError> {
+        let directory = directory.as_ref();
+        let entries = fs::read_dir(directory).map_err(|e| FontError::Io(directory.display().to_string(), e.to_string()))?;
+
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("ttf")))
+            .collect();
+        paths.sort();
+
+        for path in &paths {
+            self.register_font(Font::load(path)?);
+        }
+        Ok(paths.len())
+    }
+
+    /// Get a font by name
+    pub fn get_font(&self, name: &str) -> Option<&Font> {
+        self.fonts.get(name)
+    }
+
+    /// Get the atlas for a font at a size, with every character of `text`
+    /// rasterized. The atlas texture is (re)registered when it changes.
+    pub fn prepare_atlas(&mut self, font_name: &str, text_size: u32, text: &str, textures: &mut TextureManager) -> Option<&GlyphAtlas> {
+        let font = self.fonts.get(font_name)?;
+        let key = (font_name.to_string(), text_size.max(1));
+
+        let mut changed = false;
+        let atlas = self.atlases.entry(key).or_insert_with(|| {
+            changed = true;
+            GlyphAtlas::new(font, text_size)
+        });
+        for ch in text.chars() {
+            if !atlas.contains(ch) {
+                atlas.add_glyph(font, ch);
+                changed = true;
+            }
+        }
+
+        if changed || textures.get_texture(&atlas.name()).is_none() {
+            textures.register_texture(atlas.to_texture());
+        }
+        Some(atlas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comic_sans() -> Font {
+        Font::load(concat!(env!("CARGO_MANIFEST_DIR"), "/../Content/RNR/fonts/ComicSans.ttf")).unwrap()
+    }
+
+    fn options(text_size: f32, bounds: Vec2) -> TextLayoutOptions {
+        TextLayoutOptions {
+            text_size,
+            bounds,
+            wrapped: false,
+            scaled: false,
+            alignment: Vec2::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_load_font() {
+        let font = comic_sans();
+        assert_eq!(font.name(), "ComicSans");
+        assert!(font.line_height(14.0) >= 14.0);
+        assert!(font.measure_line("Hello", 14.0) > font.measure_line("Hi", 14.0));
+        assert_eq!(font.measure_line("", 14.0), 0.0);
+
+        assert!(matches!(Font::from_bytes("junk", vec![1, 2, 3]), Err(FontError::InvalidFont(_))));
+        assert!(matches!(Font::load("missing.ttf"), Err(FontError::Io(_, _))));
+    }
+
+    #[test]
+    fn test_atlas_is_deterministic() {
+        let font = comic_sans();
+        let atlas = GlyphAtlas::new(&font, 14);
+        let again = GlyphAtlas::new(&font, 14);
+        assert_eq!(atlas.data(), again.data());
+        assert_eq!(atlas.name(), "font:ComicSans:14");
+
+        // Spaces have no bitmap, letters do
+        assert!(atlas.contains(' ') && atlas.glyph(' ').is_none());
+        let a = atlas.glyph('A').unwrap();
+        assert!(a.size.x > 0.0 && a.size.y > 0.0);
+        assert!(a.offset.y < 0.0, "glyphs sit above the baseline");
+        assert!(a.uv_max.x <= 1.0 && a.uv_max.y <= 1.0);
+        assert!(atlas.data().contains(&255));
+
+        let texture = atlas.to_texture();
+        assert_eq!(texture.format, TextureFormat::R8);
+        assert!(texture.is_valid());
+    }
+
+    #[test]
+    fn test_atlas_grows_for_new_glyphs() {
+        let font = comic_sans();
+        let mut atlas = GlyphAtlas::new(&font, 100);
+        let (_, height) = atlas.size();
+        let before = atlas.glyph('A').unwrap();
+
+        atlas.add_glyph(&font, 'é');
+        assert!(atlas.contains('é'));
+        assert!(atlas.size().1 >= height);
+        // Growing keeps existing glyphs' pixels where they were
+        let after = atlas.glyph('A').unwrap();
+        assert_eq!(before.size, after.size);
+        assert_eq!(before.uv_min.x, after.uv_min.x);
+    }
+
+    #[test]
+    fn test_layout_alignment() {
+        let font = comic_sans();
+        let bounds = Vec2::new(200.0, 100.0);
+        let left = layout_text(&font, "Hello", &options(14.0, bounds));
+        assert_eq!(left.lines, vec!["Hello"]);
+        assert_eq!(left.glyphs.len(), 5);
+        assert_eq!(left.glyphs[0].position.x, 0.0);
+        assert_eq!(left.text_bounds, Vec2::new(font.measure_line("Hello", 14.0), font.line_height(14.0)));
+
+        let centered = layout_text(&font, "Hello", &TextLayoutOptions { alignment: Vec2::splat(0.5), ..options(14.0, bounds) });
+        let free = bounds - centered.text_bounds;
+        assert!((centered.glyphs[0].position.x - free.x / 2.0).abs() < 1e-4);
+        assert!((centered.glyphs[0].position.y - (free.y / 2.0 + font.ascent(14.0))).abs() < 1e-4);
+
+        let multiline = layout_text(&font, "a\nbb", &options(14.0, bounds));
+        assert_eq!(multiline.lines, vec!["a", "bb"]);
+        assert_eq!(multiline.text_bounds.y, font.line_height(14.0) * 2.0);
+    }
+
+    #[test]
+    fn test_layout_wrapping_and_scaling() {
+        let font = comic_sans();
+        let width = font.measure_line("the quick", 14.0).max(font.measure_line("brown fox", 14.0)) + 1.0;
+        let wrapped = layout_text(&font, "the quick brown fox", &TextLayoutOptions { wrapped: true, ..options(14.0, Vec2::new(width, 100.0)) });
+        assert_eq!(wrapped.lines, vec!["the quick", "brown fox"]);
+        assert!(wrapped.text_bounds.x <= width);
+
+        // A word longer than the box breaks between characters
+        let narrow = layout_text(&font, "abcdefgh", &TextLayoutOptions { wrapped: true, ..options(14.0, Vec2::new(20.0, 100.0)) });
+        assert!(narrow.lines.len() > 1);
+        assert_eq!(narrow.lines.concat(), "abcdefgh");
+
+        let bounds = Vec2::new(200.0, 50.0);
+        let scaled = layout_text(&font, "Play", &TextLayoutOptions { scaled: true, ..options(14.0, bounds) });
+        assert!(scaled.text_size > 14.0 && scaled.text_size <= MAX_TEXT_SIZE as f32);
+        assert!(scaled.text_bounds.x <= bounds.x && scaled.text_bounds.y <= bounds.y);
+        let bigger = layout_text(&font, "Play", &TextLayoutOptions { scaled: true, ..options(scaled.text_size + 1.0, bounds) });
+        assert_eq!(bigger.text_size, scaled.text_size);
+    }
+
+    #[test]
+    fn test_font_manager() {
+        let mut manager = FontManager::new();
+        let loaded = manager.load_directory(concat!(env!("CARGO_MANIFEST_DIR"), "/../Content/RNR/fonts")).unwrap();
+        assert_eq!(loaded, 1);
+        assert!(manager.get_font(DEFAULT_FONT).is_some());
+
+        let mut textures = TextureManager::new();
+        let name = manager.prepare_atlas(DEFAULT_FONT, 18, "Hi ☺", &mut textures).unwrap().name();
+        assert!(textures.get_texture(&name).is_some());
+        assert!(manager.prepare_atlas("Missing", 18, "Hi", &mut textures).is_none());
+    }
+}
@@ -4,10 +4,12 @@ pub mod mesh;
 pub mod texture;
 pub mod renderer;
 pub mod camera;
+pub mod font;
 
 pub use material::*;
 pub use shader::*;
 pub use mesh::*;
 pub use texture::*;
 pub use renderer::*;
-pub use camera::*;
\ No newline at end of file
+pub use camera::*;
+pub use font::*;
\ No newline at end of file
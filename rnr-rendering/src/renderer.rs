@@ -1,5 +1,6 @@
-use crate::{material::*, shader::*, mesh::*, texture::*, camera::*};
-use glam::{Mat4, Vec4};
+use std::path::Path;
+use crate::{material::*, shader::*, mesh::*, texture::*, camera::*, font::*};
+use glam::{Mat4, Vec2, Vec4};
 
 /// Main renderer responsible for drawing 3D graphics
 pub struct Renderer {
@@ -9,6 +10,9 @@ pub struct Renderer {
     pub texture_manager: TextureManager,
     pub camera: Option<Camera>,
     pub render_queue: Vec<RenderCommand>,
+    /// 2D quads drawn over the scene, back to front
+    pub gui_queue: Vec<GuiQuad>,
+    pub font_manager: FontManager,
 }
 
 #[derive(Debug, Clone)]
@@ -19,6 +23,22 @@ pub struct RenderCommand {
     pub color: Vec4,
 }
 
+/// A screen-space rectangle drawn over the 3D scene, in pixels from the top
+/// left of the viewport
+#[derive(Debug, Clone)]
+pub struct GuiQuad {
+    pub position: Vec2,
+    pub size: Vec2,
+    pub color: Vec4,
+    /// Texture drawn over the quad, tinted by `color`
+    pub texture_name: Option<String>,
+    /// Part of the texture drawn, from (0, 0) to (1, 1) for all of it
+    pub uv_min: Vec2,
+    pub uv_max: Vec2,
+    /// Pixels outside this rectangle (position, size) are not drawn
+    pub clip: Option<(Vec2, Vec2)>,
+}
+
 impl Renderer {
     /// Create a new renderer
     pub fn new() -> Self {
@@ -29,6 +49,8 @@ impl Renderer {
             texture_manager: TextureManager::new(),
             camera: None,
             render_queue: Vec::new(),
+            gui_queue: Vec::new(),
+            font_manager: FontManager::new(),
         };
 
         // Initialize default resources
@@ -78,6 +100,48 @@ impl Renderer {
         self.render_queue.clear();
     }
 
+    /// Add a 2D quad on top of everything queued so far
+    pub fn draw_gui_quad(&mut self, quad: GuiQuad) {
+        self.gui_queue.push(quad);
+    }
+
+    /// Clear the GUI queue
+    pub fn clear_gui_queue(&mut self) {
+        self.gui_queue.clear();
+    }
+
+    /// Load every font in a directory, returning how many were loaded
+    pub fn load_fonts(&mut self, directory: impl AsRef<Path>) -> Result<usize, FontError> {
+        self.font_manager.load_directory(directory)
+    }
+
+    /// Lay out text in a box whose top left is at `position` and queue a quad
+    /// per glyph. Returns None if the font isn't loaded.
+    pub fn draw_text(&mut self, font_name: &str, text: &str, position: Vec2, options: &TextLayoutOptions, color: Vec4, clip: Option<(Vec2, Vec2)>) -> Option<TextLayout> {
+        // Atlases hold whole sizes, so lay out at the size that will be drawn
+        let options = TextLayoutOptions { text_size: options.text_size.round().max(1.0), ..*options };
+        let layout = layout_text(self.font_manager.get_font(font_name)?, text, &options);
+        let atlas = self.font_manager.prepare_atlas(font_name, layout.text_size as u32, text, &mut self.texture_manager)?;
+
+        let texture_name = atlas.name();
+        for glyph in &layout.glyphs {
+            let Some(atlas_glyph) = atlas.glyph(glyph.ch) else {
+                continue;
+            };
+            // Snap pens to whole pixels so glyphs sample the atlas 1:1
+            self.gui_queue.push(GuiQuad {
+                position: (position + glyph.position).round() + atlas_glyph.offset,
+                size: atlas_glyph.size,
+                color,
+                texture_name: Some(texture_name.clone()),
+                uv_min: atlas_glyph.uv_min,
+                uv_max: atlas_glyph.uv_max,
+                clip,
+            });
+        }
+        Some(layout)
+    }
+
     /// Render all queued commands (this would be called by the actual rendering backend)
     pub fn render_frame(&mut self) -> Result<(), RenderError> {
         if self.camera.is_none() {
@@ -108,8 +172,17 @@ impl Renderer {
             // For now, we just validate the command
         }
 
-        // Clear queue after rendering
+        for quad in &self.gui_queue {
+            if let Some(texture_name) = &quad.texture_name {
+                if self.texture_manager.get_texture(texture_name).is_none() {
+                    eprintln!("Warning: Texture '{}' not found", texture_name);
+                }
+            }
+        }
+
+        // Clear queues after rendering
         self.render_queue.clear();
+        self.gui_queue.clear();
 
         Ok(())
     }
@@ -122,6 +195,7 @@ impl Renderer {
             meshes_count: self.mesh_manager.meshes.len(),
             textures_count: self.texture_manager.textures.len(),
             queued_commands: self.render_queue.len(),
+            queued_gui_quads: self.gui_queue.len(),
         }
     }
 }
@@ -133,6 +207,7 @@ pub struct RenderStats {
     pub meshes_count: usize,
     pub textures_count: usize,
     pub queued_commands: usize,
+    pub queued_gui_quads: usize,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -270,6 +345,52 @@ mod tests {
         assert_eq!(renderer.render_queue.len(), 0); // Should be cleared
     }
 
+    #[test]
+    fn test_gui_quads() {
+        let mut renderer = Renderer::new();
+        renderer.set_camera(Camera::new(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO));
+
+        renderer.draw_gui_quad(GuiQuad {
+            position: Vec2::new(10.0, 10.0),
+            size: Vec2::new(100.0, 50.0),
+            color: Vec4::ONE,
+            texture_name: Some("placeholder".to_string()),
+            uv_min: Vec2::ZERO,
+            uv_max: Vec2::ONE,
+            clip: None,
+        });
+        assert_eq!(renderer.get_stats().queued_gui_quads, 1);
+
+        assert!(renderer.render_frame().is_ok());
+        assert!(renderer.gui_queue.is_empty());
+    }
+
+    #[test]
+    fn test_draw_text() {
+        let mut renderer = Renderer::new();
+        renderer.set_camera(Camera::new(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO));
+        let options = TextLayoutOptions {
+            text_size: 14.0,
+            bounds: Vec2::new(200.0, 50.0),
+            wrapped: false,
+            scaled: false,
+            alignment: Vec2::ZERO,
+        };
+        assert!(renderer.draw_text(DEFAULT_FONT, "Hi", Vec2::ZERO, &options, Vec4::ONE, None).is_none());
+
+        assert_eq!(renderer.load_fonts(concat!(env!("CARGO_MANIFEST_DIR"), "/../Content/RNR/fonts")).unwrap(), 1);
+        let layout = renderer.draw_text(DEFAULT_FONT, "Hi there", Vec2::new(10.0, 20.0), &options, Vec4::ONE, None).unwrap();
+        assert_eq!(layout.glyphs.len(), 8);
+        // The space has no quad
+        assert_eq!(renderer.gui_queue.len(), 7);
+        let quad = &renderer.gui_queue[0];
+        assert_eq!(quad.texture_name.as_deref(), Some("font:ComicSans:14"));
+        assert!(quad.position.x >= 10.0 && quad.position.y >= 20.0);
+
+        // Atlas textures are registered, so the frame validates
+        assert!(renderer.render_frame().is_ok());
+    }
+
     #[test]
     fn test_render_pass() {
         let mut pass = RenderPass::new("MainPass")
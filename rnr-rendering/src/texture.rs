@@ -1,4 +1,18 @@
 use std::collections::HashMap;
+use std::io::Cursor;
+use rnr_core::content::{ContentError, ContentProvider};
+
+/// Name of the white texture drawn in place of textures that aren't loaded
+pub const PLACEHOLDER_TEXTURE: &str = "placeholder";
+
+/// Texture loading errors
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum TextureError {
+    #[error("Failed to decode image: {0}")]
+    Decode(String),
+    #[error(transparent)]
+    Content(#[from] ContentError),
+}
 
 /// Texture resource for materials
 #[derive(Debug, Clone)]
@@ -55,6 +69,26 @@ impl Texture {
         Self::new(name, width, height, data, TextureFormat::Rgba8)
     }
 
+    /// Decode a PNG into an Rgba8 texture
+    pub fn from_png(name: &str, bytes: &[u8]) -> Result<Self, TextureError> {
+        let decode_error = |e: png::DecodingError| TextureError::Decode(e.to_string());
+        let mut decoder = png::Decoder::new(Cursor::new(bytes));
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().map_err(decode_error)?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).map_err(decode_error)?;
+        buffer.truncate(info.buffer_size());
+
+        let data = match info.color_type {
+            png::ColorType::Rgba => buffer,
+            png::ColorType::Rgb => buffer.chunks_exact(3).flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255]).collect(),
+            png::ColorType::GrayscaleAlpha => buffer.chunks_exact(2).flat_map(|ga| [ga[0], ga[0], ga[0], ga[1]]).collect(),
+            png::ColorType::Grayscale => buffer.iter().flat_map(|&gray| [gray, gray, gray, 255]).collect(),
+            png::ColorType::Indexed => return Err(TextureError::Decode("unexpanded palette".to_string())),
+        };
+        Ok(Self::new(name, info.width, info.height, data, TextureFormat::Rgba8))
+    }
+
     /// Get the size in bytes of one pixel
     pub fn bytes_per_pixel(&self) -> u32 {
         match self.format {
@@ -80,15 +114,55 @@ impl Texture {
 /// Texture manager for handling texture resources
 pub struct TextureManager {
     pub textures: HashMap<String, Texture>,
+    content: ContentProvider,
+    /// Content ids that failed to load, so they aren't read again
+    failed: HashMap<String, TextureError>,
 }
 
 impl TextureManager {
     pub fn new() -> Self {
+        Self::with_content(ContentProvider::default())
+    }
+
+    /// Create a texture manager that loads images through `content`
+    pub fn with_content(content: ContentProvider) -> Self {
         Self {
             textures: HashMap::new(),
+            content,
+            failed: HashMap::new(),
         }
     }
 
+    /// Set where images are loaded from
+    pub fn set_content_provider(&mut self, content: ContentProvider) {
+        self.content = content;
+        self.failed.clear();
+    }
+
+    /// Get the texture for a content id, loading and decoding it the first
+    /// time. Failures are remembered; registering a texture under the id
+    /// later still replaces them.
+    pub fn load_texture(&mut self, content_id: &str) -> Result<&Texture, TextureError> {
+        if !self.textures.contains_key(content_id) {
+            if let Some(error) = self.failed.get(content_id) {
+                return Err(error.clone());
+            }
+            let texture = self.content
+                .load(content_id)
+                .map_err(TextureError::from)
+                .and_then(|bytes| Texture::from_png(content_id, &bytes));
+            match texture {
+                Ok(texture) => self.register_texture(texture),
+                Err(error) => {
+                    eprintln!("Failed to load texture '{}': {}", content_id, error);
+                    self.failed.insert(content_id.to_string(), error.clone());
+                    return Err(error);
+                }
+            }
+        }
+        Ok(&self.textures[content_id])
+    }
+
     /// Register a texture
     pub fn register_texture(&mut self, texture: Texture) {
         self.textures.insert(texture.name.clone(), texture);
@@ -102,7 +176,7 @@ impl TextureManager {
     /// Create and register default textures
     pub fn create_default_textures(&mut self) {
         // Placeholder texture (white)
-        let placeholder = Texture::solid_color("placeholder", [255, 255, 255, 255]);
+        let placeholder = Texture::solid_color(PLACEHOLDER_TEXTURE, [255, 255, 255, 255]);
         self.register_texture(placeholder);
 
         // Checkerboard for debugging
@@ -215,6 +289,33 @@ mod tests {
         assert!(manager.get_texture("nonexistent").is_none());
     }
 
+    #[test]
+    fn test_load_png_texture() {
+        let dir = std::env::temp_dir().join(format!("rnr_texture_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut png_bytes = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut png_bytes, 2, 1);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.write_header().unwrap().write_image_data(&[255, 0, 0, 0, 0, 255]).unwrap();
+        }
+        std::fs::write(dir.join("shirt.png"), &png_bytes).unwrap();
+        std::fs::write(dir.join("broken.png"), b"not a png").unwrap();
+
+        let mut manager = TextureManager::with_content(ContentProvider::new(&dir));
+        let texture = manager.load_texture("rbxasset://shirt.png").unwrap();
+        assert_eq!((texture.width, texture.height), (2, 1));
+        assert_eq!(texture.data, vec![255, 0, 0, 255, 0, 0, 255, 255]);
+        assert!(texture.is_valid());
+
+        assert!(matches!(manager.load_texture("rbxasset://broken.png"), Err(TextureError::Decode(_))));
+        assert!(matches!(manager.load_texture("rbxasset://missing.png"), Err(TextureError::Content(ContentError::NotFound(_)))));
+        manager.register_texture(Texture::solid_color("rbxasset://missing.png", [0, 255, 0, 255]));
+        assert!(manager.load_texture("rbxasset://missing.png").is_ok());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_sampler_state_default() {
         let sampler = SamplerState::default();
@@ -0,0 +1,722 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::io::Read;
+use std::thread;
+use std::time::Duration;
+use serde_json::{Map, Number, Value};
+use tokio::sync::oneshot;
+use rnr_core::instance::Instance;
+use rnr_datamodel::DataModel;
+use crate::bridge::{ScriptValue, ScriptError, Scriptable};
+
+/// How long a request may take before it fails, in seconds
+pub const DEFAULT_REQUEST_TIMEOUT: u64 = 30;
+
+/// Largest response body a request will read, in bytes
+pub const MAX_RESPONSE_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Content type of a PostAsync body (Enum.HttpContentType)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpContentType {
+    ApplicationJson = 0,
+    ApplicationXml = 1,
+    ApplicationUrlEncoded = 2,
+    TextPlain = 3,
+    TextXml = 4,
+}
+
+impl HttpContentType {
+    /// Get the enum value
+    pub fn value(self) -> i64 {
+        self as i64
+    }
+
+    /// Look up a content type by enum value
+    pub fn from_value(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(HttpContentType::ApplicationJson),
+            1 => Some(HttpContentType::ApplicationXml),
+            2 => Some(HttpContentType::ApplicationUrlEncoded),
+            3 => Some(HttpContentType::TextPlain),
+            4 => Some(HttpContentType::TextXml),
+            _ => None,
+        }
+    }
+
+    /// Get the MIME type sent in the Content-Type header
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            HttpContentType::ApplicationJson => "application/json",
+            HttpContentType::ApplicationXml => "application/xml",
+            HttpContentType::ApplicationUrlEncoded => "application/x-www-form-urlencoded",
+            HttpContentType::TextPlain => "text/plain",
+            HttpContentType::TextXml => "text/xml",
+        }
+    }
+}
+
+/// HttpService errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum HttpError {
+    /// HttpEnabled is false
+    HttpDisabled,
+    /// The URL could not be parsed or uses an unsupported scheme
+    InvalidUrl(String),
+    /// A RequestAsync table was missing fields or had the wrong types
+    InvalidRequest(String),
+    /// The server answered with a status outside 200-299
+    Status(u16, String),
+    /// The connection failed or timed out
+    Transport(String),
+    /// A value could not be converted to or from JSON
+    Json(String),
+}
+
+impl std::fmt::Display for HttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HttpError::HttpDisabled => write!(f, "Http requests are not enabled"),
+            HttpError::InvalidUrl(url) => write!(f, "Invalid URL '{}'", url),
+            HttpError::InvalidRequest(msg) => write!(f, "Invalid request: {}", msg),
+            HttpError::Status(code, message) => write!(f, "HTTP {} ({})", code, message),
+            HttpError::Transport(msg) => write!(f, "Http request failed: {}", msg),
+            HttpError::Json(msg) => write!(f, "Can't convert JSON: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+impl From<HttpError> for ScriptError {
+    fn from(error: HttpError) -> Self {
+        match error {
+            HttpError::Json(_) | HttpError::InvalidRequest(_) => ScriptError::InvalidArguments(error.to_string()),
+            _ => ScriptError::RuntimeError(error.to_string()),
+        }
+    }
+}
+
+/// A request made through RequestAsync
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRequest {
+    pub url: String,
+    pub method: String,
+    pub headers: BTreeMap<String, String>,
+    pub body: Option<String>,
+}
+
+impl HttpRequest {
+    /// Create a GET request with no headers
+    pub fn get(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            method: "GET".to_string(),
+            headers: BTreeMap::new(),
+            body: None,
+        }
+    }
+
+    /// Create a POST request with a body of the given content type
+    pub fn post(url: &str, body: &str, content_type: HttpContentType) -> Self {
+        let mut headers = BTreeMap::new();
+        headers.insert("Content-Type".to_string(), content_type.mime_type().to_string());
+        Self {
+            url: url.to_string(),
+            method: "POST".to_string(),
+            headers,
+            body: Some(body.to_string()),
+        }
+    }
+
+    /// Read a request from a script table with Url, Method, Headers and Body
+    /// fields. Method defaults to GET.
+    pub fn from_script_value(value: &ScriptValue) -> Result<Self, HttpError> {
+        let ScriptValue::Table(fields) = value else {
+            return Err(HttpError::InvalidRequest("expected a table".to_string()));
+        };
+
+        let url = match fields.get("Url") {
+            Some(ScriptValue::String(url)) => url.clone(),
+            _ => return Err(HttpError::InvalidRequest("Url must be a string".to_string())),
+        };
+        let method = match fields.get("Method") {
+            None | Some(ScriptValue::Nil) => "GET".to_string(),
+            Some(ScriptValue::String(method)) => method.to_ascii_uppercase(),
+            Some(_) => return Err(HttpError::InvalidRequest("Method must be a string".to_string())),
+        };
+
+        let mut headers = BTreeMap::new();
+        match fields.get("Headers") {
+            None | Some(ScriptValue::Nil) => {}
+            Some(ScriptValue::Table(values)) => {
+                for (name, value) in values {
+                    let ScriptValue::String(value) = value else {
+                        return Err(HttpError::InvalidRequest(format!("header '{}' must be a string", name)));
+                    };
+                    headers.insert(name.clone(), value.clone());
+                }
+            }
+            Some(_) => return Err(HttpError::InvalidRequest("Headers must be a table".to_string())),
+        }
+
+        let body = match fields.get("Body") {
+            None | Some(ScriptValue::Nil) => None,
+            Some(ScriptValue::String(body)) => Some(body.clone()),
+            Some(_) => return Err(HttpError::InvalidRequest("Body must be a string".to_string())),
+        };
+
+        Ok(Self { url, method, headers, body })
+    }
+}
+
+/// The result of RequestAsync. Unlike GetAsync and PostAsync, a status
+/// outside 200-299 is reported here rather than as an error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status_code: u16,
+    pub status_message: String,
+    /// Header names are lowercase
+    pub headers: BTreeMap<String, String>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// Check if the status code is in the 200-299 range
+    pub fn success(&self) -> bool {
+        (200..300).contains(&self.status_code)
+    }
+
+    /// Convert to the table RequestAsync returns to scripts
+    pub fn to_script_value(&self) -> ScriptValue {
+        let headers = self.headers
+            .iter()
+            .map(|(name, value)| (name.clone(), ScriptValue::String(value.clone())))
+            .collect();
+
+        let mut fields = HashMap::new();
+        fields.insert("Success".to_string(), ScriptValue::Bool(self.success()));
+        fields.insert("StatusCode".to_string(), ScriptValue::Int(self.status_code as i64));
+        fields.insert("StatusMessage".to_string(), ScriptValue::String(self.status_message.clone()));
+        fields.insert("Headers".to_string(), ScriptValue::Table(headers));
+        fields.insert("Body".to_string(), ScriptValue::String(self.body.clone()));
+        ScriptValue::Table(fields)
+    }
+}
+
+/// HttpService - JSON helpers, GUIDs and outgoing HTTP requests.
+///
+/// JSON, GUID and URL helpers always work. Requests fail with
+/// `HttpError::HttpDisabled` until HttpEnabled is turned on, which the game
+/// has to opt into explicitly.
+///
+/// Requests run on their own thread and are awaited, so a slow server
+/// yields the caller instead of holding up the world's step.
+pub struct HttpService {
+    instance: Rc<RefCell<Instance>>,
+    agent: ureq::Agent,
+}
+
+impl HttpService {
+    /// Create a new HttpService with requests disabled
+    pub fn new() -> Rc<RefCell<Self>> {
+        let instance = Instance::new();
+        instance.borrow_mut().set_name("HttpService");
+        instance.borrow_mut().set_class_name("HttpService");
+        instance.borrow_mut().set_property("HttpEnabled", false);
+
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(DEFAULT_REQUEST_TIMEOUT))
+            .redirects(5)
+            .build();
+
+        Rc::new(RefCell::new(Self { instance, agent }))
+    }
+
+    /// Get the instance
+    pub fn instance(&self) -> &Rc<RefCell<Instance>> {
+        &self.instance
+    }
+
+    /// Check if outgoing requests are allowed
+    pub fn http_enabled(&self) -> bool {
+        self.instance.borrow().get_property("HttpEnabled").and_then(|value| value.as_bool()).unwrap_or(false)
+    }
+
+    /// Allow or block outgoing requests
+    pub fn set_http_enabled(&mut self, enabled: bool) {
+        self.instance.borrow_mut().set_property("HttpEnabled", enabled);
+    }
+
+    /// Encode a script value as a JSON string
+    pub fn json_encode(&self, value: &ScriptValue) -> Result<String, HttpError> {
+        let json = script_value_to_json(value)?;
+        serde_json::to_string(&json).map_err(|e| HttpError::Json(e.to_string()))
+    }
+
+    /// Decode a JSON string into a script value
+    pub fn json_decode(&self, json: &str) -> Result<ScriptValue, HttpError> {
+        let json: Value = serde_json::from_str(json).map_err(|e| HttpError::Json(e.to_string()))?;
+        Ok(json_to_script_value(json))
+    }
+
+    /// Generate a random GUID, like `{04AEBFEA-87FC-480F-A98B-E5E221007A90}`
+    pub fn generate_guid(&self, wrap_in_curly_braces: bool) -> String {
+        let guid = uuid::Uuid::new_v4().hyphenated().to_string().to_uppercase();
+        if wrap_in_curly_braces {
+            format!("{{{}}}", guid)
+        } else {
+            guid
+        }
+    }
+
+    /// Get the GUID an instance is registered under in the data model,
+    /// generating and registering a new one if it has none yet
+    pub fn generate_instance_guid(&self, datamodel: &mut DataModel, instance: &Rc<RefCell<Instance>>) -> String {
+        if let Some(guid) = datamodel.get_guid_for_instance(instance) {
+            return guid;
+        }
+
+        let guid = self.generate_guid(false);
+        datamodel.register_instance_guid(instance.clone(), guid.clone());
+        guid
+    }
+
+    /// Percent-encode a string for use in a URL. Only letters, digits and
+    /// `-_.~` are left as they are.
+    pub fn url_encode(&self, input: &str) -> String {
+        let mut encoded = String::with_capacity(input.len());
+        for byte in input.bytes() {
+            if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+                encoded.push(byte as char);
+            } else {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        }
+        encoded
+    }
+
+    /// Send a GET request and return the body. Fails on any status outside
+    /// 200-299.
+    pub fn get_async(&self, url: &str) -> impl Future<Output = Result<String, HttpError>> + 'static {
+        let response = self.request_async(&HttpRequest::get(url));
+        async move { into_body(response.await?) }
+    }
+
+    /// Send a POST request and return the body. Fails on any status outside
+    /// 200-299.
+    pub fn post_async(&self, url: &str, body: &str, content_type: HttpContentType) -> impl Future<Output = Result<String, HttpError>> + 'static {
+        let response = self.request_async(&HttpRequest::post(url, body, content_type));
+        async move { into_body(response.await?) }
+    }
+
+    /// Send a request and return the response, whatever its status. The
+    /// request starts right away on its own thread; the returned future
+    /// finishes when the response has been read, and doesn't borrow the
+    /// service.
+    pub fn request_async(&self, request: &HttpRequest) -> impl Future<Output = Result<HttpResponse, HttpError>> + 'static {
+        let (sender, receiver) = oneshot::channel();
+        if self.http_enabled() {
+            let agent = self.agent.clone();
+            let request = request.clone();
+            thread::spawn(move || {
+                let _ = sender.send(send_request(&agent, &request));
+            });
+        } else {
+            let _ = sender.send(Err(HttpError::HttpDisabled));
+        }
+
+        async move { receiver.await.unwrap_or_else(|e| Err(HttpError::Transport(e.to_string()))) }
+    }
+
+    /// Get the error a script gets for calling a request method that would
+    /// have to yield
+    fn yield_error(&self, method: &str) -> ScriptError {
+        if !self.http_enabled() {
+            return HttpError::HttpDisabled.into();
+        }
+        ScriptError::RuntimeError(format!("{} yields, which script method calls can't do", method))
+    }
+}
+
+impl Scriptable for HttpService {
+    fn script_class_name(&self) -> &str {
+        "HttpService"
+    }
+
+    fn get_script_property(&self, name: &str) -> Option<ScriptValue> {
+        match name {
+            "Name" => Some(ScriptValue::String(self.instance.borrow().name().to_string())),
+            "HttpEnabled" => Some(ScriptValue::Bool(self.http_enabled())),
+            _ => None,
+        }
+    }
+
+    fn set_script_property(&mut self, name: &str, value: ScriptValue) -> Result<(), ScriptError> {
+        match name {
+            "HttpEnabled" => {
+                if let ScriptValue::Bool(enabled) = value {
+                    self.set_http_enabled(enabled);
+                    Ok(())
+                } else {
+                    Err(ScriptError::TypeMismatch("Expected bool for HttpEnabled".to_string()))
+                }
+            }
+            _ => Err(ScriptError::PropertyNotFound(name.to_string())),
+        }
+    }
+
+    fn call_script_method(&mut self, name: &str, args: Vec<ScriptValue>) -> Result<ScriptValue, ScriptError> {
+        match name {
+            "JSONEncode" => {
+                let value = args.first().unwrap_or(&ScriptValue::Nil);
+                Ok(ScriptValue::String(self.json_encode(value)?))
+            }
+            "JSONDecode" => {
+                let json = string_arg(&args, 0, "JSONDecode")?;
+                Ok(self.json_decode(json)?)
+            }
+            "GenerateGUID" => {
+                let wrap = match args.first() {
+                    None | Some(ScriptValue::Nil) => true,
+                    Some(ScriptValue::Bool(wrap)) => *wrap,
+                    Some(_) => return Err(ScriptError::InvalidArguments("GenerateGUID expects a bool".to_string())),
+                };
+                Ok(ScriptValue::String(self.generate_guid(wrap)))
+            }
+            "UrlEncode" => {
+                let input = string_arg(&args, 0, "UrlEncode")?;
+                Ok(ScriptValue::String(self.url_encode(input)))
+            }
+            // Requests yield, which a method call through the bridge can't do,
+            // so scripts get an error rather than a call that stalls the step
+            "GetAsync" => {
+                string_arg(&args, 0, "GetAsync")?;
+                Err(self.yield_error(name))
+            }
+            "PostAsync" => {
+                string_arg(&args, 0, "PostAsync")?;
+                string_arg(&args, 1, "PostAsync")?;
+                match args.get(2) {
+                    None | Some(ScriptValue::Nil) => {}
+                    Some(ScriptValue::Int(value)) => {
+                        HttpContentType::from_value(*value)
+                            .ok_or_else(|| ScriptError::InvalidArguments(format!("Unknown HttpContentType {}", value)))?;
+                    }
+                    Some(_) => return Err(ScriptError::InvalidArguments("PostAsync expects an HttpContentType".to_string())),
+                }
+                Err(self.yield_error(name))
+            }
+            "RequestAsync" => {
+                HttpRequest::from_script_value(args.first().unwrap_or(&ScriptValue::Nil))?;
+                Err(self.yield_error(name))
+            }
+            _ => Err(ScriptError::MethodNotFound(name.to_string())),
+        }
+    }
+}
+
+/// Convert a script value to JSON. Instances and non-finite numbers have no
+/// JSON form and are rejected.
+pub fn script_value_to_json(value: &ScriptValue) -> Result<Value, HttpError> {
+    match value {
+        ScriptValue::Nil => Ok(Value::Null),
+        ScriptValue::Bool(b) => Ok(Value::Bool(*b)),
+        ScriptValue::Int(i) => Ok(Value::Number((*i).into())),
+        ScriptValue::Float(f) => Number::from_f64(*f)
+            .map(Value::Number)
+            .ok_or_else(|| HttpError::Json(format!("{} is not a valid JSON number", f))),
+        ScriptValue::String(s) => Ok(Value::String(s.clone())),
+        ScriptValue::Instance(_) => Err(HttpError::Json("Instances can't be encoded".to_string())),
+        ScriptValue::Array(values) => values.iter().map(script_value_to_json).collect::<Result<_, _>>().map(Value::Array),
+        ScriptValue::Table(fields) => {
+            let mut object = Map::new();
+            for (key, value) in fields {
+                object.insert(key.clone(), script_value_to_json(value)?);
+            }
+            Ok(Value::Object(object))
+        }
+    }
+}
+
+/// Convert JSON to a script value. Whole numbers that fit in an i64 become
+/// Int, every other number becomes Float.
+pub fn json_to_script_value(value: Value) -> ScriptValue {
+    match value {
+        Value::Null => ScriptValue::Nil,
+        Value::Bool(b) => ScriptValue::Bool(b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => ScriptValue::Int(i),
+            None => ScriptValue::Float(n.as_f64().unwrap_or(f64::NAN)),
+        },
+        Value::String(s) => ScriptValue::String(s),
+        Value::Array(values) => ScriptValue::Array(values.into_iter().map(json_to_script_value).collect()),
+        Value::Object(fields) => ScriptValue::Table(
+            fields.into_iter().map(|(key, value)| (key, json_to_script_value(value))).collect()
+        ),
+    }
+}
+
+/// Send a request and wait for the response. This blocks for up to
+/// DEFAULT_REQUEST_TIMEOUT, so it only runs on a request's own thread.
+fn send_request(agent: &ureq::Agent, request: &HttpRequest) -> Result<HttpResponse, HttpError> {
+    let mut call = agent.request(&request.method, &request.url);
+    for (name, value) in &request.headers {
+        call = call.set(name, value);
+    }
+
+    let result = match &request.body {
+        Some(body) => call.send_string(body),
+        None => call.call(),
+    };
+
+    let response = match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(ureq::Error::Transport(transport)) => {
+            return Err(match transport.kind() {
+                ureq::ErrorKind::InvalidUrl | ureq::ErrorKind::UnknownScheme => HttpError::InvalidUrl(request.url.clone()),
+                _ => HttpError::Transport(transport.to_string()),
+            });
+        }
+    };
+
+    let status_code = response.status();
+    let status_message = response.status_text().to_string();
+    let headers = response
+        .headers_names()
+        .into_iter()
+        .filter_map(|name| {
+            let value = response.header(&name)?.to_string();
+            Some((name.to_ascii_lowercase(), value))
+        })
+        .collect();
+
+    let mut body = String::new();
+    response
+        .into_reader()
+        .take(MAX_RESPONSE_SIZE)
+        .read_to_string(&mut body)
+        .map_err(|e| HttpError::Transport(e.to_string()))?;
+
+    Ok(HttpResponse { status_code, status_message, headers, body })
+}
+
+fn into_body(response: HttpResponse) -> Result<String, HttpError> {
+    if response.success() {
+        Ok(response.body)
+    } else {
+        Err(HttpError::Status(response.status_code, response.status_message))
+    }
+}
+
+fn string_arg<'a>(args: &'a [ScriptValue], index: usize, method: &str) -> Result<&'a str, ScriptError> {
+    match args.get(index) {
+        Some(ScriptValue::String(s)) => Ok(s),
+        _ => Err(ScriptError::InvalidArguments(format!("{} expects a string as argument {}", method, index + 1))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Serve one request on a local port with a canned response. Returns the
+    /// base URL and a channel that receives the raw request.
+    fn serve_once(status: &str, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let status = status.to_string();
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nX-Test: yes\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status, body.len(), body
+            ).unwrap();
+            sender.send(request).unwrap();
+        });
+
+        (url, receiver)
+    }
+
+    fn enabled_service() -> Rc<RefCell<HttpService>> {
+        let service = HttpService::new();
+        service.borrow_mut().set_http_enabled(true);
+        service
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(future)
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let service = HttpService::new();
+        let mut fields = HashMap::new();
+        fields.insert("name".to_string(), ScriptValue::String("Builderman".to_string()));
+        fields.insert("scores".to_string(), ScriptValue::Array(vec![ScriptValue::Int(1), ScriptValue::Float(2.5)]));
+        fields.insert("banned".to_string(), ScriptValue::Bool(false));
+        fields.insert("team".to_string(), ScriptValue::Nil);
+
+        let json = service.borrow().json_encode(&ScriptValue::Table(fields)).unwrap();
+        assert_eq!(json, r#"{"banned":false,"name":"Builderman","scores":[1,2.5],"team":null}"#);
+
+        let ScriptValue::Table(decoded) = service.borrow().json_decode(&json).unwrap() else {
+            panic!("expected a table");
+        };
+        assert!(matches!(decoded.get("name"), Some(ScriptValue::String(name)) if name == "Builderman"));
+        assert!(matches!(&decoded["scores"], ScriptValue::Array(scores)
+            if matches!(scores[..], [ScriptValue::Int(1), ScriptValue::Float(f)] if f == 2.5)));
+        assert!(matches!(decoded.get("team"), Some(ScriptValue::Nil)));
+    }
+
+    #[test]
+    fn test_json_rejects_unencodable_values() {
+        let service = HttpService::new();
+        let service = service.borrow();
+
+        assert!(matches!(service.json_encode(&ScriptValue::Float(f64::NAN)), Err(HttpError::Json(_))));
+        assert!(matches!(service.json_encode(&ScriptValue::Instance(Instance::new())), Err(HttpError::Json(_))));
+        assert!(matches!(service.json_decode("{not json"), Err(HttpError::Json(_))));
+    }
+
+    #[test]
+    fn test_generate_guid() {
+        let service = HttpService::new();
+        let service = service.borrow();
+
+        let wrapped = service.generate_guid(true);
+        assert_eq!(wrapped.len(), 38);
+        assert!(wrapped.starts_with('{') && wrapped.ends_with('}'));
+        assert_eq!(wrapped, wrapped.to_uppercase());
+
+        let plain = service.generate_guid(false);
+        assert_eq!(plain.len(), 36);
+        assert_ne!(plain, service.generate_guid(false));
+    }
+
+    #[test]
+    fn test_generate_instance_guid() {
+        let service = HttpService::new();
+        let datamodel = DataModel::new();
+        let instance = Instance::new();
+
+        let guid = service.borrow().generate_instance_guid(&mut datamodel.borrow_mut(), &instance);
+        assert!(Rc::ptr_eq(&datamodel.borrow().get_instance_by_guid(&guid).unwrap(), &instance));
+
+        // An instance keeps the GUID it already has
+        assert_eq!(service.borrow().generate_instance_guid(&mut datamodel.borrow_mut(), &instance), guid);
+    }
+
+    #[test]
+    fn test_url_encode() {
+        let service = HttpService::new();
+        assert_eq!(service.borrow().url_encode("a b&c=d/é~"), "a%20b%26c%3Dd%2F%C3%A9~");
+    }
+
+    #[test]
+    fn test_requests_require_http_enabled() {
+        let service = HttpService::new();
+        assert_eq!(block_on(service.borrow().get_async("http://127.0.0.1:1/")), Err(HttpError::HttpDisabled));
+
+        let result = service.borrow_mut().call_script_method("GetAsync", vec![ScriptValue::String("http://127.0.0.1:1/".to_string())]);
+        assert!(matches!(result, Err(ScriptError::RuntimeError(_))));
+    }
+
+    #[test]
+    fn test_get_async() {
+        let (url, request) = serve_once("200 OK", "hello");
+        let service = enabled_service();
+
+        assert_eq!(block_on(service.borrow().get_async(&format!("{}/motd", url))).unwrap(), "hello");
+        assert!(request.recv().unwrap().starts_with("GET /motd HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn test_post_async() {
+        let (url, request) = serve_once("201 Created", "{}");
+        let service = enabled_service();
+
+        let body = block_on(service.borrow().post_async(&format!("{}/events", url), r#"{"kind":"join"}"#, HttpContentType::ApplicationJson)).unwrap();
+        assert_eq!(body, "{}");
+
+        let request = request.recv().unwrap();
+        assert!(request.starts_with("POST /events HTTP/1.1\r\n"));
+        assert!(request.to_ascii_lowercase().contains("content-type: application/json\r\n"));
+        assert!(request.ends_with(r#"{"kind":"join"}"#));
+    }
+
+    #[test]
+    fn test_get_async_fails_on_error_status() {
+        let (url, _request) = serve_once("404 Not Found", "missing");
+        let service = enabled_service();
+
+        assert_eq!(block_on(service.borrow().get_async(&url)), Err(HttpError::Status(404, "Not Found".to_string())));
+    }
+
+    #[test]
+    fn test_request_async() {
+        let (url, request) = serve_once("403 Forbidden", "denied");
+        let service = enabled_service();
+
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".to_string(), ScriptValue::String("Bearer token".to_string()));
+        let mut fields = HashMap::new();
+        fields.insert("Url".to_string(), ScriptValue::String(format!("{}/moderation", url)));
+        fields.insert("Method".to_string(), ScriptValue::String("put".to_string()));
+        fields.insert("Headers".to_string(), ScriptValue::Table(headers));
+        fields.insert("Body".to_string(), ScriptValue::String("ban".to_string()));
+
+        let fields = ScriptValue::Table(fields);
+        let result = service.borrow_mut().call_script_method("RequestAsync", vec![fields.clone()]);
+        assert!(matches!(result, Err(ScriptError::RuntimeError(_))));
+
+        let request_async = service.borrow().request_async(&HttpRequest::from_script_value(&fields).unwrap());
+        let ScriptValue::Table(response) = block_on(request_async).unwrap().to_script_value() else {
+            panic!("expected a table");
+        };
+        assert!(matches!(response["Success"], ScriptValue::Bool(false)));
+        assert!(matches!(response["StatusCode"], ScriptValue::Int(403)));
+        assert!(matches!(&response["Body"], ScriptValue::String(body) if body == "denied"));
+        assert!(matches!(&response["Headers"], ScriptValue::Table(headers)
+            if matches!(headers.get("x-test"), Some(ScriptValue::String(value)) if value == "yes")));
+
+        let request = request.recv().unwrap();
+        assert!(request.starts_with("PUT /moderation HTTP/1.1\r\n"));
+        assert!(request.contains("Authorization: Bearer token\r\n"));
+    }
+
+    #[test]
+    fn test_invalid_url() {
+        let service = enabled_service();
+        assert!(matches!(block_on(service.borrow().get_async("ftp://example.com")), Err(HttpError::InvalidUrl(_))));
+        assert!(matches!(block_on(service.borrow().get_async("not a url")), Err(HttpError::InvalidUrl(_))));
+    }
+}
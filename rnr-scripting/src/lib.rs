@@ -1,7 +1,9 @@
 pub mod context;
 pub mod script;
 pub mod bridge;
+pub mod http_service;
 
 pub use context::*;
 pub use script::*;
 pub use bridge::*;
+pub use http_service::*;
@@ -1,9 +1,10 @@
 use clap::Parser;
+use std::path::PathBuf;
 use std::time::Instant;
 use tokio;
+use rnr_datamodel::RunContext;
+use rnr_network::server::NetworkServer;
 use rnr_world::{World, WorldConfig};
-// NetworkServer not yet implemented
-// use rnr_network::server::NetworkServer;
 
 /// Command line arguments for the RNR server
 #[derive(Parser)]
@@ -33,6 +34,10 @@ struct Args {
     /// Maximum players
     #[arg(long, default_value = "50")]
     max_players: u32,
+
+    /// Directory to keep DataStoreService data in (kept in memory if not set)
+    #[arg(long)]
+    data_store_dir: Option<PathBuf>,
 }
 
 #[tokio::main]
@@ -44,16 +49,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Listening on port: {}", args.port);
     println!("Max players: {}", args.max_players);
     println!("Tick rate: {} Hz", args.tick_rate);
+    if let Some(dir) = &args.data_store_dir {
+        println!("Data stores: {}", dir.display());
+    }
 
     // Create world configuration (server doesn't need rendering or input)
     let config = WorldConfig {
+        run_context: RunContext::Server,
         enable_rendering: false,  // Server doesn't render
         enable_networking: args.network,
         enable_physics: args.physics,
         enable_input: false,      // Server doesn't handle input
+        enable_audio: false,      // Server doesn't play sounds
         target_fps: args.tick_rate,
         viewport_width: 1,        // Dummy values
         viewport_height: 1,
+        data_store_dir: args.data_store_dir.clone(),
     };
 
     // Create and initialize world
@@ -61,20 +72,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     world.initialize().await?;
 
     // Set up networking if enabled
-    if args.network {
+    let server = if args.network {
         println!("Setting up network server...");
-
-        // In a real implementation, this would create and start a NetworkServer
-        // and register it with the data model
+        let server = NetworkServer::new(([0, 0, 0, 0], args.port).into())?;
         println!("Network server started on port {}", args.port);
-    }
+        Some(server)
+    } else {
+        None
+    };
 
     // Main server loop
     println!("Starting server loop...");
     let start_time = Instant::now();
 
     loop {
-        // Process one server tick
+        // Handle what clients sent since the last tick, then process one server tick
+        if let Some(server) = &server {
+            server.poll(world.workspace().borrow().instance())?;
+        }
         world.step().await?;
 
         // Check for shutdown conditions